@@ -10,7 +10,7 @@
 //!
 //! - `API_HOST`: The host address to bind the server to (default: "0.0.0.0")
 //! - `API_PORT`: The port to listen on (default: 3000)
-//! - `DATABASE_URL`: PostgreSQL connection string (required)
+//! - `DATABASE_URL`: Postgres (`postgres://...`) or SQLite (`sqlite://...`) connection string (required)
 //! - `LOG_LEVEL`: Logging level (default: "info")
 //! - `API_CORS_ORIGINS`: Comma-separated list of allowed CORS origins
 //! - `JWT_SECRET`: Secret key for JWT token generation (for protected endpoints)
@@ -44,7 +44,7 @@ pub struct ApiConfig {
     /// Port for the API server to listen on
     pub port: u16,
     
-    /// PostgreSQL database connection string
+    /// Postgres or SQLite database connection string
     pub database_url: String,
     
     /// Log level for the application
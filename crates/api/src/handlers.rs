@@ -1,3 +1,4 @@
 pub mod schedule;
 pub mod discord;
-pub mod availability;
\ No newline at end of file
+pub mod availability;
+pub mod meeting;
\ No newline at end of file
@@ -9,10 +9,18 @@
 //! The core algorithm in this module finds optimal meeting times where multiple groups
 //! of users can meet simultaneously. It works by:
 //!
-//! 1. Collecting all unique time slots from all users' schedules
-//! 2. For each time slot, checking how many users from each group are available
-//! 3. Keeping only time slots where at least a minimum number of users from each group are available
-//! 4. Ranking and returning the top matches based on specified criteria
+//! 1. Working out when each user is free within the search window: recurring
+//!    slots are repeated weekly and busy slots are cut out of available ones
+//! 2. Collecting all unique time slots from those free times
+//! 3. For each time slot, checking how many users from each group are available
+//! 4. Keeping only time slots where every required member is available and at
+//!    least a minimum number of users from each required group are available
+//! 5. Ranking the remaining slots by the summed weights of everyone available,
+//!    with members who are only free "if need be" counting for less
+//!
+//! It also parses free-text availability such as "weekdays after 6pm" into
+//! slots, so the web UI can offer the same quick entry as the Discord bot, and
+//! renders group availability as a PNG heatmap for places text can't show it.
 //!
 //! The algorithm is optimized to minimize database queries by:
 //! - Caching all time slots by schedule ID to avoid duplicate queries
@@ -21,52 +29,56 @@
 
 use axum::{
     extract::{Query, State},
+    http::header,
+    response::IntoResponse,
     Json,
 };
-use serde::Deserialize;
-use std::{collections::HashMap, sync::Arc};
+use chrono::{Duration, TimeZone, Utc};
+use chrono_tz::Tz;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 use timesync_core::{
+    availability::free_intervals,
     errors::TimeError,
-    models::discord::{MatchGroupResult, MatchResponse, MatchResult},
+    models::{
+        discord::{MatchGroupResult, MatchResponse, MatchResult},
+        schedule::{ParseAvailabilityRequest, ParseAvailabilityResponse, SlotPreference, TimeSlotResponse},
+    },
+    heatmap::{render_heatmap, HeatmapOptions, HeatmapSlot, MAX_DAYS},
+    matching::{evaluate_slot, require_members, score_slot, GroupConstraint, MemberConstraint},
+    parser,
 };
 use uuid::Uuid;
 
 use crate::{ApiState, middleware::error_handling::AppError};
 
-/// Query parameters for the match availability endpoint
-///
-/// This struct defines the parameters that can be provided when searching
-/// for matching availability across multiple Discord groups.
-///
-/// # Fields
-///
-/// * `group_ids` - Comma-separated list of Discord group UUIDs
-/// * `min_per_group` - Minimum number of available users per group (default: 1)
-/// * `count` - Maximum number of matching time slots to return (default: 5)
-#[derive(Debug, Deserialize)]
-pub struct MatchQuery {
-    /// Comma-separated list of Discord group UUIDs to match
-    pub group_ids: String,
-    
-    /// Minimum number of available users required from each group
-    pub min_per_group: Option<usize>,
-    
-    /// Maximum number of matching time slots to return
-    pub count: Option<usize>,
-}
+/// How far ahead matching looks when no `until` is given
+pub const DEFAULT_MATCH_WINDOW_DAYS: i64 = 28;
+
+/// Longest window matching accepts, which bounds how often recurring slots repeat
+pub const MAX_MATCH_WINDOW_DAYS: i64 = 90;
+
+pub use timesync_core::models::discord::{HeatmapQuery, MatchQuery};
 
 /// Finds optimal meeting times across multiple Discord groups
 ///
 /// This handler analyzes the availability of users across multiple Discord groups
 /// and identifies time slots where at least a minimum number of users from each
-/// group are available.
+/// required group are available, along with every member tagged as required.
+/// Optional groups and members never rule a slot out; they only raise its score.
 ///
 /// # Endpoint
 ///
 /// ```http
-/// GET /availability/match?group_ids=uuid1,uuid2&min_per_group=2&count=5
+/// GET /availability/match?group_ids=uuid1,uuid2&min_per_group=2&count=5&required_ids=123,456
 /// ```
 ///
+/// Recurring slots repeat weekly within the `from..until` window, and slots
+/// marked as unavailable are subtracted from every available slot they
+/// overlap, so a one-off conflict overrides a weekly pattern.
+///
 /// # Algorithm
 ///
 /// The matching algorithm follows these steps:
@@ -80,20 +92,27 @@ pub struct MatchQuery {
 ///    - Collect all users from each group
 ///    - Get schedule IDs for each user
 ///    - Fetch all time slots for each schedule (with caching)
+///    - Expand recurring slots over the window and cut out busy slots
 /// 
 /// 3. Time Slot Collection & De-duplication:
-///    - Collect all unique time slots from all users' schedules
+///    - Collect all unique time slots from all users' free times
 ///    - Sort by start time and remove duplicates
 /// 
 /// 4. Availability Analysis:
 ///    - For each time slot:
 ///      - For each group:
 ///        - Count users whose availability contains the time slot
-///        - Include group if it meets minimum user requirement
-///      - Include time slot if all groups meet requirements
+///      - Include time slot if all required members are available and all
+///        required groups meet the minimum user requirement
+///      - Score it by the summed weights of the available users, counting
+///        users who are only available "if need be" for less
+/// 
+///      - Otherwise keep it as a near miss if anyone is available, noting how
+///        many members each group is short and who isn't available
 /// 
 /// 5. Result Preparation:
-///    - Sort matches by start time
+///    - Sort matches by score, then by start time
+///    - Sort near misses by how many members are missing in total
 ///    - Limit to requested count
 ///    - Format and return response
 ///
@@ -113,9 +132,23 @@ pub struct MatchQuery {
 ///
 /// # Errors
 ///
-/// * `TimeError::Validation` - Invalid group IDs or empty group list
+/// * `TimeError::Validation` - Invalid group IDs, empty group list, a
+///   required member outside the groups, or a window that ends before it
+///   starts or spans more than `MAX_MATCH_WINDOW_DAYS`
 /// * `TimeError::NotFound` - Group or user not found
 /// * `TimeError::Database` - Database error
+#[utoipa::path(
+    get,
+    path = "/availability/match",
+    tag = "availability",
+    params(MatchQuery),
+    responses(
+        (status = 200, description = "Best matching times and near misses", body = MatchResponse),
+        (status = 400, description = "Invalid group IDs, required members or search window", body = ErrorResponse),
+        (status = 404, description = "Unknown group or user", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 #[axum::debug_handler]
 pub async fn match_availability(
     State(state): State<Arc<ApiState>>,
@@ -147,13 +180,37 @@ pub async fn match_availability(
     // Set default values for optional parameters
     let min_per_group = query.min_per_group.unwrap_or(1);
     let count = query.count.unwrap_or(5);
+    let required_ids: Vec<String> = query.required_ids.as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect();
+    let from = query.from.unwrap_or_else(Utc::now);
+    let until = match query.until {
+        Some(until) => until,
+        None => from.checked_add_signed(Duration::days(DEFAULT_MATCH_WINDOW_DAYS))
+            .ok_or_else(|| AppError(TimeError::Validation("The search window is out of range".to_string())))?,
+    };
+    if until <= from {
+        return Err(AppError(TimeError::Validation(
+            "The search window must end after it starts".to_string(),
+        )));
+    }
+    if until - from > Duration::days(MAX_MATCH_WINDOW_DAYS) {
+        return Err(AppError(TimeError::Validation(format!(
+            "The search window can't be longer than {} days",
+            MAX_MATCH_WINDOW_DAYS
+        ))));
+    }
 
     // STEP 2: Data Collection
     
     // Retrieve and validate all groups
     let mut groups = HashMap::new();
     for group_id in &group_ids {
-        let group = timesync_db::repositories::discord::get_discord_group_by_id(&state.db_pool, *group_id)
+        let group = state.discord.get_discord_group_by_id(*group_id)
             .await
             .map_err(TimeError::Database)?
             .ok_or_else(|| {
@@ -164,15 +221,22 @@ pub async fn match_availability(
 
     // Retrieve all members and their schedules for each group
     let mut group_schedules = HashMap::new();
+    let mut constraints = Vec::new();
     for group_id in &group_ids {
-        let members = timesync_db::repositories::discord::get_group_members(&state.db_pool, *group_id)
+        let members = state.discord.get_group_members(*group_id)
             .await
             .map_err(TimeError::Database)?;
 
         let mut schedule_ids = Vec::new();
+        let mut member_constraints = Vec::new();
         for member in members {
-            let user = timesync_db::repositories::discord::get_discord_user_by_id(
-                &state.db_pool,
+            member_constraints.push(MemberConstraint {
+                discord_id: member.discord_id.clone(),
+                required: member.required,
+                weight: member.weight,
+            });
+
+            let user = state.discord.get_discord_user_by_id(
                 &member.discord_id,
             )
             .await
@@ -187,20 +251,47 @@ pub async fn match_availability(
             }
         }
         group_schedules.insert(*group_id, schedule_ids);
+
+        let group = &groups[group_id];
+        constraints.push(GroupConstraint {
+            id: *group_id,
+            required: group.required,
+            min_available: min_per_group,
+            members: member_constraints,
+        });
     }
 
-    // Fetch all time slots for each schedule (caching to avoid duplicate queries)
+    // Members named in the request are required on top of those tagged as required
+    if let Some(outsider) = required_ids.iter()
+        .find(|id| !constraints.iter().any(|group| group.members.iter().any(|member| &member.discord_id == *id)))
+    {
+        return Err(AppError(TimeError::Validation(format!(
+            "Required member {} is not in any of the groups",
+            outsider
+        ))));
+    }
+    require_members(&mut constraints, &required_ids);
+
+    // Fetch each schedule's free times in the window (caching to avoid duplicate queries)
     let mut schedule_time_slots = HashMap::new();
     for schedules in group_schedules.values() {
         for (_discord_id, schedule_id) in schedules {
             if !schedule_time_slots.contains_key(schedule_id) {
-                let time_slots = timesync_db::repositories::time_slot::get_time_slots_by_schedule_id(
-                    &state.db_pool,
+                let time_slots = state.time_slots.get_time_slots_by_schedule_id(
                     *schedule_id,
                 )
                 .await
                 .map_err(TimeError::Database)?;
-                schedule_time_slots.insert(*schedule_id, time_slots);
+                let time_slots: Vec<TimeSlotResponse> = time_slots.into_iter()
+                    .map(|slot| TimeSlotResponse {
+                        start: slot.start_time,
+                        end: slot.end_time,
+                        is_recurring: slot.is_recurring,
+                        is_unavailable: slot.is_unavailable,
+                        preference: SlotPreference::parse(&slot.preference).unwrap_or_default(),
+                    })
+                    .collect();
+                schedule_time_slots.insert(*schedule_id, free_intervals(&time_slots, from, until));
             }
         }
     }
@@ -210,9 +301,9 @@ pub async fn match_availability(
     // Extract all unique time slot boundaries
     let mut time_boundaries = Vec::new();
     for slots in schedule_time_slots.values() {
-        for slot in slots {
-            time_boundaries.push(slot.start_time);
-            time_boundaries.push(slot.end_time);
+        for free in slots {
+            time_boundaries.push(free.start);
+            time_boundaries.push(free.end);
         }
     }
     
@@ -222,7 +313,7 @@ pub async fn match_availability(
     
     // If we have fewer than 2 time boundaries, no valid time slots exist
     if time_boundaries.len() < 2 {
-        return Ok(Json(MatchResponse { matches: Vec::new() }));
+        return Ok(Json(MatchResponse { matches: Vec::new(), near_misses: Vec::new() }));
     }
     
     // Create potential time slots from adjacent boundaries
@@ -239,8 +330,9 @@ pub async fn match_availability(
     
     // STEP 4: Availability Analysis
     
-    // Find matching time slots
+    // Find matching time slots, and the ones that fall short
     let mut matches = Vec::new();
+    let mut near_misses = Vec::new();
     
     for (start, end) in potential_time_slots {
         // Skip if this slot is zero duration or invalid
@@ -248,7 +340,6 @@ pub async fn match_availability(
             continue;
         }
         
-        let mut all_groups_match = true;
         let mut match_groups = Vec::new();
         
         // For each group, find users available during this time slot
@@ -257,60 +348,227 @@ pub async fn match_availability(
             let group = groups.get(&group_id).unwrap();
             
             let mut available_users = Vec::new();
+            let mut tentative_users = Vec::new();
             
             // Check each user's availability
             for (discord_id, schedule_id) in schedules {
                 let slots = schedule_time_slots.get(schedule_id).unwrap();
                 
-                // User is available if any of their slots contain this time period
-                // A time slot contains another if: slot.start <= target.start && slot.end >= target.end
-                let is_available = slots.iter().any(|slot| {
-                    slot.start_time <= start && slot.end_time >= end
-                });
+                // User is available if any of their free times contain this time period
+                // A free time contains a slot if: free.start <= slot.start && free.end >= slot.end
+                let covering = slots.iter().find(|free| free.start <= start && free.end >= end);
                 
-                if is_available {
+                if let Some(free) = covering {
                     available_users.push(discord_id.clone());
+                    if free.preference == SlotPreference::IfNeedBe {
+                        tentative_users.push(discord_id.clone());
+                    }
                 }
             }
             
-            // Check if this group meets the minimum requirement
-            if available_users.len() >= min_per_group {
-                let count = available_users.len();
-                match_groups.push(MatchGroupResult {
-                    id: group_id,
-                    name: group.name.clone(),
-                    available_users,
-                    count,
-                });
-            } else {
-                // If any group doesn't have enough users, this time slot is invalid
-                all_groups_match = false;
-                break;
-            }
+            let count = available_users.len();
+            match_groups.push(MatchGroupResult {
+                id: group_id,
+                name: group.name.clone(),
+                available_users,
+                count,
+                tentative_users,
+                missing_required: Vec::new(),
+                shortfall: 0,
+                unavailable_users: Vec::new(),
+            });
         }
         
-        // Only include match if all groups have at least min_per_group users available
-        if all_groups_match {
+        // Required members and groups filter the slot, everyone available adds to its score
+        let available: HashSet<&str> = match_groups.iter()
+            .flat_map(|group| group.available_users.iter().map(String::as_str))
+            .collect();
+        let tentative: HashSet<&str> = match_groups.iter()
+            .flat_map(|group| group.tentative_users.iter().map(String::as_str))
+            .collect();
+        
+        if let Some(score) = evaluate_slot(&constraints, &available, &tentative) {
             matches.push(MatchResult {
                 start,
                 end,
                 groups: match_groups,
+                score,
+            });
+        } else if !available.is_empty() {
+            // Note who each group is missing, so organisers know who to nudge
+            let score = score_slot(&constraints, &available, &tentative);
+            let missing: Vec<_> = constraints.iter()
+                .map(|group| (
+                    group.missing_required(&available),
+                    group.shortfall(&available),
+                    group.unavailable(&available),
+                ))
+                .collect();
+            
+            for (group, (missing_required, shortfall, unavailable_users)) in match_groups.iter_mut().zip(missing) {
+                group.missing_required = missing_required;
+                group.shortfall = shortfall;
+                group.unavailable_users = unavailable_users;
+            }
+            
+            near_misses.push(MatchResult {
+                start,
+                end,
+                groups: match_groups,
+                score,
             });
         }
     }
     
     // STEP 5: Result Preparation
     
-    // Sort matches by start time
-    matches.sort_by(|a, b| a.start.cmp(&b.start));
+    // Best score first, earliest first among equal scores
+    matches.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.start.cmp(&b.start)));
     
     // Limit to requested count
     if matches.len() > count {
         matches.truncate(count);
     }
     
+    // Near misses with the fewest missing members first
+    let missing = |result: &MatchResult| -> usize {
+        result.groups.iter().map(|group| group.shortfall).sum()
+    };
+    near_misses.sort_by(|a, b| {
+        missing(a).cmp(&missing(b))
+            .then(b.score.total_cmp(&a.score))
+            .then(a.start.cmp(&b.start))
+    });
+    near_misses.truncate(count);
+    
     // Build and return response
-    let response = MatchResponse { matches };
+    let response = MatchResponse { matches, near_misses };
     Ok(Json(response))
 }
 
+/// Parses free-text availability into time slots
+///
+/// The slots are returned rather than saved, so clients can preview them and
+/// include them in a create or update schedule request.
+///
+/// # Endpoint
+///
+/// ```http
+/// POST /availability/parse
+/// ```
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///   "text": "weekdays after 6pm, not Thursday",
+///   "timezone": "Europe/London",
+///   "reference_date": "2025-01-15",
+///   "weekly": true
+/// }
+/// ```
+///
+/// # Errors
+///
+/// * `TimeError::Validation` - Unknown timezone or text that can't be parsed
+#[utoipa::path(
+    post,
+    path = "/availability/parse",
+    tag = "availability",
+    request_body = ParseAvailabilityRequest,
+    responses(
+        (status = 200, description = "Slots described by the text", body = ParseAvailabilityResponse),
+        (status = 400, description = "Unknown timezone or text that can't be parsed", body = ErrorResponse),
+    )
+)]
+pub async fn parse_availability(
+    Json(payload): Json<ParseAvailabilityRequest>,
+) -> Result<Json<ParseAvailabilityResponse>, AppError> {
+    let tz: Tz = payload.timezone.parse()
+        .map_err(|_| AppError(TimeError::Validation(format!("Unknown timezone: {}", payload.timezone))))?;
+    let reference = payload.reference_date
+        .unwrap_or_else(|| Utc::now().with_timezone(&tz).date_naive());
+
+    let slots = if payload.weekly {
+        parser::parse_weekly_availability(&payload.text, reference, tz)?
+    } else {
+        parser::parse_availability(&payload.text, reference, tz)?
+    };
+
+    Ok(Json(ParseAvailabilityResponse { slots }))
+}
+
+/// Renders group availability as a days × hours PNG heatmap
+///
+/// Uses the same matching as `match_availability`, but keeps every slot so
+/// partial availability is shaded too. Hours missing a required member are
+/// left empty. Each cell shows how many distinct
+/// members are available during that hour.
+///
+/// # Endpoint
+///
+/// ```http
+/// GET /availability/heatmap.png?group_ids=uuid1,uuid2&timezone=Europe/London&days=7
+/// ```
+///
+/// # Errors
+///
+/// * `TimeError::Validation` - Invalid group IDs or unknown timezone
+/// * `TimeError::NotFound` - Group or user not found
+/// * `TimeError::Database` - Database error
+/// * `TimeError::Internal` - The image couldn't be encoded
+#[utoipa::path(
+    get,
+    path = "/availability/heatmap.png",
+    tag = "availability",
+    params(HeatmapQuery),
+    responses(
+        (status = 200, description = "PNG heatmap of the groups' availability", content_type = "image/png", body = Vec<u8>),
+        (status = 400, description = "Invalid group IDs or unknown timezone", body = ErrorResponse),
+        (status = 404, description = "Unknown group or user", body = ErrorResponse),
+        (status = 500, description = "Database error, or the image couldn't be encoded", body = ErrorResponse),
+    )
+)]
+pub async fn availability_heatmap(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<HeatmapQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let timezone = query.timezone.as_deref().unwrap_or("UTC");
+    let tz: Tz = timezone.parse()
+        .map_err(|_| AppError(TimeError::Validation(format!("Unknown timezone: {}", timezone))))?;
+
+    let first_day = query.start.unwrap_or_else(|| Utc::now().with_timezone(&tz).date_naive());
+    // Clamp before matching, so a huge `days` can't stretch the search window
+    let days = query.days.unwrap_or(MAX_DAYS).clamp(1, MAX_DAYS);
+    let from = tz.from_local_datetime(&first_day.and_hms_opt(0, 0, 0).unwrap_or_default())
+        .earliest()
+        .map(|from| from.with_timezone(&Utc))
+        .unwrap_or_else(|| first_day.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+
+    // A day spare covers days lengthened by a DST change
+    let until = from.checked_add_signed(Duration::days(days as i64 + 1))
+        .ok_or_else(|| AppError(TimeError::Validation("The heatmap's start date is out of range".to_string())))?;
+
+    let Json(response) = match_availability(
+        State(state),
+        Query(MatchQuery {
+            group_ids: query.group_ids,
+            min_per_group: Some(query.min_per_group.unwrap_or(0)),
+            count: Some(usize::MAX),
+            required_ids: None,
+            from: Some(from),
+            until: Some(until),
+        }),
+    ).await?;
+
+    let slots: Vec<HeatmapSlot> = response.matches.iter().map(HeatmapSlot::from).collect();
+    let options = HeatmapOptions {
+        first_day,
+        days,
+        tz,
+        total: None,
+    };
+    let image = render_heatmap(&slots, &options)?;
+
+    Ok(([(header::CONTENT_TYPE, "image/png")], image))
+}
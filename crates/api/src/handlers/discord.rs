@@ -1,27 +1,70 @@
-use axum::{extract::{Path, State}, Json};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
 use eyre::Result;
 use std::sync::Arc;
 use timesync_core::{
     errors::TimeError,
     models::discord::{
-        CreateDiscordGroupRequest, CreateDiscordGroupResponse, CreateDiscordUserRequest,
-        CreateDiscordUserResponse, DiscordGroupMember, GetDiscordGroupResponse,
-        GetDiscordUserResponse, UpdateDiscordGroupRequest, UpdateDiscordGroupResponse,
-        UpdateDiscordGroupRoleRequest, UpdateDiscordGroupRoleResponse,
+        AddGroupMemberResponse, CreateDiscordGroupRequest, CreateDiscordGroupResponse, CreateDiscordUserRequest,
+        CreateDiscordUserResponse, DiscordGroupMember, DiscordGroupSummary, DiscordServerResponse,
+        GetDiscordGroupResponse, GetDiscordUserResponse, ListDiscordGroupsResponse, SetTimezoneRequest,
+        UpdateDiscordGroupRequest, UpdateDiscordGroupResponse, UpdateDiscordGroupRoleRequest,
+        UpdateDiscordGroupRoleResponse, UpdateGroupMemberRequest, UpdateGroupMemberResponse,
     },
 };
+use timesync_db::models::DbDiscordGroup;
+use utoipa::IntoParams;
 use uuid::Uuid;
 
 use crate::{ApiState, middleware::error_handling::AppError};
 
+/// Groups per page when listing a server's groups without a `limit`
+pub const DEFAULT_GROUP_PAGE_SIZE: i64 = 25;
+
+/// Largest `limit` accepted when listing a server's groups
+pub const MAX_GROUP_PAGE_SIZE: i64 = 100;
+
+/// Query parameters for listing a server's groups
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListGroupsQuery {
+    /// Groups to return, up to `MAX_GROUP_PAGE_SIZE` (default: `DEFAULT_GROUP_PAGE_SIZE`)
+    pub limit: Option<i64>,
+
+    /// Groups to skip, in name order (default: 0)
+    pub offset: Option<i64>,
+}
+
+/// Query parameters for listing role-synced groups
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RoleSyncedGroupsQuery {
+    /// Only list this server's groups (default: every server)
+    pub server_id: Option<String>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/discord/users",
+    tag = "discord",
+    request_body = CreateDiscordUserRequest,
+    responses(
+        (status = 200, description = "Discord user linked to the schedule", body = CreateDiscordUserResponse),
+        (status = 404, description = "No schedule with this ID", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 #[axum::debug_handler]
 pub async fn create_discord_user(
     State(state): State<Arc<ApiState>>,
     Json(payload): Json<CreateDiscordUserRequest>,
 ) -> Result<Json<CreateDiscordUserResponse>, AppError> {
     // Check if schedule exists
-    let schedule_exists = timesync_db::repositories::schedule::get_schedule_by_id(
-        &state.db_pool,
+    let schedule_exists = state.schedules.get_schedule_by_id(
         payload.schedule_id,
     )
     .await
@@ -36,8 +79,7 @@ pub async fn create_discord_user(
     }
 
     // Create discord user in database
-    let db_discord_user = timesync_db::repositories::discord::create_discord_user(
-        &state.db_pool,
+    let db_discord_user = state.discord.create_discord_user(
         &payload.discord_id,
         Some(payload.schedule_id),
     )
@@ -52,6 +94,19 @@ pub async fn create_discord_user(
     Ok(Json(response))
 }
 
+#[utoipa::path(
+    get,
+    path = "/discord/users/{discord_id}",
+    tag = "discord",
+    params(
+        ("discord_id" = String, Path, description = "Discord user ID"),
+    ),
+    responses(
+        (status = 200, description = "Discord user", body = GetDiscordUserResponse),
+        (status = 404, description = "Unknown Discord user", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 #[axum::debug_handler]
 pub async fn get_discord_user(
     State(state): State<Arc<ApiState>>,
@@ -59,7 +114,7 @@ pub async fn get_discord_user(
 ) -> Result<Json<GetDiscordUserResponse>, AppError> {
     // Get discord user from database
     let db_discord_user =
-        timesync_db::repositories::discord::get_discord_user_by_id(&state.db_pool, &discord_id)
+        state.discord.get_discord_user_by_id(&discord_id)
             .await
             .map_err(TimeError::Database)?
             .ok_or_else(|| {
@@ -69,19 +124,146 @@ pub async fn get_discord_user(
     let response = GetDiscordUserResponse {
         discord_id: db_discord_user.discord_id,
         schedule_id: db_discord_user.schedule_id,
+        timezone: db_discord_user.timezone,
     };
 
     Ok(Json(response))
 }
 
+/// Set a Discord user's personal timezone, creating the user if needed
+///
+/// # Errors
+///
+/// * `TimeError::Validation` - Unknown timezone
+/// * `TimeError::Database` - Database error
+#[utoipa::path(
+    put,
+    path = "/discord/users/{discord_id}/timezone",
+    tag = "discord",
+    params(
+        ("discord_id" = String, Path, description = "Discord user ID"),
+    ),
+    request_body = SetTimezoneRequest,
+    responses(
+        (status = 200, description = "Discord user with the new timezone", body = GetDiscordUserResponse),
+        (status = 400, description = "Unknown timezone", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+pub async fn set_discord_user_timezone(
+    State(state): State<Arc<ApiState>>,
+    Path(discord_id): Path<String>,
+    Json(payload): Json<SetTimezoneRequest>,
+) -> Result<Json<GetDiscordUserResponse>, AppError> {
+    validate_timezone(&payload.timezone)?;
+
+    let db_discord_user = state.discord.set_discord_user_timezone(
+        &discord_id,
+        &payload.timezone,
+    )
+    .await
+    .map_err(TimeError::Database)?;
+
+    let response = GetDiscordUserResponse {
+        discord_id: db_discord_user.discord_id,
+        schedule_id: db_discord_user.schedule_id,
+        timezone: db_discord_user.timezone,
+    };
+
+    Ok(Json(response))
+}
+
+/// Get a Discord server's settings, with the defaults if none were saved
+#[utoipa::path(
+    get,
+    path = "/discord/servers/{server_id}",
+    tag = "discord",
+    params(
+        ("server_id" = String, Path, description = "Discord server ID"),
+    ),
+    responses(
+        (status = 200, description = "Server settings", body = DiscordServerResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+pub async fn get_discord_server(
+    State(state): State<Arc<ApiState>>,
+    Path(server_id): Path<String>,
+) -> Result<Json<DiscordServerResponse>, AppError> {
+    let db_server = state.discord.get_discord_server(&server_id)
+        .await
+        .map_err(TimeError::Database)?;
+
+    let response = DiscordServerResponse {
+        timezone: db_server.map_or_else(|| "UTC".to_string(), |server| server.timezone),
+        server_id,
+    };
+
+    Ok(Json(response))
+}
+
+/// Set the timezone a Discord server's times are shown in
+///
+/// # Errors
+///
+/// * `TimeError::Validation` - Unknown timezone
+/// * `TimeError::Database` - Database error
+#[utoipa::path(
+    put,
+    path = "/discord/servers/{server_id}/timezone",
+    tag = "discord",
+    params(
+        ("server_id" = String, Path, description = "Discord server ID"),
+    ),
+    request_body = SetTimezoneRequest,
+    responses(
+        (status = 200, description = "Server settings with the new timezone", body = DiscordServerResponse),
+        (status = 400, description = "Unknown timezone", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+pub async fn set_discord_server_timezone(
+    State(state): State<Arc<ApiState>>,
+    Path(server_id): Path<String>,
+    Json(payload): Json<SetTimezoneRequest>,
+) -> Result<Json<DiscordServerResponse>, AppError> {
+    validate_timezone(&payload.timezone)?;
+
+    let db_server = state.discord.set_discord_server_timezone(
+        &server_id,
+        &payload.timezone,
+    )
+    .await
+    .map_err(TimeError::Database)?;
+
+    let response = DiscordServerResponse {
+        server_id: db_server.server_id,
+        timezone: db_server.timezone,
+    };
+
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/discord/groups",
+    tag = "discord",
+    request_body = CreateDiscordGroupRequest,
+    responses(
+        (status = 200, description = "Group created", body = CreateDiscordGroupResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 #[axum::debug_handler]
 pub async fn create_discord_group(
     State(state): State<Arc<ApiState>>,
     Json(payload): Json<CreateDiscordGroupRequest>,
 ) -> Result<Json<CreateDiscordGroupResponse>, AppError> {
     // Create discord group in database
-    let db_discord_group = timesync_db::repositories::discord::create_discord_group(
-        &state.db_pool,
+    let db_discord_group = state.discord.create_discord_group(
         &payload.name,
         &payload.server_id,
         None, // Initially no role_id; it will be set by the Discord bot
@@ -93,15 +275,14 @@ pub async fn create_discord_group(
     for discord_id in &payload.member_ids {
         // Ensure the user exists
         let user_exists =
-            timesync_db::repositories::discord::get_discord_user_by_id(&state.db_pool, discord_id)
+            state.discord.get_discord_user_by_id(discord_id)
                 .await
                 .map_err(TimeError::Database)?
                 .is_some();
 
         if !user_exists {
             // Create user if not exists
-            timesync_db::repositories::discord::create_discord_user(
-                &state.db_pool,
+            state.discord.create_discord_user(
                 discord_id,
                 None,
             )
@@ -110,8 +291,7 @@ pub async fn create_discord_group(
         }
 
         // Add user to group
-        timesync_db::repositories::discord::add_member_to_group(
-            &state.db_pool,
+        state.discord.add_member_to_group(
             db_discord_group.id,
             discord_id,
         )
@@ -129,6 +309,19 @@ pub async fn create_discord_group(
     Ok(Json(response))
 }
 
+#[utoipa::path(
+    get,
+    path = "/discord/groups/{id}",
+    tag = "discord",
+    params(
+        ("id" = Uuid, Path, description = "Group ID"),
+    ),
+    responses(
+        (status = 200, description = "Group with its members", body = GetDiscordGroupResponse),
+        (status = 404, description = "No group with this ID", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 #[axum::debug_handler]
 pub async fn get_discord_group(
     State(state): State<Arc<ApiState>>,
@@ -136,13 +329,52 @@ pub async fn get_discord_group(
 ) -> Result<Json<GetDiscordGroupResponse>, AppError> {
     // Get discord group from database
     let db_discord_group =
-        timesync_db::repositories::discord::get_discord_group_by_id(&state.db_pool, id)
+        state.discord.get_discord_group_by_id(id)
             .await
             .map_err(TimeError::Database)?
             .ok_or_else(|| TimeError::NotFound(format!("Discord group with ID {} not found", id)))?;
 
+    Ok(Json(group_response(&state, db_discord_group).await?))
+}
+
+/// Get a group by its name within a server
+///
+/// # Errors
+///
+/// * `TimeError::NotFound` - The server has no group with this name
+/// * `TimeError::Database` - Database error
+#[utoipa::path(
+    get,
+    path = "/discord/servers/{server_id}/groups/{name}",
+    tag = "discord",
+    params(
+        ("server_id" = String, Path, description = "Discord server ID"),
+        ("name" = String, Path, description = "Group name"),
+    ),
+    responses(
+        (status = 200, description = "Group with its members", body = GetDiscordGroupResponse),
+        (status = 404, description = "The server has no group with this name", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+pub async fn get_discord_group_by_name(
+    State(state): State<Arc<ApiState>>,
+    Path((server_id, name)): Path<(String, String)>,
+) -> Result<Json<GetDiscordGroupResponse>, AppError> {
+    let db_discord_group =
+        state.discord.get_discord_group_by_name(&server_id, &name)
+            .await
+            .map_err(TimeError::Database)?
+            .ok_or_else(|| TimeError::NotFound(format!("Discord group {} not found", name)))?;
+
+    Ok(Json(group_response(&state, db_discord_group).await?))
+}
+
+/// A group along with its members and their linked schedules
+async fn group_response(state: &ApiState, db_discord_group: DbDiscordGroup) -> Result<GetDiscordGroupResponse, AppError> {
     // Get group members
-    let db_members = timesync_db::repositories::discord::get_group_members(&state.db_pool, id)
+    let db_members = state.discord.get_group_members(db_discord_group.id)
         .await
         .map_err(TimeError::Database)?;
 
@@ -150,8 +382,7 @@ pub async fn get_discord_group(
     let mut members = Vec::new();
     for member in db_members {
         let user =
-            timesync_db::repositories::discord::get_discord_user_by_id(
-                &state.db_pool,
+            state.discord.get_discord_user_by_id(
                 &member.discord_id,
             )
             .await
@@ -166,20 +397,37 @@ pub async fn get_discord_group(
         members.push(DiscordGroupMember {
             discord_id: member.discord_id,
             schedule_id: user.schedule_id,
+            required: member.required,
+            weight: member.weight,
         });
     }
 
-    let response = GetDiscordGroupResponse {
+    Ok(GetDiscordGroupResponse {
         id: db_discord_group.id,
         name: db_discord_group.name,
         server_id: db_discord_group.server_id,
         role_id: db_discord_group.role_id,
+        role_sync: db_discord_group.role_sync,
+        required: db_discord_group.required,
         members,
-    };
-
-    Ok(Json(response))
+        created_at: db_discord_group.created_at,
+    })
 }
 
+#[utoipa::path(
+    put,
+    path = "/discord/groups/{id}",
+    tag = "discord",
+    params(
+        ("id" = Uuid, Path, description = "Group ID"),
+    ),
+    request_body = UpdateDiscordGroupRequest,
+    responses(
+        (status = 200, description = "Group updated", body = UpdateDiscordGroupResponse),
+        (status = 404, description = "No group with this ID", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 #[axum::debug_handler]
 pub async fn update_discord_group(
     State(state): State<Arc<ApiState>>,
@@ -188,7 +436,7 @@ pub async fn update_discord_group(
 ) -> Result<Json<UpdateDiscordGroupResponse>, AppError> {
     // Check if group exists
     let group_exists =
-        timesync_db::repositories::discord::get_discord_group_by_id(&state.db_pool, id)
+        state.discord.get_discord_group_by_id(id)
             .await
             .map_err(TimeError::Database)?
             .is_some();
@@ -202,36 +450,22 @@ pub async fn update_discord_group(
 
     // Update group name if provided
     if let Some(name) = &payload.name {
-        timesync_db::repositories::discord::update_discord_group(&state.db_pool, id, Some(name), None)
+        state.discord.update_discord_group(id, Some(name), None)
             .await
             .map_err(TimeError::Database)?;
     }
 
-    // Add new members if provided
-    if let Some(add_member_ids) = &payload.add_member_ids {
-        for discord_id in add_member_ids {
-            // Ensure the user exists
-            let user_exists = timesync_db::repositories::discord::get_discord_user_by_id(
-                &state.db_pool,
-                discord_id,
-            )
+    // Tag the group as required or optional if provided
+    if let Some(required) = payload.required {
+        state.discord.set_group_required(id, required)
             .await
-            .map_err(TimeError::Database)?
-            .is_some();
-
-            if !user_exists {
-                // Create user if not exists
-                timesync_db::repositories::discord::create_discord_user(
-                    &state.db_pool,
-                    discord_id,
-                    None,
-                )
-                .await
-                .map_err(TimeError::Database)?;
-            }
+            .map_err(TimeError::Database)?;
+    }
 
-            // Add user to group
-            timesync_db::repositories::discord::add_member_to_group(&state.db_pool, id, discord_id)
+    // Add new members if provided, skipping anyone who is already a member
+    if let Some(add_member_ids) = &payload.add_member_ids {
+        for discord_id in add_member_ids {
+            state.discord.ensure_group_member(id, discord_id)
                 .await
                 .map_err(TimeError::Database)?;
         }
@@ -240,8 +474,7 @@ pub async fn update_discord_group(
     // Remove members if provided
     if let Some(remove_member_ids) = &payload.remove_member_ids {
         for discord_id in remove_member_ids {
-            timesync_db::repositories::discord::remove_member_from_group(
-                &state.db_pool,
+            state.discord.remove_member_from_group(
                 id,
                 discord_id,
             )
@@ -258,6 +491,19 @@ pub async fn update_discord_group(
     Ok(Json(response))
 }
 
+#[utoipa::path(
+    put,
+    path = "/discord/groups/{id}/role",
+    tag = "discord",
+    params(
+        ("id" = Uuid, Path, description = "Group ID"),
+    ),
+    request_body = UpdateDiscordGroupRoleRequest,
+    responses(
+        (status = 200, description = "Role set", body = UpdateDiscordGroupRoleResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 #[axum::debug_handler]
 pub async fn update_discord_group_role(
     State(state): State<Arc<ApiState>>,
@@ -266,8 +512,7 @@ pub async fn update_discord_group_role(
 ) -> Result<Json<UpdateDiscordGroupRoleResponse>, AppError> {
     // Update the role ID for the group
     // Update the group's role ID
-    let _updated_group = timesync_db::repositories::discord::update_group_role_id(
-        &state.db_pool,
+    let _updated_group = state.discord.update_group_role_id(
         id,
         &payload.role_id,
     )
@@ -281,4 +526,374 @@ pub async fn update_discord_group_role(
     };
 
     Ok(Json(response))
-}
\ No newline at end of file
+}
+
+/// Tag a group member as required or optional and set their matching weight
+///
+/// # Errors
+///
+/// * `TimeError::Validation` - Weight is negative or not a number
+/// * `TimeError::NotFound` - The user isn't a member of the group
+/// * `TimeError::Database` - Database error
+#[utoipa::path(
+    put,
+    path = "/discord/groups/{id}/members/{discord_id}",
+    tag = "discord",
+    params(
+        ("id" = Uuid, Path, description = "Group ID"),
+        ("discord_id" = String, Path, description = "Discord user ID"),
+    ),
+    request_body = UpdateGroupMemberRequest,
+    responses(
+        (status = 200, description = "Member updated", body = UpdateGroupMemberResponse),
+        (status = 400, description = "Weight is negative or not a number", body = ErrorResponse),
+        (status = 404, description = "The user isn't a member of the group", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+pub async fn update_group_member(
+    State(state): State<Arc<ApiState>>,
+    Path((id, discord_id)): Path<(Uuid, String)>,
+    Json(payload): Json<UpdateGroupMemberRequest>,
+) -> Result<Json<UpdateGroupMemberResponse>, AppError> {
+    if payload.weight.is_some_and(|weight| !weight.is_finite() || weight < 0.0) {
+        return Err(AppError(TimeError::Validation(
+            "Weight must be a non-negative number".to_string(),
+        )));
+    }
+
+    let member = state.discord.update_group_member(
+        id,
+        &discord_id,
+        payload.required,
+        payload.weight,
+    )
+    .await
+    .map_err(TimeError::Database)?
+    .ok_or_else(|| {
+        TimeError::NotFound(format!(
+            "Discord user {} is not a member of group {}",
+            discord_id, id
+        ))
+    })?;
+
+    let response = UpdateGroupMemberResponse {
+        group_id: member.group_id,
+        discord_id: member.discord_id,
+        required: member.required,
+        weight: member.weight,
+    };
+
+    Ok(Json(response))
+}
+
+/// List a server's groups, one page at a time, ordered by name
+///
+/// # Endpoint
+///
+/// ```http
+/// GET /discord/servers/:server_id/groups?limit=25&offset=0
+/// ```
+///
+/// # Errors
+///
+/// * `TimeError::Validation` - `limit` outside 1 to `MAX_GROUP_PAGE_SIZE`, or a negative `offset`
+/// * `TimeError::Database` - Database error
+#[utoipa::path(
+    get,
+    path = "/discord/servers/{server_id}/groups",
+    tag = "discord",
+    params(
+        ("server_id" = String, Path, description = "Discord server ID"),
+        ListGroupsQuery,
+    ),
+    responses(
+        (status = 200, description = "One page of the server's groups", body = ListDiscordGroupsResponse),
+        (status = 400, description = "Page size or offset out of range", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+pub async fn list_server_groups(
+    State(state): State<Arc<ApiState>>,
+    Path(server_id): Path<String>,
+    Query(query): Query<ListGroupsQuery>,
+) -> Result<Json<ListDiscordGroupsResponse>, AppError> {
+    let limit = query.limit.unwrap_or(DEFAULT_GROUP_PAGE_SIZE);
+    let offset = query.offset.unwrap_or(0);
+
+    if !(1..=MAX_GROUP_PAGE_SIZE).contains(&limit) {
+        return Err(AppError(TimeError::Validation(format!(
+            "Limit must be between 1 and {}",
+            MAX_GROUP_PAGE_SIZE
+        ))));
+    }
+    if offset < 0 {
+        return Err(AppError(TimeError::Validation("Offset can't be negative".to_string())));
+    }
+
+    let db_groups = state.discord.list_discord_groups_by_server(
+        &server_id,
+        limit,
+        offset,
+    )
+    .await
+    .map_err(TimeError::Database)?;
+
+    let total = state.discord.count_discord_groups_by_server(&server_id)
+        .await
+        .map_err(TimeError::Database)?;
+
+    let mut groups = Vec::with_capacity(db_groups.len());
+    for group in db_groups {
+        let members = state.discord.get_group_members(group.id)
+            .await
+            .map_err(TimeError::Database)?;
+
+        groups.push(DiscordGroupSummary {
+            id: group.id,
+            name: group.name,
+            server_id: group.server_id,
+            role_id: group.role_id,
+            role_sync: group.role_sync,
+            required: group.required,
+            member_count: members.len(),
+        });
+    }
+
+    let response = ListDiscordGroupsResponse {
+        groups,
+        total,
+        limit,
+        offset,
+    };
+
+    Ok(Json(response))
+}
+
+/// Delete a group along with its memberships
+///
+/// Meetings keep their attendees but forget the deleted group.
+///
+/// # Errors
+///
+/// * `TimeError::NotFound` - No group with this ID
+/// * `TimeError::Database` - Database error
+#[utoipa::path(
+    delete,
+    path = "/discord/groups/{id}",
+    tag = "discord",
+    params(
+        ("id" = Uuid, Path, description = "Group ID"),
+    ),
+    responses(
+        (status = 204, description = "Group deleted"),
+        (status = 404, description = "No group with this ID", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+pub async fn delete_discord_group(
+    State(state): State<Arc<ApiState>>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    let deleted = state.discord.delete_discord_group(id)
+        .await
+        .map_err(TimeError::Database)?;
+
+    if !deleted {
+        return Err(AppError(TimeError::NotFound(format!("Discord group with ID {} not found", id))));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Add someone to a group, succeeding if they're already a member
+///
+/// Creates the Discord user if needed.
+///
+/// # Errors
+///
+/// * `TimeError::NotFound` - No group with this ID
+/// * `TimeError::Database` - Database error
+#[utoipa::path(
+    post,
+    path = "/discord/groups/{id}/members/{discord_id}",
+    tag = "discord",
+    params(
+        ("id" = Uuid, Path, description = "Group ID"),
+        ("discord_id" = String, Path, description = "Discord user ID"),
+    ),
+    responses(
+        (status = 200, description = "Whether the user was added", body = AddGroupMemberResponse),
+        (status = 404, description = "No group with this ID", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+pub async fn add_group_member(
+    State(state): State<Arc<ApiState>>,
+    Path((id, discord_id)): Path<(Uuid, String)>,
+) -> Result<Json<AddGroupMemberResponse>, AppError> {
+    find_group(&state, id).await?;
+
+    let added = state.discord.ensure_group_member(id, &discord_id)
+        .await
+        .map_err(TimeError::Database)?;
+
+    let response = AddGroupMemberResponse {
+        group_id: id,
+        discord_id,
+        added,
+    };
+
+    Ok(Json(response))
+}
+
+/// Remove someone from a group; removing a non-member does nothing
+#[utoipa::path(
+    delete,
+    path = "/discord/groups/{id}/members/{discord_id}",
+    tag = "discord",
+    params(
+        ("id" = Uuid, Path, description = "Group ID"),
+        ("discord_id" = String, Path, description = "Discord user ID"),
+    ),
+    responses(
+        (status = 204, description = "User is no longer a member"),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+pub async fn remove_group_member(
+    State(state): State<Arc<ApiState>>,
+    Path((id, discord_id)): Path<(Uuid, String)>,
+) -> Result<StatusCode, AppError> {
+    state.discord.remove_member_from_group(id, &discord_id)
+        .await
+        .map_err(TimeError::Database)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Bind a group to a role so its membership follows the role's holders
+///
+/// The bot adds and removes members as the role changes hands.
+///
+/// # Errors
+///
+/// * `TimeError::NotFound` - No group with this ID
+/// * `TimeError::Database` - Database error
+#[utoipa::path(
+    put,
+    path = "/discord/groups/{id}/role-sync",
+    tag = "discord",
+    params(
+        ("id" = Uuid, Path, description = "Group ID"),
+    ),
+    request_body = UpdateDiscordGroupRoleRequest,
+    responses(
+        (status = 200, description = "Group now synced with the role", body = GetDiscordGroupResponse),
+        (status = 404, description = "No group with this ID", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+pub async fn bind_group_role(
+    State(state): State<Arc<ApiState>>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateDiscordGroupRoleRequest>,
+) -> Result<Json<GetDiscordGroupResponse>, AppError> {
+    find_group(&state, id).await?;
+
+    let db_discord_group = state.discord.bind_group_to_role(id, &payload.role_id)
+        .await
+        .map_err(TimeError::Database)?;
+
+    Ok(Json(group_response(&state, db_discord_group).await?))
+}
+
+/// Stop syncing a group's membership from its role, keeping its members
+///
+/// # Errors
+///
+/// * `TimeError::NotFound` - No group with this ID
+/// * `TimeError::Database` - Database error
+#[utoipa::path(
+    delete,
+    path = "/discord/groups/{id}/role-sync",
+    tag = "discord",
+    params(
+        ("id" = Uuid, Path, description = "Group ID"),
+    ),
+    responses(
+        (status = 200, description = "Group no longer synced with a role", body = GetDiscordGroupResponse),
+        (status = 404, description = "No group with this ID", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+pub async fn unbind_group_role(
+    State(state): State<Arc<ApiState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<GetDiscordGroupResponse>, AppError> {
+    find_group(&state, id).await?;
+
+    let db_discord_group = state.discord.unbind_group_from_role(id)
+        .await
+        .map_err(TimeError::Database)?;
+
+    Ok(Json(group_response(&state, db_discord_group).await?))
+}
+
+/// List the groups bound to a role, with their members
+///
+/// # Endpoint
+///
+/// ```http
+/// GET /discord/role-synced-groups?server_id=123
+/// ```
+#[utoipa::path(
+    get,
+    path = "/discord/role-synced-groups",
+    tag = "discord",
+    params(RoleSyncedGroupsQuery),
+    responses(
+        (status = 200, description = "Groups kept in sync with a role", body = Vec<GetDiscordGroupResponse>),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+pub async fn list_role_synced_groups(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<RoleSyncedGroupsQuery>,
+) -> Result<Json<Vec<GetDiscordGroupResponse>>, AppError> {
+    let db_groups = state.discord.get_role_synced_groups(
+        query.server_id.as_deref(),
+    )
+    .await
+    .map_err(TimeError::Database)?;
+
+    let mut groups = Vec::with_capacity(db_groups.len());
+    for group in db_groups {
+        groups.push(group_response(&state, group).await?);
+    }
+
+    Ok(Json(groups))
+}
+
+async fn find_group(state: &ApiState, id: Uuid) -> Result<DbDiscordGroup, AppError> {
+    let group = state.discord.get_discord_group_by_id(id)
+        .await
+        .map_err(TimeError::Database)?
+        .ok_or_else(|| TimeError::NotFound(format!("Discord group with ID {} not found", id)))?;
+
+    Ok(group)
+}
+
+fn validate_timezone(timezone: &str) -> Result<(), AppError> {
+    timezone.parse::<chrono_tz::Tz>()
+        .map(|_| ())
+        .map_err(|_| AppError(TimeError::Validation(format!("Unknown timezone: {}", timezone))))
+}
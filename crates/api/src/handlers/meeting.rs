@@ -0,0 +1,218 @@
+//! # Meeting Handlers
+//!
+//! Meetings are recorded when a /match poll in Discord is finalized, and can
+//! later be cancelled or rescheduled. Each meeting remembers the groups it was
+//! matched for and who agreed to attend.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use timesync_core::{
+    errors::TimeError,
+    models::meeting::{
+        CreateMeetingRequest, ListMeetingsResponse, Meeting, MeetingStatus, UpdateMeetingStatusRequest,
+    },
+};
+use timesync_db::models::{DbMeeting, NewMeeting};
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::{ApiState, middleware::error_handling::AppError};
+
+/// Query parameters for listing a server's upcoming meetings
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListMeetingsQuery {
+    /// Only list meetings with this status (default: scheduled)
+    pub status: Option<MeetingStatus>,
+}
+
+/// Record a meeting finalized from a poll
+///
+/// If the meeting replaces an earlier one, that meeting is marked as
+/// rescheduled.
+///
+/// # Errors
+///
+/// * `TimeError::Validation` - The meeting doesn't end after it starts
+/// * `TimeError::Database` - Database error
+#[utoipa::path(
+    post,
+    path = "/meetings",
+    tag = "meetings",
+    request_body = CreateMeetingRequest,
+    responses(
+        (status = 200, description = "Meeting recorded", body = Meeting),
+        (status = 400, description = "The meeting doesn't end after it starts", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+pub async fn create_meeting(
+    State(state): State<Arc<ApiState>>,
+    Json(payload): Json<CreateMeetingRequest>,
+) -> Result<Json<Meeting>, AppError> {
+    if payload.end_time <= payload.start_time {
+        return Err(AppError(TimeError::Validation(
+            "A meeting has to end after it starts".to_string(),
+        )));
+    }
+
+    let new_meeting = NewMeeting {
+        server_id: payload.server_id,
+        channel_id: payload.channel_id,
+        organizer_id: payload.organizer_id,
+        start_time: payload.start_time,
+        end_time: payload.end_time,
+        timezone: payload.timezone,
+        min_per_group: payload.min_per_group,
+        slot_duration: payload.slot_duration,
+        rescheduled_from: payload.rescheduled_from,
+        group_ids: payload.group_ids,
+        attendee_ids: payload.attendee_ids,
+    };
+
+    let db_meeting = state.meetings.create_meeting(&new_meeting)
+        .await
+        .map_err(TimeError::Database)?;
+
+    Ok(Json(meeting_response(&state, db_meeting).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/meetings/{id}",
+    tag = "meetings",
+    params(
+        ("id" = Uuid, Path, description = "Meeting ID"),
+    ),
+    responses(
+        (status = 200, description = "Meeting", body = Meeting),
+        (status = 404, description = "No meeting with this ID", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+pub async fn get_meeting(
+    State(state): State<Arc<ApiState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Meeting>, AppError> {
+    let db_meeting = find_meeting(&state, id).await?;
+
+    Ok(Json(meeting_response(&state, db_meeting).await?))
+}
+
+/// Cancel a meeting, or otherwise change its status
+///
+/// # Errors
+///
+/// * `TimeError::NotFound` - No meeting with this ID
+/// * `TimeError::Database` - Database error
+#[utoipa::path(
+    put,
+    path = "/meetings/{id}/status",
+    tag = "meetings",
+    params(
+        ("id" = Uuid, Path, description = "Meeting ID"),
+    ),
+    request_body = UpdateMeetingStatusRequest,
+    responses(
+        (status = 200, description = "Meeting with its new status", body = Meeting),
+        (status = 404, description = "No meeting with this ID", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+pub async fn update_meeting_status(
+    State(state): State<Arc<ApiState>>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateMeetingStatusRequest>,
+) -> Result<Json<Meeting>, AppError> {
+    find_meeting(&state, id).await?;
+
+    let db_meeting = state.meetings.update_meeting_status(id, payload.status)
+        .await
+        .map_err(TimeError::Database)?;
+
+    Ok(Json(meeting_response(&state, db_meeting).await?))
+}
+
+/// List a server's meetings that haven't ended yet, soonest first
+///
+/// # Endpoint
+///
+/// ```http
+/// GET /discord/servers/:server_id/meetings?status=scheduled
+/// ```
+#[utoipa::path(
+    get,
+    path = "/discord/servers/{server_id}/meetings",
+    tag = "meetings",
+    params(
+        ("server_id" = String, Path, description = "Discord server ID"),
+        ListMeetingsQuery,
+    ),
+    responses(
+        (status = 200, description = "The server's upcoming meetings", body = ListMeetingsResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+pub async fn list_server_meetings(
+    State(state): State<Arc<ApiState>>,
+    Path(server_id): Path<String>,
+    Query(query): Query<ListMeetingsQuery>,
+) -> Result<Json<ListMeetingsResponse>, AppError> {
+    let db_meetings = state.meetings.get_upcoming_meetings_by_server(
+        &server_id,
+        query.status.unwrap_or(MeetingStatus::Scheduled),
+    )
+    .await
+    .map_err(TimeError::Database)?;
+
+    let mut meetings = Vec::with_capacity(db_meetings.len());
+    for db_meeting in db_meetings {
+        meetings.push(meeting_response(&state, db_meeting).await?);
+    }
+
+    Ok(Json(ListMeetingsResponse { meetings }))
+}
+
+async fn find_meeting(state: &ApiState, id: Uuid) -> Result<DbMeeting, AppError> {
+    let db_meeting = state.meetings.get_meeting_by_id(id)
+        .await
+        .map_err(TimeError::Database)?
+        .ok_or_else(|| TimeError::NotFound(format!("Meeting with ID {} not found", id)))?;
+
+    Ok(db_meeting)
+}
+
+/// A meeting along with its groups and attendees
+async fn meeting_response(state: &ApiState, db_meeting: DbMeeting) -> Result<Meeting, AppError> {
+    let groups = state.meetings.get_meeting_groups(db_meeting.id)
+        .await
+        .map_err(TimeError::Database)?;
+    let attendees = state.meetings.get_meeting_attendees(db_meeting.id)
+        .await
+        .map_err(TimeError::Database)?;
+
+    Ok(Meeting {
+        id: db_meeting.id,
+        server_id: db_meeting.server_id,
+        channel_id: db_meeting.channel_id,
+        organizer_id: db_meeting.organizer_id,
+        start_time: db_meeting.start_time,
+        end_time: db_meeting.end_time,
+        timezone: db_meeting.timezone,
+        status: MeetingStatus::parse(&db_meeting.status).unwrap_or(MeetingStatus::Scheduled),
+        min_per_group: db_meeting.min_per_group,
+        slot_duration: db_meeting.slot_duration,
+        group_ids: groups.into_iter().map(|group| group.id).collect(),
+        attendee_ids: attendees.into_iter().map(|attendee| attendee.discord_id).collect(),
+        rescheduled_from: db_meeting.rescheduled_from,
+        created_at: db_meeting.created_at,
+    })
+}
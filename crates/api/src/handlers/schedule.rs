@@ -5,7 +5,7 @@ use std::sync::Arc;
 use timesync_core::{
     errors::TimeError,
     models::schedule::{
-        CreateScheduleRequest, CreateScheduleResponse, GetScheduleResponse, TimeSlotResponse,
+        CreateScheduleRequest, CreateScheduleResponse, GetScheduleResponse, SlotPreference, TimeSlotResponse,
         UpdateScheduleRequest, UpdateScheduleResponse, VerifyPasswordRequest, VerifyPasswordResponse,
     },
 };
@@ -13,6 +13,16 @@ use uuid::Uuid;
 
 use crate::{middleware::{auth, error_handling::AppError}, ApiState};
 
+#[utoipa::path(
+    post,
+    path = "/schedules",
+    tag = "schedules",
+    request_body = CreateScheduleRequest,
+    responses(
+        (status = 200, description = "Schedule created", body = CreateScheduleResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 #[axum::debug_handler]
 pub async fn create_schedule(
     State(state): State<Arc<ApiState>>,
@@ -25,8 +35,7 @@ pub async fn create_schedule(
     };
 
     // Create schedule in database
-    let db_schedule = timesync_db::repositories::schedule::create_schedule(
-        &state.db_pool,
+    let db_schedule = state.schedules.create_schedule(
         &payload.name,
         password_hash.as_deref(),
         &payload.timezone,
@@ -36,12 +45,13 @@ pub async fn create_schedule(
 
     // Create time slots if provided
     for slot in &payload.slots {
-        timesync_db::repositories::time_slot::create_time_slot(
-            &state.db_pool,
+        state.time_slots.create_time_slot(
             db_schedule.id,
             slot.start,
             slot.end,
             slot.is_recurring,
+            slot.is_unavailable,
+            slot.preference,
         )
         .await
         .map_err(TimeError::Database)?;
@@ -49,8 +59,7 @@ pub async fn create_schedule(
 
     // If discord_id is provided, associate schedule with Discord user
     if let Some(discord_id) = &payload.discord_id {
-        timesync_db::repositories::discord::create_discord_user(
-            &state.db_pool,
+        state.discord.create_discord_user(
             discord_id,
             Some(db_schedule.id),
         )
@@ -69,20 +78,32 @@ pub async fn create_schedule(
     Ok(Json(response))
 }
 
+#[utoipa::path(
+    get,
+    path = "/schedules/{id}",
+    tag = "schedules",
+    params(
+        ("id" = Uuid, Path, description = "Schedule ID"),
+    ),
+    responses(
+        (status = 200, description = "Schedule with its time slots", body = GetScheduleResponse),
+        (status = 404, description = "No schedule with this ID", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 #[axum::debug_handler]
 pub async fn get_schedule(
     State(state): State<Arc<ApiState>>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<GetScheduleResponse>, AppError> {
     // Get schedule from database
-    let db_schedule = timesync_db::repositories::schedule::get_schedule_by_id(&state.db_pool, id)
+    let db_schedule = state.schedules.get_schedule_by_id(id)
         .await
         .map_err(TimeError::Database)?
         .ok_or_else(|| TimeError::NotFound(format!("Schedule with ID {} not found", id)))?;
 
     // Get time slots for schedule
-    let time_slots = timesync_db::repositories::time_slot::get_time_slots_by_schedule_id(
-        &state.db_pool,
+    let time_slots = state.time_slots.get_time_slots_by_schedule_id(
         id,
     )
     .await
@@ -100,6 +121,8 @@ pub async fn get_schedule(
                 start: slot.start_time,
                 end: slot.end_time,
                 is_recurring: slot.is_recurring,
+                is_unavailable: slot.is_unavailable,
+                preference: SlotPreference::parse(&slot.preference).unwrap_or_default(),
             })
             .collect(),
     };
@@ -107,6 +130,21 @@ pub async fn get_schedule(
     Ok(Json(response))
 }
 
+#[utoipa::path(
+    put,
+    path = "/schedules/{id}",
+    tag = "schedules",
+    params(
+        ("id" = Uuid, Path, description = "Schedule ID"),
+    ),
+    request_body = UpdateScheduleRequest,
+    responses(
+        (status = 200, description = "Schedule updated", body = UpdateScheduleResponse),
+        (status = 401, description = "Missing or wrong password for a protected schedule", body = ErrorResponse),
+        (status = 404, description = "No schedule with this ID", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 #[axum::debug_handler]
 pub async fn update_schedule(
     State(state): State<Arc<ApiState>>,
@@ -115,7 +153,7 @@ pub async fn update_schedule(
 ) -> Result<Json<UpdateScheduleResponse>, AppError> {
     // Verify password if provided
     if let Some(password) = &payload.password {
-        let is_valid = auth::verify_schedule_password(&state.db_pool, id, password)
+        let is_valid = auth::verify_schedule_password(state.schedules.as_ref(), id, password)
             .await
             .map_err(TimeError::Database)?;
 
@@ -125,7 +163,7 @@ pub async fn update_schedule(
     } else {
         // Check if schedule is password-protected
         let db_schedule =
-            timesync_db::repositories::schedule::get_schedule_by_id(&state.db_pool, id)
+            state.schedules.get_schedule_by_id(id)
                 .await
                 .map_err(TimeError::Database)?
                 .ok_or_else(|| {
@@ -141,8 +179,7 @@ pub async fn update_schedule(
 
     // Update schedule name and timezone if provided
     if let Some(name) = &payload.name {
-        timesync_db::repositories::schedule::update_schedule(
-            &state.db_pool, 
+        state.schedules.update_schedule(
             id, 
             Some(name),
             payload.timezone.as_deref(),
@@ -151,8 +188,7 @@ pub async fn update_schedule(
         .map_err(TimeError::Database)?;
     } else if payload.timezone.is_some() {
         // Update just the timezone if name is not provided
-        timesync_db::repositories::schedule::update_schedule(
-            &state.db_pool, 
+        state.schedules.update_schedule(
             id, 
             None,
             payload.timezone.as_deref(),
@@ -161,25 +197,11 @@ pub async fn update_schedule(
         .map_err(TimeError::Database)?;
     }
 
-    // Update time slots
-    // First, delete existing time slots
-    timesync_db::repositories::time_slot::delete_time_slots_by_schedule_id(&state.db_pool, id)
+    // Replace the time slots in one go, so a failure keeps the old ones
+    state.time_slots.replace_time_slots(id, &payload.slots)
         .await
         .map_err(TimeError::Database)?;
 
-    // Then, create new time slots
-    for slot in &payload.slots {
-        timesync_db::repositories::time_slot::create_time_slot(
-            &state.db_pool,
-            id,
-            slot.start,
-            slot.end,
-            slot.is_recurring,
-        )
-        .await
-        .map_err(TimeError::Database)?;
-    }
-
     let response = UpdateScheduleResponse {
         id,
         updated_at: Utc::now(),
@@ -188,13 +210,26 @@ pub async fn update_schedule(
     Ok(Json(response))
 }
 
+#[utoipa::path(
+    post,
+    path = "/schedules/{id}/verify",
+    tag = "schedules",
+    params(
+        ("id" = Uuid, Path, description = "Schedule ID"),
+    ),
+    request_body = VerifyPasswordRequest,
+    responses(
+        (status = 200, description = "Whether the password matches", body = VerifyPasswordResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 #[axum::debug_handler]
 pub async fn verify_password(
     State(state): State<Arc<ApiState>>,
     Path(id): Path<Uuid>,
     Json(payload): Json<VerifyPasswordRequest>,
 ) -> Result<Json<VerifyPasswordResponse>, AppError> {
-    let is_valid = auth::verify_schedule_password(&state.db_pool, id, &payload.password)
+    let is_valid = auth::verify_schedule_password(state.schedules.as_ref(), id, &payload.password)
         .await
         .map_err(TimeError::Database)?;
 
@@ -13,8 +13,16 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
-use serde_json::json;
+use serde::Serialize;
 use timesync_core::errors::TimeError;
+use utoipa::ToSchema;
+
+/// JSON body of every error response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorResponse {
+    /// The error kind followed by what went wrong, e.g. `Validation error: Unknown timezone: Mars/Base`
+    pub error: String,
+}
 
 /// Application error wrapper that provides HTTP status code mapping
 ///
@@ -73,8 +81,7 @@ impl IntoResponse for AppError {
         };
     
         // Get the error message and format as JSON
-        let message = self.0.to_string();
-        let body = Json(json!({ "error": message }));
+        let body = Json(ErrorResponse { error: self.0.to_string() });
     
         // Combine status code and JSON body into a response
         (status, body).into_response()
@@ -0,0 +1,131 @@
+//! # OpenAPI Specification
+//!
+//! The OpenAPI document is generated from the `#[utoipa::path]` attributes on
+//! the handlers and the `timesync_core::models` types they take and return, so
+//! it changes whenever an endpoint does. It is served at `/api/openapi.json`
+//! and rendered by the docs page at `/api/docs`.
+//!
+//! Paths are relative to the `/api` prefix the routes are nested under, which
+//! the document lists as its server.
+
+use timesync_core::models::{
+    discord::{
+        AddGroupMemberResponse, CreateDiscordGroupRequest, CreateDiscordGroupResponse, CreateDiscordUserRequest,
+        CreateDiscordUserResponse, DiscordGroupMember, DiscordGroupSummary, DiscordServerResponse,
+        GetDiscordGroupResponse, GetDiscordUserResponse, ListDiscordGroupsResponse, MatchGroupResult,
+        MatchResponse, MatchResult, SetTimezoneRequest, UpdateDiscordGroupRequest, UpdateDiscordGroupResponse,
+        UpdateDiscordGroupRoleRequest, UpdateDiscordGroupRoleResponse, UpdateGroupMemberRequest,
+        UpdateGroupMemberResponse,
+    },
+    meeting::{CreateMeetingRequest, ListMeetingsResponse, Meeting, MeetingStatus, UpdateMeetingStatusRequest},
+    schedule::{
+        CreateScheduleRequest, CreateScheduleResponse, CreateTimeSlotRequest, GetScheduleResponse,
+        ParseAvailabilityRequest, ParseAvailabilityResponse, SlotPreference, TimeSlotResponse,
+        UpdateScheduleRequest, UpdateScheduleResponse, VerifyPasswordRequest, VerifyPasswordResponse,
+    },
+};
+use utoipa::OpenApi;
+
+use crate::{
+    handlers,
+    middleware::error_handling::ErrorResponse,
+    routes::health::{self, HealthResponse, VersionResponse},
+};
+
+/// The TimeSync API's OpenAPI document
+///
+/// Every route in `api_routes` has to be listed under `paths`, and every type
+/// its handlers use under `components`.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "TimeSync API",
+        description = "Share availability, find times groups can meet and keep track of the meetings that come of it.",
+        license(name = "MIT")
+    ),
+    servers((url = "/api")),
+    paths(
+        health::health_check,
+        health::version,
+        handlers::schedule::create_schedule,
+        handlers::schedule::get_schedule,
+        handlers::schedule::update_schedule,
+        handlers::schedule::verify_password,
+        handlers::discord::create_discord_user,
+        handlers::discord::get_discord_user,
+        handlers::discord::set_discord_user_timezone,
+        handlers::discord::get_discord_server,
+        handlers::discord::set_discord_server_timezone,
+        handlers::discord::create_discord_group,
+        handlers::discord::get_discord_group,
+        handlers::discord::update_discord_group,
+        handlers::discord::delete_discord_group,
+        handlers::discord::list_server_groups,
+        handlers::discord::get_discord_group_by_name,
+        handlers::discord::list_role_synced_groups,
+        handlers::discord::update_discord_group_role,
+        handlers::discord::bind_group_role,
+        handlers::discord::unbind_group_role,
+        handlers::discord::update_group_member,
+        handlers::discord::add_group_member,
+        handlers::discord::remove_group_member,
+        handlers::availability::match_availability,
+        handlers::availability::availability_heatmap,
+        handlers::availability::parse_availability,
+        handlers::meeting::create_meeting,
+        handlers::meeting::get_meeting,
+        handlers::meeting::update_meeting_status,
+        handlers::meeting::list_server_meetings,
+    ),
+    components(schemas(
+        ErrorResponse,
+        HealthResponse,
+        VersionResponse,
+        CreateScheduleRequest,
+        CreateScheduleResponse,
+        CreateTimeSlotRequest,
+        GetScheduleResponse,
+        TimeSlotResponse,
+        SlotPreference,
+        UpdateScheduleRequest,
+        UpdateScheduleResponse,
+        VerifyPasswordRequest,
+        VerifyPasswordResponse,
+        ParseAvailabilityRequest,
+        ParseAvailabilityResponse,
+        CreateDiscordUserRequest,
+        CreateDiscordUserResponse,
+        GetDiscordUserResponse,
+        SetTimezoneRequest,
+        DiscordServerResponse,
+        CreateDiscordGroupRequest,
+        CreateDiscordGroupResponse,
+        GetDiscordGroupResponse,
+        DiscordGroupMember,
+        DiscordGroupSummary,
+        ListDiscordGroupsResponse,
+        UpdateDiscordGroupRequest,
+        UpdateDiscordGroupResponse,
+        UpdateDiscordGroupRoleRequest,
+        UpdateDiscordGroupRoleResponse,
+        UpdateGroupMemberRequest,
+        UpdateGroupMemberResponse,
+        AddGroupMemberResponse,
+        MatchResponse,
+        MatchResult,
+        MatchGroupResult,
+        Meeting,
+        MeetingStatus,
+        CreateMeetingRequest,
+        UpdateMeetingStatusRequest,
+        ListMeetingsResponse,
+    )),
+    tags(
+        (name = "health", description = "Liveness and version"),
+        (name = "schedules", description = "Schedules and their time slots"),
+        (name = "discord", description = "Discord users, servers and groups"),
+        (name = "availability", description = "Matching, heatmaps and free-text parsing"),
+        (name = "meetings", description = "Meetings finalized from Discord polls"),
+    )
+)]
+pub struct ApiDoc;
@@ -1,4 +1,6 @@
 pub mod health;
 pub mod schedule;
 pub mod discord;
-pub mod availability;
\ No newline at end of file
+pub mod availability;
+pub mod meeting;
+pub mod docs;
\ No newline at end of file
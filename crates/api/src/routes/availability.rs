@@ -1,11 +1,20 @@
-use axum::{routing::get, Router};
+use axum::{routing::{get, post}, Router};
 use std::sync::Arc;
 
 use crate::{handlers, ApiState};
 
 pub fn routes() -> Router<Arc<ApiState>> {
-    Router::new().route(
-        "/availability/match",
-        get(handlers::availability::match_availability),
-    )
+    Router::new()
+        .route(
+            "/availability/match",
+            get(handlers::availability::match_availability),
+        )
+        .route(
+            "/availability/heatmap.png",
+            get(handlers::availability::availability_heatmap),
+        )
+        .route(
+            "/availability/parse",
+            post(handlers::availability::parse_availability),
+        )
 }
\ No newline at end of file
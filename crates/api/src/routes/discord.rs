@@ -1,5 +1,5 @@
 use axum::{
-    routing::{get, post, put},
+    routing::{delete, get, post, put},
     Router,
 };
 use std::sync::Arc;
@@ -16,6 +16,18 @@ pub fn routes() -> Router<Arc<ApiState>> {
             "/discord/users/:discord_id",
             get(handlers::discord::get_discord_user),
         )
+        .route(
+            "/discord/users/:discord_id/timezone",
+            put(handlers::discord::set_discord_user_timezone),
+        )
+        .route(
+            "/discord/servers/:server_id",
+            get(handlers::discord::get_discord_server),
+        )
+        .route(
+            "/discord/servers/:server_id/timezone",
+            put(handlers::discord::set_discord_server_timezone),
+        )
         .route(
             "/discord/groups",
             post(handlers::discord::create_discord_group),
@@ -28,8 +40,44 @@ pub fn routes() -> Router<Arc<ApiState>> {
             "/discord/groups/:id",
             put(handlers::discord::update_discord_group),
         )
+        .route(
+            "/discord/groups/:id",
+            delete(handlers::discord::delete_discord_group),
+        )
+        .route(
+            "/discord/servers/:server_id/groups",
+            get(handlers::discord::list_server_groups),
+        )
+        .route(
+            "/discord/servers/:server_id/groups/:name",
+            get(handlers::discord::get_discord_group_by_name),
+        )
+        .route(
+            "/discord/role-synced-groups",
+            get(handlers::discord::list_role_synced_groups),
+        )
         .route(
             "/discord/groups/:id/role",
             put(handlers::discord::update_discord_group_role),
         )
+        .route(
+            "/discord/groups/:id/role-sync",
+            put(handlers::discord::bind_group_role),
+        )
+        .route(
+            "/discord/groups/:id/role-sync",
+            delete(handlers::discord::unbind_group_role),
+        )
+        .route(
+            "/discord/groups/:id/members/:discord_id",
+            put(handlers::discord::update_group_member),
+        )
+        .route(
+            "/discord/groups/:id/members/:discord_id",
+            post(handlers::discord::add_group_member),
+        )
+        .route(
+            "/discord/groups/:id/members/:discord_id",
+            delete(handlers::discord::remove_group_member),
+        )
 }
\ No newline at end of file
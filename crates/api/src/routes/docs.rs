@@ -0,0 +1,42 @@
+use axum::{
+    http::header,
+    response::{Html, IntoResponse},
+    routing::get,
+    Json, Router,
+};
+use std::sync::Arc;
+use utoipa::OpenApi;
+
+use crate::{openapi::ApiDoc, ApiState};
+
+/// Swagger UI page that renders `openapi.json`
+const DOCS_PAGE: &str = include_str!("docs.html");
+
+/// Swagger UI's dist files (v5.17.14, Apache-2.0), so the docs page works
+/// offline and without loading anything from a CDN
+const SWAGGER_UI_JS: &str = include_str!("swagger-ui/swagger-ui-bundle.js");
+const SWAGGER_UI_CSS: &str = include_str!("swagger-ui/swagger-ui.css");
+
+async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+async fn docs_page() -> Html<&'static str> {
+    Html(DOCS_PAGE)
+}
+
+async fn swagger_ui_js() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "text/javascript")], SWAGGER_UI_JS)
+}
+
+async fn swagger_ui_css() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "text/css")], SWAGGER_UI_CSS)
+}
+
+pub fn routes() -> Router<Arc<ApiState>> {
+    Router::new()
+        .route("/openapi.json", get(openapi_json))
+        .route("/docs", get(docs_page))
+        .route("/docs/swagger-ui-bundle.js", get(swagger_ui_js))
+        .route("/docs/swagger-ui.css", get(swagger_ui_css))
+}
@@ -4,25 +4,42 @@ use axum::{
 };
 use serde::Serialize;
 use std::sync::Arc;
+use utoipa::ToSchema;
 
 use crate::ApiState;
 
-#[derive(Serialize)]
-struct HealthResponse {
+#[derive(Serialize, ToSchema)]
+pub struct HealthResponse {
     status: String,
 }
 
-#[derive(Serialize)]
-struct VersionResponse {
+#[derive(Serialize, ToSchema)]
+pub struct VersionResponse {
     version: String,
 }
 
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "health",
+    responses(
+        (status = 200, description = "The server is up", body = HealthResponse),
+    )
+)]
 async fn health_check() -> Json<HealthResponse> {
     Json(HealthResponse {
         status: "ok".to_string(),
     })
 }
 
+#[utoipa::path(
+    get,
+    path = "/version",
+    tag = "health",
+    responses(
+        (status = 200, description = "Version of the running server", body = VersionResponse),
+    )
+)]
 async fn version() -> Json<VersionResponse> {
     Json(VersionResponse {
         version: env!("CARGO_PKG_VERSION").to_string(),
@@ -33,4 +50,4 @@ pub fn routes() -> Router<Arc<ApiState>> {
     Router::new()
         .route("/health", get(health_check))
         .route("/version", get(version))
-}
\ No newline at end of file
+}
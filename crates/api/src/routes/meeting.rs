@@ -0,0 +1,21 @@
+use axum::{
+    routing::{get, post, put},
+    Router,
+};
+use std::sync::Arc;
+
+use crate::{handlers, ApiState};
+
+pub fn routes() -> Router<Arc<ApiState>> {
+    Router::new()
+        .route("/meetings", post(handlers::meeting::create_meeting))
+        .route("/meetings/:id", get(handlers::meeting::get_meeting))
+        .route(
+            "/meetings/:id/status",
+            put(handlers::meeting::update_meeting_status),
+        )
+        .route(
+            "/discord/servers/:server_id/meetings",
+            get(handlers::meeting::list_server_meetings),
+        )
+}
@@ -1,4 +1,5 @@
 mod schedule_test;
 mod discord_test;
 mod availability_test;
-mod middleware_test;
\ No newline at end of file
+mod middleware_test;
+mod openapi_test;
\ No newline at end of file
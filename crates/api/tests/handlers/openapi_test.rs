@@ -0,0 +1,114 @@
+use std::path::PathBuf;
+
+use axum::{body::{to_bytes, Body}, http::{Request, StatusCode}};
+use pretty_assertions::assert_eq;
+use timesync_api::{api_routes, openapi::ApiDoc};
+use tower::ServiceExt;
+use utoipa::OpenApi;
+
+use crate::test_utils::TestContext;
+
+fn snapshot_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots/openapi.json")
+}
+
+/// Fails when the generated spec drifts from the committed one
+///
+/// After an intended API change, rerun with `UPDATE_SNAPSHOTS=1` to rewrite
+/// the snapshot and commit it alongside the change.
+#[test]
+fn test_openapi_spec_matches_snapshot() {
+    let spec = ApiDoc::openapi().to_pretty_json().unwrap() + "\n";
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::write(snapshot_path(), &spec).unwrap();
+        return;
+    }
+
+    let snapshot = std::fs::read_to_string(snapshot_path())
+        .expect("missing snapshot, run with UPDATE_SNAPSHOTS=1 to create it");
+    assert_eq!(
+        snapshot, spec,
+        "OpenAPI spec changed; if intended, rerun with UPDATE_SNAPSHOTS=1 and commit the snapshot"
+    );
+}
+
+#[test]
+fn test_openapi_spec_documents_every_route() {
+    let spec = ApiDoc::openapi();
+    let paths: Vec<&str> = spec.paths.paths.keys().map(String::as_str).collect();
+
+    for path in [
+        "/health",
+        "/version",
+        "/schedules",
+        "/schedules/{id}",
+        "/schedules/{id}/verify",
+        "/discord/users",
+        "/discord/users/{discord_id}",
+        "/discord/users/{discord_id}/timezone",
+        "/discord/servers/{server_id}",
+        "/discord/servers/{server_id}/timezone",
+        "/discord/servers/{server_id}/groups",
+        "/discord/servers/{server_id}/groups/{name}",
+        "/discord/servers/{server_id}/meetings",
+        "/discord/groups",
+        "/discord/groups/{id}",
+        "/discord/groups/{id}/role",
+        "/discord/groups/{id}/role-sync",
+        "/discord/groups/{id}/members/{discord_id}",
+        "/discord/role-synced-groups",
+        "/availability/match",
+        "/availability/heatmap.png",
+        "/availability/parse",
+        "/meetings",
+        "/meetings/{id}",
+        "/meetings/{id}/status",
+    ] {
+        assert!(paths.contains(&path), "{} is missing from the spec", path);
+    }
+    assert_eq!(paths.len(), 25);
+}
+
+#[tokio::test]
+async fn test_openapi_json_is_served() {
+    let app = api_routes().with_state(TestContext::new().build_state());
+
+    let response = app
+        .oneshot(Request::get("/openapi.json").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let served: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(served, serde_json::to_value(ApiDoc::openapi()).unwrap());
+}
+
+#[tokio::test]
+async fn test_docs_page_is_served() {
+    let app = api_routes().with_state(TestContext::new().build_state());
+
+    let response = app
+        .oneshot(Request::get("/docs").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let page = String::from_utf8(body.to_vec()).unwrap();
+    assert!(page.contains("url: 'openapi.json'"));
+
+    // Everything the page loads is served by the API itself
+    assert!(!page.contains("http://") && !page.contains("https://") && !page.contains("=\"//"));
+    for asset in ["/docs/swagger-ui-bundle.js", "/docs/swagger-ui.css"] {
+        assert!(page.contains(&format!("\"{}\"", asset.trim_start_matches('/'))));
+
+        let response = api_routes()
+            .with_state(TestContext::new().build_state())
+            .oneshot(Request::get(asset).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{}", asset);
+    }
+}
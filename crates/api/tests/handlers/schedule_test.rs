@@ -1,317 +1,155 @@
-use axum::Json;
+use axum::{
+    extract::{Path, State},
+    Json,
+};
 use chrono::Utc;
-use mockall::predicate;
 use timesync_core::{
     errors::TimeError,
     models::schedule::{
-        CreateScheduleRequest, CreateTimeSlotRequest, GetScheduleResponse, UpdateScheduleRequest,
+        CreateScheduleRequest, CreateTimeSlotRequest, SlotPreference, UpdateScheduleRequest, VerifyPasswordRequest,
     },
 };
-use timesync_db::models::{DbSchedule, DbTimeSlot};
 use uuid::Uuid;
 
 use crate::test_utils::TestContext;
-use timesync_api::middleware::error_handling::AppError;
-
-// Create test wrappers for handlers that directly test what we want
-async fn test_get_schedule_wrapper(
-    ctx: &mut TestContext,
-    id: Uuid
-) -> Result<Json<GetScheduleResponse>, AppError> {
-    // This replaces the real DB calls with our mocks
-    let db_schedule = ctx.schedule_repo.get_schedule_by_id(id).await;
-    if let Ok(Some(schedule)) = db_schedule {
-        let time_slots = ctx.time_slot_repo.get_time_slots_by_schedule_id(id).await.unwrap_or_default();
-        
-        let response = GetScheduleResponse {
-            id: schedule.id,
-            name: schedule.name,
-            created_at: schedule.created_at,
-            is_editable: schedule.password_hash.is_some(),
-            timezone: schedule.timezone.clone(),
-            slots: time_slots
-                .into_iter()
-                .map(|slot| timesync_core::models::schedule::TimeSlotResponse {
-                    start: slot.start_time,
-                    end: slot.end_time,
-                    is_recurring: slot.is_recurring,
-                })
-                .collect(),
-        };
-        
-        Ok(Json(response))
-    } else if let Ok(None) = db_schedule {
-        Err(AppError(TimeError::NotFound(format!("Schedule with ID {} not found", id))))
-    } else {
-        Err(AppError(TimeError::Database(eyre::eyre!("Database error"))))
-    }
-}
+use timesync_api::handlers::schedule::*;
 
-// Add wrapper for update_schedule
-async fn test_update_schedule_wrapper(
-    ctx: &mut TestContext,
-    id: Uuid,
-    request: UpdateScheduleRequest,
-) -> Result<Json<GetScheduleResponse>, AppError> {
-    // Check if the schedule exists and if a password is required
-    let schedule = match ctx.schedule_repo.get_schedule_by_id(id).await? {
-        Some(schedule) => schedule,
-        None => return Err(AppError(TimeError::NotFound(format!("Schedule with ID {} not found", id)))),
-    };
-    
-    // If the schedule is password-protected, verify the password
-    if let Some(_password_hash) = &schedule.password_hash {
-        if let Some(password) = &request.password {
-            // Use static reference for mock
-            let password_static = Box::leak(password.clone().into_boxed_str());
-            
-            let is_valid = ctx.schedule_repo.verify_password(id, password_static).await?;
-            if !is_valid {
-                return Err(AppError(TimeError::Authentication("Invalid password".into())));
-            }
-        } else {
-            // Password is required but not provided
-            return Err(AppError(TimeError::Authentication("Password required".into())));
-        }
-    }
-    
-    // Process the update requests
-    let update_name = if let Some(name) = &request.name {
-        // Create static str for mockall
-        let name_str: &'static str = Box::leak(name.clone().into_boxed_str());
-        Some(name_str)
-    } else {
-        None
-    };
-    
-    // Call update with the 'static str
-    let updated_schedule = ctx.schedule_repo.update_schedule(id, update_name).await?;
-    
-    // Delete existing time slots and create new ones
-    if !request.slots.is_empty() {
-        ctx.time_slot_repo.delete_time_slots_by_schedule_id(id).await?;
-        
-        for slot in &request.slots {
-            ctx.time_slot_repo.create_time_slot(id, slot.start, slot.end).await?;
-        }
+fn slot(hours_from_now: i64, preference: SlotPreference) -> CreateTimeSlotRequest {
+    let start = Utc::now() + chrono::Duration::hours(hours_from_now);
+
+    CreateTimeSlotRequest {
+        start,
+        end: start + chrono::Duration::hours(1),
+        is_recurring: false,
+        is_unavailable: false,
+        preference,
     }
-    
-    // Get the updated time slots
-    let time_slots = ctx.time_slot_repo.get_time_slots_by_schedule_id(id).await?;
-    
-    // Return the response
-    Ok(Json(GetScheduleResponse {
-        id: updated_schedule.id,
-        name: updated_schedule.name,
-        created_at: updated_schedule.created_at,
-        is_editable: updated_schedule.password_hash.is_some(),
-        timezone: updated_schedule.timezone.clone(),
-        slots: time_slots
-            .into_iter()
-            .map(|slot| timesync_core::models::schedule::TimeSlotResponse {
-                start: slot.start_time,
-                end: slot.end_time,
-                is_recurring: slot.is_recurring,
-            })
-            .collect(),
-    }))
 }
 
-// Add wrapper for verify_password 
-async fn test_verify_password_wrapper(
-    ctx: &mut TestContext,
-    id: Uuid,
-    password: String,
-) -> Result<Json<timesync_core::models::schedule::VerifyPasswordResponse>, AppError> {
-    // Create a static reference for mockall
-    let password_static = Box::leak(password.into_boxed_str());
-    
-    // Verify the password
-    let is_valid = ctx.schedule_repo.verify_password(id, password_static).await?;
-    
-    // Return the response
-    Ok(Json(timesync_core::models::schedule::VerifyPasswordResponse {
-        valid: is_valid,
-    }))
+fn update_request(name: Option<&str>, password: Option<&str>) -> UpdateScheduleRequest {
+    UpdateScheduleRequest {
+        name: name.map(str::to_string),
+        timezone: Some("UTC".to_string()),
+        slots: vec![],
+        password: password.map(str::to_string),
+    }
 }
 
-// For create_schedule test, we'll use a different approach
-// Instead of trying to simulate the handler, we'll just check that the mock expectations are met
-
 #[tokio::test]
 async fn test_create_schedule_success() {
-    let mut ctx = TestContext::new();
-    let schedule_id = Uuid::new_v4();
-    let now = Utc::now();
-    let name = "Test Schedule".to_string();
-    
-    // Set up schedule repository mock
-    ctx.schedule_repo.expect_create_schedule()
-        .with(predicate::eq("Test Schedule"), predicate::always())
-        .returning(move |name, _| {
-            Ok(DbSchedule {
-                id: schedule_id,
-                name: name.to_string(),
-                timezone: "UTC".to_string(),
-                password_hash: None,
-                created_at: now,
-            })
-        });
-
-    // Set up time_slot repository mock
-    ctx.time_slot_repo.expect_create_time_slot()
-        .times(0) // We don't expect any time slots to be created
-        .returning(|_, _, _| {
-            panic!("Should not be called")
-        });
-
-    // Set up discord_user repository mock
-    ctx.discord_user_repo.expect_create_discord_user()
-        .times(0) // We don't expect any discord users to be created
-        .returning(|_, _| {
-            panic!("Should not be called")
-        });
-    
-    // Create the request payload
-    let _request = CreateScheduleRequest {
-        name: name.clone(),
+    let ctx = TestContext::new();
+
+    let request = CreateScheduleRequest {
+        name: "Test Schedule".to_string(),
         password: None,
         slots: vec![],
         discord_id: None,
         timezone: "UTC".to_string(),
     };
-    
-    // The mocks are set up with expectations, and since we're not calling the real DB,
-    // let's just assert that the repository would be called with the right values
-    // We'll simulate what we would expect to happen
-
-    // First we'd create the schedule
-    let _db_schedule = DbSchedule {
-        id: schedule_id,
-        name: name.clone(),
-        password_hash: None,
-        created_at: now,
-        timezone: "UTC".to_string(),
-    };
-    
-    // Then we'd get a successful response
-    let expected_response = timesync_core::models::schedule::CreateScheduleResponse {
-        id: schedule_id,
-        name,
-        created_at: now,
-        is_editable: false,
-        timezone: "UTC".to_string(),
-    };
-    
-    // Assert the expected values match what we'd expect from the handler
-    assert_eq!(expected_response.id, schedule_id);
-    assert_eq!(expected_response.name, "Test Schedule");
-    assert_eq!(expected_response.is_editable, false);
+
+    let response = create_schedule(State(ctx.build_state()), Json(request)).await.unwrap().0;
+
+    assert_eq!(response.name, "Test Schedule");
+    assert_eq!(response.timezone, "UTC");
+    assert!(!response.is_editable);
+
+    let stored = ctx.state.schedules.get_schedule_by_id(response.id).await.unwrap().unwrap();
+    assert_eq!(stored.name, "Test Schedule");
+    assert!(stored.password_hash.is_none());
 }
 
 #[tokio::test]
 async fn test_create_schedule_with_slots() {
-    let _ctx = TestContext::new();
-    let schedule_id = Uuid::new_v4();
-    let now = Utc::now();
-    let start_time = now;
-    let end_time = now + chrono::Duration::hours(1);
-    
-    // For this simplified test approach, we won't set expectations that require calls
-    // We're just going to simulate what would happen
-    
-    // Create the request payload with time slots
-    let _request = CreateScheduleRequest {
+    let ctx = TestContext::new();
+
+    let request = CreateScheduleRequest {
         name: "Test Schedule".to_string(),
         password: None,
         timezone: "UTC".to_string(),
-        slots: vec![
-            CreateTimeSlotRequest {
-                start: start_time,
-                end: end_time,
-                is_recurring: false,
-            }
-        ],
+        slots: vec![slot(1, SlotPreference::Available), slot(3, SlotPreference::IfNeedBe)],
         discord_id: None,
     };
-    
-    // We will not call the handler directly, since it would try to connect to a DB
-    
-    // Instead, just validate our expectations are correctly set up
-    let expected_response = timesync_core::models::schedule::CreateScheduleResponse {
-        id: schedule_id,
-        name: "Test Schedule".to_string(),
-        created_at: now,
-        is_editable: false,
-        timezone: "UTC".to_string(),
-    };
-    
-    // Assert basic expectations would be met
-    assert_eq!(expected_response.id, schedule_id);
+
+    let response = create_schedule(State(ctx.build_state()), Json(request)).await.unwrap().0;
+
+    let slots = ctx.state.time_slots.get_time_slots_by_schedule_id(response.id).await.unwrap();
+    assert_eq!(slots.len(), 2);
+    assert_eq!(slots[0].preference, "available");
+    assert_eq!(slots[1].preference, "if_need_be");
 }
 
 #[tokio::test]
 async fn test_create_schedule_with_discord_user() {
-    let _ctx = TestContext::new();
-    let schedule_id = Uuid::new_v4();
-    let now = Utc::now();
-    let discord_id = "discord123".to_string();
-    
-    // For this simplified test approach, we'll just simulate what would happen
-    
-    // Create the request payload with discord_id
-    let _request = CreateScheduleRequest {
+    let ctx = TestContext::new();
+
+    let request = CreateScheduleRequest {
         name: "Test Schedule".to_string(),
         password: None,
         timezone: "UTC".to_string(),
         slots: vec![],
-        discord_id: Some(discord_id),
+        discord_id: Some("discord123".to_string()),
     };
-    
-    // Just validate our expectations would be met
-    // The handlers would use these values
-    let expected_response = timesync_core::models::schedule::CreateScheduleResponse {
-        id: schedule_id,
+
+    let response = create_schedule(State(ctx.build_state()), Json(request)).await.unwrap().0;
+
+    let user = ctx.state.discord.get_discord_user_by_id("discord123").await.unwrap().unwrap();
+    assert_eq!(user.schedule_id, Some(response.id));
+}
+
+#[tokio::test]
+async fn test_create_schedule_with_password_is_editable() {
+    let ctx = TestContext::new();
+
+    let request = CreateScheduleRequest {
         name: "Test Schedule".to_string(),
-        created_at: now,
-        is_editable: false,
+        password: Some("password123".to_string()),
         timezone: "UTC".to_string(),
+        slots: vec![],
+        discord_id: None,
     };
-    
-    // Assert the expected values are what we'd expect
-    assert_eq!(expected_response.id, schedule_id);
-    assert_eq!(expected_response.name, "Test Schedule");
+
+    let response = create_schedule(State(ctx.build_state()), Json(request)).await.unwrap().0;
+    assert!(response.is_editable);
+
+    // Only the hash is stored
+    let stored = ctx.state.schedules.get_schedule_by_id(response.id).await.unwrap().unwrap();
+    assert_ne!(stored.password_hash.as_deref(), Some("password123"));
 }
 
 #[tokio::test]
 async fn test_get_schedule_has_password() {
-    // Skip test entirely - this is a placeholder for a Rstest case that isn't working
-    return;
+    let ctx = TestContext::new();
+    let schedule = ctx.create_schedule("Protected", Some("password123")).await;
+    let start = Utc::now();
+    ctx.add_slot(&schedule, start, start + chrono::Duration::hours(1)).await;
+
+    let response = get_schedule(State(ctx.build_state()), Path(schedule.id)).await.unwrap().0;
+
+    assert_eq!(response.id, schedule.id);
+    assert_eq!(response.name, "Protected");
+    assert!(response.is_editable);
+    assert_eq!(response.slots.len(), 1);
+    assert_eq!(response.slots[0].start, start);
 }
 
 #[tokio::test]
 async fn test_get_schedule_no_password() {
-    // Skip test entirely - this is a placeholder for a Rstest case that isn't working
-    return;
+    let ctx = TestContext::new();
+    let schedule = ctx.create_schedule("Open", None).await;
+
+    let response = get_schedule(State(ctx.build_state()), Path(schedule.id)).await.unwrap().0;
+
+    assert_eq!(response.name, "Open");
+    assert!(!response.is_editable);
+    assert!(response.slots.is_empty());
 }
 
 #[tokio::test]
 async fn test_get_schedule_not_found() {
-    let mut ctx = TestContext::new();
-    let id = Uuid::new_v4();
-    
-    // Set up schedule repository mock
-    ctx.schedule_repo.expect_get_schedule_by_id()
-        .with(predicate::eq(id))
-        .returning(move |_| {
-            Ok(None)
-        });
-    
-    // Call our wrapper function instead
-    let result = test_get_schedule_wrapper(&mut ctx, id).await;
-    
-    // Assert the error response
-    assert!(result.is_err());
+    let ctx = TestContext::new();
+
+    let result = get_schedule(State(ctx.build_state()), Path(Uuid::new_v4())).await;
+
     match result.unwrap_err().0 {
         TimeError::NotFound(_) => {}, // Expected
         e => panic!("Expected NotFound error, got: {:?}", e),
@@ -320,251 +158,119 @@ async fn test_get_schedule_not_found() {
 
 #[tokio::test]
 async fn test_update_schedule_success() {
-    let mut ctx = TestContext::new();
-    let id = Uuid::new_v4();
-    let now = Utc::now();
-    
-    // Set up mock expectations for schedule repository
-    ctx.schedule_repo.expect_get_schedule_by_id()
-        .with(predicate::eq(id))
-        .returning(move |_| {
-            Ok(Some(DbSchedule {
-                id,
-                name: "Test Schedule".to_string(),
-                password_hash: None,
-                timezone: "UTC".to_string(),
-                created_at: now,
-            }))
-        });
-    
-    ctx.schedule_repo.expect_update_schedule()
-        .with(
-            predicate::eq(id),
-            predicate::eq(Some("Updated Schedule"))
-        )
-        .returning(move |id, name| {
-            Ok(DbSchedule {
-                id,
-                name: name.unwrap_or("Test Schedule").to_string(),
-                password_hash: None,
-                timezone: "UTC".to_string(),
-                created_at: now,
-            })
-        });
-    
-    // Set up mock expectations for time_slot repository
-    ctx.time_slot_repo.expect_delete_time_slots_by_schedule_id()
-        .with(predicate::eq(id))
-        .times(1)
-        .returning(|_| Ok(()));
-    
-    ctx.time_slot_repo.expect_create_time_slot()
-        .with(
-            predicate::eq(id),
-            predicate::always(),
-            predicate::always()
-        )
-        .times(1)
-        .returning(move |schedule_id, start, end| {
-            Ok(DbTimeSlot {
-                id: Uuid::new_v4(),
-                schedule_id,
-                start_time: start,
-                end_time: end,
-                created_at: now,
-                is_recurring: false,
-            })
-        });
-    
-    // Setup mock for getting time slots after update
-    ctx.time_slot_repo.expect_get_time_slots_by_schedule_id()
-        .with(predicate::eq(id))
-        .returning(move |_| {
-            Ok(vec![
-                DbTimeSlot {
-                    id: Uuid::new_v4(),
-                    schedule_id: id,
-                    start_time: now,
-                    end_time: now + chrono::Duration::hours(1),
-                    is_recurring: false,
-                    created_at: now,
-                }
-            ])
-        });
-    
-    // Create the request payload
-    let start_time = now;
-    let end_time = now + chrono::Duration::hours(1);
-    let request = UpdateScheduleRequest {
-        name: Some("Updated Schedule".to_string()),
-        timezone: Some("UTC".to_string()),
-        slots: vec![CreateTimeSlotRequest {
-            start: start_time,
-            end: end_time,
-            is_recurring: false,
-        }],
-        password: None,
-    };
-    
-    // Call our wrapper function instead of the actual handler
-    let result = test_update_schedule_wrapper(&mut ctx, id, request).await;
-    
-    // Assert the response
-    assert!(result.is_ok());
-    let response = result.unwrap();
-    assert_eq!(response.0.id, id);
-    assert_eq!(response.0.name, "Updated Schedule");
+    let ctx = TestContext::new();
+    let schedule = ctx.create_schedule("Test Schedule", None).await;
+    let start = Utc::now();
+    ctx.add_slot(&schedule, start, start + chrono::Duration::hours(1)).await;
+
+    let mut request = update_request(Some("Updated Schedule"), None);
+    request.timezone = Some("Europe/London".to_string());
+    request.slots = vec![slot(5, SlotPreference::Available)];
+
+    let response = update_schedule(State(ctx.build_state()), Path(schedule.id), Json(request))
+        .await
+        .unwrap()
+        .0;
+    assert_eq!(response.id, schedule.id);
+
+    let stored = ctx.state.schedules.get_schedule_by_id(schedule.id).await.unwrap().unwrap();
+    assert_eq!(stored.name, "Updated Schedule");
+    assert_eq!(stored.timezone, "Europe/London");
+
+    // The old slot is replaced
+    let slots = ctx.state.time_slots.get_time_slots_by_schedule_id(schedule.id).await.unwrap();
+    assert_eq!(slots.len(), 1);
+    assert!(slots[0].start_time > start);
 }
 
 #[tokio::test]
 async fn test_update_schedule_with_password() {
-    let mut ctx = TestContext::new();
-    let id = Uuid::new_v4();
-    let now = Utc::now();
-    
-    // Set up mock expectations for schedule with password
-    ctx.schedule_repo.expect_get_schedule_by_id()
-        .with(predicate::eq(id))
-        .returning(move |_| {
-            Ok(Some(DbSchedule {
-                id,
-                name: "Test Schedule".to_string(),
-                password_hash: Some("hashed_password".to_string()),
-                timezone: "UTC".to_string(),
-                created_at: now,
-            }))
-        });
-    
-    // Set up password verification
-    ctx.schedule_repo.expect_verify_password()
-        .with(
-            predicate::eq(id),
-            predicate::eq("password123")
-        )
-        .returning(|_, _| Ok(true));
-    
-    // Mock the update and delete/create calls
-    ctx.schedule_repo.expect_update_schedule()
-        .returning(move |id, name| {
-            Ok(DbSchedule {
-                id,
-                name: name.unwrap_or("Test Schedule").to_string(),
-                password_hash: Some("hashed_password".to_string()),
-                timezone: "UTC".to_string(),
-                created_at: now,
-            })
-        });
-    
-    ctx.time_slot_repo.expect_delete_time_slots_by_schedule_id()
-        .returning(|_| Ok(()));
-    
-    // Add mock for getting time slots
-    ctx.time_slot_repo.expect_get_time_slots_by_schedule_id()
-        .returning(|_| Ok(vec![]));
-    
-    // Create the request payload with password
-    let request = UpdateScheduleRequest {
-        name: Some("Updated Schedule".to_string()),
-        timezone: Some("UTC".to_string()),
-        slots: vec![],
-        password: Some("password123".to_string()),
-    };
-    
-    // Call our wrapper function instead of the actual handler
-    let result = test_update_schedule_wrapper(&mut ctx, id, request).await;
-    
-    // Assert the response
-    assert!(result.is_ok());
-    let response = result.unwrap();
-    assert_eq!(response.0.name, "Updated Schedule");
+    let ctx = TestContext::new();
+    let schedule = ctx.create_schedule("Test Schedule", Some("password123")).await;
+
+    let request = update_request(Some("Updated Schedule"), Some("password123"));
+    let response = update_schedule(State(ctx.build_state()), Path(schedule.id), Json(request))
+        .await
+        .unwrap()
+        .0;
+    assert_eq!(response.id, schedule.id);
+
+    let stored = ctx.state.schedules.get_schedule_by_id(schedule.id).await.unwrap().unwrap();
+    assert_eq!(stored.name, "Updated Schedule");
 }
 
 #[tokio::test]
 async fn test_update_schedule_invalid_password() {
-    let mut ctx = TestContext::new();
-    let id = Uuid::new_v4();
-    let now = Utc::now();
-    
-    // Set up mock expectations for schedule with password
-    ctx.schedule_repo.expect_get_schedule_by_id()
-        .with(predicate::eq(id))
-        .returning(move |_| {
-            Ok(Some(DbSchedule {
-                id,
-                name: "Test Schedule".to_string(),
-                password_hash: Some("hashed_password".to_string()),
-                timezone: "UTC".to_string(),
-                created_at: now,
-            }))
-        });
-    
-    // Set up password verification to fail
-    ctx.schedule_repo.expect_verify_password()
-        .with(
-            predicate::eq(id),
-            predicate::eq("wrong_password")
-        )
-        .returning(|_, _| Ok(false));
-    
-    // Create the request payload with wrong password
-    let request = UpdateScheduleRequest {
-        name: Some("Updated Schedule".to_string()),
-        timezone: Some("UTC".to_string()),
-        slots: vec![],
-        password: Some("wrong_password".to_string()),
-    };
-    
-    // Call our wrapper function instead of the actual handler
-    let result = test_update_schedule_wrapper(&mut ctx, id, request).await;
-    
-    // Assert authentication error
-    assert!(result.is_err());
+    let ctx = TestContext::new();
+    let schedule = ctx.create_schedule("Test Schedule", Some("password123")).await;
+
+    let request = update_request(Some("Updated Schedule"), Some("wrong_password"));
+    let result = update_schedule(State(ctx.build_state()), Path(schedule.id), Json(request)).await;
+
     match result.unwrap_err().0 {
         TimeError::Authentication(_) => {}, // Expected
         e => panic!("Expected Authentication error, got: {:?}", e),
     }
+
+    let stored = ctx.state.schedules.get_schedule_by_id(schedule.id).await.unwrap().unwrap();
+    assert_eq!(stored.name, "Test Schedule");
+}
+
+#[tokio::test]
+async fn test_update_schedule_missing_password() {
+    let ctx = TestContext::new();
+    let schedule = ctx.create_schedule("Test Schedule", Some("password123")).await;
+
+    let request = update_request(Some("Updated Schedule"), None);
+    let result = update_schedule(State(ctx.build_state()), Path(schedule.id), Json(request)).await;
+
+    match result.unwrap_err().0 {
+        TimeError::Authentication(message) => assert!(message.contains("Password required")),
+        e => panic!("Expected Authentication error, got: {:?}", e),
+    }
+}
+
+#[tokio::test]
+async fn test_update_schedule_not_found() {
+    let ctx = TestContext::new();
+
+    let request = update_request(Some("Updated Schedule"), None);
+    let result = update_schedule(State(ctx.build_state()), Path(Uuid::new_v4()), Json(request)).await;
+
+    match result.unwrap_err().0 {
+        TimeError::NotFound(_) => {}, // Expected
+        e => panic!("Expected NotFound error, got: {:?}", e),
+    }
 }
 
 #[tokio::test]
 async fn test_verify_password_success() {
-    let mut ctx = TestContext::new();
-    let id = Uuid::new_v4();
-    
-    // Set up mock expectations
-    ctx.schedule_repo.expect_verify_password()
-        .with(
-            predicate::eq(id),
-            predicate::eq("password123")
-        )
-        .returning(|_, _| Ok(true));
-    
-    // Call our wrapper function instead of the actual handler
-    let result = test_verify_password_wrapper(&mut ctx, id, "password123".to_string()).await;
-    
-    // Assert the response
-    assert!(result.is_ok());
-    let response = result.unwrap();
-    assert_eq!(response.0.valid, true);
+    let ctx = TestContext::new();
+    let schedule = ctx.create_schedule("Test Schedule", Some("password123")).await;
+
+    let request = VerifyPasswordRequest {
+        password: "password123".to_string(),
+    };
+    let response = verify_password(State(ctx.build_state()), Path(schedule.id), Json(request))
+        .await
+        .unwrap()
+        .0;
+
+    assert!(response.valid);
 }
 
 #[tokio::test]
 async fn test_verify_password_invalid() {
-    let mut ctx = TestContext::new();
-    let id = Uuid::new_v4();
-    
-    // Set up mock expectations
-    ctx.schedule_repo.expect_verify_password()
-        .with(
-            predicate::eq(id),
-            predicate::eq("wrong_password")
-        )
-        .returning(|_, _| Ok(false));
-    
-    // Call our wrapper function instead of the actual handler
-    let result = test_verify_password_wrapper(&mut ctx, id, "wrong_password".to_string()).await;
-    
-    // Assert the response
-    assert!(result.is_ok());
-    let response = result.unwrap();
-    assert_eq!(response.0.valid, false);
-}
\ No newline at end of file
+    let ctx = TestContext::new();
+    let schedule = ctx.create_schedule("Test Schedule", Some("password123")).await;
+
+    let request = VerifyPasswordRequest {
+        password: "wrong_password".to_string(),
+    };
+    let response = verify_password(State(ctx.build_state()), Path(schedule.id), Json(request))
+        .await
+        .unwrap()
+        .0;
+
+    assert!(!response.valid);
+}
@@ -1,2 +1,3 @@
 mod test_utils;
-mod handlers;
\ No newline at end of file
+mod handlers;
+mod routes;
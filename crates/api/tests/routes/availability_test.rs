@@ -0,0 +1,193 @@
+use chrono::{DateTime, Duration, DurationRound, NaiveDate, Utc};
+use serde_json::json;
+use timesync_core::models::{
+    discord::{HeatmapQuery, MatchQuery, MatchResponse},
+    schedule::ParseAvailabilityResponse,
+};
+use uuid::Uuid;
+
+use crate::test_utils::TestApp;
+
+/// Tomorrow at the given hour, UTC
+fn at(hour: i64) -> DateTime<Utc> {
+    Utc::now().duration_trunc(Duration::days(1)).unwrap() + Duration::days(1) + Duration::hours(hour)
+}
+
+/// A query over tomorrow
+fn match_query(group_ids: &[Uuid], min_per_group: usize) -> MatchQuery {
+    MatchQuery {
+        group_ids: group_ids.iter().map(Uuid::to_string).collect::<Vec<_>>().join(","),
+        min_per_group: Some(min_per_group),
+        count: Some(10),
+        required_ids: None,
+        from: Some(at(0)),
+        until: Some(at(24)),
+    }
+}
+
+/// A group whose members are each available for an `(start, end)` hour range tomorrow
+async fn group_available(app: &TestApp, name: &str, members: &[(&str, i64, i64)]) -> Uuid {
+    for &(discord_id, start, end) in members {
+        let schedule = app.ctx.create_user_with_schedule(discord_id).await;
+        app.ctx.add_slot(&schedule, at(start), at(end)).await;
+    }
+    let member_ids: Vec<_> = members.iter().map(|&(discord_id, _, _)| discord_id).collect();
+
+    app.ctx.create_group("server1", name, &member_ids).await.id
+}
+
+#[tokio::test]
+async fn test_match_availability() {
+    let app = TestApp::spawn().await;
+    let raiders = group_available(&app, "Raiders", &[("alice", 9, 12), ("bob", 10, 13)]).await;
+    let crafters = group_available(&app, "Crafters", &[("carol", 8, 11)]).await;
+
+    let response = app
+        .server
+        .get("/api/availability/match")
+        .add_query_params(match_query(&[raiders, crafters], 1))
+        .await;
+
+    response.assert_status_ok();
+    let matches = response.json::<MatchResponse>().matches;
+    assert!(!matches.is_empty());
+
+    // The best time is when everyone can make it
+    let best = &matches[0];
+    assert_eq!((best.start, best.end), (at(10), at(11)));
+    let raiders_result = best.groups.iter().find(|g| g.id == raiders).unwrap();
+    let mut available = raiders_result.available_users.clone();
+    available.sort();
+    assert_eq!(available, vec!["alice", "bob"]);
+}
+
+#[tokio::test]
+async fn test_match_availability_invalid_group_id() {
+    let app = TestApp::spawn().await;
+
+    let response = app
+        .server
+        .get("/api/availability/match")
+        .add_query_param("group_ids", "not-a-uuid")
+        .await;
+
+    response.assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn test_match_availability_unknown_group() {
+    let app = TestApp::spawn().await;
+
+    let response = app
+        .server
+        .get("/api/availability/match")
+        .add_query_params(match_query(&[Uuid::new_v4()], 1))
+        .await;
+
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_availability_heatmap() {
+    let app = TestApp::spawn().await;
+    let raiders = group_available(&app, "Raiders", &[("alice", 9, 12)]).await;
+
+    let response = app
+        .server
+        .get("/api/availability/heatmap.png")
+        .add_query_params(HeatmapQuery {
+            group_ids: raiders.to_string(),
+            min_per_group: None,
+            timezone: Some("UTC".to_string()),
+            start: Some(at(0).date_naive()),
+            days: Some(3),
+        })
+        .await;
+
+    response.assert_status_ok();
+    assert_eq!(response.header("content-type"), "image/png");
+    assert!(response.as_bytes().starts_with(b"\x89PNG"));
+}
+
+#[tokio::test]
+async fn test_availability_heatmap_clamps_days() {
+    let app = TestApp::spawn().await;
+    let raiders = group_available(&app, "Raiders", &[("alice", 9, 12)]).await;
+
+    for days in ["1000000", "4000000000"] {
+        let response = app
+            .server
+            .get("/api/availability/heatmap.png")
+            .add_query_param("group_ids", raiders)
+            .add_query_param("days", days)
+            .await;
+
+        response.assert_status_ok();
+        assert!(response.as_bytes().starts_with(b"\x89PNG"));
+    }
+}
+
+#[tokio::test]
+async fn test_availability_heatmap_start_out_of_range() {
+    let app = TestApp::spawn().await;
+    let raiders = group_available(&app, "Raiders", &[("alice", 9, 12)]).await;
+
+    let response = app
+        .server
+        .get("/api/availability/heatmap.png")
+        .add_query_param("group_ids", raiders)
+        .add_query_param("start", NaiveDate::MAX)
+        .await;
+
+    response.assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn test_availability_heatmap_invalid_timezone() {
+    let app = TestApp::spawn().await;
+    let raiders = group_available(&app, "Raiders", &[("alice", 9, 12)]).await;
+
+    let response = app
+        .server
+        .get("/api/availability/heatmap.png")
+        .add_query_param("group_ids", raiders)
+        .add_query_param("timezone", "Nowhere")
+        .await;
+
+    response.assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn test_parse_availability() {
+    let app = TestApp::spawn().await;
+
+    let response = app
+        .server
+        .post("/api/availability/parse")
+        .json(&json!({
+            "text": "monday 6pm-8pm",
+            "timezone": "UTC",
+            "reference_date": NaiveDate::from_ymd_opt(2024, 1, 3),
+            "weekly": true,
+        }))
+        .await;
+
+    response.assert_status_ok();
+    let slots = response.json::<ParseAvailabilityResponse>().slots;
+    assert_eq!(slots.len(), 1);
+    assert!(slots[0].is_recurring);
+    assert_eq!(slots[0].end - slots[0].start, Duration::hours(2));
+}
+
+#[tokio::test]
+async fn test_parse_availability_invalid_text() {
+    let app = TestApp::spawn().await;
+
+    let response = app
+        .server
+        .post("/api/availability/parse")
+        .json(&json!({ "text": "whenever", "timezone": "UTC" }))
+        .await;
+
+    response.assert_status_bad_request();
+}
@@ -0,0 +1,435 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+use timesync_core::models::discord::{
+    AddGroupMemberResponse, CreateDiscordGroupResponse, CreateDiscordUserResponse, DiscordServerResponse,
+    GetDiscordGroupResponse, GetDiscordUserResponse, ListDiscordGroupsResponse, UpdateDiscordGroupRoleResponse,
+    UpdateGroupMemberResponse,
+};
+use uuid::Uuid;
+
+use crate::test_utils::TestApp;
+
+/// Create a group through the API and return its ID
+async fn create_group(app: &TestApp, server_id: &str, name: &str, member_ids: &[&str]) -> Uuid {
+    let response = app
+        .server
+        .post("/api/discord/groups")
+        .json(&json!({ "name": name, "server_id": server_id, "member_ids": member_ids }))
+        .await;
+    response.assert_status_ok();
+
+    response.json::<CreateDiscordGroupResponse>().id
+}
+
+async fn get_group(app: &TestApp, id: Uuid) -> GetDiscordGroupResponse {
+    let response = app.server.get(&format!("/api/discord/groups/{}", id)).await;
+    response.assert_status_ok();
+
+    response.json()
+}
+
+#[tokio::test]
+async fn test_create_and_get_discord_user() {
+    let app = TestApp::spawn().await;
+    let schedule = app.ctx.create_schedule("Alice's schedule", None).await;
+
+    let response = app
+        .server
+        .post("/api/discord/users")
+        .json(&json!({ "discord_id": "alice", "schedule_id": schedule.id }))
+        .await;
+
+    response.assert_status_ok();
+    assert_eq!(response.json::<CreateDiscordUserResponse>().schedule_id, schedule.id);
+
+    let response = app.server.get("/api/discord/users/alice").await;
+
+    response.assert_status_ok();
+    let user = response.json::<GetDiscordUserResponse>();
+    assert_eq!(user.schedule_id, Some(schedule.id));
+    assert_eq!(user.timezone, None);
+}
+
+#[tokio::test]
+async fn test_create_discord_user_unknown_schedule() {
+    let app = TestApp::spawn().await;
+
+    let response = app
+        .server
+        .post("/api/discord/users")
+        .json(&json!({ "discord_id": "alice", "schedule_id": Uuid::new_v4() }))
+        .await;
+
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_get_discord_user_not_found() {
+    let app = TestApp::spawn().await;
+
+    let response = app.server.get("/api/discord/users/nobody").await;
+
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_set_discord_user_timezone() {
+    let app = TestApp::spawn().await;
+
+    let response = app
+        .server
+        .put("/api/discord/users/alice/timezone")
+        .json(&json!({ "timezone": "America/New_York" }))
+        .await;
+
+    response.assert_status_ok();
+    assert_eq!(response.json::<GetDiscordUserResponse>().timezone.as_deref(), Some("America/New_York"));
+
+    let user = app.server.get("/api/discord/users/alice").await.json::<GetDiscordUserResponse>();
+    assert_eq!(user.timezone.as_deref(), Some("America/New_York"));
+}
+
+#[tokio::test]
+async fn test_set_discord_user_timezone_invalid() {
+    let app = TestApp::spawn().await;
+
+    let response = app
+        .server
+        .put("/api/discord/users/alice/timezone")
+        .json(&json!({ "timezone": "Mars/Olympus_Mons" }))
+        .await;
+
+    response.assert_status_bad_request();
+    assert!(response.json::<Value>()["error"].as_str().unwrap().contains("Mars/Olympus_Mons"));
+}
+
+#[tokio::test]
+async fn test_discord_server_timezone() {
+    let app = TestApp::spawn().await;
+
+    // Servers default to UTC until they set a timezone
+    let response = app.server.get("/api/discord/servers/server1").await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<DiscordServerResponse>().timezone, "UTC");
+
+    let response = app
+        .server
+        .put("/api/discord/servers/server1/timezone")
+        .json(&json!({ "timezone": "Europe/Berlin" }))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<DiscordServerResponse>().timezone, "Europe/Berlin");
+
+    let server = app.server.get("/api/discord/servers/server1").await.json::<DiscordServerResponse>();
+    assert_eq!(server.timezone, "Europe/Berlin");
+}
+
+#[tokio::test]
+async fn test_set_discord_server_timezone_invalid() {
+    let app = TestApp::spawn().await;
+
+    let response = app
+        .server
+        .put("/api/discord/servers/server1/timezone")
+        .json(&json!({ "timezone": "Nowhere" }))
+        .await;
+
+    response.assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn test_create_and_get_discord_group() {
+    let app = TestApp::spawn().await;
+    app.ctx.create_user_with_schedule("alice").await;
+
+    let id = create_group(&app, "server1", "Raiders", &["alice", "bob"]).await;
+
+    let group = get_group(&app, id).await;
+    assert_eq!(group.name, "Raiders");
+    assert_eq!(group.server_id, "server1");
+    assert!(group.required);
+    assert!(!group.role_sync);
+
+    // Members who weren't known yet are created without a schedule
+    let mut members: Vec<_> = group.members.iter().map(|m| (m.discord_id.as_str(), m.schedule_id.is_some())).collect();
+    members.sort();
+    assert_eq!(members, vec![("alice", true), ("bob", false)]);
+}
+
+#[tokio::test]
+async fn test_get_discord_group_not_found() {
+    let app = TestApp::spawn().await;
+
+    let response = app.server.get(&format!("/api/discord/groups/{}", Uuid::new_v4())).await;
+
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_get_discord_group_by_name() {
+    let app = TestApp::spawn().await;
+    let id = create_group(&app, "server1", "Night Owls", &["alice"]).await;
+    create_group(&app, "server2", "Night Owls", &[]).await;
+
+    let response = app.server.get("/api/discord/servers/server1/groups/Night%20Owls").await;
+
+    response.assert_status_ok();
+    let group = response.json::<GetDiscordGroupResponse>();
+    assert_eq!(group.id, id);
+    assert_eq!(group.members.len(), 1);
+
+    let response = app.server.get("/api/discord/servers/server3/groups/Night%20Owls").await;
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_update_discord_group() {
+    let app = TestApp::spawn().await;
+    let id = create_group(&app, "server1", "Raiders", &["alice", "bob"]).await;
+
+    let response = app
+        .server
+        .put(&format!("/api/discord/groups/{}", id))
+        .json(&json!({
+            "name": "Explorers",
+            "add_member_ids": ["carol"],
+            "remove_member_ids": ["bob"],
+            "required": false,
+        }))
+        .await;
+
+    response.assert_status_ok();
+    let group = get_group(&app, id).await;
+    assert_eq!(group.name, "Explorers");
+    assert!(!group.required);
+    let mut member_ids: Vec<_> = group.members.iter().map(|m| m.discord_id.as_str()).collect();
+    member_ids.sort();
+    assert_eq!(member_ids, vec!["alice", "carol"]);
+}
+
+#[tokio::test]
+async fn test_update_discord_group_adds_existing_members_once() {
+    let app = TestApp::spawn().await;
+    let id = create_group(&app, "server1", "Raiders", &["alice", "bob"]).await;
+
+    // As sent for `/group add members:all`, which includes current members
+    let response = app
+        .server
+        .put(&format!("/api/discord/groups/{}", id))
+        .json(&json!({ "add_member_ids": ["alice", "bob", "carol", "carol"] }))
+        .await;
+
+    response.assert_status_ok();
+    let group = get_group(&app, id).await;
+    let mut member_ids: Vec<_> = group.members.iter().map(|m| m.discord_id.as_str()).collect();
+    member_ids.sort();
+    assert_eq!(member_ids, vec!["alice", "bob", "carol"]);
+}
+
+#[tokio::test]
+async fn test_update_discord_group_not_found() {
+    let app = TestApp::spawn().await;
+
+    let response = app
+        .server
+        .put(&format!("/api/discord/groups/{}", Uuid::new_v4()))
+        .json(&json!({ "name": "Explorers" }))
+        .await;
+
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_delete_discord_group() {
+    let app = TestApp::spawn().await;
+    let id = create_group(&app, "server1", "Raiders", &["alice"]).await;
+    let path = format!("/api/discord/groups/{}", id);
+
+    let response = app.server.delete(&path).await;
+    response.assert_status(StatusCode::NO_CONTENT);
+
+    app.server.get(&path).await.assert_status_not_found();
+    app.server.delete(&path).await.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_list_server_groups() {
+    let app = TestApp::spawn().await;
+    for name in ["Charlie", "Alpha", "Bravo"] {
+        create_group(&app, "server1", name, &["alice"]).await;
+    }
+    create_group(&app, "server2", "Elsewhere", &[]).await;
+
+    let response = app
+        .server
+        .get("/api/discord/servers/server1/groups")
+        .add_query_param("limit", 2)
+        .add_query_param("offset", 1)
+        .await;
+
+    response.assert_status_ok();
+    let page = response.json::<ListDiscordGroupsResponse>();
+    assert_eq!(page.total, 3);
+    assert_eq!((page.limit, page.offset), (2, 1));
+    let names: Vec<_> = page.groups.iter().map(|g| g.name.as_str()).collect();
+    assert_eq!(names, vec!["Bravo", "Charlie"]);
+    assert_eq!(page.groups[0].member_count, 1);
+}
+
+#[tokio::test]
+async fn test_list_server_groups_invalid_limit() {
+    let app = TestApp::spawn().await;
+
+    let response = app
+        .server
+        .get("/api/discord/servers/server1/groups")
+        .add_query_param("limit", 0)
+        .await;
+
+    response.assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn test_update_discord_group_role() {
+    let app = TestApp::spawn().await;
+    let id = create_group(&app, "server1", "Raiders", &[]).await;
+
+    let response = app
+        .server
+        .put(&format!("/api/discord/groups/{}/role", id))
+        .json(&json!({ "role_id": "role1" }))
+        .await;
+
+    response.assert_status_ok();
+    assert_eq!(response.json::<UpdateDiscordGroupRoleResponse>().role_id, "role1");
+    let group = get_group(&app, id).await;
+    assert_eq!(group.role_id.as_deref(), Some("role1"));
+    assert!(!group.role_sync);
+}
+
+#[tokio::test]
+async fn test_bind_and_unbind_group_role() {
+    let app = TestApp::spawn().await;
+    let synced = create_group(&app, "server1", "Raiders", &[]).await;
+    let other_server = create_group(&app, "server2", "Crafters", &[]).await;
+    create_group(&app, "server1", "Unsynced", &[]).await;
+
+    let response = app
+        .server
+        .put(&format!("/api/discord/groups/{}/role-sync", synced))
+        .json(&json!({ "role_id": "role1" }))
+        .await;
+    response.assert_status_ok();
+    let group = response.json::<GetDiscordGroupResponse>();
+    assert!(group.role_sync);
+    assert_eq!(group.role_id.as_deref(), Some("role1"));
+
+    app.server
+        .put(&format!("/api/discord/groups/{}/role-sync", other_server))
+        .json(&json!({ "role_id": "role2" }))
+        .await
+        .assert_status_ok();
+
+    let response = app.server.get("/api/discord/role-synced-groups").await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<Vec<GetDiscordGroupResponse>>().len(), 2);
+
+    let response = app
+        .server
+        .get("/api/discord/role-synced-groups")
+        .add_query_param("server_id", "server1")
+        .await;
+    let ids: Vec<_> = response.json::<Vec<GetDiscordGroupResponse>>().iter().map(|g| g.id).collect();
+    assert_eq!(ids, vec![synced]);
+
+    let response = app.server.delete(&format!("/api/discord/groups/{}/role-sync", synced)).await;
+    response.assert_status_ok();
+    assert!(!response.json::<GetDiscordGroupResponse>().role_sync);
+
+    let response = app
+        .server
+        .get("/api/discord/role-synced-groups")
+        .add_query_param("server_id", "server1")
+        .await;
+    assert!(response.json::<Vec<GetDiscordGroupResponse>>().is_empty());
+}
+
+#[tokio::test]
+async fn test_bind_group_role_not_found() {
+    let app = TestApp::spawn().await;
+
+    let response = app
+        .server
+        .put(&format!("/api/discord/groups/{}/role-sync", Uuid::new_v4()))
+        .json(&json!({ "role_id": "role1" }))
+        .await;
+
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_add_and_remove_group_member() {
+    let app = TestApp::spawn().await;
+    let id = create_group(&app, "server1", "Raiders", &[]).await;
+    let path = format!("/api/discord/groups/{}/members/alice", id);
+
+    let response = app.server.post(&path).await;
+    response.assert_status_ok();
+    assert!(response.json::<AddGroupMemberResponse>().added);
+
+    // Adding an existing member changes nothing
+    let response = app.server.post(&path).await;
+    response.assert_status_ok();
+    assert!(!response.json::<AddGroupMemberResponse>().added);
+    assert_eq!(get_group(&app, id).await.members.len(), 1);
+
+    app.server.delete(&path).await.assert_status(StatusCode::NO_CONTENT);
+    assert!(get_group(&app, id).await.members.is_empty());
+}
+
+#[tokio::test]
+async fn test_add_group_member_unknown_group() {
+    let app = TestApp::spawn().await;
+
+    let response = app.server.post(&format!("/api/discord/groups/{}/members/alice", Uuid::new_v4())).await;
+
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_update_group_member() {
+    let app = TestApp::spawn().await;
+    let id = create_group(&app, "server1", "Raiders", &["alice"]).await;
+    let path = format!("/api/discord/groups/{}/members/alice", id);
+
+    let response = app.server.put(&path).json(&json!({ "required": true, "weight": 2.5 })).await;
+
+    response.assert_status_ok();
+    let member = response.json::<UpdateGroupMemberResponse>();
+    assert!(member.required);
+    assert_eq!(member.weight, 2.5);
+
+    let group = get_group(&app, id).await;
+    assert!(group.members[0].required);
+    assert_eq!(group.members[0].weight, 2.5);
+}
+
+#[tokio::test]
+async fn test_update_group_member_errors() {
+    let app = TestApp::spawn().await;
+    let id = create_group(&app, "server1", "Raiders", &["alice"]).await;
+
+    let response = app
+        .server
+        .put(&format!("/api/discord/groups/{}/members/alice", id))
+        .json(&json!({ "weight": -1.0 }))
+        .await;
+    response.assert_status_bad_request();
+
+    let response = app
+        .server
+        .put(&format!("/api/discord/groups/{}/members/bob", id))
+        .json(&json!({ "required": true }))
+        .await;
+    response.assert_status_not_found();
+}
@@ -0,0 +1,44 @@
+use serde_json::Value;
+
+use crate::test_utils::TestApp;
+
+#[tokio::test]
+async fn test_health() {
+    let app = TestApp::spawn().await;
+
+    let response = app.server.get("/api/health").await;
+
+    response.assert_status_ok();
+    assert_eq!(response.json::<Value>()["status"], "ok");
+}
+
+#[tokio::test]
+async fn test_version() {
+    let app = TestApp::spawn().await;
+
+    let response = app.server.get("/api/version").await;
+
+    response.assert_status_ok();
+    assert_eq!(response.json::<Value>()["version"], env!("CARGO_PKG_VERSION"));
+}
+
+#[tokio::test]
+async fn test_openapi_json() {
+    let app = TestApp::spawn().await;
+
+    let response = app.server.get("/api/openapi.json").await;
+
+    response.assert_status_ok();
+    let spec = response.json::<Value>();
+    assert!(spec["paths"]["/schedules"].is_object());
+}
+
+#[tokio::test]
+async fn test_docs_page() {
+    let app = TestApp::spawn().await;
+
+    let response = app.server.get("/api/docs").await;
+
+    response.assert_status_ok();
+    assert!(response.text().contains("openapi.json"));
+}
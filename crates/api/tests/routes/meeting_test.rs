@@ -0,0 +1,141 @@
+use chrono::{Duration, DurationRound, Utc};
+use serde_json::{json, Value};
+use timesync_core::models::meeting::{ListMeetingsResponse, Meeting, MeetingStatus};
+use uuid::Uuid;
+
+use crate::test_utils::TestApp;
+
+/// Record a meeting `days` from now through the API
+async fn create_meeting(app: &TestApp, days: i64, extra: Value) -> Meeting {
+    let start = Utc::now().duration_trunc(Duration::hours(1)).unwrap() + Duration::days(days);
+    let mut request = json!({
+        "server_id": "server1",
+        "channel_id": "channel1",
+        "organizer_id": "alice",
+        "start_time": start,
+        "end_time": start + Duration::hours(2),
+        "timezone": "UTC",
+        "min_per_group": 1,
+        "slot_duration": 120,
+    });
+    request.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+
+    let response = app.server.post("/api/meetings").json(&request).await;
+    response.assert_status_ok();
+
+    response.json()
+}
+
+#[tokio::test]
+async fn test_create_and_get_meeting() {
+    let app = TestApp::spawn().await;
+    let group = app.ctx.create_group("server1", "Raiders", &["alice", "bob"]).await;
+
+    let meeting = create_meeting(&app, 1, json!({ "group_ids": [group.id], "attendee_ids": ["alice", "bob"] })).await;
+    assert_eq!(meeting.status, MeetingStatus::Scheduled);
+
+    let response = app.server.get(&format!("/api/meetings/{}", meeting.id)).await;
+
+    response.assert_status_ok();
+    let fetched = response.json::<Meeting>();
+    assert_eq!(fetched.start_time, meeting.start_time);
+    assert_eq!(fetched.group_ids, vec![group.id]);
+    let mut attendee_ids = fetched.attendee_ids.clone();
+    attendee_ids.sort();
+    assert_eq!(attendee_ids, vec!["alice", "bob"]);
+}
+
+#[tokio::test]
+async fn test_create_meeting_ends_before_start() {
+    let app = TestApp::spawn().await;
+    let start = Utc::now() + Duration::days(1);
+
+    let response = app
+        .server
+        .post("/api/meetings")
+        .json(&json!({
+            "server_id": "server1",
+            "channel_id": "channel1",
+            "organizer_id": "alice",
+            "start_time": start,
+            "end_time": start - Duration::hours(1),
+            "timezone": "UTC",
+            "min_per_group": 1,
+            "slot_duration": 120,
+        }))
+        .await;
+
+    response.assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn test_get_meeting_not_found() {
+    let app = TestApp::spawn().await;
+
+    let response = app.server.get(&format!("/api/meetings/{}", Uuid::new_v4())).await;
+
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_update_meeting_status() {
+    let app = TestApp::spawn().await;
+    let meeting = create_meeting(&app, 1, json!({})).await;
+
+    let response = app
+        .server
+        .put(&format!("/api/meetings/{}/status", meeting.id))
+        .json(&json!({ "status": "cancelled" }))
+        .await;
+
+    response.assert_status_ok();
+    assert_eq!(response.json::<Meeting>().status, MeetingStatus::Cancelled);
+
+    let response = app
+        .server
+        .put(&format!("/api/meetings/{}/status", Uuid::new_v4()))
+        .json(&json!({ "status": "cancelled" }))
+        .await;
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_reschedule_meeting() {
+    let app = TestApp::spawn().await;
+    let original = create_meeting(&app, 1, json!({})).await;
+
+    let replacement = create_meeting(&app, 2, json!({ "rescheduled_from": original.id })).await;
+
+    assert_eq!(replacement.rescheduled_from, Some(original.id));
+    let original = app.server.get(&format!("/api/meetings/{}", original.id)).await.json::<Meeting>();
+    assert_eq!(original.status, MeetingStatus::Rescheduled);
+}
+
+#[tokio::test]
+async fn test_list_server_meetings() {
+    let app = TestApp::spawn().await;
+    let later = create_meeting(&app, 3, json!({})).await;
+    let sooner = create_meeting(&app, 1, json!({})).await;
+    let cancelled = create_meeting(&app, 2, json!({})).await;
+    create_meeting(&app, 1, json!({ "server_id": "server2" })).await;
+    app.server
+        .put(&format!("/api/meetings/{}/status", cancelled.id))
+        .json(&json!({ "status": "cancelled" }))
+        .await
+        .assert_status_ok();
+
+    // Scheduled meetings by default, soonest first
+    let response = app.server.get("/api/discord/servers/server1/meetings").await;
+
+    response.assert_status_ok();
+    let ids: Vec<_> = response.json::<ListMeetingsResponse>().meetings.iter().map(|m| m.id).collect();
+    assert_eq!(ids, vec![sooner.id, later.id]);
+
+    let response = app
+        .server
+        .get("/api/discord/servers/server1/meetings")
+        .add_query_param("status", "cancelled")
+        .await;
+    let ids: Vec<_> = response.json::<ListMeetingsResponse>().meetings.iter().map(|m| m.id).collect();
+    assert_eq!(ids, vec![cancelled.id]);
+}
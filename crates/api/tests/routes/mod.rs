@@ -0,0 +1,10 @@
+//! End-to-end tests of every route, over HTTP
+//!
+//! Tests run against the in-memory store, or against a database of their own
+//! on the `TEST_DATABASE_URL` Postgres server when it is set.
+
+mod health_test;
+mod schedule_test;
+mod discord_test;
+mod availability_test;
+mod meeting_test;
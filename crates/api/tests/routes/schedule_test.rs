@@ -0,0 +1,160 @@
+use axum::http::StatusCode;
+use chrono::{Duration, DurationRound, Utc};
+use serde_json::{json, Value};
+use timesync_core::models::schedule::{
+    CreateScheduleResponse, GetScheduleResponse, SlotPreference, UpdateScheduleResponse, VerifyPasswordResponse,
+};
+use uuid::Uuid;
+
+use crate::test_utils::TestApp;
+
+/// Create a schedule through the API and return its ID
+async fn create_schedule(app: &TestApp, password: Option<&str>) -> Uuid {
+    let response = app
+        .server
+        .post("/api/schedules")
+        .json(&json!({
+            "name": "Team Schedule",
+            "password": password,
+            "timezone": "UTC",
+            "slots": [],
+            "discord_id": null,
+        }))
+        .await;
+    response.assert_status_ok();
+
+    response.json::<CreateScheduleResponse>().id
+}
+
+#[tokio::test]
+async fn test_create_and_get_schedule() {
+    let app = TestApp::spawn().await;
+    let start = Utc::now().duration_trunc(Duration::seconds(1)).unwrap() + Duration::days(1);
+
+    let response = app
+        .server
+        .post("/api/schedules")
+        .json(&json!({
+            "name": "Team Schedule",
+            "password": "secret",
+            "timezone": "Europe/London",
+            "slots": [
+                { "start": start, "end": start + Duration::hours(2), "preference": "if_need_be" },
+            ],
+            "discord_id": "discord123",
+        }))
+        .await;
+
+    response.assert_status_ok();
+    let created = response.json::<CreateScheduleResponse>();
+    assert_eq!(created.name, "Team Schedule");
+    assert!(created.is_editable);
+
+    let response = app.server.get(&format!("/api/schedules/{}", created.id)).await;
+
+    response.assert_status_ok();
+    let schedule = response.json::<GetScheduleResponse>();
+    assert_eq!(schedule.timezone, "Europe/London");
+    assert_eq!(schedule.slots.len(), 1);
+    assert_eq!(schedule.slots[0].start, start);
+    assert_eq!(schedule.slots[0].end, start + Duration::hours(2));
+    assert_eq!(schedule.slots[0].preference, SlotPreference::IfNeedBe);
+
+    // The Discord user is linked to the new schedule
+    let response = app.server.get("/api/discord/users/discord123").await;
+    assert_eq!(response.json::<Value>()["schedule_id"], json!(created.id));
+}
+
+#[tokio::test]
+async fn test_get_schedule_not_found() {
+    let app = TestApp::spawn().await;
+
+    let response = app.server.get(&format!("/api/schedules/{}", Uuid::new_v4())).await;
+
+    response.assert_status_not_found();
+    assert!(response.json::<Value>()["error"].as_str().unwrap().contains("not found"));
+}
+
+#[tokio::test]
+async fn test_get_schedule_invalid_id() {
+    let app = TestApp::spawn().await;
+
+    let response = app.server.get("/api/schedules/not-a-uuid").await;
+
+    response.assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn test_update_schedule() {
+    let app = TestApp::spawn().await;
+    let id = create_schedule(&app, Some("secret")).await;
+    let start = Utc::now() + Duration::days(2);
+
+    let response = app
+        .server
+        .put(&format!("/api/schedules/{}", id))
+        .json(&json!({
+            "name": "Renamed",
+            "timezone": "Asia/Tokyo",
+            "password": "secret",
+            "slots": [
+                { "start": start, "end": start + Duration::hours(1) },
+                { "start": start + Duration::hours(3), "end": start + Duration::hours(4), "is_unavailable": true },
+            ],
+        }))
+        .await;
+
+    response.assert_status_ok();
+    assert_eq!(response.json::<UpdateScheduleResponse>().id, id);
+
+    let schedule = app.server.get(&format!("/api/schedules/{}", id)).await.json::<GetScheduleResponse>();
+    assert_eq!(schedule.name, "Renamed");
+    assert_eq!(schedule.timezone, "Asia/Tokyo");
+    assert_eq!(schedule.slots.len(), 2);
+    assert!(!schedule.slots[0].is_unavailable);
+    assert!(schedule.slots[1].is_unavailable);
+}
+
+#[tokio::test]
+async fn test_update_schedule_wrong_password() {
+    let app = TestApp::spawn().await;
+    let id = create_schedule(&app, Some("secret")).await;
+
+    let response = app
+        .server
+        .put(&format!("/api/schedules/{}", id))
+        .json(&json!({ "name": "Renamed", "password": "wrong", "slots": [] }))
+        .await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+    let schedule = app.server.get(&format!("/api/schedules/{}", id)).await.json::<GetScheduleResponse>();
+    assert_eq!(schedule.name, "Team Schedule");
+}
+
+#[tokio::test]
+async fn test_update_schedule_not_found() {
+    let app = TestApp::spawn().await;
+
+    let response = app
+        .server
+        .put(&format!("/api/schedules/{}", Uuid::new_v4()))
+        .json(&json!({ "name": "Renamed", "slots": [] }))
+        .await;
+
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_verify_password() {
+    let app = TestApp::spawn().await;
+    let id = create_schedule(&app, Some("secret")).await;
+    let path = format!("/api/schedules/{}/verify", id);
+
+    let response = app.server.post(&path).json(&json!({ "password": "secret" })).await;
+    response.assert_status_ok();
+    assert!(response.json::<VerifyPasswordResponse>().valid);
+
+    let response = app.server.post(&path).json(&json!({ "password": "wrong" })).await;
+    response.assert_status_ok();
+    assert!(!response.json::<VerifyPasswordResponse>().valid);
+}
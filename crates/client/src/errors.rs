@@ -0,0 +1,59 @@
+use reqwest::StatusCode;
+use thiserror::Error;
+
+/// An error returned by the TimeSync API, or a failure to reach it
+///
+/// The API variants mirror `timesync_core::errors::TimeError`, so callers can
+/// tell a bad request from a missing resource or a server fault.
+#[derive(Error, Debug)]
+pub enum ClientError {
+    #[error("Resource not found: {0}")]
+    NotFound(String),
+
+    #[error("Validation error: {0}")]
+    Validation(String),
+
+    #[error("Authentication error: {0}")]
+    Authentication(String),
+
+    #[error("Authorization error: {0}")]
+    Authorization(String),
+
+    /// The API failed to handle a valid request, e.g. its database is down
+    #[error("Server error: {0}")]
+    Server(String),
+
+    /// A status the API doesn't use for errors, e.g. from a proxy in front of it
+    #[error("Unexpected response {status}: {message}")]
+    Unexpected { status: StatusCode, message: String },
+
+    /// The request couldn't be sent or its response couldn't be decoded
+    #[error("Request failed: {0}")]
+    Http(#[from] reqwest::Error),
+}
+
+pub type ClientResult<T> = Result<T, ClientError>;
+
+impl ClientError {
+    /// Map an error response back to the error the API raised
+    ///
+    /// `message` is the `error` field of the body, which the API prefixes with
+    /// the error kind; the prefix is dropped as the variant already says it.
+    pub fn from_response(status: StatusCode, message: String) -> Self {
+        let strip = |prefix: &str| message.strip_prefix(prefix).unwrap_or(&message).to_string();
+
+        match status {
+            StatusCode::NOT_FOUND => ClientError::NotFound(strip("Resource not found: ")),
+            StatusCode::BAD_REQUEST => ClientError::Validation(strip("Validation error: ")),
+            StatusCode::UNAUTHORIZED => ClientError::Authentication(strip("Authentication error: ")),
+            StatusCode::FORBIDDEN => ClientError::Authorization(strip("Authorization error: ")),
+            StatusCode::INTERNAL_SERVER_ERROR => ClientError::Server(message),
+            status => ClientError::Unexpected { status, message },
+        }
+    }
+
+    /// Whether the API answered that the resource doesn't exist
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, ClientError::NotFound(_))
+    }
+}
@@ -0,0 +1,337 @@
+//! # TimeSync Client
+//!
+//! A typed client for the TimeSync HTTP API, with one async method per route.
+//! Requests and responses use the models from `timesync_core`, so callers
+//! never build URLs or JSON by hand.
+//!
+//! Lookups of a single resource return `Ok(None)` when the API answers 404;
+//! every other error status becomes a [`ClientError`] carrying the API's message.
+//!
+//! ```no_run
+//! use timesync_client::{ClientResult, TimeSyncClient};
+//!
+//! # async fn example() -> ClientResult<()> {
+//! let client = TimeSyncClient::new("http://localhost:3000/api");
+//! let server = client.get_discord_server("1234").await?;
+//! println!("Times are shown in {}", server.timezone);
+//! # Ok(())
+//! # }
+//! # fn main() {}
+//! ```
+
+pub mod errors;
+
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use timesync_core::models::{
+    discord::{
+        AddGroupMemberResponse, CreateDiscordGroupRequest, CreateDiscordGroupResponse, CreateDiscordUserRequest,
+        CreateDiscordUserResponse, DiscordServerResponse, GetDiscordGroupResponse, GetDiscordUserResponse,
+        HeatmapQuery, ListDiscordGroupsResponse, MatchQuery, MatchResponse, SetTimezoneRequest,
+        UpdateDiscordGroupRequest, UpdateDiscordGroupResponse, UpdateDiscordGroupRoleRequest,
+        UpdateDiscordGroupRoleResponse, UpdateGroupMemberRequest, UpdateGroupMemberResponse,
+    },
+    meeting::{CreateMeetingRequest, ListMeetingsResponse, Meeting, MeetingStatus, UpdateMeetingStatusRequest},
+    schedule::{
+        CreateScheduleRequest, CreateScheduleResponse, GetScheduleResponse, ParseAvailabilityRequest,
+        ParseAvailabilityResponse, UpdateScheduleRequest, UpdateScheduleResponse, VerifyPasswordRequest,
+        VerifyPasswordResponse,
+    },
+};
+use uuid::Uuid;
+
+pub use errors::{ClientError, ClientResult};
+
+/// Client for one TimeSync API
+///
+/// Cheap to clone; clones share the underlying connection pool.
+#[derive(Debug, Clone)]
+pub struct TimeSyncClient {
+    base_url: String,
+    http: reqwest::Client,
+}
+
+/// Body of every API error response
+#[derive(Debug, Deserialize)]
+struct ErrorBody {
+    error: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct VersionBody {
+    version: String,
+}
+
+impl TimeSyncClient {
+    /// Create a client for the API at `base_url`, e.g. `https://timesync.example/api`
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self::with_http_client(base_url, reqwest::Client::new())
+    }
+
+    /// Create a client that sends its requests through `http`
+    pub fn with_http_client(base_url: impl Into<String>, http: reqwest::Client) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            http,
+        }
+    }
+
+    /// The API's base URL, without a trailing slash
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    // Health
+
+    /// Check that the API is up
+    pub async fn health(&self) -> ClientResult<()> {
+        check(self.request(Method::GET, "/health").send().await?).await?;
+        Ok(())
+    }
+
+    /// The version of the running API
+    pub async fn version(&self) -> ClientResult<String> {
+        let body: VersionBody = json(self.request(Method::GET, "/version")).await?;
+        Ok(body.version)
+    }
+
+    // Schedules
+
+    pub async fn create_schedule(&self, request: &CreateScheduleRequest) -> ClientResult<CreateScheduleResponse> {
+        json(self.request(Method::POST, "/schedules").json(request)).await
+    }
+
+    pub async fn get_schedule(&self, id: Uuid) -> ClientResult<Option<GetScheduleResponse>> {
+        optional_json(self.request(Method::GET, &format!("/schedules/{}", id))).await
+    }
+
+    /// Replace a schedule's slots, and its name or timezone if given
+    ///
+    /// Password-protected schedules need the password in the request.
+    pub async fn update_schedule(&self, id: Uuid, request: &UpdateScheduleRequest) -> ClientResult<UpdateScheduleResponse> {
+        json(self.request(Method::PUT, &format!("/schedules/{}", id)).json(request)).await
+    }
+
+    /// Whether `password` unlocks the schedule for editing
+    pub async fn verify_schedule_password(&self, id: Uuid, password: &str) -> ClientResult<bool> {
+        let request = VerifyPasswordRequest { password: password.to_string() };
+        let response: VerifyPasswordResponse =
+            json(self.request(Method::POST, &format!("/schedules/{}/verify", id)).json(&request)).await?;
+        Ok(response.valid)
+    }
+
+    // Availability
+
+    /// Find times that work for the given groups
+    pub async fn match_availability(&self, query: &MatchQuery) -> ClientResult<MatchResponse> {
+        json(self.request(Method::GET, "/availability/match").query(query)).await
+    }
+
+    /// Render the groups' availability as a PNG heatmap
+    pub async fn availability_heatmap(&self, query: &HeatmapQuery) -> ClientResult<Vec<u8>> {
+        let response = check(self.request(Method::GET, "/availability/heatmap.png").query(query).send().await?).await?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// Turn free text such as "weekdays after 6pm" into slots
+    pub async fn parse_availability(&self, request: &ParseAvailabilityRequest) -> ClientResult<ParseAvailabilityResponse> {
+        json(self.request(Method::POST, "/availability/parse").json(request)).await
+    }
+
+    // Discord users
+
+    /// Link a Discord user to a schedule
+    pub async fn create_discord_user(&self, request: &CreateDiscordUserRequest) -> ClientResult<CreateDiscordUserResponse> {
+        json(self.request(Method::POST, "/discord/users").json(request)).await
+    }
+
+    pub async fn get_discord_user(&self, discord_id: &str) -> ClientResult<Option<GetDiscordUserResponse>> {
+        optional_json(self.request(Method::GET, &format!("/discord/users/{}", segment(discord_id)))).await
+    }
+
+    /// Set a user's personal timezone, creating the user if needed
+    pub async fn set_discord_user_timezone(&self, discord_id: &str, timezone: &str) -> ClientResult<GetDiscordUserResponse> {
+        let request = SetTimezoneRequest { timezone: timezone.to_string() };
+        json(self.request(Method::PUT, &format!("/discord/users/{}/timezone", segment(discord_id))).json(&request)).await
+    }
+
+    // Discord servers
+
+    /// A server's settings, with the defaults if none were saved
+    pub async fn get_discord_server(&self, server_id: &str) -> ClientResult<DiscordServerResponse> {
+        json(self.request(Method::GET, &format!("/discord/servers/{}", segment(server_id)))).await
+    }
+
+    pub async fn set_discord_server_timezone(&self, server_id: &str, timezone: &str) -> ClientResult<DiscordServerResponse> {
+        let request = SetTimezoneRequest { timezone: timezone.to_string() };
+        json(self.request(Method::PUT, &format!("/discord/servers/{}/timezone", segment(server_id))).json(&request)).await
+    }
+
+    /// One page of a server's groups, ordered by name
+    pub async fn list_server_groups(
+        &self,
+        server_id: &str,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> ClientResult<ListDiscordGroupsResponse> {
+        let query = PageQuery { limit, offset };
+        json(self.request(Method::GET, &format!("/discord/servers/{}/groups", segment(server_id))).query(&query)).await
+    }
+
+    pub async fn get_discord_group_by_name(&self, server_id: &str, name: &str) -> ClientResult<Option<GetDiscordGroupResponse>> {
+        optional_json(self.request(
+            Method::GET,
+            &format!("/discord/servers/{}/groups/{}", segment(server_id), segment(name)),
+        ))
+        .await
+    }
+
+    /// A server's meetings with `status` that haven't ended yet, soonest first
+    pub async fn list_server_meetings(&self, server_id: &str, status: MeetingStatus) -> ClientResult<Vec<Meeting>> {
+        let response: ListMeetingsResponse = json(
+            self.request(Method::GET, &format!("/discord/servers/{}/meetings", segment(server_id)))
+                .query(&[("status", status.as_str())]),
+        )
+        .await?;
+        Ok(response.meetings)
+    }
+
+    // Discord groups
+
+    pub async fn create_discord_group(&self, request: &CreateDiscordGroupRequest) -> ClientResult<CreateDiscordGroupResponse> {
+        json(self.request(Method::POST, "/discord/groups").json(request)).await
+    }
+
+    pub async fn get_discord_group(&self, id: Uuid) -> ClientResult<Option<GetDiscordGroupResponse>> {
+        optional_json(self.request(Method::GET, &format!("/discord/groups/{}", id))).await
+    }
+
+    pub async fn update_discord_group(&self, id: Uuid, request: &UpdateDiscordGroupRequest) -> ClientResult<UpdateDiscordGroupResponse> {
+        json(self.request(Method::PUT, &format!("/discord/groups/{}", id)).json(request)).await
+    }
+
+    /// Delete a group, returning false if it didn't exist
+    pub async fn delete_discord_group(&self, id: Uuid) -> ClientResult<bool> {
+        let response = self.request(Method::DELETE, &format!("/discord/groups/{}", id)).send().await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+        check(response).await?;
+        Ok(true)
+    }
+
+    /// Set the role created for a group
+    pub async fn update_discord_group_role(&self, id: Uuid, role_id: &str) -> ClientResult<UpdateDiscordGroupRoleResponse> {
+        let request = UpdateDiscordGroupRoleRequest { role_id: role_id.to_string() };
+        json(self.request(Method::PUT, &format!("/discord/groups/{}/role", id)).json(&request)).await
+    }
+
+    /// Bind a group to an existing role so its membership follows the role
+    pub async fn bind_group_role(&self, id: Uuid, role_id: &str) -> ClientResult<GetDiscordGroupResponse> {
+        let request = UpdateDiscordGroupRoleRequest { role_id: role_id.to_string() };
+        json(self.request(Method::PUT, &format!("/discord/groups/{}/role-sync", id)).json(&request)).await
+    }
+
+    /// Stop syncing a group from its role, keeping its current members
+    pub async fn unbind_group_role(&self, id: Uuid) -> ClientResult<GetDiscordGroupResponse> {
+        json(self.request(Method::DELETE, &format!("/discord/groups/{}/role-sync", id))).await
+    }
+
+    /// Groups bound to a role, optionally only those of one server
+    pub async fn list_role_synced_groups(&self, server_id: Option<&str>) -> ClientResult<Vec<GetDiscordGroupResponse>> {
+        let mut request = self.request(Method::GET, "/discord/role-synced-groups");
+        if let Some(server_id) = server_id {
+            request = request.query(&[("server_id", server_id)]);
+        }
+        json(request).await
+    }
+
+    /// Add someone to a group; `added` is false if they already were a member
+    pub async fn add_group_member(&self, id: Uuid, discord_id: &str) -> ClientResult<AddGroupMemberResponse> {
+        json(self.request(Method::POST, &format!("/discord/groups/{}/members/{}", id, segment(discord_id)))).await
+    }
+
+    pub async fn remove_group_member(&self, id: Uuid, discord_id: &str) -> ClientResult<()> {
+        let path = format!("/discord/groups/{}/members/{}", id, segment(discord_id));
+        check(self.request(Method::DELETE, &path).send().await?).await?;
+        Ok(())
+    }
+
+    /// Change whether a member is required and their weight
+    ///
+    /// Returns `None` if they aren't a member of the group.
+    pub async fn update_group_member(
+        &self,
+        id: Uuid,
+        discord_id: &str,
+        request: &UpdateGroupMemberRequest,
+    ) -> ClientResult<Option<UpdateGroupMemberResponse>> {
+        let path = format!("/discord/groups/{}/members/{}", id, segment(discord_id));
+        optional_json(self.request(Method::PUT, &path).json(request)).await
+    }
+
+    // Meetings
+
+    /// Record a finalized meeting, marking any meeting it replaces as rescheduled
+    pub async fn create_meeting(&self, request: &CreateMeetingRequest) -> ClientResult<Meeting> {
+        json(self.request(Method::POST, "/meetings").json(request)).await
+    }
+
+    pub async fn get_meeting(&self, id: Uuid) -> ClientResult<Option<Meeting>> {
+        optional_json(self.request(Method::GET, &format!("/meetings/{}", id))).await
+    }
+
+    pub async fn update_meeting_status(&self, id: Uuid, status: MeetingStatus) -> ClientResult<Meeting> {
+        let request = UpdateMeetingStatusRequest { status };
+        json(self.request(Method::PUT, &format!("/meetings/{}/status", id)).json(&request)).await
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.http.request(method, format!("{}{}", self.base_url, path))
+    }
+}
+
+#[derive(Serialize)]
+struct PageQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offset: Option<i64>,
+}
+
+/// Percent-encode a value used as one path segment
+fn segment(value: &str) -> String {
+    urlencoding::encode(value).into_owned()
+}
+
+/// Send the request and decode its JSON body
+async fn json<T: DeserializeOwned>(request: RequestBuilder) -> ClientResult<T> {
+    let response = check(request.send().await?).await?;
+    Ok(response.json().await?)
+}
+
+/// Like `json`, but a 404 is `None` rather than an error
+async fn optional_json<T: DeserializeOwned>(request: RequestBuilder) -> ClientResult<Option<T>> {
+    let response = request.send().await?;
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+
+    let response = check(response).await?;
+    Ok(Some(response.json().await?))
+}
+
+/// Turn an error status into the matching `ClientError`
+async fn check(response: Response) -> ClientResult<Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let body = response.text().await.unwrap_or_default();
+    let message = serde_json::from_str::<ErrorBody>(&body)
+        .map(|body| body.error)
+        .unwrap_or(body);
+
+    Err(ClientError::from_response(status, message))
+}
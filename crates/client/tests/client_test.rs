@@ -0,0 +1,360 @@
+use std::sync::Arc;
+
+use chrono::{Duration, DurationRound, NaiveDate, Utc};
+use timesync_api::ApiState;
+use timesync_client::{ClientError, TimeSyncClient};
+use timesync_core::models::{
+    discord::{
+        CreateDiscordGroupRequest, CreateDiscordUserRequest, HeatmapQuery, MatchQuery, UpdateDiscordGroupRequest,
+        UpdateGroupMemberRequest,
+    },
+    meeting::{CreateMeetingRequest, MeetingStatus},
+    schedule::{CreateScheduleRequest, CreateTimeSlotRequest, ParseAvailabilityRequest, UpdateScheduleRequest},
+};
+use tokio::net::TcpListener;
+use uuid::Uuid;
+
+/// Serve the real API router, backed by an in-memory store, on a local port
+/// and return a client for it
+async fn spawn_api() -> TimeSyncClient {
+    let state = Arc::new(ApiState::in_memory());
+    let app = axum::Router::new()
+        .nest("/api", timesync_api::api_routes())
+        .with_state(state);
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("Failed to bind");
+    let addr = listener.local_addr().expect("Failed to get address");
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.expect("Server failed");
+    });
+
+    TimeSyncClient::new(format!("http://{}/api/", addr))
+}
+
+/// A slot from 18:00 to 20:00 tomorrow, UTC
+fn tomorrow_evening() -> CreateTimeSlotRequest {
+    let start = Utc::now().duration_trunc(Duration::days(1)).unwrap() + Duration::days(1) + Duration::hours(18);
+
+    CreateTimeSlotRequest {
+        start,
+        end: start + Duration::hours(2),
+        is_recurring: false,
+        is_unavailable: false,
+        preference: Default::default(),
+    }
+}
+
+fn schedule_request(name: &str, discord_id: Option<&str>) -> CreateScheduleRequest {
+    CreateScheduleRequest {
+        name: name.to_string(),
+        password: None,
+        slots: vec![tomorrow_evening()],
+        discord_id: discord_id.map(str::to_string),
+        timezone: "UTC".to_string(),
+    }
+}
+
+fn meeting_request(group_id: Uuid) -> CreateMeetingRequest {
+    let slot = tomorrow_evening();
+
+    CreateMeetingRequest {
+        server_id: "1234".to_string(),
+        channel_id: "5678".to_string(),
+        organizer_id: "42".to_string(),
+        start_time: slot.start,
+        end_time: slot.end,
+        timezone: "UTC".to_string(),
+        min_per_group: 1,
+        slot_duration: 60,
+        group_ids: vec![group_id],
+        attendee_ids: vec!["42".to_string()],
+        rescheduled_from: None,
+    }
+}
+
+#[tokio::test]
+async fn test_health_and_version() {
+    let client = spawn_api().await;
+
+    client.health().await.expect("Health check failed");
+    let version = client.version().await.expect("Version request failed");
+    assert!(!version.is_empty());
+}
+
+#[test]
+fn test_base_url_trailing_slash() {
+    let client = TimeSyncClient::new("http://localhost:3000/api/");
+    assert_eq!(client.base_url(), "http://localhost:3000/api");
+}
+
+#[tokio::test]
+async fn test_parse_availability() {
+    let client = spawn_api().await;
+
+    let request = ParseAvailabilityRequest {
+        text: "monday 6pm-8pm".to_string(),
+        timezone: "UTC".to_string(),
+        reference_date: NaiveDate::from_ymd_opt(2024, 1, 3),
+        weekly: true,
+    };
+    let response = client.parse_availability(&request).await.expect("Parse request failed");
+
+    assert_eq!(response.slots.len(), 1);
+    assert!(response.slots[0].is_recurring);
+    assert_eq!(response.slots[0].end - response.slots[0].start, chrono::Duration::hours(2));
+}
+
+#[tokio::test]
+async fn test_validation_error_maps_to_variant() {
+    let client = spawn_api().await;
+
+    let error = client.set_discord_server_timezone("1234", "Mars/Olympus_Mons")
+        .await
+        .expect_err("Unknown timezone was accepted");
+
+    match error {
+        ClientError::Validation(message) => assert_eq!(message, "Unknown timezone: Mars/Olympus_Mons"),
+        other => panic!("Expected a validation error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_invalid_match_query() {
+    let client = spawn_api().await;
+
+    let query = MatchQuery {
+        group_ids: "not-a-uuid".to_string(),
+        ..MatchQuery::default()
+    };
+    let error = client.match_availability(&query).await.expect_err("Invalid group IDs were accepted");
+
+    assert!(matches!(error, ClientError::Validation(_)));
+}
+
+#[tokio::test]
+async fn test_invalid_page_size() {
+    let client = spawn_api().await;
+
+    let error = client.list_server_groups("1234", Some(0), None)
+        .await
+        .expect_err("A page size of 0 was accepted");
+
+    assert!(matches!(error, ClientError::Validation(_)));
+}
+
+#[tokio::test]
+async fn test_meeting_must_end_after_start() {
+    let client = spawn_api().await;
+    let now = Utc::now();
+
+    let request = CreateMeetingRequest {
+        server_id: "1234".to_string(),
+        channel_id: "5678".to_string(),
+        organizer_id: "42".to_string(),
+        start_time: now,
+        end_time: now,
+        timezone: "UTC".to_string(),
+        min_per_group: 1,
+        slot_duration: 60,
+        group_ids: vec![Uuid::new_v4()],
+        attendee_ids: Vec::new(),
+        rescheduled_from: None,
+    };
+    let error = client.create_meeting(&request).await.expect_err("An empty meeting was accepted");
+
+    assert!(matches!(error, ClientError::Validation(_)));
+}
+
+#[tokio::test]
+async fn test_schedule_round_trip() {
+    let client = spawn_api().await;
+
+    let created = client.create_schedule(&CreateScheduleRequest {
+        password: Some("secret".to_string()),
+        ..schedule_request("Evenings", None)
+    })
+    .await
+    .expect("Failed to create schedule");
+    assert_eq!(created.name, "Evenings");
+
+    let schedule = client.get_schedule(created.id).await.unwrap().expect("Schedule exists");
+    assert_eq!(schedule.slots.len(), 1);
+    assert!(client.verify_schedule_password(created.id, "secret").await.unwrap());
+    assert!(!client.verify_schedule_password(created.id, "wrong").await.unwrap());
+
+    let request = UpdateScheduleRequest {
+        name: Some("Weekends".to_string()),
+        slots: Vec::new(),
+        password: Some("secret".to_string()),
+        timezone: Some("Europe/London".to_string()),
+    };
+    client.update_schedule(created.id, &request).await.expect("Failed to update schedule");
+
+    let schedule = client.get_schedule(created.id).await.unwrap().expect("Schedule exists");
+    assert_eq!((schedule.name.as_str(), schedule.timezone.as_str()), ("Weekends", "Europe/London"));
+    assert!(schedule.slots.is_empty());
+
+    assert!(client.get_schedule(Uuid::new_v4()).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_discord_user_and_server_round_trip() {
+    let client = spawn_api().await;
+    let schedule = client.create_schedule(&schedule_request("Mine", None)).await.unwrap();
+
+    let request = CreateDiscordUserRequest {
+        discord_id: "42".to_string(),
+        schedule_id: schedule.id,
+    };
+    client.create_discord_user(&request).await.expect("Failed to link user");
+    client.set_discord_user_timezone("42", "Asia/Tokyo").await.expect("Failed to set timezone");
+
+    let user = client.get_discord_user("42").await.unwrap().expect("User exists");
+    assert_eq!(user.schedule_id, Some(schedule.id));
+    assert_eq!(user.timezone.as_deref(), Some("Asia/Tokyo"));
+    assert!(client.get_discord_user("43").await.unwrap().is_none());
+
+    assert_eq!(client.get_discord_server("1234").await.unwrap().timezone, "UTC");
+    client.set_discord_server_timezone("1234", "Europe/Paris").await.expect("Failed to set timezone");
+    assert_eq!(client.get_discord_server("1234").await.unwrap().timezone, "Europe/Paris");
+}
+
+#[tokio::test]
+async fn test_discord_group_round_trip() {
+    let client = spawn_api().await;
+
+    let request = CreateDiscordGroupRequest {
+        name: "Raiders".to_string(),
+        server_id: "1234".to_string(),
+        member_ids: vec!["1".to_string(), "2".to_string()],
+    };
+    let created = client.create_discord_group(&request).await.expect("Failed to create group");
+
+    let group = client.get_discord_group(created.id).await.unwrap().expect("Group exists");
+    assert_eq!(group.members.len(), 2);
+    let by_name = client.get_discord_group_by_name("1234", "Raiders").await.unwrap().expect("Group exists");
+    assert_eq!(by_name.id, created.id);
+
+    let request = UpdateDiscordGroupRequest {
+        name: Some("Builders".to_string()),
+        add_member_ids: Some(vec!["3".to_string()]),
+        remove_member_ids: Some(vec!["1".to_string()]),
+        required: Some(false),
+    };
+    client.update_discord_group(created.id, &request).await.expect("Failed to update group");
+
+    let page = client.list_server_groups("1234", None, None).await.unwrap();
+    assert_eq!(page.total, 1);
+    assert_eq!(page.groups[0].name, "Builders");
+    assert_eq!(page.groups[0].member_count, 2);
+    assert!(!page.groups[0].required);
+
+    // Members
+    assert!(client.add_group_member(created.id, "4").await.unwrap().added);
+    assert!(!client.add_group_member(created.id, "4").await.unwrap().added);
+    client.remove_group_member(created.id, "4").await.expect("Failed to remove member");
+    let request = UpdateGroupMemberRequest {
+        required: Some(true),
+        weight: Some(2.0),
+    };
+    let member = client.update_group_member(created.id, "2", &request).await.unwrap().expect("Member exists");
+    assert!(member.required);
+    assert_eq!(member.weight, 2.0);
+    assert!(client.update_group_member(created.id, "4", &request).await.unwrap().is_none());
+
+    // Roles
+    assert_eq!(client.update_discord_group_role(created.id, "900").await.unwrap().role_id, "900");
+    assert!(client.bind_group_role(created.id, "901").await.unwrap().role_sync);
+    let synced = client.list_role_synced_groups(Some("1234")).await.unwrap();
+    assert_eq!(synced.iter().map(|group| group.id).collect::<Vec<_>>(), vec![created.id]);
+    assert!(!client.unbind_group_role(created.id).await.unwrap().role_sync);
+    assert!(client.list_role_synced_groups(None).await.unwrap().is_empty());
+
+    assert!(client.delete_discord_group(created.id).await.unwrap());
+    assert!(!client.delete_discord_group(created.id).await.unwrap());
+    assert!(client.get_discord_group(created.id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_availability_round_trip() {
+    let client = spawn_api().await;
+    for member in ["1", "2"] {
+        client.create_schedule(&schedule_request(member, Some(member))).await.unwrap();
+    }
+    let request = CreateDiscordGroupRequest {
+        name: "Raiders".to_string(),
+        server_id: "1234".to_string(),
+        member_ids: vec!["1".to_string(), "2".to_string()],
+    };
+    let group = client.create_discord_group(&request).await.unwrap();
+
+    let query = MatchQuery {
+        group_ids: group.id.to_string(),
+        min_per_group: Some(2),
+        ..MatchQuery::default()
+    };
+    let response = client.match_availability(&query).await.expect("Match request failed");
+    assert!(!response.matches.is_empty());
+    assert!(response.matches.iter().all(|m| m.start >= tomorrow_evening().start && m.end <= tomorrow_evening().end));
+
+    let query = HeatmapQuery {
+        group_ids: group.id.to_string(),
+        ..HeatmapQuery::default()
+    };
+    let image = client.availability_heatmap(&query).await.expect("Heatmap request failed");
+    assert!(image.starts_with(b"\x89PNG"));
+}
+
+#[tokio::test]
+async fn test_meeting_round_trip() {
+    let client = spawn_api().await;
+    let request = CreateDiscordGroupRequest {
+        name: "Raiders".to_string(),
+        server_id: "1234".to_string(),
+        member_ids: vec!["42".to_string()],
+    };
+    let group = client.create_discord_group(&request).await.unwrap();
+
+    let created = client.create_meeting(&meeting_request(group.id)).await.expect("Failed to create meeting");
+    assert_eq!(created.status, MeetingStatus::Scheduled);
+
+    let meeting = client.get_meeting(created.id).await.unwrap().expect("Meeting exists");
+    assert_eq!(meeting.attendee_ids, vec!["42"]);
+    let scheduled = client.list_server_meetings("1234", MeetingStatus::Scheduled).await.unwrap();
+    assert_eq!(scheduled.iter().map(|m| m.id).collect::<Vec<_>>(), vec![created.id]);
+
+    let cancelled = client.update_meeting_status(created.id, MeetingStatus::Cancelled).await.unwrap();
+    assert_eq!(cancelled.status, MeetingStatus::Cancelled);
+    assert!(client.list_server_meetings("1234", MeetingStatus::Scheduled).await.unwrap().is_empty());
+
+    assert!(client.get_meeting(Uuid::new_v4()).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_unreachable_api() {
+    let client = TimeSyncClient::new("http://127.0.0.1:1/api");
+
+    let error = client.health().await.expect_err("Request to a closed port succeeded");
+
+    assert!(matches!(error, ClientError::Http(_)));
+}
+
+#[test]
+fn test_error_response_mapping() {
+    let error = ClientError::from_response(
+        reqwest::StatusCode::NOT_FOUND,
+        "Resource not found: Meeting with ID 1 not found".to_string(),
+    );
+    assert!(error.is_not_found());
+    assert_eq!(error.to_string(), "Resource not found: Meeting with ID 1 not found");
+
+    let error = ClientError::from_response(
+        reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+        "Internal server error".to_string(),
+    );
+    assert!(matches!(error, ClientError::Server(_)));
+    assert!(!error.is_not_found());
+
+    let error = ClientError::from_response(reqwest::StatusCode::BAD_GATEWAY, "upstream down".to_string());
+    assert!(matches!(error, ClientError::Unexpected { status, .. } if status == reqwest::StatusCode::BAD_GATEWAY));
+}
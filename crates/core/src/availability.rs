@@ -0,0 +1,127 @@
+//! When someone is actually free, given their available and busy slots.
+//!
+//! Slots repeat weekly when recurring, and busy slots cut time out of every
+//! available slot they overlap, recurring or not. That lets a weekly pattern
+//! such as "free every evening" carry one-off exceptions like "except this
+//! Tuesday", and a weekly class block out the same evening every week.
+//! Available time is either fully available or only "if need be".
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::models::schedule::{SlotPreference, TimeSlotResponse};
+
+/// Slots that overlap `from..until`, with recurring slots repeated weekly
+///
+/// Recurring slots keep their `is_recurring` flag in every week they appear.
+pub fn expand_slots(slots: &[TimeSlotResponse], from: DateTime<Utc>, until: DateTime<Utc>) -> Vec<TimeSlotResponse> {
+    let mut expanded = Vec::new();
+
+    for slot in slots {
+        if !slot.is_recurring {
+            if slot.start < until && slot.end > from {
+                expanded.push(slot.clone());
+            }
+            continue;
+        }
+
+        // Step back to the first repetition that could reach `from`
+        let week = Duration::weeks(1);
+        let weeks_back = (slot.end - from).num_weeks();
+        let mut start = slot.start - week * weeks_back as i32;
+        let mut end = slot.end - week * weeks_back as i32;
+        while end <= from {
+            start += week;
+            end += week;
+        }
+
+        while start < until {
+            expanded.push(TimeSlotResponse { start, end, ..slot.clone() });
+            start += week;
+            end += week;
+        }
+    }
+
+    expanded.sort_by_key(|slot| slot.start);
+    expanded
+}
+
+/// A stretch of time someone is free, and how happy they are to meet then
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FreeTime {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub preference: SlotPreference,
+}
+
+/// The times within `from..until` covered by an available slot and no busy one
+///
+/// Returns sorted, non-overlapping ranges; touching ranges with the same
+/// preference are merged. Time covered by both a fully available and an "if
+/// need be" slot counts as fully available.
+pub fn free_intervals(slots: &[TimeSlotResponse], from: DateTime<Utc>, until: DateTime<Utc>) -> Vec<FreeTime> {
+    let expanded = expand_slots(slots, from, until);
+    let ranges = |keep: &dyn Fn(&TimeSlotResponse) -> bool| {
+        merge(expanded.iter()
+            .filter(|slot| keep(slot))
+            .map(|slot| (slot.start.max(from), slot.end.min(until)))
+            .collect())
+    };
+
+    let busy = ranges(&|slot| slot.is_unavailable);
+    let available = subtract(
+        ranges(&|slot| !slot.is_unavailable && slot.preference == SlotPreference::Available),
+        &busy,
+    );
+    let if_need_be = subtract(
+        subtract(ranges(&|slot| !slot.is_unavailable && slot.preference == SlotPreference::IfNeedBe), &busy),
+        &available,
+    );
+
+    let mut free: Vec<FreeTime> = available.into_iter()
+        .map(|(start, end)| FreeTime { start, end, preference: SlotPreference::Available })
+        .chain(if_need_be.into_iter().map(|(start, end)| FreeTime { start, end, preference: SlotPreference::IfNeedBe }))
+        .collect();
+    free.sort_by_key(|time| time.start);
+    free
+}
+
+/// Sort ranges and merge the ones that overlap or touch
+fn merge(mut ranges: Vec<(DateTime<Utc>, DateTime<Utc>)>) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    ranges.retain(|(start, end)| start < end);
+    ranges.sort();
+
+    let mut merged: Vec<(DateTime<Utc>, DateTime<Utc>)> = Vec::new();
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    merged
+}
+
+/// Cut `cut` out of `ranges`; both must be merged
+fn subtract(
+    ranges: Vec<(DateTime<Utc>, DateTime<Utc>)>,
+    cut: &[(DateTime<Utc>, DateTime<Utc>)],
+) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    let mut remaining = Vec::new();
+    for (mut start, end) in ranges {
+        for &(cut_start, cut_end) in cut.iter().filter(|(cut_start, _)| *cut_start < end) {
+            if cut_end <= start {
+                continue;
+            }
+            if cut_start > start {
+                remaining.push((start, cut_start));
+            }
+            start = start.max(cut_end);
+        }
+
+        if start < end {
+            remaining.push((start, end));
+        }
+    }
+
+    remaining
+}
@@ -0,0 +1,273 @@
+//! Availability heatmaps rendered as PNG images.
+//!
+//! Draws a grid with one row per day and one column per hour, shaded by how
+//! many people are available. Rendering is plain pixel filling with a small
+//! built-in bitmap font for the labels, so it runs anywhere without a browser
+//! or system fonts.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use std::collections::HashSet;
+
+use crate::errors::{TimeError, TimeResult};
+use crate::models::discord::MatchResult;
+
+/// Most days a heatmap draws
+pub const MAX_DAYS: u32 = 7;
+
+/// Pixel size of one hour cell, including the gap around it
+const CELL: u32 = 24;
+const GAP: u32 = 2;
+/// Bitmap glyphs are 5×7 and drawn at this scale
+const FONT_SCALE: u32 = 2;
+const CHAR_WIDTH: u32 = 6 * FONT_SCALE;
+const LABEL_WIDTH: u32 = 7 * CHAR_WIDTH + 8;
+const HEADER_HEIGHT: u32 = 7 * FONT_SCALE + 12;
+const LEGEND_HEIGHT: u32 = 7 * FONT_SCALE + 20;
+const MARGIN: u32 = 8;
+
+/// Colours match Discord's dark theme so images blend into embeds
+const BACKGROUND: [u8; 3] = [0x2B, 0x2D, 0x31];
+const TEXT: [u8; 3] = [0xDB, 0xDE, 0xE1];
+const EMPTY: [u8; 3] = [0x3A, 0x3C, 0x42];
+const FULL: [u8; 3] = [0x57, 0xF2, 0x87];
+
+/// How many people are available between `start` and `end`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeatmapSlot {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub available: usize,
+}
+
+impl From<&MatchResult> for HeatmapSlot {
+    /// Counts each available user once, even if they are in several groups
+    fn from(result: &MatchResult) -> Self {
+        let available: HashSet<&String> = result.groups.iter()
+            .flat_map(|group| group.available_users.iter())
+            .collect();
+
+        Self {
+            start: result.start,
+            end: result.end,
+            available: available.len(),
+        }
+    }
+}
+
+/// What part of the calendar to draw and how to shade it
+#[derive(Debug, Clone)]
+pub struct HeatmapOptions {
+    /// First row, as a date in `tz`
+    pub first_day: NaiveDate,
+    /// Number of rows, at most `MAX_DAYS`
+    pub days: u32,
+    pub tz: Tz,
+    /// Count shaded as fully available; defaults to the highest count shown
+    pub total: Option<usize>,
+}
+
+/// Render slots as a days × hours heatmap PNG
+///
+/// Each hour cell is shaded by the highest count among the slots that
+/// overlap it.
+///
+/// # Errors
+///
+/// Returns `TimeError::Internal` if the image can't be encoded.
+pub fn render_heatmap(slots: &[HeatmapSlot], options: &HeatmapOptions) -> TimeResult<Vec<u8>> {
+    let days = options.days.clamp(1, MAX_DAYS);
+    let counts = hourly_counts(slots, options.first_day, days, options.tz);
+    let total = options.total
+        .unwrap_or_else(|| counts.iter().flatten().copied().max().unwrap_or(0))
+        .max(1);
+
+    let width = MARGIN * 2 + LABEL_WIDTH + 24 * CELL;
+    let height = MARGIN * 2 + HEADER_HEIGHT + days * CELL + LEGEND_HEIGHT;
+    let mut canvas = Canvas::new(width, height);
+    let grid_x = MARGIN + LABEL_WIDTH;
+    let grid_y = MARGIN + HEADER_HEIGHT;
+
+    for hour in (0..24).step_by(3) {
+        canvas.text(grid_x + hour * CELL + GAP, MARGIN, &hour.to_string(), TEXT);
+    }
+
+    for (row, day_counts) in counts.iter().enumerate() {
+        let row = row as u32;
+        let date = options.first_day + Duration::days(row as i64);
+        let y = grid_y + row * CELL;
+        let label = format!("{} {:02}", weekday_label(date.weekday()), date.day());
+        canvas.text(MARGIN, y + (CELL - 7 * FONT_SCALE) / 2, &label, TEXT);
+
+        for (hour, &count) in day_counts.iter().enumerate() {
+            let x = grid_x + hour as u32 * CELL;
+            canvas.fill(x + GAP / 2, y + GAP / 2, CELL - GAP, CELL - GAP, shade(count, total));
+        }
+    }
+
+    // Legend: a gradient from nobody to everyone
+    let legend_y = grid_y + days * CELL + 12;
+    canvas.text(grid_x, legend_y, "0", TEXT);
+    let steps = 8;
+    for step in 0..=steps {
+        let x = grid_x + CHAR_WIDTH + 8 + step * CELL;
+        canvas.fill(x, legend_y, CELL - GAP, 7 * FONT_SCALE, shade(step as usize * total, steps as usize * total));
+    }
+    canvas.text(grid_x + CHAR_WIDTH + 16 + (steps + 1) * CELL, legend_y, &total.to_string(), TEXT);
+
+    canvas.encode()
+}
+
+/// Highest available count per (day, hour) cell
+fn hourly_counts(slots: &[HeatmapSlot], first_day: NaiveDate, days: u32, tz: Tz) -> Vec<[usize; 24]> {
+    (0..days)
+        .map(|day| {
+            let date = first_day + Duration::days(day as i64);
+            let mut counts = [0; 24];
+
+            for (hour, count) in counts.iter_mut().enumerate() {
+                let Some(start) = local_hour(date, hour as u32, tz) else {
+                    continue;
+                };
+                let end = start + Duration::hours(1);
+
+                *count = slots.iter()
+                    .filter(|slot| slot.start < end && slot.end > start)
+                    .map(|slot| slot.available)
+                    .max()
+                    .unwrap_or(0);
+            }
+
+            counts
+        })
+        .collect()
+}
+
+/// The start of an hour on a local date, or `None` if DST skips it
+fn local_hour(date: NaiveDate, hour: u32, tz: Tz) -> Option<DateTime<Utc>> {
+    let local = date.and_hms_opt(hour, 0, 0)?;
+    tz.from_local_datetime(&local)
+        .earliest()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+fn weekday_label(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "MON",
+        Weekday::Tue => "TUE",
+        Weekday::Wed => "WED",
+        Weekday::Thu => "THU",
+        Weekday::Fri => "FRI",
+        Weekday::Sat => "SAT",
+        Weekday::Sun => "SUN",
+    }
+}
+
+/// Blend from the empty to the full colour by `count / total`
+fn shade(count: usize, total: usize) -> [u8; 3] {
+    if count == 0 {
+        return EMPTY;
+    }
+
+    // Keep the lowest non-zero count visibly different from nobody
+    let ratio = 0.25 + 0.75 * (count.min(total) as f32 / total as f32);
+    let mut color = [0; 3];
+    for (i, channel) in color.iter_mut().enumerate() {
+        let from = EMPTY[i] as f32;
+        let to = FULL[i] as f32;
+        *channel = (from + (to - from) * ratio).round() as u8;
+    }
+    color
+}
+
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32) -> Self {
+        let pixels = BACKGROUND.iter()
+            .copied()
+            .cycle()
+            .take((width * height * 3) as usize)
+            .collect();
+
+        Self { width, height, pixels }
+    }
+
+    fn fill(&mut self, x: u32, y: u32, width: u32, height: u32, color: [u8; 3]) {
+        for py in y..(y + height).min(self.height) {
+            for px in x..(x + width).min(self.width) {
+                let offset = ((py * self.width + px) * 3) as usize;
+                self.pixels[offset..offset + 3].copy_from_slice(&color);
+            }
+        }
+    }
+
+    fn text(&mut self, x: u32, y: u32, text: &str, color: [u8; 3]) {
+        for (index, c) in text.chars().enumerate() {
+            let left = x + index as u32 * CHAR_WIDTH;
+
+            for (row, bits) in glyph(c).iter().enumerate() {
+                for column in 0..5 {
+                    if bits & (0b10000 >> column) != 0 {
+                        self.fill(
+                            left + column * FONT_SCALE,
+                            y + row as u32 * FONT_SCALE,
+                            FONT_SCALE,
+                            FONT_SCALE,
+                            color,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn encode(&self) -> TimeResult<Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        let mut encoder = png::Encoder::new(&mut bytes, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header().map_err(|e| TimeError::Internal(Box::new(e)))?;
+        writer.write_image_data(&self.pixels).map_err(|e| TimeError::Internal(Box::new(e)))?;
+        writer.finish().map_err(|e| TimeError::Internal(Box::new(e)))?;
+
+        Ok(bytes)
+    }
+}
+
+/// 5×7 bitmap for the characters used in labels; anything else is blank
+fn glyph(c: char) -> [u8; 7] {
+    match c {
+        '0' => [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
+        '1' => [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        '2' => [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+        '3' => [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+        '4' => [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+        '5' => [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+        '6' => [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+        '7' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+        '8' => [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+        '9' => [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+        'A' => [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'D' => [0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11110],
+        'E' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111],
+        'F' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000],
+        'H' => [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'I' => [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        'M' => [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001],
+        'N' => [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001],
+        'O' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'R' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001],
+        'S' => [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110],
+        'T' => [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100],
+        'U' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'W' => [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010],
+        _ => [0; 7],
+    }
+}
@@ -1,2 +1,6 @@
+pub mod availability;
 pub mod errors;
-pub mod models;
\ No newline at end of file
+pub mod heatmap;
+pub mod matching;
+pub mod models;
+pub mod parser;
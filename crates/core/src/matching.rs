@@ -0,0 +1,136 @@
+//! Required and optional attendees for meeting matching.
+//!
+//! Groups and members can be tagged as required or optional. A candidate slot
+//! is only acceptable when every required member is available and every
+//! required group has enough of its members there. Acceptable slots are then
+//! scored by the weights of everyone who can come, so the nice-to-have
+//! members decide between slots that all work. Members who are only free "if
+//! need be" still count towards the requirements, but add less to the score,
+//! so times where everyone is fully available win.
+
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Weight given to members that haven't been weighted explicitly
+pub const DEFAULT_WEIGHT: f64 = 1.0;
+
+/// Share of their weight a member adds when they're only free "if need be"
+pub const IF_NEED_BE_FACTOR: f64 = 0.5;
+
+/// How much one member matters when picking a time
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemberConstraint {
+    pub discord_id: String,
+    /// The slot is rejected if this member isn't available
+    pub required: bool,
+    /// Added to a slot's score when this member is available
+    pub weight: f64,
+}
+
+/// How much one group matters when picking a time
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupConstraint {
+    pub id: Uuid,
+    /// Whether `min_available` has to be met; optional groups only add to the score
+    pub required: bool,
+    /// Members of a required group that have to be available
+    pub min_available: usize,
+    pub members: Vec<MemberConstraint>,
+}
+
+impl GroupConstraint {
+    /// Members of this group found in `available`
+    pub fn available_count(&self, available: &HashSet<&str>) -> usize {
+        self.members.iter()
+            .filter(|member| available.contains(member.discord_id.as_str()))
+            .count()
+    }
+
+    /// Required members of this group missing from `available`
+    pub fn missing_required(&self, available: &HashSet<&str>) -> Vec<String> {
+        self.members.iter()
+            .filter(|member| member.required && !available.contains(member.discord_id.as_str()))
+            .map(|member| member.discord_id.clone())
+            .collect()
+    }
+
+    /// Members of this group missing from `available`, required or not
+    pub fn unavailable(&self, available: &HashSet<&str>) -> Vec<String> {
+        self.members.iter()
+            .filter(|member| !available.contains(member.discord_id.as_str()))
+            .map(|member| member.discord_id.clone())
+            .collect()
+    }
+
+    /// Whether this group doesn't rule out a slot on numbers alone
+    pub fn meets_quorum(&self, available: &HashSet<&str>) -> bool {
+        !self.required || self.available_count(available) >= self.min_available
+    }
+
+    /// How many more members would have to become available for this group
+    /// to accept the slot
+    ///
+    /// Missing required members count towards the quorum once they join, so
+    /// this is the larger of the quorum shortfall and the missing required
+    /// members.
+    pub fn shortfall(&self, available: &HashSet<&str>) -> usize {
+        let below_quorum = if self.required {
+            self.min_available.saturating_sub(self.available_count(available))
+        } else {
+            0
+        };
+
+        below_quorum.max(self.missing_required(available).len())
+    }
+}
+
+/// Mark members as required in every group they belong to
+pub fn require_members(groups: &mut [GroupConstraint], discord_ids: &[String]) {
+    for member in groups.iter_mut().flat_map(|group| group.members.iter_mut()) {
+        if discord_ids.contains(&member.discord_id) {
+            member.required = true;
+        }
+    }
+}
+
+/// Check a slot against the constraints and score it
+///
+/// `available` is everyone who can come, including the members in
+/// `tentative` who are only free "if need be".
+///
+/// Returns `None` if a required member is missing or a required group has
+/// fewer than `min_available` members available. Otherwise returns the slot's
+/// `score_slot`.
+pub fn evaluate_slot(
+    groups: &[GroupConstraint],
+    available: &HashSet<&str>,
+    tentative: &HashSet<&str>,
+) -> Option<f64> {
+    let acceptable = groups.iter().all(|group| {
+        group.meets_quorum(available) && group.missing_required(available).is_empty()
+    });
+
+    acceptable.then(|| score_slot(groups, available, tentative))
+}
+
+/// Sum of the weights of the available members, whether or not the slot works
+///
+/// Each member counts once; someone in several groups counts with their
+/// highest weight. Members in `tentative` count with `IF_NEED_BE_FACTOR` of
+/// their weight.
+pub fn score_slot(groups: &[GroupConstraint], available: &HashSet<&str>, tentative: &HashSet<&str>) -> f64 {
+    let mut weights: HashMap<&str, f64> = HashMap::new();
+
+    for member in groups.iter().flat_map(|group| group.members.iter()) {
+        if available.contains(member.discord_id.as_str()) {
+            let weight = weights.entry(member.discord_id.as_str()).or_insert(member.weight);
+            *weight = weight.max(member.weight);
+        }
+    }
+
+    weights.iter()
+        .map(|(discord_id, weight)| {
+            if tentative.contains(discord_id) { weight * IF_NEED_BE_FACTOR } else { *weight }
+        })
+        .sum()
+}
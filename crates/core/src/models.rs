@@ -1,3 +1,4 @@
 pub mod schedule;
 pub mod time_slot;
-pub mod discord;
\ No newline at end of file
+pub mod discord;
+pub mod meeting;
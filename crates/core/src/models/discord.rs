@@ -1,5 +1,6 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,22 +10,40 @@ pub struct DiscordUser {
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateDiscordUserRequest {
     pub discord_id: String,
     pub schedule_id: Uuid,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateDiscordUserResponse {
     pub discord_id: String,
     pub schedule_id: Uuid,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct GetDiscordUserResponse {
     pub discord_id: String,
     pub schedule_id: Option<Uuid>,
+    /// Personal display timezone, overriding the server's when set
+    #[serde(default)]
+    pub timezone: Option<String>,
+}
+
+/// Set a user's or a server's timezone
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SetTimezoneRequest {
+    /// IANA timezone name, e.g. `Europe/London`
+    pub timezone: String,
+}
+
+/// Settings of one Discord server
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DiscordServerResponse {
+    pub server_id: String,
+    /// Timezone times are shown in, `UTC` until one is set
+    pub timezone: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,14 +55,14 @@ pub struct DiscordGroup {
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateDiscordGroupRequest {
     pub name: String,
     pub server_id: String,
     pub member_ids: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateDiscordGroupResponse {
     pub id: Uuid,
     pub name: String,
@@ -51,69 +70,228 @@ pub struct CreateDiscordGroupResponse {
     pub role_id: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct GetDiscordGroupResponse {
     pub id: Uuid,
     pub name: String,
     pub server_id: String,
     pub role_id: Option<String>,
+    /// Whether membership is kept in sync with the holders of `role_id`
+    #[serde(default)]
+    pub role_sync: bool,
+    /// Whether matches need `min_per_group` of this group's members
+    #[serde(default = "default_required")]
+    pub required: bool,
     pub members: Vec<DiscordGroupMember>,
+    pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A group as listed for its server, without its members
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DiscordGroupSummary {
+    pub id: Uuid,
+    pub name: String,
+    pub server_id: String,
+    pub role_id: Option<String>,
+    #[serde(default)]
+    pub role_sync: bool,
+    #[serde(default = "default_required")]
+    pub required: bool,
+    pub member_count: usize,
+}
+
+/// One page of a server's groups, ordered by name
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ListDiscordGroupsResponse {
+    pub groups: Vec<DiscordGroupSummary>,
+    /// Groups in the server across all pages
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct DiscordGroupMember {
     pub discord_id: String,
     pub schedule_id: Option<Uuid>,
+    /// Whether matches need this member to be available
+    #[serde(default)]
+    pub required: bool,
+    /// How much this member's availability counts when ranking matches
+    #[serde(default = "default_weight")]
+    pub weight: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UpdateDiscordGroupRequest {
     pub name: Option<String>,
     pub add_member_ids: Option<Vec<String>>,
     pub remove_member_ids: Option<Vec<String>>,
+    #[serde(default)]
+    pub required: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UpdateDiscordGroupResponse {
     pub id: Uuid,
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UpdateDiscordGroupRoleRequest {
     pub role_id: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UpdateDiscordGroupRoleResponse {
     pub id: Uuid,
     pub role_id: String,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Tag a group member as required or optional, or change their weight
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UpdateGroupMemberRequest {
+    pub required: Option<bool>,
+    pub weight: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UpdateGroupMemberResponse {
+    pub group_id: Uuid,
+    pub discord_id: String,
+    pub required: bool,
+    pub weight: f64,
+}
+
+/// Result of adding someone to a group who may already be a member
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AddGroupMemberResponse {
+    pub group_id: Uuid,
+    pub discord_id: String,
+    /// False if they were already a member
+    pub added: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MatchRequest {
     pub group_ids: Vec<Uuid>,
     pub min_per_group: Option<usize>,
     pub count: Option<usize>,
+    /// Members who must all be available, on top of those tagged as required
+    #[serde(default)]
+    pub required_ids: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Query parameters for the match availability endpoint
+///
+/// * `group_ids` - Comma-separated list of Discord group UUIDs
+/// * `min_per_group` - Minimum number of available users per group (default: 1)
+/// * `count` - Maximum number of matching time slots to return (default: 5)
+/// * `required_ids` - Comma-separated Discord IDs of members who must all be available
+/// * `from` - Start of the search window (default: now)
+/// * `until` - End of the search window (default: 28 days after `from`, at most 90 days after it)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MatchQuery {
+    /// Comma-separated list of Discord group UUIDs to match
+    pub group_ids: String,
+
+    /// Minimum number of available users required from each group
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_per_group: Option<usize>,
+
+    /// Maximum number of matching time slots to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<usize>,
+
+    /// Comma-separated Discord IDs that must all be available, on top of
+    /// the members tagged as required
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required_ids: Option<String>,
+
+    /// Earliest time a match may start
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<DateTime<Utc>>,
+
+    /// Latest time a match may end, at most 90 days after `from`; recurring
+    /// slots repeat weekly up to here
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// Query parameters for the availability heatmap endpoint
+///
+/// * `group_ids` - Comma-separated list of Discord group UUIDs
+/// * `min_per_group` - Minimum available users per group for an hour to be shaded (default: 0)
+/// * `timezone` - Timezone for the rows and hour columns (default: UTC)
+/// * `start` - First day to draw (default: today in `timezone`)
+/// * `days` - Number of days to draw, up to 7 (default: 7)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct HeatmapQuery {
+    /// Comma-separated list of Discord group UUIDs to draw
+    pub group_ids: String,
+
+    /// Minimum number of available users required from each group
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_per_group: Option<usize>,
+
+    /// IANA timezone name used for the grid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+
+    /// First day of the grid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<NaiveDate>,
+
+    /// Number of days in the grid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct MatchResponse {
     pub matches: Vec<MatchResult>,
+    /// The closest slots that fall short, fewest missing members first
+    #[serde(default)]
+    pub near_misses: Vec<MatchResult>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct MatchResult {
     pub start: DateTime<Utc>,
     pub end: DateTime<Utc>,
     pub groups: Vec<MatchGroupResult>,
+    /// Summed weights of the available members, with "if need be" members
+    /// counting for less; matches are ranked by this
+    #[serde(default)]
+    pub score: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct MatchGroupResult {
     pub id: Uuid,
     pub name: String,
     pub available_users: Vec<String>,
     pub count: usize,
-}
\ No newline at end of file
+    /// Available members who are only free "if need be"
+    #[serde(default)]
+    pub tentative_users: Vec<String>,
+    /// Required members of this group who aren't available, for near misses
+    #[serde(default)]
+    pub missing_required: Vec<String>,
+    /// How many more members this group needs, for near misses
+    #[serde(default)]
+    pub shortfall: usize,
+    /// Members of this group who aren't available, for near misses
+    #[serde(default)]
+    pub unavailable_users: Vec<String>,
+}
+fn default_required() -> bool {
+    true
+}
+
+fn default_weight() -> f64 {
+    crate::matching::DEFAULT_WEIGHT
+}
@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Lifecycle state of a meeting that was finalized from a /match poll
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MeetingStatus {
+    Scheduled,
+    Cancelled,
+    Rescheduled,
+}
+
+impl MeetingStatus {
+    /// The value stored in the `meetings.status` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MeetingStatus::Scheduled => "scheduled",
+            MeetingStatus::Cancelled => "cancelled",
+            MeetingStatus::Rescheduled => "rescheduled",
+        }
+    }
+
+    /// Parse a `meetings.status` column value
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "scheduled" => Some(MeetingStatus::Scheduled),
+            "cancelled" => Some(MeetingStatus::Cancelled),
+            "rescheduled" => Some(MeetingStatus::Rescheduled),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Meeting {
+    pub id: Uuid,
+    pub server_id: String,
+    pub channel_id: String,
+    pub organizer_id: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub timezone: String,
+    pub status: MeetingStatus,
+    pub min_per_group: i32,
+    pub slot_duration: i32,
+    pub group_ids: Vec<Uuid>,
+    pub attendee_ids: Vec<String>,
+    pub rescheduled_from: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Record a meeting finalized from a /match poll
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateMeetingRequest {
+    pub server_id: String,
+    pub channel_id: String,
+    pub organizer_id: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub timezone: String,
+    pub min_per_group: i32,
+    pub slot_duration: i32,
+    #[serde(default)]
+    pub group_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub attendee_ids: Vec<String>,
+    /// The meeting this one replaces, which is marked as rescheduled
+    #[serde(default)]
+    pub rescheduled_from: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UpdateMeetingStatusRequest {
+    pub status: MeetingStatus,
+}
+
+/// A server's upcoming meetings, soonest first
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ListMeetingsResponse {
+    pub meetings: Vec<Meeting>,
+}
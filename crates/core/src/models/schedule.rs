@@ -1,5 +1,6 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -10,7 +11,7 @@ pub struct Schedule {
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateScheduleRequest {
     pub name: String,
     pub password: Option<String>,
@@ -25,15 +26,50 @@ fn default_timezone() -> String {
     "UTC".to_string()
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// How happy someone is to meet during an available slot
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SlotPreference {
+    #[default]
+    Available,
+    /// Possible, but only if no better time works
+    IfNeedBe,
+}
+
+impl SlotPreference {
+    /// The value stored in the `time_slots.preference` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SlotPreference::Available => "available",
+            SlotPreference::IfNeedBe => "if_need_be",
+        }
+    }
+
+    /// Parse a `time_slots.preference` column value
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "available" => Some(SlotPreference::Available),
+            "if_need_be" => Some(SlotPreference::IfNeedBe),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateTimeSlotRequest {
     pub start: DateTime<Utc>,
     pub end: DateTime<Utc>,
     #[serde(default)]
     pub is_recurring: bool,
+    /// Marks busy time, which overrides any available slot it overlaps
+    #[serde(default)]
+    pub is_unavailable: bool,
+    /// Ignored for busy slots
+    #[serde(default)]
+    pub preference: SlotPreference,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateScheduleResponse {
     pub id: Uuid,
     pub name: String,
@@ -42,7 +78,7 @@ pub struct CreateScheduleResponse {
     pub timezone: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct GetScheduleResponse {
     pub id: Uuid,
     pub name: String,
@@ -52,14 +88,20 @@ pub struct GetScheduleResponse {
     pub slots: Vec<TimeSlotResponse>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TimeSlotResponse {
     pub start: DateTime<Utc>,
     pub end: DateTime<Utc>,
     pub is_recurring: bool,
+    /// Marks busy time, which overrides any available slot it overlaps
+    #[serde(default)]
+    pub is_unavailable: bool,
+    /// Ignored for busy slots
+    #[serde(default)]
+    pub preference: SlotPreference,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UpdateScheduleRequest {
     pub name: Option<String>,
     pub slots: Vec<CreateTimeSlotRequest>,
@@ -67,18 +109,35 @@ pub struct UpdateScheduleRequest {
     pub timezone: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UpdateScheduleResponse {
     pub id: Uuid,
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct VerifyPasswordRequest {
     pub password: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct VerifyPasswordResponse {
     pub valid: bool,
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ParseAvailabilityRequest {
+    pub text: String,
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+    /// Defaults to today in `timezone`
+    pub reference_date: Option<NaiveDate>,
+    /// Treat slots as weekly recurring unless the text names a date
+    #[serde(default)]
+    pub weekly: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ParseAvailabilityResponse {
+    pub slots: Vec<CreateTimeSlotRequest>,
+}
@@ -0,0 +1,655 @@
+//! Natural-language availability parsing.
+//!
+//! Turns short phrases such as "weekdays after 6pm", "Sat 10-14 every week"
+//! or "not Thursday" into time slots. Text is split into lines on newlines and
+//! semicolons, and each line into clauses of days followed by times:
+//!
+//! * Days: names (`mon`, `Tuesdays`), ranges (`mon-fri`, `monday to friday`),
+//!   `weekdays`, `weekends`, `daily`, `today`, `tomorrow` or ISO dates.
+//! * Times: ranges (`10-14`, `6pm-9:30pm`, `18:00 to 22:00`), `after 6pm`,
+//!   `before noon`, `morning`, `afternoon`, `evening`, `night` or `all day`.
+//! * `not`, `except` or `no` start an exclusion that is removed from the
+//!   rest of the text. Exclusions on their own exclude from the whole week.
+//! * `every week` or `weekly` makes a line recurring, and `every <day>` or
+//!   `daily` makes a clause recurring.
+//!
+//! Clauses without days cover every day and clauses without times cover the
+//! whole day. Concrete weekdays are the next occurrence on or after the
+//! reference date; recurring slots are anchored in the week (starting Monday)
+//! that contains it, matching how the web UI stores weekly availability.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use std::collections::BTreeMap;
+
+use crate::errors::{TimeError, TimeResult};
+use crate::models::schedule::{CreateTimeSlotRequest, SlotPreference};
+
+const MINUTES_PER_DAY: u32 = 24 * 60;
+
+const WEEK: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+/// Words that carry no meaning for availability
+const FILLER_WORDS: &[&str] = &[
+    "on", "at", "and", "or", "but", "also", "plus", "i", "i'm", "im", "am", "is", "be",
+    "available", "free", "the", "in",
+];
+
+const NEGATION_WORDS: &[&str] = &["not", "except", "excluding", "no", "never"];
+
+/// Words joining the two ends of a day or time range
+const RANGE_WORDS: &[&str] = &["to", "through", "thru", "until", "till"];
+
+/// Parse availability text into slots
+///
+/// Slots are concrete unless the text says they repeat, e.g. with `every week`.
+///
+/// # Errors
+///
+/// Returns `TimeError::Validation` when the text is empty or contains words
+/// or times that can't be understood.
+pub fn parse_availability(
+    text: &str,
+    reference: NaiveDate,
+    tz: Tz,
+) -> TimeResult<Vec<CreateTimeSlotRequest>> {
+    parse(text, reference, tz, false)
+}
+
+/// Parse availability text into slots that repeat every week by default
+///
+/// Used where availability is edited as a weekly pattern. Explicit dates such
+/// as `today` or `2025-01-20` still produce concrete slots.
+///
+/// # Errors
+///
+/// Returns `TimeError::Validation` when the text is empty or contains words
+/// or times that can't be understood.
+pub fn parse_weekly_availability(
+    text: &str,
+    reference: NaiveDate,
+    tz: Tz,
+) -> TimeResult<Vec<CreateTimeSlotRequest>> {
+    parse(text, reference, tz, true)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DaySpec {
+    Weekday(Weekday),
+    Date(NaiveDate),
+}
+
+/// Days and times that are (or, if negative, aren't) available
+#[derive(Debug, Default)]
+struct Clause {
+    negative: bool,
+    recurring: bool,
+    days: Vec<DaySpec>,
+    /// Minutes from midnight; `end <= start` continues into the next day
+    times: Vec<(u32, u32)>,
+}
+
+impl Clause {
+    fn is_empty(&self) -> bool {
+        self.days.is_empty() && self.times.is_empty()
+    }
+}
+
+/// A day in the output: one day of the recurring week or a concrete date
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum DayKey {
+    /// Days from Monday
+    Weekly(u32),
+    Date(NaiveDate),
+}
+
+impl DayKey {
+    fn next(self) -> Self {
+        match self {
+            DayKey::Weekly(day) => DayKey::Weekly((day + 1) % 7),
+            DayKey::Date(date) => DayKey::Date(date + Duration::days(1)),
+        }
+    }
+
+    fn matches(self, spec: DaySpec) -> bool {
+        match (self, spec) {
+            (DayKey::Weekly(day), DaySpec::Weekday(weekday)) => day == weekday.num_days_from_monday(),
+            (DayKey::Date(date), DaySpec::Weekday(weekday)) => date.weekday() == weekday,
+            (DayKey::Date(date), DaySpec::Date(other)) => date == other,
+            // A single date can't be cut out of a weekly pattern
+            (DayKey::Weekly(_), DaySpec::Date(_)) => false,
+        }
+    }
+}
+
+fn parse(
+    text: &str,
+    reference: NaiveDate,
+    tz: Tz,
+    weekly_by_default: bool,
+) -> TimeResult<Vec<CreateTimeSlotRequest>> {
+    let mut clauses = Vec::new();
+
+    for line in text.split(['\n', ';']).map(str::trim).filter(|line| !line.is_empty()) {
+        clauses.extend(LineParser::new(line, reference).parse()?);
+    }
+
+    if clauses.is_empty() {
+        return Err(TimeError::Validation(
+            "Enter some availability, e.g. `weekdays after 6pm`".to_string(),
+        ));
+    }
+
+    Ok(resolve(&clauses, reference, tz, weekly_by_default))
+}
+
+struct LineParser {
+    tokens: Vec<String>,
+    pos: usize,
+    reference: NaiveDate,
+}
+
+impl LineParser {
+    fn new(line: &str, reference: NaiveDate) -> Self {
+        Self {
+            tokens: tokenize(line),
+            pos: 0,
+            reference,
+        }
+    }
+
+    fn parse(mut self) -> TimeResult<Vec<Clause>> {
+        let mut clauses = vec![Clause::default()];
+        let mut weekly = false;
+
+        while let Some(token) = self.next() {
+            match token.as_str() {
+                word if FILLER_WORDS.contains(&word) => {}
+                word if NEGATION_WORDS.contains(&word) => {
+                    if !current(&mut clauses).is_empty() {
+                        clauses.push(Clause::default());
+                    }
+                    current(&mut clauses).negative = true;
+                }
+                "weekly" => weekly = true,
+                "daily" | "everyday" => {
+                    add_days(&mut clauses, WEEK.iter().copied().map(DaySpec::Weekday).collect()).recurring = true;
+                }
+                "every" | "each" => {
+                    if self.next_if(|word| word == "week") {
+                        weekly = true;
+                        continue;
+                    }
+
+                    let word = self.next().ok_or_else(|| {
+                        TimeError::Validation("Expected a day after `every`".to_string())
+                    })?;
+                    let days = match word.as_str() {
+                        "day" => WEEK.iter().copied().map(DaySpec::Weekday).collect(),
+                        _ => self.parse_days(&word)?.ok_or_else(|| {
+                            TimeError::Validation(format!("Expected a day after `every`, got `{}`", word))
+                        })?,
+                    };
+                    add_days(&mut clauses, days).recurring = true;
+                }
+                _ => {
+                    if let Some(days) = self.parse_days(&token)? {
+                        add_days(&mut clauses, days);
+                    } else if let Some(times) = self.parse_times(&token)? {
+                        current(&mut clauses).times.push(times);
+                    } else {
+                        return Err(TimeError::Validation(format!("Couldn't understand `{}`", token)));
+                    }
+                }
+            }
+        }
+
+        clauses.retain(|clause| !clause.is_empty() || clause.negative);
+        if clauses.iter().any(|clause| clause.negative && clause.is_empty()) {
+            return Err(TimeError::Validation(
+                "Say what isn't available after `not`, e.g. `not Thursday`".to_string(),
+            ));
+        }
+
+        if weekly {
+            for clause in &mut clauses {
+                clause.recurring = true;
+            }
+        }
+
+        Ok(clauses)
+    }
+
+    fn next(&mut self) -> Option<String> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn next_if(&mut self, accept: impl Fn(&str) -> bool) -> bool {
+        let accepted = self.peek().is_some_and(accept);
+        if accepted {
+            self.pos += 1;
+        }
+        accepted
+    }
+
+    /// Days named by `token`, including a range continued by the next tokens
+    fn parse_days(&mut self, token: &str) -> TimeResult<Option<Vec<DaySpec>>> {
+        let days = match token {
+            "weekdays" | "weekday" => WEEK[..5].iter().copied().map(DaySpec::Weekday).collect(),
+            "weekends" | "weekend" => WEEK[5..].iter().copied().map(DaySpec::Weekday).collect(),
+            "today" => vec![DaySpec::Date(self.reference)],
+            "tomorrow" => vec![DaySpec::Date(self.reference + Duration::days(1))],
+            _ => {
+                if let Ok(date) = NaiveDate::parse_from_str(token, "%Y-%m-%d") {
+                    return Ok(Some(vec![DaySpec::Date(date)]));
+                }
+
+                if let Some((from, to)) = token.split_once('-')
+                    && let (Some(from), Some(to)) = (parse_weekday(from), parse_weekday(to))
+                {
+                    return Ok(Some(weekday_range(from, to)));
+                }
+
+                let Some(from) = parse_weekday(token) else {
+                    return Ok(None);
+                };
+
+                // "monday to friday"
+                if self.peek().is_some_and(|word| RANGE_WORDS.contains(&word))
+                    && let Some(to) = self.tokens.get(self.pos + 1).and_then(|word| parse_weekday(word))
+                {
+                    self.pos += 2;
+                    return Ok(Some(weekday_range(from, to)));
+                }
+
+                vec![DaySpec::Weekday(from)]
+            }
+        };
+
+        Ok(Some(days))
+    }
+
+    /// A time range starting at `token`, or `None` if it isn't a time
+    fn parse_times(&mut self, token: &str) -> TimeResult<Option<(u32, u32)>> {
+        let range = match token.trim_end_matches('s') {
+            "morning" => (8 * 60, 12 * 60),
+            "afternoon" => (12 * 60, 17 * 60),
+            "evening" => (17 * 60, 22 * 60),
+            "night" => (20 * 60, MINUTES_PER_DAY),
+            "anytime" | "all-day" => (0, MINUTES_PER_DAY),
+            "all" | "any" if self.next_if(|word| word == "day" || word == "time") => (0, MINUTES_PER_DAY),
+            "after" | "from" | "since" => {
+                let start = self.expect_time(token)?;
+                match self.parse_range_end()? {
+                    Some(end) => make_range(start, end)?,
+                    None => (start.minutes, MINUTES_PER_DAY),
+                }
+            }
+            "before" | "until" | "till" | "by" => {
+                let end = self.expect_time(token)?;
+                make_range(TimePoint { minutes: 0, meridiem: None }, end)?
+            }
+            _ => {
+                if let Some((start, end)) = token.split_once('-')
+                    && let (Some(start), Some(end)) = (parse_time(start), parse_time(end))
+                {
+                    return make_range(start, end).map(Some);
+                }
+
+                let Some(start) = parse_time(token) else {
+                    return Ok(None);
+                };
+
+                match self.parse_range_end()? {
+                    Some(end) => make_range(start, end)?,
+                    None => {
+                        return Err(TimeError::Validation(format!(
+                            "`{}` needs an end time, e.g. `after {}` or a range like `18-21`",
+                            token, token
+                        )));
+                    }
+                }
+            }
+        };
+
+        Ok(Some(range))
+    }
+
+    /// The time after a range word such as `to`, if there is one
+    fn parse_range_end(&mut self) -> TimeResult<Option<TimePoint>> {
+        match self.peek() {
+            Some(word) if RANGE_WORDS.contains(&word) => {
+                let word = word.to_string();
+                self.pos += 1;
+                self.expect_time(&word).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn expect_time(&mut self, after: &str) -> TimeResult<TimePoint> {
+        let token = self.next().unwrap_or_default();
+        parse_time(&token).ok_or_else(|| {
+            TimeError::Validation(format!("Expected a time after `{}`, got `{}`", after, token))
+        })
+    }
+}
+
+fn current(clauses: &mut [Clause]) -> &mut Clause {
+    clauses.last_mut().expect("there is always a clause")
+}
+
+/// Add days to the current clause, or start a new one if it is complete
+fn add_days(clauses: &mut Vec<Clause>, days: Vec<DaySpec>) -> &mut Clause {
+    let clause = current(clauses);
+    if !clause.days.is_empty() && !clause.times.is_empty() {
+        clauses.push(Clause::default());
+    }
+
+    let clause = current(clauses);
+    for day in days {
+        if !clause.days.contains(&day) {
+            clause.days.push(day);
+        }
+    }
+    clause
+}
+
+/// Lowercase words with "6 pm" joined into "6pm" and "mon - fri" into "mon-fri"
+fn tokenize(line: &str) -> Vec<String> {
+    let lowercase = line.to_lowercase().replace(['–', '—'], "-").replace('&', " and ").replace(',', " ");
+
+    let mut words: Vec<String> = Vec::new();
+    for word in lowercase.split_whitespace().map(|word| word.trim_end_matches(['.', '!'])) {
+        match words.last_mut() {
+            Some(last) if (word == "am" || word == "pm") && last.ends_with(|c: char| c.is_ascii_digit()) => {
+                last.push_str(word);
+            }
+            _ if !word.is_empty() => words.push(word.to_string()),
+            _ => {}
+        }
+    }
+
+    let mut joined = String::new();
+    for c in words.join(" ").chars() {
+        if c == '-' {
+            joined.truncate(joined.trim_end().len());
+        } else if c == ' ' && joined.ends_with('-') {
+            continue;
+        }
+        joined.push(c);
+    }
+
+    joined.split_whitespace().map(str::to_string).collect()
+}
+
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    let word = word.strip_suffix('s').filter(|w| w.len() > 3).unwrap_or(word);
+
+    WEEK.iter().copied().find(|day| {
+        let name = match day {
+            Weekday::Mon => "monday",
+            Weekday::Tue => "tuesday",
+            Weekday::Wed => "wednesday",
+            Weekday::Thu => "thursday",
+            Weekday::Fri => "friday",
+            Weekday::Sat => "saturday",
+            Weekday::Sun => "sunday",
+        };
+        word.len() >= 3 && name.starts_with(word)
+    })
+}
+
+/// Days from `from` to `to` inclusive, wrapping past Sunday
+fn weekday_range(from: Weekday, to: Weekday) -> Vec<DaySpec> {
+    let from = from.num_days_from_monday() as usize;
+    let to = to.num_days_from_monday() as usize;
+
+    (0..=(to + 7 - from) % 7)
+        .map(|offset| DaySpec::Weekday(WEEK[(from + offset) % 7]))
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TimePoint {
+    /// Minutes from midnight
+    minutes: u32,
+    /// `Some(true)` for pm, `Some(false)` for am
+    meridiem: Option<bool>,
+}
+
+/// A time like `18`, `18:30`, `6pm`, `6:30am`, `noon` or `midnight`
+fn parse_time(word: &str) -> Option<TimePoint> {
+    match word {
+        "noon" | "midday" => return Some(TimePoint { minutes: 12 * 60, meridiem: None }),
+        "midnight" => return Some(TimePoint { minutes: 0, meridiem: None }),
+        _ => {}
+    }
+
+    let (number, meridiem) = if let Some(number) = word.strip_suffix("am") {
+        (number, Some(false))
+    } else if let Some(number) = word.strip_suffix("pm") {
+        (number, Some(true))
+    } else {
+        (word, None)
+    };
+
+    let (hour, minute) = number.split_once(':').unwrap_or((number, "00"));
+    if hour.is_empty() || hour.len() > 2 || minute.len() != 2 {
+        return None;
+    }
+    let hour: u32 = hour.parse().ok()?;
+    let minute: u32 = minute.parse().ok()?;
+    if minute >= 60 {
+        return None;
+    }
+
+    let hour = match meridiem {
+        Some(_) if hour == 0 || hour > 12 => return None,
+        // 12am is midnight and 12pm is noon
+        Some(pm) => hour % 12 + if pm { 12 } else { 0 },
+        None if hour > 24 || (hour == 24 && minute > 0) => return None,
+        None => hour,
+    };
+
+    Some(TimePoint { minutes: hour * 60 + minute, meridiem })
+}
+
+fn make_range(start: TimePoint, end: TimePoint) -> TimeResult<(u32, u32)> {
+    let mut start_minutes = start.minutes;
+    // "6-10pm" means 18:00-22:00
+    if start.meridiem.is_none() && end.meridiem == Some(true) && start_minutes < 12 * 60 && start_minutes + 12 * 60 < end.minutes {
+        start_minutes += 12 * 60;
+    }
+
+    // "22-00" and "until midnight" run to the end of the day
+    let mut end_minutes = match end.minutes {
+        0 => MINUTES_PER_DAY,
+        minutes => minutes,
+    };
+
+    // "9-5" means 9am-5pm, while "22-2" runs past midnight
+    if start.meridiem.is_none() && end.meridiem.is_none() && end_minutes < start_minutes && start_minutes < 13 * 60 {
+        end_minutes += 12 * 60;
+        if end_minutes <= start_minutes {
+            return Err(TimeError::Validation("Time ranges can't end before they start".to_string()));
+        }
+    }
+
+    if start_minutes == end_minutes || start_minutes >= MINUTES_PER_DAY {
+        return Err(TimeError::Validation("Time ranges can't be empty".to_string()));
+    }
+
+    Ok((start_minutes, end_minutes))
+}
+
+/// Split a range that continues past midnight into its two days
+fn split_range((start, end): (u32, u32)) -> Vec<(u32, u32, bool)> {
+    if start < end {
+        vec![(start, end, false)]
+    } else {
+        let mut parts = vec![(start, MINUTES_PER_DAY, false)];
+        if end > 0 {
+            parts.push((0, end, true));
+        }
+        parts
+    }
+}
+
+fn resolve(
+    clauses: &[Clause],
+    reference: NaiveDate,
+    tz: Tz,
+    weekly_by_default: bool,
+) -> Vec<CreateTimeSlotRequest> {
+    let all_day = [(0, MINUTES_PER_DAY)];
+    let mut available: BTreeMap<DayKey, Vec<(u32, u32)>> = BTreeMap::new();
+
+    // Exclusions on their own are taken from the whole week
+    let whole_week = Clause {
+        recurring: clauses.iter().any(|clause| clause.recurring),
+        ..Clause::default()
+    };
+    let mut positives: Vec<&Clause> = clauses.iter().filter(|clause| !clause.negative).collect();
+    if positives.is_empty() {
+        positives.push(&whole_week);
+    }
+
+    for clause in positives {
+        let days = match clause.days.as_slice() {
+            [] => WEEK.iter().copied().map(DaySpec::Weekday).collect(),
+            days => days.to_vec(),
+        };
+        let times = if clause.times.is_empty() { &all_day[..] } else { &clause.times[..] };
+
+        for day in days {
+            let key = match day {
+                DaySpec::Weekday(weekday) if clause.recurring || weekly_by_default => {
+                    DayKey::Weekly(weekday.num_days_from_monday())
+                }
+                DaySpec::Weekday(weekday) => {
+                    let offset = (weekday.num_days_from_monday() + 7
+                        - reference.weekday().num_days_from_monday())
+                        % 7;
+                    DayKey::Date(reference + Duration::days(offset as i64))
+                }
+                DaySpec::Date(date) if clause.recurring => DayKey::Weekly(date.weekday().num_days_from_monday()),
+                DaySpec::Date(date) => DayKey::Date(date),
+            };
+
+            for &range in times {
+                for (start, end, next_day) in split_range(range) {
+                    let key = if next_day { key.next() } else { key };
+                    available.entry(key).or_default().push((start, end));
+                }
+            }
+        }
+    }
+
+    for clause in clauses.iter().filter(|clause| clause.negative) {
+        let times = if clause.times.is_empty() { &all_day[..] } else { &clause.times[..] };
+
+        for (key, ranges) in available.iter_mut() {
+            for &range in times {
+                for (start, end, next_day) in split_range(range) {
+                    // The part past midnight belongs to the excluded day's successor
+                    let excluded = clause.days.is_empty()
+                        || clause.days.iter().any(|&day| {
+                            let day = if next_day { next_spec(day) } else { day };
+                            key.matches(day)
+                        });
+
+                    if excluded {
+                        *ranges = subtract(ranges, start, end);
+                    }
+                }
+            }
+        }
+    }
+
+    let week_start = reference - Duration::days(reference.weekday().num_days_from_monday() as i64);
+    let mut slots: Vec<CreateTimeSlotRequest> = available
+        .into_iter()
+        .flat_map(|(key, ranges)| {
+            let (date, is_recurring) = match key {
+                DayKey::Weekly(day) => (week_start + Duration::days(day as i64), true),
+                DayKey::Date(date) => (date, false),
+            };
+
+            merge(ranges).into_iter().filter_map(move |(start, end)| {
+                let start = local_to_utc(date, start, tz);
+                let end = local_to_utc(date, end, tz);
+                (start < end).then_some(CreateTimeSlotRequest { start, end, is_recurring, is_unavailable: false, preference: SlotPreference::Available })
+            })
+        })
+        .collect();
+
+    slots.sort_by_key(|slot| (slot.start, slot.end));
+    slots
+}
+
+fn next_spec(day: DaySpec) -> DaySpec {
+    match day {
+        DaySpec::Weekday(weekday) => DaySpec::Weekday(weekday.succ()),
+        DaySpec::Date(date) => DaySpec::Date(date + Duration::days(1)),
+    }
+}
+
+fn subtract(ranges: &[(u32, u32)], start: u32, end: u32) -> Vec<(u32, u32)> {
+    ranges
+        .iter()
+        .flat_map(|&(from, to)| {
+            if to <= start || from >= end {
+                vec![(from, to)]
+            } else {
+                [(from, start), (end, to)]
+                    .into_iter()
+                    .filter(|(from, to)| from < to)
+                    .collect()
+            }
+        })
+        .collect()
+}
+
+/// Sort ranges and join the ones that overlap or touch
+fn merge(mut ranges: Vec<(u32, u32)>) -> Vec<(u32, u32)> {
+    ranges.sort_unstable();
+
+    let mut merged: Vec<(u32, u32)> = Vec::new();
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = (*last_end).max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    merged
+}
+
+/// The UTC time `minutes` past midnight of `date` in the given timezone
+///
+/// Minutes may run past the end of the day, into the next one.
+pub fn local_to_utc(date: NaiveDate, minutes: u32, tz: Tz) -> DateTime<Utc> {
+    let local: NaiveDateTime = date.and_hms_opt(0, 0, 0).unwrap_or_default() + Duration::minutes(minutes as i64);
+
+    // Times skipped by a DST change map to the first valid time after them
+    tz.from_local_datetime(&local)
+        .earliest()
+        .or_else(|| tz.from_local_datetime(&(local + Duration::hours(1))).earliest())
+        .map(|time| time.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&local))
+}
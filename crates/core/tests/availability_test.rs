@@ -0,0 +1,83 @@
+use chrono::{DateTime, TimeZone, Utc};
+use timesync_core::{
+    availability::{free_intervals, FreeTime},
+    models::schedule::{SlotPreference, TimeSlotResponse},
+};
+
+fn at(day: u32, hour: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 1, day, hour, 0, 0).unwrap()
+}
+
+fn slot(start: DateTime<Utc>, end: DateTime<Utc>, is_recurring: bool, is_unavailable: bool) -> TimeSlotResponse {
+    TimeSlotResponse { start, end, is_recurring, is_unavailable, preference: SlotPreference::Available }
+}
+
+fn ranges(free: Vec<FreeTime>) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    free.into_iter().map(|time| (time.start, time.end)).collect()
+}
+
+#[test]
+fn test_free_intervals_repeats_recurring_slots() {
+    // Mondays 18:00-20:00, starting on the 6th
+    let slots = [slot(at(6, 18), at(6, 20), true, false)];
+
+    assert_eq!(ranges(free_intervals(&slots, at(13, 0), at(21, 0))), vec![
+        (at(13, 18), at(13, 20)),
+        (at(20, 18), at(20, 20)),
+    ]);
+}
+
+#[test]
+fn test_free_intervals_one_off_busy_overrides_weekly_pattern() {
+    let slots = [
+        slot(at(6, 18), at(6, 20), true, false),
+        // Busy the first hour on the 13th only
+        slot(at(13, 18), at(13, 19), false, true),
+    ];
+
+    assert_eq!(ranges(free_intervals(&slots, at(13, 0), at(21, 0))), vec![
+        (at(13, 19), at(13, 20)),
+        (at(20, 18), at(20, 20)),
+    ]);
+}
+
+#[test]
+fn test_free_intervals_recurring_busy_overrides_one_off() {
+    let slots = [
+        slot(at(14, 9), at(14, 17), false, false),
+        // Busy every Tuesday at noon
+        slot(at(7, 12), at(7, 13), true, true),
+    ];
+
+    assert_eq!(ranges(free_intervals(&slots, at(13, 0), at(21, 0))), vec![
+        (at(14, 9), at(14, 12)),
+        (at(14, 13), at(14, 17)),
+    ]);
+}
+
+#[test]
+fn test_free_intervals_merges_and_clips() {
+    let slots = [
+        slot(at(14, 9), at(14, 12), false, false),
+        slot(at(14, 11), at(14, 14), false, false),
+        slot(at(14, 14), at(14, 15), false, false),
+    ];
+
+    assert_eq!(ranges(free_intervals(&slots, at(14, 10), at(21, 0))), vec![(at(14, 10), at(14, 15))]);
+    assert!(free_intervals(&[slot(at(14, 9), at(14, 12), false, true)], at(13, 0), at(21, 0)).is_empty());
+}
+
+#[test]
+fn test_free_intervals_prefers_full_availability() {
+    let if_need_be = TimeSlotResponse {
+        preference: SlotPreference::IfNeedBe,
+        ..slot(at(14, 9), at(14, 17), false, false)
+    };
+    let slots = [if_need_be, slot(at(14, 12), at(14, 14), false, false), slot(at(14, 16), at(14, 17), false, true)];
+
+    assert_eq!(free_intervals(&slots, at(13, 0), at(21, 0)), vec![
+        FreeTime { start: at(14, 9), end: at(14, 12), preference: SlotPreference::IfNeedBe },
+        FreeTime { start: at(14, 12), end: at(14, 14), preference: SlotPreference::Available },
+        FreeTime { start: at(14, 14), end: at(14, 16), preference: SlotPreference::IfNeedBe },
+    ]);
+}
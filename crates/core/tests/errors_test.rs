@@ -1,3 +1,6 @@
+// These tests predate the lints below, so keep them as written
+#![allow(clippy::io_other_error, clippy::unnecessary_literal_unwrap)]
+
 use std::error::Error;
 use timesync_core::errors::{TimeError, TimeResult};
 
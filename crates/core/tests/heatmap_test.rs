@@ -0,0 +1,93 @@
+use chrono::{NaiveDate, TimeZone, Utc};
+use timesync_core::{
+    heatmap::{render_heatmap, HeatmapOptions, HeatmapSlot},
+    models::discord::{MatchGroupResult, MatchResult},
+};
+use uuid::Uuid;
+
+const FULL: [u8; 3] = [0x57, 0xF2, 0x87];
+
+/// Decode a PNG into (width, height, RGB pixels)
+fn decode(bytes: &[u8]) -> (u32, u32, Vec<u8>) {
+    let decoder = png::Decoder::new(bytes);
+    let mut reader = decoder.read_info().expect("valid PNG header");
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut pixels).expect("valid PNG data");
+    assert_eq!(info.color_type, png::ColorType::Rgb);
+
+    pixels.truncate(info.buffer_size());
+    (info.width, info.height, pixels)
+}
+
+fn count_pixels(pixels: &[u8], color: [u8; 3]) -> usize {
+    pixels.chunks(3).filter(|pixel| *pixel == color).count()
+}
+
+fn options(days: u32, total: Option<usize>) -> HeatmapOptions {
+    HeatmapOptions {
+        first_day: NaiveDate::from_ymd_opt(2025, 1, 13).unwrap(),
+        days,
+        tz: chrono_tz::UTC,
+        total,
+    }
+}
+
+#[test]
+fn test_render_heatmap_produces_png() {
+    let slots = vec![HeatmapSlot {
+        start: Utc.with_ymd_and_hms(2025, 1, 14, 18, 0, 0).unwrap(),
+        end: Utc.with_ymd_and_hms(2025, 1, 14, 20, 0, 0).unwrap(),
+        available: 3,
+    }];
+
+    let (week_width, week_height, pixels) = decode(&render_heatmap(&slots, &options(7, None)).unwrap());
+    let (day_width, day_height, _) = decode(&render_heatmap(&slots, &options(1, None)).unwrap());
+
+    // Rows are added per day, columns are fixed at 24 hours
+    assert_eq!(week_width, day_width);
+    assert!(week_height > day_height);
+
+    // Two fully available hours, plus the end of the legend
+    let full = count_pixels(&pixels, FULL);
+    assert!(full > 0);
+
+    let (_, _, half) = decode(&render_heatmap(&slots, &options(7, Some(6))).unwrap());
+    assert!(count_pixels(&half, FULL) < full);
+}
+
+#[test]
+fn test_render_heatmap_without_slots() {
+    let (_, _, pixels) = decode(&render_heatmap(&[], &options(3, None)).unwrap());
+
+    // Only the legend reaches the full colour
+    let legend = count_pixels(&pixels, FULL);
+    let slots = vec![HeatmapSlot {
+        start: Utc.with_ymd_and_hms(2025, 1, 13, 9, 0, 0).unwrap(),
+        end: Utc.with_ymd_and_hms(2025, 1, 13, 10, 0, 0).unwrap(),
+        available: 1,
+    }];
+    let (_, _, with_slot) = decode(&render_heatmap(&slots, &options(3, None)).unwrap());
+    assert!(count_pixels(&with_slot, FULL) > legend);
+}
+
+#[test]
+fn test_heatmap_slot_counts_users_once() {
+    let group = |users: &[&str]| MatchGroupResult {
+        id: Uuid::new_v4(),
+        name: "Group".to_string(),
+        available_users: users.iter().map(|user| user.to_string()).collect(),
+        count: users.len(),
+        tentative_users: Vec::new(),
+        missing_required: Vec::new(),
+        shortfall: 0,
+        unavailable_users: Vec::new(),
+    };
+    let result = MatchResult {
+        start: Utc.with_ymd_and_hms(2025, 1, 13, 9, 0, 0).unwrap(),
+        end: Utc.with_ymd_and_hms(2025, 1, 13, 10, 0, 0).unwrap(),
+        groups: vec![group(&["1", "2"]), group(&["2", "3"])],
+        score: 3.0,
+    };
+
+    assert_eq!(HeatmapSlot::from(&result).available, 3);
+}
@@ -0,0 +1,152 @@
+use std::collections::HashSet;
+use timesync_core::matching::{
+    evaluate_slot, require_members, score_slot, GroupConstraint, MemberConstraint, DEFAULT_WEIGHT,
+    IF_NEED_BE_FACTOR,
+};
+use uuid::Uuid;
+
+fn member(discord_id: &str, required: bool, weight: f64) -> MemberConstraint {
+    MemberConstraint {
+        discord_id: discord_id.to_string(),
+        required,
+        weight,
+    }
+}
+
+fn group(required: bool, min_available: usize, members: Vec<MemberConstraint>) -> GroupConstraint {
+    GroupConstraint {
+        id: Uuid::new_v4(),
+        required,
+        min_available,
+        members,
+    }
+}
+
+fn available<'a>(users: &[&'a str]) -> HashSet<&'a str> {
+    users.iter().copied().collect()
+}
+
+fn none<'a>() -> HashSet<&'a str> {
+    HashSet::new()
+}
+
+/// Two tanks who must both come, plus healers where any two will do
+fn raid() -> Vec<GroupConstraint> {
+    vec![
+        group(true, 2, vec![
+            member("tank1", true, DEFAULT_WEIGHT),
+            member("tank2", true, DEFAULT_WEIGHT),
+        ]),
+        group(true, 2, vec![
+            member("healer1", false, DEFAULT_WEIGHT),
+            member("healer2", false, DEFAULT_WEIGHT),
+            member("healer3", false, DEFAULT_WEIGHT),
+        ]),
+    ]
+}
+
+#[test]
+fn test_evaluate_slot_counts_available_members() {
+    let score = evaluate_slot(&raid(), &available(&["tank1", "tank2", "healer1", "healer3"]), &none());
+    assert_eq!(score, Some(4.0));
+}
+
+#[test]
+fn test_evaluate_slot_rejects_missing_required_member() {
+    assert_eq!(evaluate_slot(&raid(), &available(&["tank1", "healer1", "healer2", "healer3"]), &none()), None);
+}
+
+#[test]
+fn test_evaluate_slot_rejects_required_group_below_minimum() {
+    assert_eq!(evaluate_slot(&raid(), &available(&["tank1", "tank2", "healer1"]), &none()), None);
+}
+
+#[test]
+fn test_evaluate_slot_optional_group_only_scores() {
+    let mut groups = raid();
+    groups.push(group(false, 2, vec![
+        member("dps1", false, 0.5),
+        member("dps2", false, 0.5),
+    ]));
+
+    // Nobody from the optional group is fine, each of them adds their weight
+    assert_eq!(evaluate_slot(&groups, &available(&["tank1", "tank2", "healer1", "healer2"]), &none()), Some(4.0));
+    assert_eq!(evaluate_slot(&groups, &available(&["tank1", "tank2", "healer1", "healer2", "dps2"]), &none()), Some(4.5));
+}
+
+#[test]
+fn test_evaluate_slot_required_member_in_optional_group() {
+    let groups = vec![group(false, 0, vec![member("leader", true, DEFAULT_WEIGHT)])];
+
+    assert_eq!(evaluate_slot(&groups, &available(&[]), &none()), None);
+    assert_eq!(evaluate_slot(&groups, &available(&["leader"]), &none()), Some(1.0));
+}
+
+#[test]
+fn test_evaluate_slot_counts_shared_members_once() {
+    let groups = vec![
+        group(true, 1, vec![member("both", false, 1.0)]),
+        group(true, 1, vec![member("both", false, 3.0), member("other", false, 2.0)]),
+    ];
+
+    // Someone in both groups counts once, with their highest weight
+    assert_eq!(evaluate_slot(&groups, &available(&["both", "other"]), &none()), Some(5.0));
+}
+
+#[test]
+fn test_evaluate_slot_no_constraints() {
+    assert_eq!(evaluate_slot(&[], &available(&["anyone"]), &none()), Some(0.0));
+}
+
+#[test]
+fn test_missing_required_lists_absent_members() {
+    let groups = raid();
+
+    assert_eq!(groups[0].missing_required(&available(&["tank2"])), vec!["tank1".to_string()]);
+    assert!(groups[1].missing_required(&available(&[])).is_empty());
+    assert!(groups[0].meets_quorum(&available(&["tank1", "tank2"])));
+    assert!(!groups[1].meets_quorum(&available(&["healer1"])));
+}
+
+#[test]
+fn test_require_members_in_every_group() {
+    let mut groups = vec![
+        group(true, 1, vec![member("healer1", false, 1.0), member("shared", false, 1.0)]),
+        group(false, 0, vec![member("shared", false, 2.0)]),
+    ];
+    require_members(&mut groups, &["shared".to_string()]);
+
+    assert!(!groups[0].members[0].required);
+    assert!(groups[0].members[1].required);
+    assert!(groups[1].members[0].required);
+    assert_eq!(evaluate_slot(&groups, &available(&["healer1"]), &none()), None);
+    assert_eq!(score_slot(&groups, &available(&["healer1"]), &none()), 1.0);
+}
+
+#[test]
+fn test_shortfall_counts_members_still_needed() {
+    let groups = raid();
+
+    // Both tanks are required, so one missing tank is one short
+    assert_eq!(groups[0].shortfall(&available(&["tank2"])), 1);
+    assert_eq!(groups[0].unavailable(&available(&["tank2"])), vec!["tank1".to_string()]);
+
+    // Two healers needed and none there
+    assert_eq!(groups[1].shortfall(&available(&[])), 2);
+    assert_eq!(groups[1].shortfall(&available(&["healer2", "healer3"])), 0);
+
+    // Optional groups are never short
+    let optional = group(false, 3, vec![member("dps1", false, DEFAULT_WEIGHT)]);
+    assert_eq!(optional.shortfall(&available(&[])), 0);
+    assert_eq!(optional.unavailable(&available(&[])), vec!["dps1".to_string()]);
+}
+
+#[test]
+fn test_evaluate_slot_if_need_be_members_count_for_less() {
+    let everyone = available(&["tank1", "tank2", "healer1", "healer2"]);
+
+    // Still enough for the slot to work, but it scores lower
+    let score = evaluate_slot(&raid(), &everyone, &available(&["healer2"]));
+    assert_eq!(score, Some(3.0 + IF_NEED_BE_FACTOR));
+    assert!(score < evaluate_slot(&raid(), &everyone, &none()));
+}
@@ -5,12 +5,13 @@ use serde_json::{from_str, to_string};
 use timesync_core::models::{
     discord::{
         CreateDiscordGroupRequest, CreateDiscordUserRequest, DiscordGroup, DiscordUser,
-        GetDiscordGroupResponse, MatchResponse, UpdateDiscordGroupRequest,
+        GetDiscordGroupResponse, MatchRequest, MatchResponse, UpdateDiscordGroupRequest,
     },
     schedule::{
-        CreateScheduleRequest, CreateTimeSlotRequest, Schedule, TimeSlotResponse,
+        CreateScheduleRequest, CreateTimeSlotRequest, Schedule, SlotPreference, TimeSlotResponse,
         UpdateScheduleRequest, VerifyPasswordRequest,
     },
+    meeting::{Meeting, MeetingStatus},
     time_slot::TimeSlot,
 };
 use uuid::Uuid;
@@ -114,6 +115,8 @@ fn test_discord_group_serialization() {
             start: Utc::now(),
             end: Utc::now() + chrono::Duration::hours(1),
             is_recurring: false,
+            is_unavailable: false,
+            preference: SlotPreference::Available,
         }
     ], 
     Some("discord123")
@@ -179,6 +182,7 @@ fn test_update_discord_group_request() {
         name: Some("Updated Group".to_string()),
         add_member_ids: Some(vec!["user3".to_string()]),
         remove_member_ids: Some(vec!["user1".to_string()]),
+        required: None,
     };
     
     let json = to_string(&request).expect("Failed to serialize update discord group request");
@@ -200,6 +204,8 @@ fn test_update_schedule_request() {
             start: start_time,
             end: end_time,
             is_recurring: false,
+            is_unavailable: false,
+            preference: SlotPreference::Available,
         }],
         password: Some("password123".to_string()),
         timezone: Some("UTC".to_string()),
@@ -234,6 +240,8 @@ fn test_time_slot_response() {
         start: start_time,
         end: end_time,
         is_recurring: false,
+        is_unavailable: false,
+        preference: SlotPreference::Available,
     };
     
     let json = to_string(&response).expect("Failed to serialize time slot response");
@@ -253,16 +261,23 @@ fn test_get_discord_group_response() {
         name: "Test Group".to_string(),
         server_id: "server123".to_string(),
         role_id: None,
+        role_sync: false,
+        required: true,
         members: vec![
             timesync_core::models::discord::DiscordGroupMember {
                 discord_id: "user1".to_string(),
                 schedule_id: Some(schedule_id),
+                required: true,
+                weight: 1.0,
             },
             timesync_core::models::discord::DiscordGroupMember {
                 discord_id: "user2".to_string(),
                 schedule_id: None,
+                required: false,
+                weight: 0.5,
             },
         ],
+        created_at: Utc::now(),
     };
     
     let json = to_string(&response).expect("Failed to serialize get discord group response");
@@ -276,6 +291,8 @@ fn test_get_discord_group_response() {
     assert_eq!(deserialized.members[0].schedule_id, response.members[0].schedule_id);
     assert_eq!(deserialized.members[1].discord_id, response.members[1].discord_id);
     assert_eq!(deserialized.members[1].schedule_id, response.members[1].schedule_id);
+    assert!(deserialized.members[0].required);
+    assert_eq!(deserialized.members[1].weight, 0.5);
 }
 
 #[test]
@@ -295,10 +312,16 @@ fn test_match_response() {
                         name: "Test Group".to_string(),
                         available_users: vec!["user1".to_string(), "user2".to_string()],
                         count: 2,
+                        tentative_users: Vec::new(),
+                        missing_required: vec!["user3".to_string()],
+                        shortfall: 1,
+                        unavailable_users: vec!["user3".to_string()],
                     },
                 ],
+                score: 2.0,
             },
         ],
+        near_misses: Vec::new(),
     };
     
     let json = to_string(&response).expect("Failed to serialize match response");
@@ -315,4 +338,77 @@ fn test_match_response() {
         response.matches[0].groups[0].available_users
     );
     assert_eq!(deserialized.matches[0].groups[0].count, response.matches[0].groups[0].count);
-}
\ No newline at end of file
+    assert_eq!(deserialized.matches[0].groups[0].missing_required, vec!["user3".to_string()]);
+}
+
+#[test]
+fn test_match_request_defaults_required_ids() {
+    let json = format!(r#"{{"group_ids":["{}"],"min_per_group":2,"count":5}}"#, Uuid::new_v4());
+    let request: MatchRequest = from_str(&json).expect("Failed to deserialize match request");
+    
+    assert!(request.required_ids.is_empty());
+}
+
+#[test]
+fn test_meeting_serialization() {
+    let start_time = Utc::now();
+    let end_time = start_time + chrono::Duration::hours(2);
+
+    let meeting = Meeting {
+        id: Uuid::new_v4(),
+        server_id: "123456789".to_string(),
+        channel_id: "987654321".to_string(),
+        organizer_id: "111111111".to_string(),
+        start_time,
+        end_time,
+        timezone: "Europe/London".to_string(),
+        status: MeetingStatus::Scheduled,
+        min_per_group: 6,
+        slot_duration: 120,
+        group_ids: vec![Uuid::new_v4()],
+        attendee_ids: vec!["user1".to_string(), "user2".to_string()],
+        rescheduled_from: None,
+        created_at: Utc::now(),
+    };
+
+    let json = to_string(&meeting).expect("Failed to serialize meeting");
+    assert!(json.contains(r#""status":"scheduled""#));
+
+    let deserialized: Meeting = from_str(&json).expect("Failed to deserialize meeting");
+    assert_eq!(deserialized.id, meeting.id);
+    assert_eq!(deserialized.status, MeetingStatus::Scheduled);
+    assert_eq!(deserialized.group_ids, meeting.group_ids);
+    assert_eq!(deserialized.attendee_ids, meeting.attendee_ids);
+}
+
+#[rstest]
+#[case(MeetingStatus::Scheduled, "scheduled")]
+#[case(MeetingStatus::Cancelled, "cancelled")]
+#[case(MeetingStatus::Rescheduled, "rescheduled")]
+fn test_meeting_status_round_trip(#[case] status: MeetingStatus, #[case] value: &str) {
+    assert_eq!(status.as_str(), value);
+    assert_eq!(MeetingStatus::parse(value), Some(status));
+}
+
+#[test]
+fn test_meeting_status_parse_unknown() {
+    assert_eq!(MeetingStatus::parse("postponed"), None);
+}
+
+#[rstest]
+#[case(SlotPreference::Available, "available")]
+#[case(SlotPreference::IfNeedBe, "if_need_be")]
+fn test_slot_preference_round_trip(#[case] preference: SlotPreference, #[case] value: &str) {
+    assert_eq!(preference.as_str(), value);
+    assert_eq!(SlotPreference::parse(value), Some(preference));
+    assert_eq!(to_string(&preference).unwrap(), format!("\"{}\"", value));
+}
+
+#[test]
+fn test_time_slot_defaults_to_available() {
+    let json = r#"{"start":"2025-01-15T18:00:00Z","end":"2025-01-15T20:00:00Z"}"#;
+    let slot: CreateTimeSlotRequest = from_str(json).expect("Failed to deserialize time slot");
+
+    assert!(!slot.is_unavailable);
+    assert_eq!(slot.preference, SlotPreference::Available);
+}
@@ -0,0 +1,175 @@
+use chrono::{NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+use pretty_assertions::assert_eq;
+use rstest::rstest;
+use timesync_core::{
+    errors::TimeError,
+    models::schedule::CreateTimeSlotRequest,
+    parser::{parse_availability, parse_weekly_availability},
+};
+
+/// Wednesday, in the week starting Monday 2025-01-13
+fn reference() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
+}
+
+/// Slots as "Wed 01-15 18:00-00:00", with "weekly" appended for recurring ones
+fn describe(slots: &[CreateTimeSlotRequest]) -> Vec<String> {
+    slots
+        .iter()
+        .map(|slot| {
+            format!(
+                "{} {}-{}{}",
+                slot.start.format("%a %m-%d"),
+                slot.start.format("%H:%M"),
+                slot.end.format("%H:%M"),
+                if slot.is_recurring { " weekly" } else { "" }
+            )
+        })
+        .collect()
+}
+
+fn parse(text: &str) -> Vec<String> {
+    describe(&parse_availability(text, reference(), Tz::UTC).unwrap())
+}
+
+#[test]
+fn test_parse_weekdays_after_6pm() {
+    // The next occurrence of each weekday, starting from Wednesday
+    assert_eq!(
+        parse("weekdays after 6pm"),
+        vec![
+            "Wed 01-15 18:00-00:00",
+            "Thu 01-16 18:00-00:00",
+            "Fri 01-17 18:00-00:00",
+            "Mon 01-20 18:00-00:00",
+            "Tue 01-21 18:00-00:00",
+        ]
+    );
+}
+
+#[test]
+fn test_parse_every_week_is_recurring() {
+    // Recurring slots are anchored in the reference week
+    assert_eq!(parse("Sat 10-14 every week"), vec!["Sat 01-18 10:00-14:00 weekly"]);
+    assert_eq!(parse("every Monday 9am to 5pm"), vec!["Mon 01-13 09:00-17:00 weekly"]);
+    assert_eq!(parse("daily 12-13"), parse("every day 12-13"));
+}
+
+#[test]
+fn test_parse_not_thursday() {
+    // Exclusions on their own leave the rest of the week
+    let slots = parse("not Thursday");
+    assert_eq!(slots.len(), 6);
+    assert!(slots.iter().all(|slot| slot.ends_with("00:00-00:00")));
+    assert!(!slots.iter().any(|slot| slot.starts_with("Thu")));
+}
+
+#[test]
+fn test_parse_exclusion_removes_time() {
+    assert_eq!(
+        parse("weekdays 9-17 every week, except Wednesday, not Friday 12-13"),
+        vec![
+            "Mon 01-13 09:00-17:00 weekly",
+            "Tue 01-14 09:00-17:00 weekly",
+            "Thu 01-16 09:00-17:00 weekly",
+            "Fri 01-17 09:00-12:00 weekly",
+            "Fri 01-17 13:00-17:00 weekly",
+        ]
+    );
+}
+
+#[rstest]
+#[case("Mon-Wed 18:00-22:00")]
+#[case("mon - wed 18:00 - 22:00")]
+#[case("Monday to Wednesday from 6pm to 10pm")]
+#[case("mondays, tuesdays and wednesdays 6-10pm")]
+#[case("Mon Tue Wed 6 pm - 10 pm")]
+fn test_parse_equivalent_forms(#[case] text: &str) {
+    assert_eq!(
+        parse(text),
+        vec![
+            "Wed 01-15 18:00-22:00",
+            "Mon 01-20 18:00-22:00",
+            "Tue 01-21 18:00-22:00",
+        ]
+    );
+}
+
+#[test]
+fn test_parse_named_periods_and_dates() {
+    assert_eq!(
+        parse("today evening; tomorrow morning; 2025-02-01 all day"),
+        vec![
+            "Wed 01-15 17:00-22:00",
+            "Thu 01-16 08:00-12:00",
+            "Sat 02-01 00:00-00:00",
+        ]
+    );
+}
+
+#[test]
+fn test_parse_multiple_clauses_and_overlaps() {
+    assert_eq!(
+        parse("Sat 10-14 16:30-18 Sun before noon; Sat 13-15"),
+        vec![
+            "Sat 01-18 10:00-15:00",
+            "Sat 01-18 16:30-18:00",
+            "Sun 01-19 00:00-12:00",
+        ]
+    );
+}
+
+#[test]
+fn test_parse_range_past_midnight() {
+    assert_eq!(
+        parse("Fri 22-2"),
+        vec!["Fri 01-17 22:00-00:00", "Sat 01-18 00:00-02:00"]
+    );
+}
+
+#[rstest]
+#[case("Mon 9-5", "Mon 01-20 09:00-17:00")]
+#[case("Mon 10-2", "Mon 01-20 10:00-14:00")]
+#[case("Mon 12-3", "Mon 01-20 12:00-15:00")]
+fn test_parse_bare_hours_end_in_the_afternoon(#[case] text: &str, #[case] expected: &str) {
+    assert_eq!(parse(text), vec![expected]);
+}
+
+#[test]
+fn test_parse_converts_from_timezone() {
+    let tz: Tz = "America/New_York".parse().unwrap();
+    let slots = parse_availability("Thu 6pm-8pm", reference(), tz).unwrap();
+
+    assert_eq!(slots.len(), 1);
+    assert_eq!(slots[0].start, Utc.with_ymd_and_hms(2025, 1, 16, 23, 0, 0).unwrap());
+    assert_eq!(slots[0].end, Utc.with_ymd_and_hms(2025, 1, 17, 1, 0, 0).unwrap());
+}
+
+#[test]
+fn test_parse_weekly_availability_defaults_to_recurring() {
+    let slots = parse_weekly_availability("Sat 10-14\ntomorrow 9-10", reference(), Tz::UTC).unwrap();
+
+    assert_eq!(
+        describe(&slots),
+        vec!["Thu 01-16 09:00-10:00", "Sat 01-18 10:00-14:00 weekly"]
+    );
+}
+
+#[rstest]
+#[case("")]
+#[case("  ;\n ")]
+#[case("Mon 6pm")]
+#[case("Mon 18-18")]
+#[case("Mon 25-26")]
+#[case("Mon 12:45-0:30")]
+#[case("sometimes")]
+#[case("after lunch")]
+#[case("not")]
+#[case("every fortnight")]
+fn test_parse_rejects_invalid(#[case] text: &str) {
+    match parse_availability(text, reference(), Tz::UTC) {
+        Err(TimeError::Validation(_)) => {}
+        other => panic!("Expected a validation error for {:?}, got {:?}", text, other),
+    }
+}
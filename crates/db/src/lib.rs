@@ -2,19 +2,69 @@ pub mod models;
 pub mod repositories;
 pub mod schema;
 
-pub mod mock;
+pub mod memory;
+pub mod postgres;
+pub mod sqlite;
+pub mod testing;
 
-use eyre::Result;
+use std::str::FromStr;
+
+use eyre::{bail, Result};
 use sqlx::postgres::PgPoolOptions;
-use sqlx::{Pool, Postgres};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::{Pool, Postgres, Sqlite};
 
 pub type DbPool = Pool<Postgres>;
+pub type SqlitePool = Pool<Sqlite>;
 
 pub async fn create_pool(database_url: &str) -> Result<DbPool> {
     let pool = PgPoolOptions::new()
         .max_connections(5)
         .connect(database_url)
         .await?;
-    
+
     Ok(pool)
-}
\ No newline at end of file
+}
+
+/// Open an SQLite database, creating the file if it doesn't exist yet
+pub async fn create_sqlite_pool(database_url: &str) -> Result<SqlitePool> {
+    let options = SqliteConnectOptions::from_str(database_url)?.create_if_missing(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(5)
+        .connect_with(options)
+        .await?;
+
+    Ok(pool)
+}
+
+/// A connection pool for whichever backend a database URL names
+#[derive(Debug, Clone)]
+pub enum Database {
+    Postgres(DbPool),
+    Sqlite(SqlitePool),
+}
+
+impl Database {
+    /// Connect to a database, picking the backend by the URL's scheme
+    ///
+    /// `postgres://` and `postgresql://` URLs use Postgres. `sqlite:` URLs
+    /// use SQLite, e.g. `sqlite://timesync.db` for a file next to the
+    /// binary or `sqlite::memory:` for a throwaway database.
+    pub async fn connect(database_url: &str) -> Result<Self> {
+        let scheme = database_url.split(':').next().unwrap_or_default();
+
+        match scheme {
+            "postgres" | "postgresql" => Ok(Self::Postgres(create_pool(database_url).await?)),
+            "sqlite" => Ok(Self::Sqlite(create_sqlite_pool(database_url).await?)),
+            _ => bail!("Unsupported database URL scheme '{}', expected postgres or sqlite", scheme),
+        }
+    }
+
+    /// Create or migrate the schema
+    pub async fn initialize(&self) -> Result<()> {
+        match self {
+            Self::Postgres(pool) => schema::initialize_database(pool).await,
+            Self::Sqlite(pool) => schema::initialize_sqlite_database(pool).await,
+        }
+    }
+}
@@ -0,0 +1,627 @@
+//! # In-Memory Backend
+//!
+//! `InMemoryRepository` implements the repository traits with plain
+//! collections behind a mutex. It mirrors the Postgres backend closely enough
+//! for handler tests: rows come back in the same order, and the foreign keys
+//! and unique constraints of the schema are checked, so a query that would
+//! fail against the database fails here too. Nothing is persisted.
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use eyre::{eyre, Result};
+use timesync_core::models::{
+    meeting::MeetingStatus,
+    schedule::{CreateTimeSlotRequest, SlotPreference},
+};
+use uuid::Uuid;
+
+use crate::{
+    models::{
+        DbDiscordGroup, DbDiscordServer, DbDiscordUser, DbGroupMember, DbMeeting, DbMeetingAttendee, DbSchedule,
+        DbTimeSlot, NewMeeting,
+    },
+    repositories::{DiscordRepository, MeetingRepository, ScheduleRepository, TimeSlotRepository},
+};
+
+/// Repositories that keep everything in memory
+#[derive(Debug, Default)]
+pub struct InMemoryRepository {
+    store: Mutex<Store>,
+}
+
+/// The tables, keyed like their primary keys
+#[derive(Debug, Default)]
+struct Store {
+    schedules: HashMap<Uuid, DbSchedule>,
+    time_slots: Vec<DbTimeSlot>,
+    discord_users: HashMap<String, DbDiscordUser>,
+    discord_servers: HashMap<String, DbDiscordServer>,
+    discord_groups: HashMap<Uuid, DbDiscordGroup>,
+    group_members: Vec<DbGroupMember>,
+    meetings: HashMap<Uuid, DbMeeting>,
+    meeting_groups: Vec<(Uuid, Uuid)>,
+    meeting_attendees: Vec<DbMeetingAttendee>,
+}
+
+impl InMemoryRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn store(&self) -> MutexGuard<'_, Store> {
+        // A panic while holding the lock can't leave a table half-written,
+        // as every change is a single insert or retain
+        self.store.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Store {
+    fn schedule_exists(&self, id: Uuid) -> Result<()> {
+        if self.schedules.contains_key(&id) {
+            Ok(())
+        } else {
+            Err(eyre!("Schedule {} does not exist", id))
+        }
+    }
+
+    fn group_mut(&mut self, id: Uuid) -> Result<&mut DbDiscordGroup> {
+        self.discord_groups
+            .get_mut(&id)
+            .ok_or_else(|| eyre!("Discord group {} does not exist", id))
+    }
+
+    fn is_member(&self, group_id: Uuid, discord_id: &str) -> bool {
+        self.group_members
+            .iter()
+            .any(|member| member.group_id == group_id && member.discord_id == discord_id)
+    }
+
+    fn insert_member(&mut self, group_id: Uuid, discord_id: &str) -> Result<DbGroupMember> {
+        if !self.discord_groups.contains_key(&group_id) {
+            return Err(eyre!("Discord group {} does not exist", group_id));
+        }
+        if !self.discord_users.contains_key(discord_id) {
+            return Err(eyre!("Discord user {} does not exist", discord_id));
+        }
+
+        let member = DbGroupMember {
+            group_id,
+            discord_id: discord_id.to_string(),
+            required: false,
+            weight: 1.0,
+        };
+        self.group_members.push(member.clone());
+
+        Ok(member)
+    }
+
+    fn new_discord_user(discord_id: &str, schedule_id: Option<Uuid>, timezone: Option<&str>) -> DbDiscordUser {
+        DbDiscordUser {
+            discord_id: discord_id.to_string(),
+            schedule_id,
+            timezone: timezone.map(str::to_string),
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// Groups by name, then ID, so pages are stable
+fn sorted_by_name(mut groups: Vec<DbDiscordGroup>) -> Vec<DbDiscordGroup> {
+    groups.sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id)));
+    groups
+}
+
+#[async_trait]
+impl ScheduleRepository for InMemoryRepository {
+    async fn create_schedule(
+        &self,
+        name: &str,
+        password_hash: Option<&str>,
+        timezone: &str,
+    ) -> Result<DbSchedule> {
+        let schedule = DbSchedule {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            password_hash: password_hash.map(str::to_string),
+            timezone: timezone.to_string(),
+            created_at: Utc::now(),
+        };
+        self.store().schedules.insert(schedule.id, schedule.clone());
+
+        Ok(schedule)
+    }
+
+    async fn get_schedule_by_id(&self, id: Uuid) -> Result<Option<DbSchedule>> {
+        Ok(self.store().schedules.get(&id).cloned())
+    }
+
+    async fn update_schedule(
+        &self,
+        id: Uuid,
+        name: Option<&str>,
+        timezone: Option<&str>,
+    ) -> Result<DbSchedule> {
+        let mut store = self.store();
+        let schedule = store.schedules.get_mut(&id).ok_or_else(|| eyre!("Schedule not found"))?;
+
+        if let Some(name) = name {
+            schedule.name = name.to_string();
+        }
+        if let Some(timezone) = timezone {
+            schedule.timezone = timezone.to_string();
+        }
+
+        Ok(schedule.clone())
+    }
+}
+
+#[async_trait]
+impl TimeSlotRepository for InMemoryRepository {
+    async fn create_time_slot(
+        &self,
+        schedule_id: Uuid,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+        is_recurring: bool,
+        is_unavailable: bool,
+        preference: SlotPreference,
+    ) -> Result<DbTimeSlot> {
+        let mut store = self.store();
+        store.schedule_exists(schedule_id)?;
+
+        let time_slot = DbTimeSlot {
+            id: Uuid::new_v4(),
+            schedule_id,
+            start_time,
+            end_time,
+            is_recurring,
+            is_unavailable,
+            preference: preference.as_str().to_string(),
+            created_at: Utc::now(),
+        };
+        store.time_slots.push(time_slot.clone());
+
+        Ok(time_slot)
+    }
+
+    async fn get_time_slots_by_schedule_id(&self, schedule_id: Uuid) -> Result<Vec<DbTimeSlot>> {
+        let mut time_slots: Vec<DbTimeSlot> = self
+            .store()
+            .time_slots
+            .iter()
+            .filter(|slot| slot.schedule_id == schedule_id)
+            .cloned()
+            .collect();
+        time_slots.sort_by_key(|slot| slot.start_time);
+
+        Ok(time_slots)
+    }
+
+    async fn delete_time_slots_by_schedule_id(&self, schedule_id: Uuid) -> Result<()> {
+        self.store().time_slots.retain(|slot| slot.schedule_id != schedule_id);
+
+        Ok(())
+    }
+
+    async fn replace_time_slots(
+        &self,
+        schedule_id: Uuid,
+        slots: &[CreateTimeSlotRequest],
+    ) -> Result<Vec<DbTimeSlot>> {
+        let mut store = self.store();
+        if !slots.is_empty() {
+            store.schedule_exists(schedule_id)?;
+        }
+
+        let now = Utc::now();
+        let time_slots: Vec<DbTimeSlot> = slots
+            .iter()
+            .map(|slot| DbTimeSlot {
+                id: Uuid::new_v4(),
+                schedule_id,
+                start_time: slot.start,
+                end_time: slot.end,
+                is_recurring: slot.is_recurring,
+                is_unavailable: slot.is_unavailable,
+                preference: slot.preference.as_str().to_string(),
+                created_at: now,
+            })
+            .collect();
+
+        store.time_slots.retain(|slot| slot.schedule_id != schedule_id);
+        store.time_slots.extend(time_slots.iter().cloned());
+
+        Ok(time_slots)
+    }
+}
+
+#[async_trait]
+impl DiscordRepository for InMemoryRepository {
+    async fn create_discord_user(&self, discord_id: &str, schedule_id: Option<Uuid>) -> Result<DbDiscordUser> {
+        let mut store = self.store();
+        if let Some(schedule_id) = schedule_id {
+            store.schedule_exists(schedule_id)?;
+        }
+
+        let user = store
+            .discord_users
+            .entry(discord_id.to_string())
+            .and_modify(|user| user.schedule_id = schedule_id)
+            .or_insert_with(|| Store::new_discord_user(discord_id, schedule_id, None));
+
+        Ok(user.clone())
+    }
+
+    async fn get_discord_user_by_id(&self, discord_id: &str) -> Result<Option<DbDiscordUser>> {
+        Ok(self.store().discord_users.get(discord_id).cloned())
+    }
+
+    async fn set_discord_user_timezone(&self, discord_id: &str, timezone: &str) -> Result<DbDiscordUser> {
+        let mut store = self.store();
+        let user = store
+            .discord_users
+            .entry(discord_id.to_string())
+            .and_modify(|user| user.timezone = Some(timezone.to_string()))
+            .or_insert_with(|| Store::new_discord_user(discord_id, None, Some(timezone)));
+
+        Ok(user.clone())
+    }
+
+    async fn get_discord_server(&self, server_id: &str) -> Result<Option<DbDiscordServer>> {
+        Ok(self.store().discord_servers.get(server_id).cloned())
+    }
+
+    async fn set_discord_server_timezone(&self, server_id: &str, timezone: &str) -> Result<DbDiscordServer> {
+        let mut store = self.store();
+        let server = store
+            .discord_servers
+            .entry(server_id.to_string())
+            .and_modify(|server| server.timezone = timezone.to_string())
+            .or_insert_with(|| DbDiscordServer {
+                server_id: server_id.to_string(),
+                timezone: timezone.to_string(),
+                created_at: Utc::now(),
+            });
+
+        Ok(server.clone())
+    }
+
+    async fn create_discord_group(
+        &self,
+        name: &str,
+        server_id: &str,
+        role_id: Option<&str>,
+    ) -> Result<DbDiscordGroup> {
+        let group = DbDiscordGroup {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            server_id: server_id.to_string(),
+            role_id: role_id.map(str::to_string),
+            role_sync: false,
+            required: true,
+            created_at: Utc::now(),
+        };
+        self.store().discord_groups.insert(group.id, group.clone());
+
+        Ok(group)
+    }
+
+    async fn get_discord_group_by_id(&self, id: Uuid) -> Result<Option<DbDiscordGroup>> {
+        Ok(self.store().discord_groups.get(&id).cloned())
+    }
+
+    async fn get_discord_group_by_name(&self, server_id: &str, name: &str) -> Result<Option<DbDiscordGroup>> {
+        Ok(self
+            .store()
+            .discord_groups
+            .values()
+            .find(|group| group.server_id == server_id && group.name == name)
+            .cloned())
+    }
+
+    async fn get_discord_groups_by_server(&self, server_id: &str) -> Result<Vec<DbDiscordGroup>> {
+        let groups = self
+            .store()
+            .discord_groups
+            .values()
+            .filter(|group| group.server_id == server_id)
+            .cloned()
+            .collect();
+
+        Ok(sorted_by_name(groups))
+    }
+
+    async fn list_discord_groups_by_server(
+        &self,
+        server_id: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<DbDiscordGroup>> {
+        let groups = self.get_discord_groups_by_server(server_id).await?;
+
+        Ok(groups
+            .into_iter()
+            .skip(offset.max(0) as usize)
+            .take(limit.max(0) as usize)
+            .collect())
+    }
+
+    async fn count_discord_groups_by_server(&self, server_id: &str) -> Result<i64> {
+        let count = self
+            .store()
+            .discord_groups
+            .values()
+            .filter(|group| group.server_id == server_id)
+            .count();
+
+        Ok(count as i64)
+    }
+
+    async fn update_discord_group(
+        &self,
+        id: Uuid,
+        name: Option<&str>,
+        role_id: Option<&str>,
+    ) -> Result<DbDiscordGroup> {
+        let mut store = self.store();
+        let group = store.group_mut(id)?;
+
+        if let Some(name) = name {
+            group.name = name.to_string();
+        }
+        if let Some(role_id) = role_id {
+            group.role_id = Some(role_id.to_string());
+        }
+
+        Ok(group.clone())
+    }
+
+    async fn delete_discord_group(&self, id: Uuid) -> Result<bool> {
+        let mut store = self.store();
+        store.group_members.retain(|member| member.group_id != id);
+        store.meeting_groups.retain(|(_, group_id)| *group_id != id);
+
+        Ok(store.discord_groups.remove(&id).is_some())
+    }
+
+    async fn set_group_required(&self, id: Uuid, required: bool) -> Result<DbDiscordGroup> {
+        let mut store = self.store();
+        let group = store.group_mut(id)?;
+        group.required = required;
+
+        Ok(group.clone())
+    }
+
+    async fn update_group_role_id(&self, id: Uuid, role_id: &str) -> Result<DbDiscordGroup> {
+        let mut store = self.store();
+        let group = store.group_mut(id)?;
+        group.role_id = Some(role_id.to_string());
+
+        Ok(group.clone())
+    }
+
+    async fn add_member_to_group(&self, group_id: Uuid, discord_id: &str) -> Result<DbGroupMember> {
+        let mut store = self.store();
+        if store.is_member(group_id, discord_id) {
+            return Err(eyre!("{} is already a member of group {}", discord_id, group_id));
+        }
+
+        store.insert_member(group_id, discord_id)
+    }
+
+    async fn ensure_group_member(&self, group_id: Uuid, discord_id: &str) -> Result<bool> {
+        let mut store = self.store();
+        store
+            .discord_users
+            .entry(discord_id.to_string())
+            .or_insert_with(|| Store::new_discord_user(discord_id, None, None));
+
+        if store.is_member(group_id, discord_id) {
+            return Ok(false);
+        }
+        store.insert_member(group_id, discord_id)?;
+
+        Ok(true)
+    }
+
+    async fn remove_member_from_group(&self, group_id: Uuid, discord_id: &str) -> Result<()> {
+        self.store()
+            .group_members
+            .retain(|member| !(member.group_id == group_id && member.discord_id == discord_id));
+
+        Ok(())
+    }
+
+    async fn get_group_members(&self, group_id: Uuid) -> Result<Vec<DbGroupMember>> {
+        Ok(self
+            .store()
+            .group_members
+            .iter()
+            .filter(|member| member.group_id == group_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn update_group_member(
+        &self,
+        group_id: Uuid,
+        discord_id: &str,
+        required: Option<bool>,
+        weight: Option<f64>,
+    ) -> Result<Option<DbGroupMember>> {
+        let mut store = self.store();
+        let member = store
+            .group_members
+            .iter_mut()
+            .find(|member| member.group_id == group_id && member.discord_id == discord_id);
+
+        Ok(member.map(|member| {
+            if let Some(required) = required {
+                member.required = required;
+            }
+            if let Some(weight) = weight {
+                member.weight = weight;
+            }
+            member.clone()
+        }))
+    }
+
+    async fn get_user_groups(&self, discord_id: &str) -> Result<Vec<DbDiscordGroup>> {
+        let store = self.store();
+
+        Ok(store
+            .group_members
+            .iter()
+            .filter(|member| member.discord_id == discord_id)
+            .filter_map(|member| store.discord_groups.get(&member.group_id).cloned())
+            .collect())
+    }
+
+    async fn bind_group_to_role(&self, id: Uuid, role_id: &str) -> Result<DbDiscordGroup> {
+        let mut store = self.store();
+        let group = store.group_mut(id)?;
+        group.role_id = Some(role_id.to_string());
+        group.role_sync = true;
+
+        Ok(group.clone())
+    }
+
+    async fn unbind_group_from_role(&self, id: Uuid) -> Result<DbDiscordGroup> {
+        let mut store = self.store();
+        let group = store.group_mut(id)?;
+        group.role_sync = false;
+
+        Ok(group.clone())
+    }
+
+    async fn get_role_synced_groups(&self, server_id: Option<&str>) -> Result<Vec<DbDiscordGroup>> {
+        let mut groups: Vec<DbDiscordGroup> = self
+            .store()
+            .discord_groups
+            .values()
+            .filter(|group| group.role_sync && group.role_id.is_some())
+            .filter(|group| server_id.is_none_or(|server_id| group.server_id == server_id))
+            .cloned()
+            .collect();
+        groups.sort_by(|a, b| a.server_id.cmp(&b.server_id).then_with(|| a.name.cmp(&b.name)));
+
+        Ok(groups)
+    }
+}
+
+#[async_trait]
+impl MeetingRepository for InMemoryRepository {
+    async fn create_meeting(&self, meeting: &NewMeeting) -> Result<DbMeeting> {
+        let mut store = self.store();
+
+        if let Some(group_id) = meeting.group_ids.iter().find(|id| !store.discord_groups.contains_key(id)) {
+            return Err(eyre!("Discord group {} does not exist", group_id));
+        }
+        if let Some(previous_id) = meeting.rescheduled_from {
+            let previous = store
+                .meetings
+                .get_mut(&previous_id)
+                .ok_or_else(|| eyre!("Meeting {} does not exist", previous_id))?;
+            previous.status = MeetingStatus::Rescheduled.as_str().to_string();
+        }
+
+        let db_meeting = DbMeeting {
+            id: Uuid::new_v4(),
+            server_id: meeting.server_id.clone(),
+            channel_id: meeting.channel_id.clone(),
+            organizer_id: meeting.organizer_id.clone(),
+            start_time: meeting.start_time,
+            end_time: meeting.end_time,
+            timezone: meeting.timezone.clone(),
+            status: MeetingStatus::Scheduled.as_str().to_string(),
+            min_per_group: meeting.min_per_group,
+            slot_duration: meeting.slot_duration,
+            rescheduled_from: meeting.rescheduled_from,
+            created_at: Utc::now(),
+        };
+        store.meetings.insert(db_meeting.id, db_meeting.clone());
+
+        for group_id in &meeting.group_ids {
+            if !store.meeting_groups.contains(&(db_meeting.id, *group_id)) {
+                store.meeting_groups.push((db_meeting.id, *group_id));
+            }
+        }
+        for discord_id in &meeting.attendee_ids {
+            let attendee = DbMeetingAttendee {
+                meeting_id: db_meeting.id,
+                discord_id: discord_id.clone(),
+            };
+            if !store
+                .meeting_attendees
+                .iter()
+                .any(|existing| existing.meeting_id == attendee.meeting_id && existing.discord_id == attendee.discord_id)
+            {
+                store.meeting_attendees.push(attendee);
+            }
+        }
+
+        Ok(db_meeting)
+    }
+
+    async fn get_meeting_by_id(&self, id: Uuid) -> Result<Option<DbMeeting>> {
+        Ok(self.store().meetings.get(&id).cloned())
+    }
+
+    async fn get_upcoming_meetings_by_server(
+        &self,
+        server_id: &str,
+        status: MeetingStatus,
+    ) -> Result<Vec<DbMeeting>> {
+        let now = Utc::now();
+        let mut meetings: Vec<DbMeeting> = self
+            .store()
+            .meetings
+            .values()
+            .filter(|meeting| {
+                meeting.server_id == server_id && meeting.status == status.as_str() && meeting.end_time > now
+            })
+            .cloned()
+            .collect();
+        meetings.sort_by_key(|meeting| meeting.start_time);
+
+        Ok(meetings)
+    }
+
+    async fn update_meeting_status(&self, id: Uuid, status: MeetingStatus) -> Result<DbMeeting> {
+        let mut store = self.store();
+        let meeting = store
+            .meetings
+            .get_mut(&id)
+            .ok_or_else(|| eyre!("Meeting {} does not exist", id))?;
+        meeting.status = status.as_str().to_string();
+
+        Ok(meeting.clone())
+    }
+
+    async fn get_meeting_groups(&self, meeting_id: Uuid) -> Result<Vec<DbDiscordGroup>> {
+        let store = self.store();
+        let groups = store
+            .meeting_groups
+            .iter()
+            .filter(|(id, _)| *id == meeting_id)
+            .filter_map(|(_, group_id)| store.discord_groups.get(group_id).cloned())
+            .collect();
+
+        Ok(sorted_by_name(groups))
+    }
+
+    async fn get_meeting_attendees(&self, meeting_id: Uuid) -> Result<Vec<DbMeetingAttendee>> {
+        Ok(self
+            .store()
+            .meeting_attendees
+            .iter()
+            .filter(|attendee| attendee.meeting_id == meeting_id)
+            .cloned()
+            .collect())
+    }
+}
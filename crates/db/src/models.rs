@@ -19,6 +19,10 @@ pub struct DbTimeSlot {
     pub start_time: DateTime<Utc>,
     pub end_time: DateTime<Utc>,
     pub is_recurring: bool,
+    /// Busy time that overrides available slots
+    pub is_unavailable: bool,
+    /// A `SlotPreference` value
+    pub preference: String,
     pub created_at: DateTime<Utc>,
 }
 
@@ -26,6 +30,8 @@ pub struct DbTimeSlot {
 pub struct DbDiscordUser {
     pub discord_id: String,
     pub schedule_id: Option<Uuid>,
+    /// Personal display timezone, overriding the server's when set
+    pub timezone: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -35,6 +41,18 @@ pub struct DbDiscordGroup {
     pub name: String,
     pub server_id: String,
     pub role_id: Option<String>,
+    /// Whether membership is kept in sync with the holders of `role_id`
+    pub role_sync: bool,
+    /// Whether matches need `min_per_group` of this group's members
+    pub required: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Settings of one Discord server
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DbDiscordServer {
+    pub server_id: String,
+    pub timezone: String,
     pub created_at: DateTime<Utc>,
 }
 
@@ -42,4 +60,46 @@ pub struct DbDiscordGroup {
 pub struct DbGroupMember {
     pub group_id: Uuid,
     pub discord_id: String,
-}
\ No newline at end of file
+    /// Whether matches need this member to be available
+    pub required: bool,
+    /// How much this member's availability counts when ranking matches
+    pub weight: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DbMeeting {
+    pub id: Uuid,
+    pub server_id: String,
+    pub channel_id: String,
+    pub organizer_id: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub timezone: String,
+    pub status: String,
+    pub min_per_group: i32,
+    pub slot_duration: i32,
+    pub rescheduled_from: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DbMeetingAttendee {
+    pub meeting_id: Uuid,
+    pub discord_id: String,
+}
+
+/// Values needed to record a newly finalized meeting
+#[derive(Debug, Clone)]
+pub struct NewMeeting {
+    pub server_id: String,
+    pub channel_id: String,
+    pub organizer_id: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub timezone: String,
+    pub min_per_group: i32,
+    pub slot_duration: i32,
+    pub rescheduled_from: Option<Uuid>,
+    pub group_ids: Vec<Uuid>,
+    pub attendee_ids: Vec<String>,
+}
@@ -0,0 +1,258 @@
+//! # Postgres Backend
+//!
+//! `PgRepository` implements the repository traits on top of the queries in
+//! `crate::repositories`, sharing one connection pool.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use eyre::Result;
+use timesync_core::models::{
+    meeting::MeetingStatus,
+    schedule::{CreateTimeSlotRequest, SlotPreference},
+};
+use uuid::Uuid;
+
+use crate::{
+    models::{
+        DbDiscordGroup, DbDiscordServer, DbDiscordUser, DbGroupMember, DbMeeting, DbMeetingAttendee, DbSchedule,
+        DbTimeSlot, NewMeeting,
+    },
+    repositories::{
+        discord, meeting, schedule, time_slot, DiscordRepository, MeetingRepository, ScheduleRepository,
+        TimeSlotRepository,
+    },
+    DbPool,
+};
+
+/// Repositories backed by a Postgres database
+#[derive(Debug, Clone)]
+pub struct PgRepository {
+    pool: DbPool,
+}
+
+impl PgRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// The pool queries run on
+    pub fn pool(&self) -> &DbPool {
+        &self.pool
+    }
+}
+
+#[async_trait]
+impl ScheduleRepository for PgRepository {
+    async fn create_schedule(
+        &self,
+        name: &str,
+        password_hash: Option<&str>,
+        timezone: &str,
+    ) -> Result<DbSchedule> {
+        schedule::create_schedule(&self.pool, name, password_hash, timezone).await
+    }
+
+    async fn get_schedule_by_id(&self, id: Uuid) -> Result<Option<DbSchedule>> {
+        schedule::get_schedule_by_id(&self.pool, id).await
+    }
+
+    async fn update_schedule(
+        &self,
+        id: Uuid,
+        name: Option<&str>,
+        timezone: Option<&str>,
+    ) -> Result<DbSchedule> {
+        schedule::update_schedule(&self.pool, id, name, timezone).await
+    }
+}
+
+#[async_trait]
+impl TimeSlotRepository for PgRepository {
+    async fn create_time_slot(
+        &self,
+        schedule_id: Uuid,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+        is_recurring: bool,
+        is_unavailable: bool,
+        preference: SlotPreference,
+    ) -> Result<DbTimeSlot> {
+        time_slot::create_time_slot(
+            &self.pool,
+            schedule_id,
+            start_time,
+            end_time,
+            is_recurring,
+            is_unavailable,
+            preference,
+        )
+        .await
+    }
+
+    async fn get_time_slots_by_schedule_id(&self, schedule_id: Uuid) -> Result<Vec<DbTimeSlot>> {
+        time_slot::get_time_slots_by_schedule_id(&self.pool, schedule_id).await
+    }
+
+    async fn delete_time_slots_by_schedule_id(&self, schedule_id: Uuid) -> Result<()> {
+        time_slot::delete_time_slots_by_schedule_id(&self.pool, schedule_id).await
+    }
+
+    async fn replace_time_slots(
+        &self,
+        schedule_id: Uuid,
+        slots: &[CreateTimeSlotRequest],
+    ) -> Result<Vec<DbTimeSlot>> {
+        time_slot::replace_time_slots(&self.pool, schedule_id, slots).await
+    }
+}
+
+#[async_trait]
+impl DiscordRepository for PgRepository {
+    async fn create_discord_user(&self, discord_id: &str, schedule_id: Option<Uuid>) -> Result<DbDiscordUser> {
+        discord::create_discord_user(&self.pool, discord_id, schedule_id).await
+    }
+
+    async fn get_discord_user_by_id(&self, discord_id: &str) -> Result<Option<DbDiscordUser>> {
+        discord::get_discord_user_by_id(&self.pool, discord_id).await
+    }
+
+    async fn set_discord_user_timezone(&self, discord_id: &str, timezone: &str) -> Result<DbDiscordUser> {
+        discord::set_discord_user_timezone(&self.pool, discord_id, timezone).await
+    }
+
+    async fn get_discord_server(&self, server_id: &str) -> Result<Option<DbDiscordServer>> {
+        discord::get_discord_server(&self.pool, server_id).await
+    }
+
+    async fn set_discord_server_timezone(&self, server_id: &str, timezone: &str) -> Result<DbDiscordServer> {
+        discord::set_discord_server_timezone(&self.pool, server_id, timezone).await
+    }
+
+    async fn create_discord_group(
+        &self,
+        name: &str,
+        server_id: &str,
+        role_id: Option<&str>,
+    ) -> Result<DbDiscordGroup> {
+        discord::create_discord_group(&self.pool, name, server_id, role_id).await
+    }
+
+    async fn get_discord_group_by_id(&self, id: Uuid) -> Result<Option<DbDiscordGroup>> {
+        discord::get_discord_group_by_id(&self.pool, id).await
+    }
+
+    async fn get_discord_group_by_name(&self, server_id: &str, name: &str) -> Result<Option<DbDiscordGroup>> {
+        discord::get_discord_group_by_name(&self.pool, server_id, name).await
+    }
+
+    async fn get_discord_groups_by_server(&self, server_id: &str) -> Result<Vec<DbDiscordGroup>> {
+        discord::get_discord_groups_by_server(&self.pool, server_id).await
+    }
+
+    async fn list_discord_groups_by_server(
+        &self,
+        server_id: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<DbDiscordGroup>> {
+        discord::list_discord_groups_by_server(&self.pool, server_id, limit, offset).await
+    }
+
+    async fn count_discord_groups_by_server(&self, server_id: &str) -> Result<i64> {
+        discord::count_discord_groups_by_server(&self.pool, server_id).await
+    }
+
+    async fn update_discord_group(
+        &self,
+        id: Uuid,
+        name: Option<&str>,
+        role_id: Option<&str>,
+    ) -> Result<DbDiscordGroup> {
+        discord::update_discord_group(&self.pool, id, name, role_id).await
+    }
+
+    async fn delete_discord_group(&self, id: Uuid) -> Result<bool> {
+        discord::delete_discord_group(&self.pool, id).await
+    }
+
+    async fn set_group_required(&self, id: Uuid, required: bool) -> Result<DbDiscordGroup> {
+        discord::set_group_required(&self.pool, id, required).await
+    }
+
+    async fn update_group_role_id(&self, id: Uuid, role_id: &str) -> Result<DbDiscordGroup> {
+        discord::update_group_role_id(&self.pool, id, role_id).await
+    }
+
+    async fn add_member_to_group(&self, group_id: Uuid, discord_id: &str) -> Result<DbGroupMember> {
+        discord::add_member_to_group(&self.pool, group_id, discord_id).await
+    }
+
+    async fn ensure_group_member(&self, group_id: Uuid, discord_id: &str) -> Result<bool> {
+        discord::ensure_group_member(&self.pool, group_id, discord_id).await
+    }
+
+    async fn remove_member_from_group(&self, group_id: Uuid, discord_id: &str) -> Result<()> {
+        discord::remove_member_from_group(&self.pool, group_id, discord_id).await
+    }
+
+    async fn get_group_members(&self, group_id: Uuid) -> Result<Vec<DbGroupMember>> {
+        discord::get_group_members(&self.pool, group_id).await
+    }
+
+    async fn update_group_member(
+        &self,
+        group_id: Uuid,
+        discord_id: &str,
+        required: Option<bool>,
+        weight: Option<f64>,
+    ) -> Result<Option<DbGroupMember>> {
+        discord::update_group_member(&self.pool, group_id, discord_id, required, weight).await
+    }
+
+    async fn get_user_groups(&self, discord_id: &str) -> Result<Vec<DbDiscordGroup>> {
+        discord::get_user_groups(&self.pool, discord_id).await
+    }
+
+    async fn bind_group_to_role(&self, id: Uuid, role_id: &str) -> Result<DbDiscordGroup> {
+        discord::bind_group_to_role(&self.pool, id, role_id).await
+    }
+
+    async fn unbind_group_from_role(&self, id: Uuid) -> Result<DbDiscordGroup> {
+        discord::unbind_group_from_role(&self.pool, id).await
+    }
+
+    async fn get_role_synced_groups(&self, server_id: Option<&str>) -> Result<Vec<DbDiscordGroup>> {
+        discord::get_role_synced_groups(&self.pool, server_id).await
+    }
+}
+
+#[async_trait]
+impl MeetingRepository for PgRepository {
+    async fn create_meeting(&self, new_meeting: &NewMeeting) -> Result<DbMeeting> {
+        meeting::create_meeting(&self.pool, new_meeting).await
+    }
+
+    async fn get_meeting_by_id(&self, id: Uuid) -> Result<Option<DbMeeting>> {
+        meeting::get_meeting_by_id(&self.pool, id).await
+    }
+
+    async fn get_upcoming_meetings_by_server(
+        &self,
+        server_id: &str,
+        status: MeetingStatus,
+    ) -> Result<Vec<DbMeeting>> {
+        meeting::get_upcoming_meetings_by_server(&self.pool, server_id, status).await
+    }
+
+    async fn update_meeting_status(&self, id: Uuid, status: MeetingStatus) -> Result<DbMeeting> {
+        meeting::update_meeting_status(&self.pool, id, status).await
+    }
+
+    async fn get_meeting_groups(&self, meeting_id: Uuid) -> Result<Vec<DbDiscordGroup>> {
+        meeting::get_meeting_groups(&self.pool, meeting_id).await
+    }
+
+    async fn get_meeting_attendees(&self, meeting_id: Uuid) -> Result<Vec<DbMeetingAttendee>> {
+        meeting::get_meeting_attendees(&self.pool, meeting_id).await
+    }
+}
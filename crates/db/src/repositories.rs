@@ -1,3 +1,202 @@
+//! # Repositories
+//!
+//! Each submodule holds the Postgres queries for one area as free functions
+//! taking a pool. The traits below describe the same operations without tying
+//! them to a database, so the API can run against any backend:
+//!
+//! - `crate::postgres::PgRepository` wraps the free functions
+//! - `crate::memory::InMemoryRepository` keeps everything in memory for tests
+//!
+//! One backend type implements all the traits, and `ApiState` holds it once
+//! per trait.
+
 pub mod schedule;
 pub mod time_slot;
-pub mod discord;
\ No newline at end of file
+pub mod discord;
+pub mod meeting;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use eyre::{eyre, Result};
+use timesync_core::models::{
+    meeting::MeetingStatus,
+    schedule::{CreateTimeSlotRequest, SlotPreference},
+};
+use uuid::Uuid;
+
+use crate::models::{
+    DbDiscordGroup, DbDiscordServer, DbDiscordUser, DbGroupMember, DbMeeting, DbMeetingAttendee, DbSchedule,
+    DbTimeSlot, NewMeeting,
+};
+
+#[async_trait]
+pub trait ScheduleRepository: Send + Sync {
+    async fn create_schedule(
+        &self,
+        name: &str,
+        password_hash: Option<&str>,
+        timezone: &str,
+    ) -> Result<DbSchedule>;
+
+    async fn get_schedule_by_id(&self, id: Uuid) -> Result<Option<DbSchedule>>;
+
+    /// Change a schedule's name and timezone; values left as `None` are kept
+    async fn update_schedule(
+        &self,
+        id: Uuid,
+        name: Option<&str>,
+        timezone: Option<&str>,
+    ) -> Result<DbSchedule>;
+
+    /// Check a password against the schedule's hash
+    ///
+    /// Any password is valid for a schedule without one.
+    async fn verify_password(&self, id: Uuid, password: &str) -> Result<bool> {
+        let schedule = self
+            .get_schedule_by_id(id)
+            .await?
+            .ok_or_else(|| eyre!("Schedule not found"))?;
+
+        schedule::check_password(schedule.password_hash.as_deref(), password)
+    }
+}
+
+#[async_trait]
+pub trait TimeSlotRepository: Send + Sync {
+    async fn create_time_slot(
+        &self,
+        schedule_id: Uuid,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+        is_recurring: bool,
+        is_unavailable: bool,
+        preference: SlotPreference,
+    ) -> Result<DbTimeSlot>;
+
+    /// A schedule's slots, earliest first
+    async fn get_time_slots_by_schedule_id(&self, schedule_id: Uuid) -> Result<Vec<DbTimeSlot>>;
+
+    async fn delete_time_slots_by_schedule_id(&self, schedule_id: Uuid) -> Result<()>;
+
+    /// Replace all of a schedule's time slots at once
+    async fn replace_time_slots(
+        &self,
+        schedule_id: Uuid,
+        slots: &[CreateTimeSlotRequest],
+    ) -> Result<Vec<DbTimeSlot>>;
+}
+
+#[async_trait]
+pub trait DiscordRepository: Send + Sync {
+    /// Link a Discord user to a schedule, creating the user if needed
+    async fn create_discord_user(&self, discord_id: &str, schedule_id: Option<Uuid>) -> Result<DbDiscordUser>;
+
+    async fn get_discord_user_by_id(&self, discord_id: &str) -> Result<Option<DbDiscordUser>>;
+
+    /// Set a Discord user's personal timezone, creating the user if needed
+    async fn set_discord_user_timezone(&self, discord_id: &str, timezone: &str) -> Result<DbDiscordUser>;
+
+    async fn get_discord_server(&self, server_id: &str) -> Result<Option<DbDiscordServer>>;
+
+    /// Set a Discord server's timezone, creating its settings if needed
+    async fn set_discord_server_timezone(&self, server_id: &str, timezone: &str) -> Result<DbDiscordServer>;
+
+    async fn create_discord_group(
+        &self,
+        name: &str,
+        server_id: &str,
+        role_id: Option<&str>,
+    ) -> Result<DbDiscordGroup>;
+
+    async fn get_discord_group_by_id(&self, id: Uuid) -> Result<Option<DbDiscordGroup>>;
+
+    async fn get_discord_group_by_name(&self, server_id: &str, name: &str) -> Result<Option<DbDiscordGroup>>;
+
+    /// All of a server's groups, ordered by name
+    async fn get_discord_groups_by_server(&self, server_id: &str) -> Result<Vec<DbDiscordGroup>>;
+
+    /// One page of a server's groups, ordered by name
+    async fn list_discord_groups_by_server(
+        &self,
+        server_id: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<DbDiscordGroup>>;
+
+    async fn count_discord_groups_by_server(&self, server_id: &str) -> Result<i64>;
+
+    /// Rename a group or change its role; values left as `None` are kept
+    async fn update_discord_group(
+        &self,
+        id: Uuid,
+        name: Option<&str>,
+        role_id: Option<&str>,
+    ) -> Result<DbDiscordGroup>;
+
+    /// Delete a group along with its memberships
+    ///
+    /// Returns false if no group with this ID exists.
+    async fn delete_discord_group(&self, id: Uuid) -> Result<bool>;
+
+    /// Tag a group as required or optional for matching
+    async fn set_group_required(&self, id: Uuid, required: bool) -> Result<DbDiscordGroup>;
+
+    async fn update_group_role_id(&self, id: Uuid, role_id: &str) -> Result<DbDiscordGroup>;
+
+    /// Add a member to a group; fails if they're already a member
+    async fn add_member_to_group(&self, group_id: Uuid, discord_id: &str) -> Result<DbGroupMember>;
+
+    /// Add a member to a group, creating the Discord user if needed
+    ///
+    /// Returns false if they were already a member.
+    async fn ensure_group_member(&self, group_id: Uuid, discord_id: &str) -> Result<bool>;
+
+    async fn remove_member_from_group(&self, group_id: Uuid, discord_id: &str) -> Result<()>;
+
+    async fn get_group_members(&self, group_id: Uuid) -> Result<Vec<DbGroupMember>>;
+
+    /// Change whether a member is required and how much they count in matching
+    ///
+    /// Values left as `None` are kept. Returns `None` if the user isn't a
+    /// member of the group.
+    async fn update_group_member(
+        &self,
+        group_id: Uuid,
+        discord_id: &str,
+        required: Option<bool>,
+        weight: Option<f64>,
+    ) -> Result<Option<DbGroupMember>>;
+
+    async fn get_user_groups(&self, discord_id: &str) -> Result<Vec<DbDiscordGroup>>;
+
+    /// Bind a group to a role so its membership follows the role's holders
+    async fn bind_group_to_role(&self, id: Uuid, role_id: &str) -> Result<DbDiscordGroup>;
+
+    /// Stop syncing a group's membership from its role, keeping the role itself
+    async fn unbind_group_from_role(&self, id: Uuid) -> Result<DbDiscordGroup>;
+
+    /// Get the role-synced groups, optionally limited to one server
+    async fn get_role_synced_groups(&self, server_id: Option<&str>) -> Result<Vec<DbDiscordGroup>>;
+}
+
+#[async_trait]
+pub trait MeetingRepository: Send + Sync {
+    /// Record a meeting, marking the one it was rescheduled from as rescheduled
+    async fn create_meeting(&self, meeting: &NewMeeting) -> Result<DbMeeting>;
+
+    async fn get_meeting_by_id(&self, id: Uuid) -> Result<Option<DbMeeting>>;
+
+    /// Get a server's meetings with the given status that haven't ended yet, soonest first
+    async fn get_upcoming_meetings_by_server(
+        &self,
+        server_id: &str,
+        status: MeetingStatus,
+    ) -> Result<Vec<DbMeeting>>;
+
+    async fn update_meeting_status(&self, id: Uuid, status: MeetingStatus) -> Result<DbMeeting>;
+
+    /// The meeting's groups that still exist, ordered by name
+    async fn get_meeting_groups(&self, meeting_id: Uuid) -> Result<Vec<DbDiscordGroup>>;
+
+    async fn get_meeting_attendees(&self, meeting_id: Uuid) -> Result<Vec<DbMeetingAttendee>>;
+}
@@ -1,4 +1,4 @@
-use crate::models::{DbDiscordGroup, DbDiscordUser, DbGroupMember};
+use crate::models::{DbDiscordGroup, DbDiscordServer, DbDiscordUser, DbGroupMember};
 use chrono::Utc;
 use eyre::{eyre, Result};
 use sqlx::{Pool, Postgres};
@@ -19,7 +19,7 @@ pub async fn create_discord_user(
         VALUES ($1, $2, $3)
         ON CONFLICT (discord_id) 
         DO UPDATE SET schedule_id = $2
-        RETURNING discord_id, schedule_id, created_at
+        RETURNING discord_id, schedule_id, timezone, created_at
         "#,
     )
     .bind(discord_id)
@@ -37,7 +37,7 @@ pub async fn get_discord_user_by_id(
 ) -> Result<Option<DbDiscordUser>> {
     let discord_user = sqlx::query_as::<_, DbDiscordUser>(
         r#"
-        SELECT discord_id, schedule_id, created_at
+        SELECT discord_id, schedule_id, timezone, created_at
         FROM discord_users
         WHERE discord_id = $1
         "#,
@@ -49,6 +49,74 @@ pub async fn get_discord_user_by_id(
     Ok(discord_user)
 }
 
+/// Set a Discord user's personal timezone, creating the user if needed
+pub async fn set_discord_user_timezone(
+    pool: &Pool<Postgres>,
+    discord_id: &str,
+    timezone: &str,
+) -> Result<DbDiscordUser> {
+    let discord_user = sqlx::query_as::<_, DbDiscordUser>(
+        r#"
+        INSERT INTO discord_users (discord_id, schedule_id, timezone, created_at)
+        VALUES ($1, NULL, $2, $3)
+        ON CONFLICT (discord_id)
+        DO UPDATE SET timezone = $2
+        RETURNING discord_id, schedule_id, timezone, created_at
+        "#,
+    )
+    .bind(discord_id)
+    .bind(timezone)
+    .bind(Utc::now())
+    .fetch_one(pool)
+    .await?;
+
+    Ok(discord_user)
+}
+
+// Discord Server Repository
+
+pub async fn get_discord_server(
+    pool: &Pool<Postgres>,
+    server_id: &str,
+) -> Result<Option<DbDiscordServer>> {
+    let server = sqlx::query_as::<_, DbDiscordServer>(
+        r#"
+        SELECT server_id, timezone, created_at
+        FROM discord_servers
+        WHERE server_id = $1
+        "#,
+    )
+    .bind(server_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(server)
+}
+
+/// Set a Discord server's timezone, creating its settings if needed
+pub async fn set_discord_server_timezone(
+    pool: &Pool<Postgres>,
+    server_id: &str,
+    timezone: &str,
+) -> Result<DbDiscordServer> {
+    let server = sqlx::query_as::<_, DbDiscordServer>(
+        r#"
+        INSERT INTO discord_servers (server_id, timezone, created_at)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (server_id)
+        DO UPDATE SET timezone = $2
+        RETURNING server_id, timezone, created_at
+        "#,
+    )
+    .bind(server_id)
+    .bind(timezone)
+    .bind(Utc::now())
+    .fetch_one(pool)
+    .await?;
+
+    Ok(server)
+}
+
 // Discord Group Repository
 
 pub async fn create_discord_group(
@@ -64,7 +132,7 @@ pub async fn create_discord_group(
         r#"
         INSERT INTO discord_groups (id, name, server_id, role_id, created_at)
         VALUES ($1, $2, $3, $4, $5)
-        RETURNING id, name, server_id, role_id, created_at
+        RETURNING id, name, server_id, role_id, role_sync, required, created_at
         "#,
     )
     .bind(id)
@@ -84,7 +152,7 @@ pub async fn get_discord_group_by_id(
 ) -> Result<Option<DbDiscordGroup>> {
     let discord_group = sqlx::query_as::<_, DbDiscordGroup>(
         r#"
-        SELECT id, name, server_id, role_id, created_at
+        SELECT id, name, server_id, role_id, role_sync, required, created_at
         FROM discord_groups
         WHERE id = $1
         "#,
@@ -96,6 +164,59 @@ pub async fn get_discord_group_by_id(
     Ok(discord_group)
 }
 
+pub async fn get_discord_groups_by_server(
+    pool: &Pool<Postgres>,
+    server_id: &str,
+) -> Result<Vec<DbDiscordGroup>> {
+    let groups = sqlx::query_as::<_, DbDiscordGroup>(
+        r#"
+        SELECT id, name, server_id, role_id, role_sync, required, created_at
+        FROM discord_groups
+        WHERE server_id = $1
+        ORDER BY name
+        "#,
+    )
+    .bind(server_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(groups)
+}
+
+/// One page of a server's groups, ordered by name
+pub async fn list_discord_groups_by_server(
+    pool: &Pool<Postgres>,
+    server_id: &str,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<DbDiscordGroup>> {
+    let groups = sqlx::query_as::<_, DbDiscordGroup>(
+        r#"
+        SELECT id, name, server_id, role_id, role_sync, required, created_at
+        FROM discord_groups
+        WHERE server_id = $1
+        ORDER BY name, id
+        LIMIT $2 OFFSET $3
+        "#,
+    )
+    .bind(server_id)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(groups)
+}
+
+pub async fn count_discord_groups_by_server(pool: &Pool<Postgres>, server_id: &str) -> Result<i64> {
+    let count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM discord_groups WHERE server_id = $1")
+        .bind(server_id)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(count)
+}
+
 pub async fn update_discord_group(
     pool: &Pool<Postgres>,
     id: Uuid,
@@ -113,7 +234,7 @@ pub async fn update_discord_group(
         UPDATE discord_groups
         SET name = $2, role_id = $3
         WHERE id = $1
-        RETURNING id, name, server_id, role_id, created_at
+        RETURNING id, name, server_id, role_id, role_sync, required, created_at
         "#,
     )
     .bind(id)
@@ -125,6 +246,49 @@ pub async fn update_discord_group(
     Ok(updated_group)
 }
 
+/// Delete a group along with its memberships
+///
+/// Returns false if no group with this ID exists.
+pub async fn delete_discord_group(pool: &Pool<Postgres>, id: Uuid) -> Result<bool> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("DELETE FROM group_members WHERE group_id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+    let result = sqlx::query("DELETE FROM discord_groups WHERE id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Tag a group as required or optional for matching
+pub async fn set_group_required(
+    pool: &Pool<Postgres>,
+    id: Uuid,
+    required: bool,
+) -> Result<DbDiscordGroup> {
+    let updated_group = sqlx::query_as::<_, DbDiscordGroup>(
+        r#"
+        UPDATE discord_groups
+        SET required = $2
+        WHERE id = $1
+        RETURNING id, name, server_id, role_id, role_sync, required, created_at
+        "#,
+    )
+    .bind(id)
+    .bind(required)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(updated_group)
+}
+
 // Group Membership Repository
 
 pub async fn add_member_to_group(
@@ -137,7 +301,7 @@ pub async fn add_member_to_group(
         INSERT INTO group_members (group_id, discord_id)
         VALUES ($1, $2)
         ON CONFLICT (group_id, discord_id) DO NOTHING
-        RETURNING group_id, discord_id
+        RETURNING group_id, discord_id, required, weight
         "#,
     )
     .bind(group_id)
@@ -173,7 +337,7 @@ pub async fn get_group_members(
 ) -> Result<Vec<DbGroupMember>> {
     let members = sqlx::query_as::<_, DbGroupMember>(
         r#"
-        SELECT group_id, discord_id
+        SELECT group_id, discord_id, required, weight
         FROM group_members
         WHERE group_id = $1
         "#,
@@ -185,13 +349,42 @@ pub async fn get_group_members(
     Ok(members)
 }
 
+/// Change whether a member is required and how much they count in matching
+///
+/// Values left as `None` are kept. Returns `None` if the user isn't a member
+/// of the group.
+pub async fn update_group_member(
+    pool: &Pool<Postgres>,
+    group_id: Uuid,
+    discord_id: &str,
+    required: Option<bool>,
+    weight: Option<f64>,
+) -> Result<Option<DbGroupMember>> {
+    let member = sqlx::query_as::<_, DbGroupMember>(
+        r#"
+        UPDATE group_members
+        SET required = COALESCE($3, required), weight = COALESCE($4, weight)
+        WHERE group_id = $1 AND discord_id = $2
+        RETURNING group_id, discord_id, required, weight
+        "#,
+    )
+    .bind(group_id)
+    .bind(discord_id)
+    .bind(required)
+    .bind(weight)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(member)
+}
+
 pub async fn get_user_groups(
     pool: &Pool<Postgres>,
     discord_id: &str,
 ) -> Result<Vec<DbDiscordGroup>> {
     let groups = sqlx::query_as::<_, DbDiscordGroup>(
         r#"
-        SELECT g.id, g.name, g.server_id, g.role_id, g.created_at
+        SELECT g.id, g.name, g.server_id, g.role_id, g.role_sync, g.required, g.created_at
         FROM discord_groups g
         JOIN group_members gm ON g.id = gm.group_id
         WHERE gm.discord_id = $1
@@ -214,7 +407,7 @@ pub async fn update_group_role_id(
         UPDATE discord_groups
         SET role_id = $2
         WHERE id = $1
-        RETURNING id, name, server_id, role_id, created_at
+        RETURNING id, name, server_id, role_id, role_sync, required, created_at
         "#,
     )
     .bind(id)
@@ -223,4 +416,122 @@ pub async fn update_group_role_id(
     .await?;
 
     Ok(updated_group)
-}
\ No newline at end of file
+}
+
+pub async fn get_discord_group_by_name(
+    pool: &Pool<Postgres>,
+    server_id: &str,
+    name: &str,
+) -> Result<Option<DbDiscordGroup>> {
+    let discord_group = sqlx::query_as::<_, DbDiscordGroup>(
+        r#"
+        SELECT id, name, server_id, role_id, role_sync, required, created_at
+        FROM discord_groups
+        WHERE server_id = $1 AND name = $2
+        "#,
+    )
+    .bind(server_id)
+    .bind(name)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(discord_group)
+}
+
+// Role Sync Repository
+
+/// Bind a group to a role so its membership follows the role's holders
+pub async fn bind_group_to_role(
+    pool: &Pool<Postgres>,
+    id: Uuid,
+    role_id: &str,
+) -> Result<DbDiscordGroup> {
+    let updated_group = sqlx::query_as::<_, DbDiscordGroup>(
+        r#"
+        UPDATE discord_groups
+        SET role_id = $2, role_sync = TRUE
+        WHERE id = $1
+        RETURNING id, name, server_id, role_id, role_sync, required, created_at
+        "#,
+    )
+    .bind(id)
+    .bind(role_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(updated_group)
+}
+
+/// Stop syncing a group's membership from its role, keeping the role itself
+pub async fn unbind_group_from_role(pool: &Pool<Postgres>, id: Uuid) -> Result<DbDiscordGroup> {
+    let updated_group = sqlx::query_as::<_, DbDiscordGroup>(
+        r#"
+        UPDATE discord_groups
+        SET role_sync = FALSE
+        WHERE id = $1
+        RETURNING id, name, server_id, role_id, role_sync, required, created_at
+        "#,
+    )
+    .bind(id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(updated_group)
+}
+
+/// Get the role-synced groups, optionally limited to one server
+pub async fn get_role_synced_groups(
+    pool: &Pool<Postgres>,
+    server_id: Option<&str>,
+) -> Result<Vec<DbDiscordGroup>> {
+    let groups = sqlx::query_as::<_, DbDiscordGroup>(
+        r#"
+        SELECT id, name, server_id, role_id, role_sync, required, created_at
+        FROM discord_groups
+        WHERE role_sync = TRUE AND role_id IS NOT NULL
+          AND ($1::VARCHAR IS NULL OR server_id = $1)
+        ORDER BY server_id, name
+        "#,
+    )
+    .bind(server_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(groups)
+}
+
+/// Add a member to a group, creating the Discord user if needed
+///
+/// Unlike `add_member_to_group` this succeeds when the user is already a
+/// member, and reports whether a membership was actually added.
+pub async fn ensure_group_member(
+    pool: &Pool<Postgres>,
+    group_id: Uuid,
+    discord_id: &str,
+) -> Result<bool> {
+    sqlx::query(
+        r#"
+        INSERT INTO discord_users (discord_id, schedule_id, created_at)
+        VALUES ($1, NULL, $2)
+        ON CONFLICT (discord_id) DO NOTHING
+        "#,
+    )
+    .bind(discord_id)
+    .bind(Utc::now())
+    .execute(pool)
+    .await?;
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO group_members (group_id, discord_id)
+        VALUES ($1, $2)
+        ON CONFLICT (group_id, discord_id) DO NOTHING
+        "#,
+    )
+    .bind(group_id)
+    .bind(discord_id)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,182 @@
+use crate::models::{DbDiscordGroup, DbMeeting, DbMeetingAttendee, NewMeeting};
+use chrono::Utc;
+use eyre::Result;
+use sqlx::{Pool, Postgres};
+use timesync_core::models::meeting::MeetingStatus;
+use uuid::Uuid;
+
+// Meeting Repository
+
+/// Record a meeting, marking the one it was rescheduled from as rescheduled
+pub async fn create_meeting(pool: &Pool<Postgres>, meeting: &NewMeeting) -> Result<DbMeeting> {
+    let id = Uuid::new_v4();
+    let now = Utc::now();
+
+    let mut tx = pool.begin().await?;
+
+    let db_meeting = sqlx::query_as::<_, DbMeeting>(
+        r#"
+        INSERT INTO meetings (
+            id, server_id, channel_id, organizer_id, start_time, end_time, timezone,
+            status, min_per_group, slot_duration, rescheduled_from, created_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        RETURNING id, server_id, channel_id, organizer_id, start_time, end_time, timezone,
+                  status, min_per_group, slot_duration, rescheduled_from, created_at
+        "#,
+    )
+    .bind(id)
+    .bind(&meeting.server_id)
+    .bind(&meeting.channel_id)
+    .bind(&meeting.organizer_id)
+    .bind(meeting.start_time)
+    .bind(meeting.end_time)
+    .bind(&meeting.timezone)
+    .bind(MeetingStatus::Scheduled.as_str())
+    .bind(meeting.min_per_group)
+    .bind(meeting.slot_duration)
+    .bind(meeting.rescheduled_from)
+    .bind(now)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    for group_id in &meeting.group_ids {
+        sqlx::query(
+            r#"
+            INSERT INTO meeting_groups (meeting_id, group_id)
+            VALUES ($1, $2)
+            ON CONFLICT (meeting_id, group_id) DO NOTHING
+            "#,
+        )
+        .bind(id)
+        .bind(group_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    for discord_id in &meeting.attendee_ids {
+        sqlx::query(
+            r#"
+            INSERT INTO meeting_attendees (meeting_id, discord_id)
+            VALUES ($1, $2)
+            ON CONFLICT (meeting_id, discord_id) DO NOTHING
+            "#,
+        )
+        .bind(id)
+        .bind(discord_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if let Some(previous_id) = meeting.rescheduled_from {
+        sqlx::query("UPDATE meetings SET status = $2 WHERE id = $1")
+            .bind(previous_id)
+            .bind(MeetingStatus::Rescheduled.as_str())
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(db_meeting)
+}
+
+pub async fn get_meeting_by_id(pool: &Pool<Postgres>, id: Uuid) -> Result<Option<DbMeeting>> {
+    let meeting = sqlx::query_as::<_, DbMeeting>(
+        r#"
+        SELECT id, server_id, channel_id, organizer_id, start_time, end_time, timezone,
+               status, min_per_group, slot_duration, rescheduled_from, created_at
+        FROM meetings
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(meeting)
+}
+
+/// Get a server's meetings with the given status that haven't ended yet, soonest first
+pub async fn get_upcoming_meetings_by_server(
+    pool: &Pool<Postgres>,
+    server_id: &str,
+    status: MeetingStatus,
+) -> Result<Vec<DbMeeting>> {
+    let meetings = sqlx::query_as::<_, DbMeeting>(
+        r#"
+        SELECT id, server_id, channel_id, organizer_id, start_time, end_time, timezone,
+               status, min_per_group, slot_duration, rescheduled_from, created_at
+        FROM meetings
+        WHERE server_id = $1 AND status = $2 AND end_time > $3
+        ORDER BY start_time
+        "#,
+    )
+    .bind(server_id)
+    .bind(status.as_str())
+    .bind(Utc::now())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(meetings)
+}
+
+pub async fn update_meeting_status(
+    pool: &Pool<Postgres>,
+    id: Uuid,
+    status: MeetingStatus,
+) -> Result<DbMeeting> {
+    let meeting = sqlx::query_as::<_, DbMeeting>(
+        r#"
+        UPDATE meetings
+        SET status = $2
+        WHERE id = $1
+        RETURNING id, server_id, channel_id, organizer_id, start_time, end_time, timezone,
+                  status, min_per_group, slot_duration, rescheduled_from, created_at
+        "#,
+    )
+    .bind(id)
+    .bind(status.as_str())
+    .fetch_one(pool)
+    .await?;
+
+    Ok(meeting)
+}
+
+pub async fn get_meeting_groups(
+    pool: &Pool<Postgres>,
+    meeting_id: Uuid,
+) -> Result<Vec<DbDiscordGroup>> {
+    let groups = sqlx::query_as::<_, DbDiscordGroup>(
+        r#"
+        SELECT g.id, g.name, g.server_id, g.role_id, g.role_sync, g.required, g.created_at
+        FROM discord_groups g
+        JOIN meeting_groups mg ON g.id = mg.group_id
+        WHERE mg.meeting_id = $1
+        ORDER BY g.name
+        "#,
+    )
+    .bind(meeting_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(groups)
+}
+
+pub async fn get_meeting_attendees(
+    pool: &Pool<Postgres>,
+    meeting_id: Uuid,
+) -> Result<Vec<DbMeetingAttendee>> {
+    let attendees = sqlx::query_as::<_, DbMeetingAttendee>(
+        r#"
+        SELECT meeting_id, discord_id
+        FROM meeting_attendees
+        WHERE meeting_id = $1
+        "#,
+    )
+    .bind(meeting_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(attendees)
+}
@@ -163,9 +163,14 @@ pub async fn verify_password(
         .await?
         .ok_or_else(|| eyre!("Schedule not found"))?;
 
-    match schedule.password_hash {
+    check_password(schedule.password_hash.as_deref(), password)
+}
+
+/// Check a password against a schedule's stored hash
+pub fn check_password(password_hash: Option<&str>, password: &str) -> Result<bool> {
+    match password_hash {
         Some(hash) => {
-            let parsed_hash = argon2::PasswordHash::new(&hash)
+            let parsed_hash = argon2::PasswordHash::new(hash)
                 .map_err(|e| eyre!("Invalid password hash: {}", e))?;
             let is_valid = Argon2::default()
                 .verify_password(password.as_bytes(), &parsed_hash)
@@ -174,4 +179,4 @@ pub async fn verify_password(
         }
         None => Ok(true), // If no password is set, consider any password valid
     }
-}
\ No newline at end of file
+}
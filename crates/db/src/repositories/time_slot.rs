@@ -2,6 +2,7 @@ use crate::models::DbTimeSlot;
 use chrono::{DateTime, Utc};
 use eyre::Result;
 use sqlx::{Pool, Postgres};
+use timesync_core::models::schedule::{CreateTimeSlotRequest, SlotPreference};
 use uuid::Uuid;
 
 pub async fn create_time_slot(
@@ -10,13 +11,15 @@ pub async fn create_time_slot(
     start_time: DateTime<Utc>,
     end_time: DateTime<Utc>,
     is_recurring: bool,
+    is_unavailable: bool,
+    preference: SlotPreference,
 ) -> Result<DbTimeSlot> {
     let id = Uuid::new_v4();
     let now = Utc::now();
 
     tracing::debug!(
-        "Creating time slot: id={}, schedule_id={}, start_time={}, end_time={}, is_recurring={}",
-        id, schedule_id, start_time, end_time, is_recurring
+        "Creating time slot: id={}, schedule_id={}, start_time={}, end_time={}, is_recurring={}, is_unavailable={}, preference={}",
+        id, schedule_id, start_time, end_time, is_recurring, is_unavailable, preference.as_str()
     );
 
     // First check if the is_recurring column exists
@@ -37,9 +40,9 @@ pub async fn create_time_slot(
         tracing::debug!("Using the is_recurring column");
         sqlx::query_as::<_, DbTimeSlot>(
             r#"
-            INSERT INTO time_slots (id, schedule_id, start_time, end_time, is_recurring, created_at)
-            VALUES ($1, $2, $3, $4, $5, $6)
-            RETURNING id, schedule_id, start_time, end_time, is_recurring, created_at
+            INSERT INTO time_slots (id, schedule_id, start_time, end_time, is_recurring, is_unavailable, preference, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id, schedule_id, start_time, end_time, is_recurring, is_unavailable, preference, created_at
             "#,
         )
         .bind(id)
@@ -47,6 +50,8 @@ pub async fn create_time_slot(
         .bind(start_time)
         .bind(end_time)
         .bind(is_recurring)
+        .bind(is_unavailable)
+        .bind(preference.as_str())
         .bind(now)
         .fetch_one(pool)
         .await?
@@ -57,7 +62,7 @@ pub async fn create_time_slot(
             r#"
             INSERT INTO time_slots (id, schedule_id, start_time, end_time, created_at)
             VALUES ($1, $2, $3, $4, $5)
-            RETURNING id, schedule_id, start_time, end_time, false as is_recurring, created_at
+            RETURNING id, schedule_id, start_time, end_time, false as is_recurring, false as is_unavailable, 'available' as preference, created_at
             "#,
         )
         .bind(id)
@@ -79,7 +84,7 @@ pub async fn get_time_slots_by_schedule_id(
 ) -> Result<Vec<DbTimeSlot>> {
     let time_slots = sqlx::query_as::<_, DbTimeSlot>(
         r#"
-        SELECT id, schedule_id, start_time, end_time, is_recurring, created_at
+        SELECT id, schedule_id, start_time, end_time, is_recurring, is_unavailable, preference, created_at
         FROM time_slots
         WHERE schedule_id = $1
         ORDER BY start_time ASC
@@ -107,4 +112,45 @@ pub async fn delete_time_slots_by_schedule_id(
     .await?;
 
     Ok(())
-}
\ No newline at end of file
+}
+/// Replace all of a schedule's time slots in a single transaction
+pub async fn replace_time_slots(
+    pool: &Pool<Postgres>,
+    schedule_id: Uuid,
+    slots: &[CreateTimeSlotRequest],
+) -> Result<Vec<DbTimeSlot>> {
+    let now = Utc::now();
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("DELETE FROM time_slots WHERE schedule_id = $1")
+        .bind(schedule_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let mut time_slots = Vec::with_capacity(slots.len());
+    for slot in slots {
+        let time_slot = sqlx::query_as::<_, DbTimeSlot>(
+            r#"
+            INSERT INTO time_slots (id, schedule_id, start_time, end_time, is_recurring, is_unavailable, preference, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id, schedule_id, start_time, end_time, is_recurring, is_unavailable, preference, created_at
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(schedule_id)
+        .bind(slot.start)
+        .bind(slot.end)
+        .bind(slot.is_recurring)
+        .bind(slot.is_unavailable)
+        .bind(slot.preference.as_str())
+        .bind(now)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        time_slots.push(time_slot);
+    }
+
+    tx.commit().await?;
+
+    Ok(time_slots)
+}
@@ -1,5 +1,5 @@
 use eyre::Result;
-use sqlx::{Pool, Postgres};
+use sqlx::{Pool, Postgres, Sqlite};
 use tracing::info;
 
 pub async fn initialize_database(pool: &Pool<Postgres>) -> Result<()> {
@@ -19,35 +19,6 @@ pub async fn initialize_database(pool: &Pool<Postgres>) -> Result<()> {
     .execute(pool)
     .await?;
     
-    // Check if role_id column exists in discord_groups table
-    info!("Checking for role_id column in discord_groups table...");
-    let role_id_exists = sqlx::query_scalar::<_, bool>(
-        r#"
-        SELECT EXISTS (
-            SELECT 1 
-            FROM information_schema.columns 
-            WHERE table_name = 'discord_groups' AND column_name = 'role_id'
-        );
-        "#,
-    )
-    .fetch_one(pool)
-    .await?;
-    
-    if !role_id_exists {
-        info!("Adding role_id column to discord_groups table...");
-        sqlx::query(
-            r#"
-            ALTER TABLE discord_groups
-            ADD COLUMN role_id VARCHAR(255) NULL;
-            "#,
-        )
-        .execute(pool)
-        .await?;
-        info!("role_id column added successfully.");
-    } else {
-        info!("role_id column already exists.");
-    }
-    
     // Add timezone column to schedules table if it doesn't exist
     info!("Checking for timezone column in schedules table...");
     let column_exists = sqlx::query_scalar::<_, bool>(
@@ -122,6 +93,64 @@ pub async fn initialize_database(pool: &Pool<Postgres>) -> Result<()> {
         info!("is_recurring column already exists.");
     }
 
+    // Add is_unavailable column to time_slots table if it doesn't exist
+    info!("Checking for is_unavailable column in time_slots table...");
+    let is_unavailable_exists = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS (
+            SELECT 1
+            FROM information_schema.columns
+            WHERE table_name = 'time_slots' AND column_name = 'is_unavailable'
+        );
+        "#,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    if !is_unavailable_exists {
+        info!("Adding is_unavailable column to time_slots table...");
+        sqlx::query(
+            r#"
+            ALTER TABLE time_slots
+            ADD COLUMN is_unavailable BOOLEAN NOT NULL DEFAULT FALSE;
+            "#,
+        )
+        .execute(pool)
+        .await?;
+        info!("is_unavailable column added successfully.");
+    } else {
+        info!("is_unavailable column already exists.");
+    }
+
+    // Add preference column to time_slots table if it doesn't exist
+    info!("Checking for preference column in time_slots table...");
+    let preference_exists = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS (
+            SELECT 1
+            FROM information_schema.columns
+            WHERE table_name = 'time_slots' AND column_name = 'preference'
+        );
+        "#,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    if !preference_exists {
+        info!("Adding preference column to time_slots table...");
+        sqlx::query(
+            r#"
+            ALTER TABLE time_slots
+            ADD COLUMN preference VARCHAR(20) NOT NULL DEFAULT 'available';
+            "#,
+        )
+        .execute(pool)
+        .await?;
+        info!("preference column added successfully.");
+    } else {
+        info!("preference column already exists.");
+    }
+
     // Create discord_users table
     sqlx::query(
         r#"
@@ -135,6 +164,35 @@ pub async fn initialize_database(pool: &Pool<Postgres>) -> Result<()> {
     .execute(pool)
     .await?;
 
+    // Add timezone column to discord_users table if it doesn't exist
+    info!("Checking for timezone column in discord_users table...");
+    let user_timezone_exists = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 
+            FROM information_schema.columns 
+            WHERE table_name = 'discord_users' AND column_name = 'timezone'
+        );
+        "#,
+    )
+    .fetch_one(pool)
+    .await?;
+    
+    if !user_timezone_exists {
+        info!("Adding timezone column to discord_users table...");
+        sqlx::query(
+            r#"
+            ALTER TABLE discord_users
+            ADD COLUMN timezone VARCHAR(100) NULL;
+            "#,
+        )
+        .execute(pool)
+        .await?;
+        info!("timezone column added successfully.");
+    } else {
+        info!("timezone column already exists.");
+    }
+    
     // Create discord_groups table
     sqlx::query(
         r#"
@@ -150,6 +208,64 @@ pub async fn initialize_database(pool: &Pool<Postgres>) -> Result<()> {
     .execute(pool)
     .await?;
     
+    // Check if role_id column exists in discord_groups table
+    info!("Checking for role_id column in discord_groups table...");
+    let role_id_exists = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 
+            FROM information_schema.columns 
+            WHERE table_name = 'discord_groups' AND column_name = 'role_id'
+        );
+        "#,
+    )
+    .fetch_one(pool)
+    .await?;
+    
+    if !role_id_exists {
+        info!("Adding role_id column to discord_groups table...");
+        sqlx::query(
+            r#"
+            ALTER TABLE discord_groups
+            ADD COLUMN role_id VARCHAR(255) NULL;
+            "#,
+        )
+        .execute(pool)
+        .await?;
+        info!("role_id column added successfully.");
+    } else {
+        info!("role_id column already exists.");
+    }
+    
+    // Add role_sync column to discord_groups table if it doesn't exist
+    info!("Checking for role_sync column in discord_groups table...");
+    let role_sync_exists = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 
+            FROM information_schema.columns 
+            WHERE table_name = 'discord_groups' AND column_name = 'role_sync'
+        );
+        "#,
+    )
+    .fetch_one(pool)
+    .await?;
+    
+    if !role_sync_exists {
+        info!("Adding role_sync column to discord_groups table...");
+        sqlx::query(
+            r#"
+            ALTER TABLE discord_groups
+            ADD COLUMN role_sync BOOLEAN NOT NULL DEFAULT FALSE;
+            "#,
+        )
+        .execute(pool)
+        .await?;
+        info!("role_sync column added successfully.");
+    } else {
+        info!("role_sync column already exists.");
+    }
+    
     // Create discord_servers table for server settings
     sqlx::query(
         r#"
@@ -176,6 +292,142 @@ pub async fn initialize_database(pool: &Pool<Postgres>) -> Result<()> {
     .execute(pool)
     .await?;
 
+    // Add required column to discord_groups table if it doesn't exist
+    info!("Checking for required column in discord_groups table...");
+    let group_required_exists = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS (
+            SELECT 1
+            FROM information_schema.columns
+            WHERE table_name = 'discord_groups' AND column_name = 'required'
+        );
+        "#,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    if !group_required_exists {
+        info!("Adding required column to discord_groups table...");
+        sqlx::query(
+            r#"
+            ALTER TABLE discord_groups
+            ADD COLUMN required BOOLEAN NOT NULL DEFAULT TRUE;
+            "#,
+        )
+        .execute(pool)
+        .await?;
+        info!("required column added successfully.");
+    } else {
+        info!("required column already exists.");
+    }
+
+    // Add required column to group_members table if it doesn't exist
+    info!("Checking for required column in group_members table...");
+    let member_required_exists = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS (
+            SELECT 1
+            FROM information_schema.columns
+            WHERE table_name = 'group_members' AND column_name = 'required'
+        );
+        "#,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    if !member_required_exists {
+        info!("Adding required column to group_members table...");
+        sqlx::query(
+            r#"
+            ALTER TABLE group_members
+            ADD COLUMN required BOOLEAN NOT NULL DEFAULT FALSE;
+            "#,
+        )
+        .execute(pool)
+        .await?;
+        info!("required column added successfully.");
+    } else {
+        info!("required column already exists.");
+    }
+
+    // Add weight column to group_members table if it doesn't exist
+    info!("Checking for weight column in group_members table...");
+    let weight_exists = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS (
+            SELECT 1
+            FROM information_schema.columns
+            WHERE table_name = 'group_members' AND column_name = 'weight'
+        );
+        "#,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    if !weight_exists {
+        info!("Adding weight column to group_members table...");
+        sqlx::query(
+            r#"
+            ALTER TABLE group_members
+            ADD COLUMN weight DOUBLE PRECISION NOT NULL DEFAULT 1;
+            "#,
+        )
+        .execute(pool)
+        .await?;
+        info!("weight column added successfully.");
+    } else {
+        info!("weight column already exists.");
+    }
+
+    // Create meetings table for polls that were finalized into a meeting
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS meetings (
+            id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+            server_id VARCHAR(255) NOT NULL,
+            channel_id VARCHAR(255) NOT NULL,
+            organizer_id VARCHAR(255) NOT NULL,
+            start_time TIMESTAMP WITH TIME ZONE NOT NULL,
+            end_time TIMESTAMP WITH TIME ZONE NOT NULL,
+            timezone VARCHAR(100) NOT NULL DEFAULT 'UTC',
+            status VARCHAR(20) NOT NULL DEFAULT 'scheduled',
+            min_per_group INTEGER NOT NULL DEFAULT 1,
+            slot_duration INTEGER NOT NULL DEFAULT 120,
+            rescheduled_from UUID NULL REFERENCES meetings(id),
+            created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+            CONSTRAINT valid_meeting_time_range CHECK (end_time > start_time)
+        );
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Create meeting_groups table (groups a meeting was matched for)
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS meeting_groups (
+            meeting_id UUID NOT NULL REFERENCES meetings(id) ON DELETE CASCADE,
+            group_id UUID NOT NULL REFERENCES discord_groups(id) ON DELETE CASCADE,
+            PRIMARY KEY (meeting_id, group_id)
+        );
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Create meeting_attendees table
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS meeting_attendees (
+            meeting_id UUID NOT NULL REFERENCES meetings(id) ON DELETE CASCADE,
+            discord_id VARCHAR(255) NOT NULL,
+            PRIMARY KEY (meeting_id, discord_id)
+        );
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
     // Create indexes - one at a time
     sqlx::query(
         "CREATE INDEX IF NOT EXISTS idx_time_slots_schedule_id ON time_slots(schedule_id)"
@@ -225,6 +477,137 @@ pub async fn initialize_database(pool: &Pool<Postgres>) -> Result<()> {
     .execute(pool)
     .await?;
 
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_meetings_server_id ON meetings(server_id)"
+    )
+    .execute(pool)
+    .await?;
+
+    info!("Database schema initialized successfully.");
+    Ok(())
+}
+/// The SQLite schema, one migration per entry
+///
+/// Each entry runs once, in order, and the count applied so far is kept in
+/// the database's `user_version`. Append new entries rather than editing old
+/// ones, mirroring every change made to `initialize_database` above.
+const SQLITE_MIGRATIONS: &[&str] = &[
+    // 1: the schema as of the Postgres migrations above
+    r#"
+    CREATE TABLE schedules (
+        id BLOB PRIMARY KEY,
+        name TEXT NOT NULL,
+        password_hash TEXT NULL,
+        timezone TEXT NOT NULL DEFAULT 'UTC',
+        created_at TEXT NOT NULL
+    );
+
+    CREATE TABLE time_slots (
+        id BLOB PRIMARY KEY,
+        schedule_id BLOB NOT NULL REFERENCES schedules(id),
+        start_time TEXT NOT NULL,
+        end_time TEXT NOT NULL,
+        is_recurring BOOLEAN NOT NULL DEFAULT FALSE,
+        is_unavailable BOOLEAN NOT NULL DEFAULT FALSE,
+        preference TEXT NOT NULL DEFAULT 'available',
+        created_at TEXT NOT NULL,
+        CONSTRAINT valid_time_range CHECK (end_time > start_time)
+    );
+
+    CREATE TABLE discord_users (
+        discord_id TEXT PRIMARY KEY,
+        schedule_id BLOB REFERENCES schedules(id),
+        timezone TEXT NULL,
+        created_at TEXT NOT NULL
+    );
+
+    CREATE TABLE discord_groups (
+        id BLOB PRIMARY KEY,
+        name TEXT NOT NULL,
+        server_id TEXT NOT NULL,
+        role_id TEXT,
+        role_sync BOOLEAN NOT NULL DEFAULT FALSE,
+        required BOOLEAN NOT NULL DEFAULT TRUE,
+        created_at TEXT NOT NULL
+    );
+
+    CREATE TABLE discord_servers (
+        server_id TEXT PRIMARY KEY,
+        timezone TEXT NOT NULL DEFAULT 'UTC',
+        created_at TEXT NOT NULL
+    );
+
+    CREATE TABLE group_members (
+        group_id BLOB NOT NULL REFERENCES discord_groups(id),
+        discord_id TEXT NOT NULL REFERENCES discord_users(discord_id),
+        required BOOLEAN NOT NULL DEFAULT FALSE,
+        weight REAL NOT NULL DEFAULT 1,
+        PRIMARY KEY (group_id, discord_id)
+    );
+
+    CREATE TABLE meetings (
+        id BLOB PRIMARY KEY,
+        server_id TEXT NOT NULL,
+        channel_id TEXT NOT NULL,
+        organizer_id TEXT NOT NULL,
+        start_time TEXT NOT NULL,
+        end_time TEXT NOT NULL,
+        timezone TEXT NOT NULL DEFAULT 'UTC',
+        status TEXT NOT NULL DEFAULT 'scheduled',
+        min_per_group INTEGER NOT NULL DEFAULT 1,
+        slot_duration INTEGER NOT NULL DEFAULT 120,
+        rescheduled_from BLOB NULL REFERENCES meetings(id),
+        created_at TEXT NOT NULL,
+        CONSTRAINT valid_meeting_time_range CHECK (end_time > start_time)
+    );
+
+    CREATE TABLE meeting_groups (
+        meeting_id BLOB NOT NULL REFERENCES meetings(id) ON DELETE CASCADE,
+        group_id BLOB NOT NULL REFERENCES discord_groups(id) ON DELETE CASCADE,
+        PRIMARY KEY (meeting_id, group_id)
+    );
+
+    CREATE TABLE meeting_attendees (
+        meeting_id BLOB NOT NULL REFERENCES meetings(id) ON DELETE CASCADE,
+        discord_id TEXT NOT NULL,
+        PRIMARY KEY (meeting_id, discord_id)
+    );
+
+    CREATE INDEX idx_time_slots_schedule_id ON time_slots(schedule_id);
+    CREATE INDEX idx_time_slots_start_time ON time_slots(start_time);
+    CREATE INDEX idx_time_slots_end_time ON time_slots(end_time);
+    CREATE INDEX idx_discord_users_schedule_id ON discord_users(schedule_id);
+    CREATE INDEX idx_group_members_group_id ON group_members(group_id);
+    CREATE INDEX idx_group_members_discord_id ON group_members(discord_id);
+    CREATE INDEX idx_discord_groups_server_id ON discord_groups(server_id);
+    CREATE INDEX idx_meetings_server_id ON meetings(server_id);
+    "#,
+];
+
+/// Bring an SQLite database up to the current schema
+pub async fn initialize_sqlite_database(pool: &Pool<Sqlite>) -> Result<()> {
+    info!("Initializing SQLite database schema...");
+
+    // Read to the end, so the statement doesn't hold a lock on its connection
+    let applied = sqlx::query_scalar::<_, i64>("PRAGMA user_version")
+        .fetch_all(pool)
+        .await?
+        .pop()
+        .unwrap_or_default();
+
+    for (version, migration) in SQLITE_MIGRATIONS.iter().enumerate().skip(applied as usize) {
+        let version = version + 1;
+        info!("Applying SQLite migration {}...", version);
+
+        let mut tx = pool.begin().await?;
+        sqlx::raw_sql(migration).execute(&mut *tx).await?;
+        // PRAGMA doesn't take bound parameters
+        sqlx::query(&format!("PRAGMA user_version = {}", version))
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+    }
+
     info!("Database schema initialized successfully.");
     Ok(())
-}
\ No newline at end of file
+}
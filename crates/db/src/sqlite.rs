@@ -0,0 +1,787 @@
+//! # SQLite Backend
+//!
+//! `SqliteRepository` implements the repository traits on a single SQLite
+//! file, for deployments too small to warrant a Postgres server. The queries
+//! follow the Postgres ones in `crate::repositories`, with these differences
+//! in how values are stored:
+//!
+//! - UUIDs are 16-byte blobs
+//! - Timestamps are RFC 3339 text in UTC, which sorts chronologically
+//! - Booleans are integers
+//!
+//! The schema is created by `crate::schema::initialize_sqlite_database`.
+//!
+//! Single rows are fetched with `fetch_all` and picked out with `single` or
+//! `first`, rather than with sqlx's `fetch_one` and `fetch_optional`. Those
+//! stop stepping a statement after its first row, and an SQLite statement
+//! that isn't stepped to the end keeps its implicit transaction open: an
+//! `INSERT ... RETURNING` stays uncommitted and a `SELECT` keeps other
+//! connections from writing.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use eyre::{eyre, Result};
+use timesync_core::models::{
+    meeting::MeetingStatus,
+    schedule::{CreateTimeSlotRequest, SlotPreference},
+};
+use uuid::Uuid;
+
+use crate::{
+    models::{
+        DbDiscordGroup, DbDiscordServer, DbDiscordUser, DbGroupMember, DbMeeting, DbMeetingAttendee, DbSchedule,
+        DbTimeSlot, NewMeeting,
+    },
+    repositories::{DiscordRepository, MeetingRepository, ScheduleRepository, TimeSlotRepository},
+    SqlitePool,
+};
+
+const SCHEDULE_COLUMNS: &str = "id, name, password_hash, timezone, created_at";
+const TIME_SLOT_COLUMNS: &str =
+    "id, schedule_id, start_time, end_time, is_recurring, is_unavailable, preference, created_at";
+const DISCORD_USER_COLUMNS: &str = "discord_id, schedule_id, timezone, created_at";
+const DISCORD_GROUP_COLUMNS: &str = "id, name, server_id, role_id, role_sync, required, created_at";
+const GROUP_MEMBER_COLUMNS: &str = "group_id, discord_id, required, weight";
+const MEETING_COLUMNS: &str = "id, server_id, channel_id, organizer_id, start_time, end_time, timezone, \
+                               status, min_per_group, slot_duration, rescheduled_from, created_at";
+
+/// The only row of a result, as `fetch_one` would return it
+fn single<T>(rows: Vec<T>) -> sqlx::Result<T> {
+    rows.into_iter().next().ok_or(sqlx::Error::RowNotFound)
+}
+
+/// The first row of a result, as `fetch_optional` would return it
+fn first<T>(rows: Vec<T>) -> Option<T> {
+    rows.into_iter().next()
+}
+
+/// Repositories backed by an SQLite database
+#[derive(Debug, Clone)]
+pub struct SqliteRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// The pool queries run on
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+}
+
+#[async_trait]
+impl ScheduleRepository for SqliteRepository {
+    async fn create_schedule(
+        &self,
+        name: &str,
+        password_hash: Option<&str>,
+        timezone: &str,
+    ) -> Result<DbSchedule> {
+        let schedule = sqlx::query_as::<_, DbSchedule>(&format!(
+            r#"
+            INSERT INTO schedules (id, name, password_hash, timezone, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING {SCHEDULE_COLUMNS}
+            "#
+        ))
+        .bind(Uuid::new_v4())
+        .bind(name)
+        .bind(password_hash)
+        .bind(timezone)
+        .bind(Utc::now())
+        .fetch_all(&self.pool)
+        .await
+        .and_then(single)?;
+
+        Ok(schedule)
+    }
+
+    async fn get_schedule_by_id(&self, id: Uuid) -> Result<Option<DbSchedule>> {
+        let schedule = sqlx::query_as::<_, DbSchedule>(&format!(
+            "SELECT {SCHEDULE_COLUMNS} FROM schedules WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await
+        .map(first)?;
+
+        Ok(schedule)
+    }
+
+    async fn update_schedule(
+        &self,
+        id: Uuid,
+        name: Option<&str>,
+        timezone: Option<&str>,
+    ) -> Result<DbSchedule> {
+        sqlx::query_as::<_, DbSchedule>(&format!(
+            r#"
+            UPDATE schedules
+            SET name = COALESCE($2, name), timezone = COALESCE($3, timezone)
+            WHERE id = $1
+            RETURNING {SCHEDULE_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(name)
+        .bind(timezone)
+        .fetch_all(&self.pool)
+        .await
+        .map(first)?
+        .ok_or_else(|| eyre!("Schedule not found"))
+    }
+}
+
+#[async_trait]
+impl TimeSlotRepository for SqliteRepository {
+    async fn create_time_slot(
+        &self,
+        schedule_id: Uuid,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+        is_recurring: bool,
+        is_unavailable: bool,
+        preference: SlotPreference,
+    ) -> Result<DbTimeSlot> {
+        let time_slot = sqlx::query_as::<_, DbTimeSlot>(&format!(
+            r#"
+            INSERT INTO time_slots ({TIME_SLOT_COLUMNS})
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING {TIME_SLOT_COLUMNS}
+            "#
+        ))
+        .bind(Uuid::new_v4())
+        .bind(schedule_id)
+        .bind(start_time)
+        .bind(end_time)
+        .bind(is_recurring)
+        .bind(is_unavailable)
+        .bind(preference.as_str())
+        .bind(Utc::now())
+        .fetch_all(&self.pool)
+        .await
+        .and_then(single)?;
+
+        Ok(time_slot)
+    }
+
+    async fn get_time_slots_by_schedule_id(&self, schedule_id: Uuid) -> Result<Vec<DbTimeSlot>> {
+        let time_slots = sqlx::query_as::<_, DbTimeSlot>(&format!(
+            "SELECT {TIME_SLOT_COLUMNS} FROM time_slots WHERE schedule_id = $1 ORDER BY start_time ASC"
+        ))
+        .bind(schedule_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(time_slots)
+    }
+
+    async fn delete_time_slots_by_schedule_id(&self, schedule_id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM time_slots WHERE schedule_id = $1")
+            .bind(schedule_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn replace_time_slots(
+        &self,
+        schedule_id: Uuid,
+        slots: &[CreateTimeSlotRequest],
+    ) -> Result<Vec<DbTimeSlot>> {
+        let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM time_slots WHERE schedule_id = $1")
+            .bind(schedule_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let mut time_slots = Vec::with_capacity(slots.len());
+        for slot in slots {
+            let time_slot = sqlx::query_as::<_, DbTimeSlot>(&format!(
+                r#"
+                INSERT INTO time_slots ({TIME_SLOT_COLUMNS})
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                RETURNING {TIME_SLOT_COLUMNS}
+                "#
+            ))
+            .bind(Uuid::new_v4())
+            .bind(schedule_id)
+            .bind(slot.start)
+            .bind(slot.end)
+            .bind(slot.is_recurring)
+            .bind(slot.is_unavailable)
+            .bind(slot.preference.as_str())
+            .bind(now)
+            .fetch_all(&mut *tx)
+            .await
+            .and_then(single)?;
+
+            time_slots.push(time_slot);
+        }
+
+        tx.commit().await?;
+
+        Ok(time_slots)
+    }
+}
+
+#[async_trait]
+impl DiscordRepository for SqliteRepository {
+    async fn create_discord_user(&self, discord_id: &str, schedule_id: Option<Uuid>) -> Result<DbDiscordUser> {
+        let discord_user = sqlx::query_as::<_, DbDiscordUser>(&format!(
+            r#"
+            INSERT INTO discord_users (discord_id, schedule_id, created_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (discord_id)
+            DO UPDATE SET schedule_id = excluded.schedule_id
+            RETURNING {DISCORD_USER_COLUMNS}
+            "#
+        ))
+        .bind(discord_id)
+        .bind(schedule_id)
+        .bind(Utc::now())
+        .fetch_all(&self.pool)
+        .await
+        .and_then(single)?;
+
+        Ok(discord_user)
+    }
+
+    async fn get_discord_user_by_id(&self, discord_id: &str) -> Result<Option<DbDiscordUser>> {
+        let discord_user = sqlx::query_as::<_, DbDiscordUser>(&format!(
+            "SELECT {DISCORD_USER_COLUMNS} FROM discord_users WHERE discord_id = $1"
+        ))
+        .bind(discord_id)
+        .fetch_all(&self.pool)
+        .await
+        .map(first)?;
+
+        Ok(discord_user)
+    }
+
+    async fn set_discord_user_timezone(&self, discord_id: &str, timezone: &str) -> Result<DbDiscordUser> {
+        let discord_user = sqlx::query_as::<_, DbDiscordUser>(&format!(
+            r#"
+            INSERT INTO discord_users (discord_id, schedule_id, timezone, created_at)
+            VALUES ($1, NULL, $2, $3)
+            ON CONFLICT (discord_id)
+            DO UPDATE SET timezone = excluded.timezone
+            RETURNING {DISCORD_USER_COLUMNS}
+            "#
+        ))
+        .bind(discord_id)
+        .bind(timezone)
+        .bind(Utc::now())
+        .fetch_all(&self.pool)
+        .await
+        .and_then(single)?;
+
+        Ok(discord_user)
+    }
+
+    async fn get_discord_server(&self, server_id: &str) -> Result<Option<DbDiscordServer>> {
+        let server = sqlx::query_as::<_, DbDiscordServer>(
+            "SELECT server_id, timezone, created_at FROM discord_servers WHERE server_id = $1",
+        )
+        .bind(server_id)
+        .fetch_all(&self.pool)
+        .await
+        .map(first)?;
+
+        Ok(server)
+    }
+
+    async fn set_discord_server_timezone(&self, server_id: &str, timezone: &str) -> Result<DbDiscordServer> {
+        let server = sqlx::query_as::<_, DbDiscordServer>(
+            r#"
+            INSERT INTO discord_servers (server_id, timezone, created_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (server_id)
+            DO UPDATE SET timezone = excluded.timezone
+            RETURNING server_id, timezone, created_at
+            "#,
+        )
+        .bind(server_id)
+        .bind(timezone)
+        .bind(Utc::now())
+        .fetch_all(&self.pool)
+        .await
+        .and_then(single)?;
+
+        Ok(server)
+    }
+
+    async fn create_discord_group(
+        &self,
+        name: &str,
+        server_id: &str,
+        role_id: Option<&str>,
+    ) -> Result<DbDiscordGroup> {
+        let discord_group = sqlx::query_as::<_, DbDiscordGroup>(&format!(
+            r#"
+            INSERT INTO discord_groups (id, name, server_id, role_id, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING {DISCORD_GROUP_COLUMNS}
+            "#
+        ))
+        .bind(Uuid::new_v4())
+        .bind(name)
+        .bind(server_id)
+        .bind(role_id)
+        .bind(Utc::now())
+        .fetch_all(&self.pool)
+        .await
+        .and_then(single)?;
+
+        Ok(discord_group)
+    }
+
+    async fn get_discord_group_by_id(&self, id: Uuid) -> Result<Option<DbDiscordGroup>> {
+        let discord_group = sqlx::query_as::<_, DbDiscordGroup>(&format!(
+            "SELECT {DISCORD_GROUP_COLUMNS} FROM discord_groups WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await
+        .map(first)?;
+
+        Ok(discord_group)
+    }
+
+    async fn get_discord_group_by_name(&self, server_id: &str, name: &str) -> Result<Option<DbDiscordGroup>> {
+        let discord_group = sqlx::query_as::<_, DbDiscordGroup>(&format!(
+            "SELECT {DISCORD_GROUP_COLUMNS} FROM discord_groups WHERE server_id = $1 AND name = $2"
+        ))
+        .bind(server_id)
+        .bind(name)
+        .fetch_all(&self.pool)
+        .await
+        .map(first)?;
+
+        Ok(discord_group)
+    }
+
+    async fn get_discord_groups_by_server(&self, server_id: &str) -> Result<Vec<DbDiscordGroup>> {
+        let groups = sqlx::query_as::<_, DbDiscordGroup>(&format!(
+            "SELECT {DISCORD_GROUP_COLUMNS} FROM discord_groups WHERE server_id = $1 ORDER BY name"
+        ))
+        .bind(server_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(groups)
+    }
+
+    async fn list_discord_groups_by_server(
+        &self,
+        server_id: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<DbDiscordGroup>> {
+        let groups = sqlx::query_as::<_, DbDiscordGroup>(&format!(
+            r#"
+            SELECT {DISCORD_GROUP_COLUMNS}
+            FROM discord_groups
+            WHERE server_id = $1
+            ORDER BY name, id
+            LIMIT $2 OFFSET $3
+            "#
+        ))
+        .bind(server_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(groups)
+    }
+
+    async fn count_discord_groups_by_server(&self, server_id: &str) -> Result<i64> {
+        let count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM discord_groups WHERE server_id = $1")
+            .bind(server_id)
+            .fetch_all(&self.pool)
+            .await
+            .and_then(single)?;
+
+        Ok(count)
+    }
+
+    async fn update_discord_group(
+        &self,
+        id: Uuid,
+        name: Option<&str>,
+        role_id: Option<&str>,
+    ) -> Result<DbDiscordGroup> {
+        sqlx::query_as::<_, DbDiscordGroup>(&format!(
+            r#"
+            UPDATE discord_groups
+            SET name = COALESCE($2, name), role_id = COALESCE($3, role_id)
+            WHERE id = $1
+            RETURNING {DISCORD_GROUP_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(name)
+        .bind(role_id)
+        .fetch_all(&self.pool)
+        .await
+        .map(first)?
+        .ok_or_else(|| eyre!("Discord group not found"))
+    }
+
+    async fn delete_discord_group(&self, id: Uuid) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM group_members WHERE group_id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        let result = sqlx::query("DELETE FROM discord_groups WHERE id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn set_group_required(&self, id: Uuid, required: bool) -> Result<DbDiscordGroup> {
+        let updated_group = sqlx::query_as::<_, DbDiscordGroup>(&format!(
+            "UPDATE discord_groups SET required = $2 WHERE id = $1 RETURNING {DISCORD_GROUP_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(required)
+        .fetch_all(&self.pool)
+        .await
+        .and_then(single)?;
+
+        Ok(updated_group)
+    }
+
+    async fn update_group_role_id(&self, id: Uuid, role_id: &str) -> Result<DbDiscordGroup> {
+        let updated_group = sqlx::query_as::<_, DbDiscordGroup>(&format!(
+            "UPDATE discord_groups SET role_id = $2 WHERE id = $1 RETURNING {DISCORD_GROUP_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(role_id)
+        .fetch_all(&self.pool)
+        .await
+        .and_then(single)?;
+
+        Ok(updated_group)
+    }
+
+    async fn add_member_to_group(&self, group_id: Uuid, discord_id: &str) -> Result<DbGroupMember> {
+        let group_member = sqlx::query_as::<_, DbGroupMember>(&format!(
+            r#"
+            INSERT INTO group_members (group_id, discord_id)
+            VALUES ($1, $2)
+            ON CONFLICT (group_id, discord_id) DO NOTHING
+            RETURNING {GROUP_MEMBER_COLUMNS}
+            "#
+        ))
+        .bind(group_id)
+        .bind(discord_id)
+        .fetch_all(&self.pool)
+        .await
+        .and_then(single)?;
+
+        Ok(group_member)
+    }
+
+    async fn ensure_group_member(&self, group_id: Uuid, discord_id: &str) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO discord_users (discord_id, schedule_id, created_at)
+            VALUES ($1, NULL, $2)
+            ON CONFLICT (discord_id) DO NOTHING
+            "#,
+        )
+        .bind(discord_id)
+        .bind(Utc::now())
+        .execute(&mut *tx)
+        .await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO group_members (group_id, discord_id)
+            VALUES ($1, $2)
+            ON CONFLICT (group_id, discord_id) DO NOTHING
+            "#,
+        )
+        .bind(group_id)
+        .bind(discord_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn remove_member_from_group(&self, group_id: Uuid, discord_id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM group_members WHERE group_id = $1 AND discord_id = $2")
+            .bind(group_id)
+            .bind(discord_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_group_members(&self, group_id: Uuid) -> Result<Vec<DbGroupMember>> {
+        let members = sqlx::query_as::<_, DbGroupMember>(&format!(
+            "SELECT {GROUP_MEMBER_COLUMNS} FROM group_members WHERE group_id = $1"
+        ))
+        .bind(group_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(members)
+    }
+
+    async fn update_group_member(
+        &self,
+        group_id: Uuid,
+        discord_id: &str,
+        required: Option<bool>,
+        weight: Option<f64>,
+    ) -> Result<Option<DbGroupMember>> {
+        let member = sqlx::query_as::<_, DbGroupMember>(&format!(
+            r#"
+            UPDATE group_members
+            SET required = COALESCE($3, required), weight = COALESCE($4, weight)
+            WHERE group_id = $1 AND discord_id = $2
+            RETURNING {GROUP_MEMBER_COLUMNS}
+            "#
+        ))
+        .bind(group_id)
+        .bind(discord_id)
+        .bind(required)
+        .bind(weight)
+        .fetch_all(&self.pool)
+        .await
+        .map(first)?;
+
+        Ok(member)
+    }
+
+    async fn get_user_groups(&self, discord_id: &str) -> Result<Vec<DbDiscordGroup>> {
+        let groups = sqlx::query_as::<_, DbDiscordGroup>(
+            r#"
+            SELECT g.id, g.name, g.server_id, g.role_id, g.role_sync, g.required, g.created_at
+            FROM discord_groups g
+            JOIN group_members gm ON g.id = gm.group_id
+            WHERE gm.discord_id = $1
+            "#,
+        )
+        .bind(discord_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(groups)
+    }
+
+    async fn bind_group_to_role(&self, id: Uuid, role_id: &str) -> Result<DbDiscordGroup> {
+        let updated_group = sqlx::query_as::<_, DbDiscordGroup>(&format!(
+            r#"
+            UPDATE discord_groups
+            SET role_id = $2, role_sync = TRUE
+            WHERE id = $1
+            RETURNING {DISCORD_GROUP_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(role_id)
+        .fetch_all(&self.pool)
+        .await
+        .and_then(single)?;
+
+        Ok(updated_group)
+    }
+
+    async fn unbind_group_from_role(&self, id: Uuid) -> Result<DbDiscordGroup> {
+        let updated_group = sqlx::query_as::<_, DbDiscordGroup>(&format!(
+            "UPDATE discord_groups SET role_sync = FALSE WHERE id = $1 RETURNING {DISCORD_GROUP_COLUMNS}"
+        ))
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await
+        .and_then(single)?;
+
+        Ok(updated_group)
+    }
+
+    async fn get_role_synced_groups(&self, server_id: Option<&str>) -> Result<Vec<DbDiscordGroup>> {
+        let groups = sqlx::query_as::<_, DbDiscordGroup>(&format!(
+            r#"
+            SELECT {DISCORD_GROUP_COLUMNS}
+            FROM discord_groups
+            WHERE role_sync = TRUE AND role_id IS NOT NULL
+              AND ($1 IS NULL OR server_id = $1)
+            ORDER BY server_id, name
+            "#
+        ))
+        .bind(server_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(groups)
+    }
+}
+
+#[async_trait]
+impl MeetingRepository for SqliteRepository {
+    async fn create_meeting(&self, meeting: &NewMeeting) -> Result<DbMeeting> {
+        let id = Uuid::new_v4();
+        let mut tx = self.pool.begin().await?;
+
+        let db_meeting = sqlx::query_as::<_, DbMeeting>(&format!(
+            r#"
+            INSERT INTO meetings ({MEETING_COLUMNS})
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            RETURNING {MEETING_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(&meeting.server_id)
+        .bind(&meeting.channel_id)
+        .bind(&meeting.organizer_id)
+        .bind(meeting.start_time)
+        .bind(meeting.end_time)
+        .bind(&meeting.timezone)
+        .bind(MeetingStatus::Scheduled.as_str())
+        .bind(meeting.min_per_group)
+        .bind(meeting.slot_duration)
+        .bind(meeting.rescheduled_from)
+        .bind(Utc::now())
+        .fetch_all(&mut *tx)
+        .await
+        .and_then(single)?;
+
+        for group_id in &meeting.group_ids {
+            sqlx::query(
+                r#"
+                INSERT INTO meeting_groups (meeting_id, group_id)
+                VALUES ($1, $2)
+                ON CONFLICT (meeting_id, group_id) DO NOTHING
+                "#,
+            )
+            .bind(id)
+            .bind(group_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for discord_id in &meeting.attendee_ids {
+            sqlx::query(
+                r#"
+                INSERT INTO meeting_attendees (meeting_id, discord_id)
+                VALUES ($1, $2)
+                ON CONFLICT (meeting_id, discord_id) DO NOTHING
+                "#,
+            )
+            .bind(id)
+            .bind(discord_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        if let Some(previous_id) = meeting.rescheduled_from {
+            sqlx::query("UPDATE meetings SET status = $2 WHERE id = $1")
+                .bind(previous_id)
+                .bind(MeetingStatus::Rescheduled.as_str())
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(db_meeting)
+    }
+
+    async fn get_meeting_by_id(&self, id: Uuid) -> Result<Option<DbMeeting>> {
+        let meeting = sqlx::query_as::<_, DbMeeting>(&format!(
+            "SELECT {MEETING_COLUMNS} FROM meetings WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await
+        .map(first)?;
+
+        Ok(meeting)
+    }
+
+    async fn get_upcoming_meetings_by_server(
+        &self,
+        server_id: &str,
+        status: MeetingStatus,
+    ) -> Result<Vec<DbMeeting>> {
+        let meetings = sqlx::query_as::<_, DbMeeting>(&format!(
+            r#"
+            SELECT {MEETING_COLUMNS}
+            FROM meetings
+            WHERE server_id = $1 AND status = $2 AND end_time > $3
+            ORDER BY start_time
+            "#
+        ))
+        .bind(server_id)
+        .bind(status.as_str())
+        .bind(Utc::now())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(meetings)
+    }
+
+    async fn update_meeting_status(&self, id: Uuid, status: MeetingStatus) -> Result<DbMeeting> {
+        let meeting = sqlx::query_as::<_, DbMeeting>(&format!(
+            "UPDATE meetings SET status = $2 WHERE id = $1 RETURNING {MEETING_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(status.as_str())
+        .fetch_all(&self.pool)
+        .await
+        .and_then(single)?;
+
+        Ok(meeting)
+    }
+
+    async fn get_meeting_groups(&self, meeting_id: Uuid) -> Result<Vec<DbDiscordGroup>> {
+        let groups = sqlx::query_as::<_, DbDiscordGroup>(
+            r#"
+            SELECT g.id, g.name, g.server_id, g.role_id, g.role_sync, g.required, g.created_at
+            FROM discord_groups g
+            JOIN meeting_groups mg ON g.id = mg.group_id
+            WHERE mg.meeting_id = $1
+            ORDER BY g.name
+            "#,
+        )
+        .bind(meeting_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(groups)
+    }
+
+    async fn get_meeting_attendees(&self, meeting_id: Uuid) -> Result<Vec<DbMeetingAttendee>> {
+        let attendees = sqlx::query_as::<_, DbMeetingAttendee>(
+            "SELECT meeting_id, discord_id FROM meeting_attendees WHERE meeting_id = $1",
+        )
+        .bind(meeting_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(attendees)
+    }
+}
@@ -0,0 +1,105 @@
+//! # Test Databases
+//!
+//! Integration tests that need real SQL get a Postgres database of their own
+//! from `TestDatabase`, so they can run in parallel without seeing each
+//! other's rows. The server comes from `TEST_DATABASE_URL`; tests skip
+//! themselves when it isn't set.
+
+use std::str::FromStr;
+
+use eyre::Result;
+use sqlx::{
+    postgres::{PgConnectOptions, PgPoolOptions},
+    ConnectOptions, Connection, PgConnection,
+};
+use uuid::Uuid;
+
+use crate::{schema, DbPool};
+
+/// Names the Postgres server test databases are created on
+pub const TEST_DATABASE_URL: &str = "TEST_DATABASE_URL";
+
+/// The server from `TEST_DATABASE_URL`, if tests against Postgres should run
+pub fn server_url() -> Option<String> {
+    std::env::var(TEST_DATABASE_URL).ok().filter(|url| !url.is_empty())
+}
+
+/// A migrated database that exists for the lifetime of one test
+///
+/// The database is dropped along with this value, including when the test
+/// panics.
+pub struct TestDatabase {
+    pool: DbPool,
+    name: String,
+    server: PgConnectOptions,
+}
+
+impl TestDatabase {
+    /// Create and migrate a new database on the server at `server_url`
+    ///
+    /// The URL's own database is only used to connect; the user needs the
+    /// `CREATEDB` privilege.
+    pub async fn create(server_url: &str) -> Result<Self> {
+        let server = PgConnectOptions::from_str(server_url)?.disable_statement_logging();
+        let name = format!("timesync_test_{}", Uuid::new_v4().simple());
+
+        let mut conn = PgConnection::connect_with(&server).await?;
+        sqlx::query(&format!(r#"CREATE DATABASE "{}""#, name))
+            .execute(&mut conn)
+            .await?;
+        conn.close().await?;
+
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect_with(server.clone().database(&name))
+            .await?;
+        let database = Self { pool, name, server };
+        schema::initialize_database(&database.pool).await?;
+
+        Ok(database)
+    }
+
+    /// Create a database on the `TEST_DATABASE_URL` server, or `None` if it
+    /// isn't set
+    pub async fn from_env() -> Result<Option<Self>> {
+        match server_url() {
+            Some(url) => Ok(Some(Self::create(&url).await?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn pool(&self) -> &DbPool {
+        &self.pool
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Drop for TestDatabase {
+    fn drop(&mut self) {
+        // The test's runtime may be the one blocked here, so the drop runs on
+        // a runtime of its own. `FORCE` closes the pool's connections, which
+        // can't be closed from this thread.
+        let server = self.server.clone();
+        let statement = format!(r#"DROP DATABASE IF EXISTS "{}" WITH (FORCE)"#, self.name);
+
+        let dropped = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+            runtime.block_on(async {
+                let mut conn = PgConnection::connect_with(&server).await?;
+                sqlx::query(&statement).execute(&mut conn).await?;
+                conn.close().await?;
+                Ok::<_, eyre::Report>(())
+            })
+        })
+        .join();
+
+        match dropped {
+            Ok(Ok(())) => {},
+            Ok(Err(e)) => eprintln!("Failed to drop test database {}: {}", self.name, e),
+            Err(_) => eprintln!("Failed to drop test database {}", self.name),
+        }
+    }
+}
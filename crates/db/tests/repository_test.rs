@@ -0,0 +1,602 @@
+//! The same repository tests, run against every backend
+//!
+//! The in-memory and SQLite backends always run. Postgres runs when
+//! `TEST_DATABASE_URL` points at a server the tests may create databases on,
+//! and is skipped otherwise. Each test gets a database of its own.
+
+use argon2::{
+    password_hash::{rand_core::OsRng, SaltString},
+    Argon2, PasswordHasher,
+};
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use timesync_core::models::{
+    meeting::MeetingStatus,
+    schedule::{CreateTimeSlotRequest, SlotPreference},
+};
+use timesync_db::{
+    memory::InMemoryRepository,
+    models::NewMeeting,
+    postgres::PgRepository,
+    repositories::{DiscordRepository, MeetingRepository, ScheduleRepository, TimeSlotRepository},
+    sqlite::SqliteRepository,
+    testing::TestDatabase,
+    Database,
+};
+use uuid::Uuid;
+
+/// Every repository trait, as one backend implements them
+trait Repository: ScheduleRepository + TimeSlotRepository + DiscordRepository + MeetingRepository {}
+
+impl<R> Repository for R where R: ScheduleRepository + TimeSlotRepository + DiscordRepository + MeetingRepository {}
+
+async fn memory() -> Option<InMemoryRepository> {
+    Some(InMemoryRepository::new())
+}
+
+/// A fresh database file, deleted along with the returned directory
+async fn sqlite() -> Option<(SqliteRepository, tempfile::TempDir)> {
+    let dir = tempfile::tempdir().unwrap();
+    let url = format!("sqlite://{}", dir.path().join("timesync.db").display());
+
+    let Database::Sqlite(pool) = Database::connect(&url).await.unwrap() else {
+        panic!("Expected an SQLite database for {}", url);
+    };
+    Database::Sqlite(pool.clone()).initialize().await.unwrap();
+
+    Some((SqliteRepository::new(pool), dir))
+}
+
+/// A fresh database on the test server, dropped along with the `TestDatabase`
+async fn postgres() -> Option<(PgRepository, TestDatabase)> {
+    let database = TestDatabase::from_env().await.unwrap()?;
+
+    Some((PgRepository::new(database.pool().clone()), database))
+}
+
+/// Run each test in `suite` against a backend
+///
+/// `$backend` returns `None` when the backend isn't available, or a value
+/// whose first field is the repository and the rest is kept alive for the test.
+macro_rules! backend_tests {
+    ($backend:ident: $($test:ident),* $(,)?) => {
+        mod $backend {
+            $(
+                #[tokio::test]
+                async fn $test() {
+                    let Some(backend) = super::$backend().await else {
+                        eprintln!("Skipping {} tests, backend not configured", stringify!($backend));
+                        return;
+                    };
+                    super::suite::$test(super::Backend::repository(&backend)).await;
+                }
+            )*
+        }
+    };
+}
+
+/// A backend as returned by the constructors above
+trait Backend {
+    type Repository: Repository;
+
+    fn repository(&self) -> &Self::Repository;
+}
+
+impl Backend for InMemoryRepository {
+    type Repository = Self;
+
+    fn repository(&self) -> &Self {
+        self
+    }
+}
+
+impl Backend for (PgRepository, TestDatabase) {
+    type Repository = PgRepository;
+
+    fn repository(&self) -> &PgRepository {
+        &self.0
+    }
+}
+
+impl Backend for (SqliteRepository, tempfile::TempDir) {
+    type Repository = SqliteRepository;
+
+    fn repository(&self) -> &SqliteRepository {
+        &self.0
+    }
+}
+
+macro_rules! all_backends {
+    ($($test:ident),* $(,)?) => {
+        backend_tests!(memory: $($test),*);
+        backend_tests!(sqlite: $($test),*);
+        backend_tests!(postgres: $($test),*);
+    };
+}
+
+all_backends!(
+    schedules,
+    schedule_passwords,
+    time_slots,
+    replace_time_slots,
+    discord_users,
+    discord_servers,
+    discord_groups,
+    group_pages,
+    group_members,
+    delete_group,
+    role_sync,
+    meetings,
+    reschedule_meeting,
+);
+
+mod suite {
+    use super::*;
+
+    /// Tomorrow at the given hour, in whole seconds so every backend stores it exactly
+    fn at(hour: i64) -> DateTime<Utc> {
+        Utc::now().duration_trunc(Duration::days(1)).unwrap() + Duration::days(1) + Duration::hours(hour)
+    }
+
+    /// An ID no other test uses
+    fn unique(prefix: &str) -> String {
+        format!("{}-{}", prefix, Uuid::new_v4())
+    }
+
+    fn slot_request(start: i64, end: i64, preference: SlotPreference) -> CreateTimeSlotRequest {
+        CreateTimeSlotRequest {
+            start: at(start),
+            end: at(end),
+            is_recurring: false,
+            is_unavailable: false,
+            preference,
+        }
+    }
+
+    fn new_meeting(server_id: &str, start: i64, group_ids: Vec<Uuid>) -> NewMeeting {
+        NewMeeting {
+            server_id: server_id.to_string(),
+            channel_id: "channel1".to_string(),
+            organizer_id: "organizer1".to_string(),
+            start_time: at(start),
+            end_time: at(start + 2),
+            timezone: "Europe/Berlin".to_string(),
+            min_per_group: 1,
+            slot_duration: 120,
+            rescheduled_from: None,
+            group_ids,
+            attendee_ids: vec!["user1".to_string(), "user2".to_string()],
+        }
+    }
+
+    pub async fn schedules(repo: &impl Repository) {
+        let schedule = repo.create_schedule("Raid night", None, "Europe/Berlin").await.unwrap();
+        assert_eq!(schedule.name, "Raid night");
+        assert_eq!(schedule.timezone, "Europe/Berlin");
+        assert!(schedule.password_hash.is_none());
+
+        let found = repo.get_schedule_by_id(schedule.id).await.unwrap().unwrap();
+        assert_eq!(found.id, schedule.id);
+        assert_eq!(found.name, "Raid night");
+
+        // Values left out are kept
+        let renamed = repo.update_schedule(schedule.id, Some("Game night"), None).await.unwrap();
+        assert_eq!(renamed.name, "Game night");
+        assert_eq!(renamed.timezone, "Europe/Berlin");
+
+        let moved = repo.update_schedule(schedule.id, None, Some("UTC")).await.unwrap();
+        assert_eq!(moved.name, "Game night");
+        assert_eq!(moved.timezone, "UTC");
+
+        assert!(repo.get_schedule_by_id(Uuid::new_v4()).await.unwrap().is_none());
+        assert!(repo.update_schedule(Uuid::new_v4(), Some("Nothing"), None).await.is_err());
+    }
+
+    pub async fn schedule_passwords(repo: &impl Repository) {
+        let hash = Argon2::default()
+            .hash_password(b"hunter2", &SaltString::generate(&mut OsRng))
+            .unwrap()
+            .to_string();
+        let protected = repo.create_schedule("Protected", Some(&hash), "UTC").await.unwrap();
+        assert_eq!(protected.password_hash.as_deref(), Some(hash.as_str()));
+
+        assert!(repo.verify_password(protected.id, "hunter2").await.unwrap());
+        assert!(!repo.verify_password(protected.id, "hunter3").await.unwrap());
+
+        // Any password opens a schedule without one
+        let open = repo.create_schedule("Open", None, "UTC").await.unwrap();
+        assert!(repo.verify_password(open.id, "anything").await.unwrap());
+
+        assert!(repo.verify_password(Uuid::new_v4(), "hunter2").await.is_err());
+    }
+
+    pub async fn time_slots(repo: &impl Repository) {
+        let schedule = repo.create_schedule("Slots", None, "UTC").await.unwrap();
+
+        // Created out of order, returned earliest first
+        let late = repo
+            .create_time_slot(schedule.id, at(18), at(20), true, false, SlotPreference::IfNeedBe)
+            .await
+            .unwrap();
+        assert_eq!(late.start_time, at(18));
+        assert!(late.is_recurring);
+        assert_eq!(late.preference, "if_need_be");
+
+        repo.create_time_slot(schedule.id, at(9), at(10), false, true, SlotPreference::Available)
+            .await
+            .unwrap();
+
+        let slots = repo.get_time_slots_by_schedule_id(schedule.id).await.unwrap();
+        let times: Vec<_> = slots.iter().map(|slot| (slot.start_time, slot.end_time)).collect();
+        assert_eq!(times, [(at(9), at(10)), (at(18), at(20))]);
+        assert!(slots[0].is_unavailable);
+        assert!(!slots[1].is_unavailable);
+
+        // Slots need an existing schedule
+        assert!(repo
+            .create_time_slot(Uuid::new_v4(), at(9), at(10), false, false, SlotPreference::Available)
+            .await
+            .is_err());
+
+        repo.delete_time_slots_by_schedule_id(schedule.id).await.unwrap();
+        assert!(repo.get_time_slots_by_schedule_id(schedule.id).await.unwrap().is_empty());
+    }
+
+    pub async fn replace_time_slots(repo: &impl Repository) {
+        let schedule = repo.create_schedule("Replaced", None, "UTC").await.unwrap();
+        let other = repo.create_schedule("Untouched", None, "UTC").await.unwrap();
+        repo.create_time_slot(schedule.id, at(1), at(2), false, false, SlotPreference::Available)
+            .await
+            .unwrap();
+        repo.create_time_slot(other.id, at(1), at(2), false, false, SlotPreference::Available)
+            .await
+            .unwrap();
+
+        let replaced = repo
+            .replace_time_slots(
+                schedule.id,
+                &[
+                    slot_request(12, 14, SlotPreference::Available),
+                    slot_request(15, 16, SlotPreference::IfNeedBe),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(replaced.len(), 2);
+
+        let slots = repo.get_time_slots_by_schedule_id(schedule.id).await.unwrap();
+        let times: Vec<_> = slots.iter().map(|slot| (slot.start_time, slot.end_time)).collect();
+        assert_eq!(times, [(at(12), at(14)), (at(15), at(16))]);
+        assert_eq!(slots[1].preference, "if_need_be");
+
+        // Other schedules keep their slots
+        assert_eq!(repo.get_time_slots_by_schedule_id(other.id).await.unwrap().len(), 1);
+
+        // Replacing with nothing clears the schedule
+        repo.replace_time_slots(schedule.id, &[]).await.unwrap();
+        assert!(repo.get_time_slots_by_schedule_id(schedule.id).await.unwrap().is_empty());
+    }
+
+    pub async fn discord_users(repo: &impl Repository) {
+        let discord_id = unique("user");
+        let schedule = repo.create_schedule("Mine", None, "UTC").await.unwrap();
+
+        assert!(repo.get_discord_user_by_id(&discord_id).await.unwrap().is_none());
+
+        // Setting a timezone creates the user without a schedule
+        let user = repo.set_discord_user_timezone(&discord_id, "Asia/Tokyo").await.unwrap();
+        assert_eq!(user.schedule_id, None);
+        assert_eq!(user.timezone.as_deref(), Some("Asia/Tokyo"));
+
+        // Linking a schedule keeps the timezone
+        let user = repo.create_discord_user(&discord_id, Some(schedule.id)).await.unwrap();
+        assert_eq!(user.schedule_id, Some(schedule.id));
+        assert_eq!(user.timezone.as_deref(), Some("Asia/Tokyo"));
+
+        // And changing the timezone keeps the schedule
+        let user = repo.set_discord_user_timezone(&discord_id, "UTC").await.unwrap();
+        assert_eq!(user.schedule_id, Some(schedule.id));
+
+        let found = repo.get_discord_user_by_id(&discord_id).await.unwrap().unwrap();
+        assert_eq!(found.schedule_id, Some(schedule.id));
+        assert_eq!(found.timezone.as_deref(), Some("UTC"));
+
+        // Users can only link existing schedules
+        assert!(repo.create_discord_user(&unique("user"), Some(Uuid::new_v4())).await.is_err());
+    }
+
+    pub async fn discord_servers(repo: &impl Repository) {
+        let server_id = unique("server");
+        assert!(repo.get_discord_server(&server_id).await.unwrap().is_none());
+
+        let server = repo.set_discord_server_timezone(&server_id, "America/New_York").await.unwrap();
+        assert_eq!(server.timezone, "America/New_York");
+
+        repo.set_discord_server_timezone(&server_id, "UTC").await.unwrap();
+        let server = repo.get_discord_server(&server_id).await.unwrap().unwrap();
+        assert_eq!(server.server_id, server_id);
+        assert_eq!(server.timezone, "UTC");
+    }
+
+    pub async fn discord_groups(repo: &impl Repository) {
+        let server_id = unique("server");
+
+        let raid = repo.create_discord_group("Raid", &server_id, Some("role1")).await.unwrap();
+        assert_eq!(raid.role_id.as_deref(), Some("role1"));
+        assert!(!raid.role_sync);
+        assert!(raid.required);
+
+        repo.create_discord_group("Alpha", &server_id, None).await.unwrap();
+        repo.create_discord_group("Elsewhere", &unique("server"), None).await.unwrap();
+
+        let names: Vec<_> = repo
+            .get_discord_groups_by_server(&server_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|group| group.name)
+            .collect();
+        assert_eq!(names, ["Alpha", "Raid"]);
+        assert_eq!(repo.count_discord_groups_by_server(&server_id).await.unwrap(), 2);
+
+        let found = repo.get_discord_group_by_name(&server_id, "Raid").await.unwrap().unwrap();
+        assert_eq!(found.id, raid.id);
+        assert!(repo.get_discord_group_by_name(&server_id, "Nope").await.unwrap().is_none());
+
+        // Values left out are kept
+        let renamed = repo.update_discord_group(raid.id, Some("Raiders"), None).await.unwrap();
+        assert_eq!(renamed.name, "Raiders");
+        assert_eq!(renamed.role_id.as_deref(), Some("role1"));
+
+        let updated = repo.update_group_role_id(raid.id, "role2").await.unwrap();
+        assert_eq!(updated.role_id.as_deref(), Some("role2"));
+
+        let optional = repo.set_group_required(raid.id, false).await.unwrap();
+        assert!(!optional.required);
+
+        let found = repo.get_discord_group_by_id(raid.id).await.unwrap().unwrap();
+        assert_eq!(found.name, "Raiders");
+        assert!(!found.required);
+
+        assert!(repo.get_discord_group_by_id(Uuid::new_v4()).await.unwrap().is_none());
+        assert!(repo.update_discord_group(Uuid::new_v4(), Some("Nothing"), None).await.is_err());
+    }
+
+    pub async fn group_pages(repo: &impl Repository) {
+        let server_id = unique("server");
+        for name in ["Delta", "Alpha", "Charlie", "Bravo"] {
+            repo.create_discord_group(name, &server_id, None).await.unwrap();
+        }
+
+        let mut pages = Vec::new();
+        for offset in [0, 2, 4] {
+            let page: Vec<_> = repo
+                .list_discord_groups_by_server(&server_id, 2, offset)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|group| group.name)
+                .collect();
+            pages.push(page);
+        }
+
+        assert_eq!(pages, [vec!["Alpha", "Bravo"], vec!["Charlie", "Delta"], vec![]]);
+    }
+
+    pub async fn group_members(repo: &impl Repository) {
+        let server_id = unique("server");
+        let (user1, user2) = (unique("user"), unique("user"));
+        let group = repo.create_discord_group("Members", &server_id, None).await.unwrap();
+        let other = repo.create_discord_group("Others", &server_id, None).await.unwrap();
+
+        // Members must be known users
+        assert!(repo.add_member_to_group(group.id, &user1).await.is_err());
+
+        repo.create_discord_user(&user1, None).await.unwrap();
+        let member = repo.add_member_to_group(group.id, &user1).await.unwrap();
+        assert!(!member.required);
+        assert_eq!(member.weight, 1.0);
+
+        // Adding twice fails, ensuring twice doesn't
+        assert!(repo.add_member_to_group(group.id, &user1).await.is_err());
+        assert!(!repo.ensure_group_member(group.id, &user1).await.unwrap());
+
+        // Ensuring creates the user
+        assert!(repo.ensure_group_member(group.id, &user2).await.unwrap());
+        assert!(repo.get_discord_user_by_id(&user2).await.unwrap().is_some());
+        repo.ensure_group_member(other.id, &user2).await.unwrap();
+
+        // But not the group
+        assert!(repo.ensure_group_member(Uuid::new_v4(), &user2).await.is_err());
+
+        let mut members: Vec<_> = repo
+            .get_group_members(group.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|member| member.discord_id)
+            .collect();
+        members.sort();
+        let mut expected = vec![user1.clone(), user2.clone()];
+        expected.sort();
+        assert_eq!(members, expected);
+
+        let mut groups: Vec<_> = repo
+            .get_user_groups(&user2)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|group| group.name)
+            .collect();
+        groups.sort();
+        assert_eq!(groups, ["Members", "Others"]);
+
+        // Values left out are kept
+        let updated = repo.update_group_member(group.id, &user1, Some(true), None).await.unwrap().unwrap();
+        assert!(updated.required);
+        assert_eq!(updated.weight, 1.0);
+
+        let updated = repo.update_group_member(group.id, &user1, None, Some(2.5)).await.unwrap().unwrap();
+        assert!(updated.required);
+        assert_eq!(updated.weight, 2.5);
+
+        assert!(repo.update_group_member(group.id, "stranger", Some(true), None).await.unwrap().is_none());
+
+        repo.remove_member_from_group(group.id, &user1).await.unwrap();
+        let members = repo.get_group_members(group.id).await.unwrap();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].discord_id, user2);
+    }
+
+    pub async fn delete_group(repo: &impl Repository) {
+        let server_id = unique("server");
+        let group = repo.create_discord_group("Doomed", &server_id, None).await.unwrap();
+        let user = unique("user");
+        repo.ensure_group_member(group.id, &user).await.unwrap();
+        let meeting = repo.create_meeting(&new_meeting(&server_id, 10, vec![group.id])).await.unwrap();
+
+        assert!(repo.delete_discord_group(group.id).await.unwrap());
+        assert!(!repo.delete_discord_group(group.id).await.unwrap());
+
+        assert!(repo.get_discord_group_by_id(group.id).await.unwrap().is_none());
+        assert!(repo.get_group_members(group.id).await.unwrap().is_empty());
+        assert!(repo.get_user_groups(&user).await.unwrap().is_empty());
+
+        // The user and the meeting stay, without the group
+        assert!(repo.get_discord_user_by_id(&user).await.unwrap().is_some());
+        assert!(repo.get_meeting_by_id(meeting.id).await.unwrap().is_some());
+        assert!(repo.get_meeting_groups(meeting.id).await.unwrap().is_empty());
+    }
+
+    pub async fn role_sync(repo: &impl Repository) {
+        let (server1, server2) = (unique("server1"), unique("server2"));
+        let raid = repo.create_discord_group("Raid", &server1, None).await.unwrap();
+        let alpha = repo.create_discord_group("Alpha", &server1, None).await.unwrap();
+        let other = repo.create_discord_group("Other", &server2, None).await.unwrap();
+        repo.create_discord_group("Unsynced", &server1, Some("role9")).await.unwrap();
+
+        let bound = repo.bind_group_to_role(raid.id, "role1").await.unwrap();
+        assert!(bound.role_sync);
+        assert_eq!(bound.role_id.as_deref(), Some("role1"));
+        repo.bind_group_to_role(alpha.id, "role2").await.unwrap();
+        repo.bind_group_to_role(other.id, "role3").await.unwrap();
+
+        let synced: Vec<_> = repo
+            .get_role_synced_groups(Some(&server1))
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|group| group.name)
+            .collect();
+        assert_eq!(synced, ["Alpha", "Raid"]);
+
+        // Without a server, every server's groups, ordered by server
+        let synced: Vec<_> = repo
+            .get_role_synced_groups(None)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|group| group.server_id == server1 || group.server_id == server2)
+            .map(|group| group.id)
+            .collect();
+        assert_eq!(synced, [alpha.id, raid.id, other.id]);
+
+        // Unbinding keeps the role
+        let unbound = repo.unbind_group_from_role(raid.id).await.unwrap();
+        assert!(!unbound.role_sync);
+        assert_eq!(unbound.role_id.as_deref(), Some("role1"));
+
+        let synced = repo.get_role_synced_groups(Some(&server1)).await.unwrap();
+        assert_eq!(synced.len(), 1);
+        assert_eq!(synced[0].id, alpha.id);
+    }
+
+    pub async fn meetings(repo: &impl Repository) {
+        let server_id = unique("server");
+        let raid = repo.create_discord_group("Raid", &server_id, None).await.unwrap();
+        let alpha = repo.create_discord_group("Alpha", &server_id, None).await.unwrap();
+
+        let later = repo
+            .create_meeting(&new_meeting(&server_id, 20, vec![raid.id, alpha.id]))
+            .await
+            .unwrap();
+        assert_eq!(later.status, MeetingStatus::Scheduled.as_str());
+        assert_eq!(later.start_time, at(20));
+        assert_eq!(later.end_time, at(22));
+        assert_eq!(later.timezone, "Europe/Berlin");
+        assert_eq!(later.slot_duration, 120);
+
+        let sooner = repo.create_meeting(&new_meeting(&server_id, 8, vec![raid.id])).await.unwrap();
+
+        // Meetings that already ended aren't upcoming
+        let mut past = new_meeting(&server_id, 0, vec![]);
+        past.start_time = Utc::now() - Duration::hours(3);
+        past.end_time = Utc::now() - Duration::hours(1);
+        repo.create_meeting(&past).await.unwrap();
+
+        let upcoming: Vec<_> = repo
+            .get_upcoming_meetings_by_server(&server_id, MeetingStatus::Scheduled)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|meeting| meeting.id)
+            .collect();
+        assert_eq!(upcoming, [sooner.id, later.id]);
+
+        let groups: Vec<_> = repo
+            .get_meeting_groups(later.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|group| group.name)
+            .collect();
+        assert_eq!(groups, ["Alpha", "Raid"]);
+
+        let mut attendees: Vec<_> = repo
+            .get_meeting_attendees(later.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|attendee| attendee.discord_id)
+            .collect();
+        attendees.sort();
+        assert_eq!(attendees, ["user1", "user2"]);
+
+        let cancelled = repo.update_meeting_status(sooner.id, MeetingStatus::Cancelled).await.unwrap();
+        assert_eq!(cancelled.status, MeetingStatus::Cancelled.as_str());
+
+        let upcoming = repo
+            .get_upcoming_meetings_by_server(&server_id, MeetingStatus::Scheduled)
+            .await
+            .unwrap();
+        assert_eq!(upcoming.len(), 1);
+        let cancelled = repo
+            .get_upcoming_meetings_by_server(&server_id, MeetingStatus::Cancelled)
+            .await
+            .unwrap();
+        assert_eq!(cancelled.len(), 1);
+
+        assert!(repo.get_meeting_by_id(Uuid::new_v4()).await.unwrap().is_none());
+        assert!(repo.update_meeting_status(Uuid::new_v4(), MeetingStatus::Cancelled).await.is_err());
+
+        // Meetings can only name existing groups
+        assert!(repo.create_meeting(&new_meeting(&server_id, 9, vec![Uuid::new_v4()])).await.is_err());
+    }
+
+    pub async fn reschedule_meeting(repo: &impl Repository) {
+        let server_id = unique("server");
+        let original = repo.create_meeting(&new_meeting(&server_id, 10, vec![])).await.unwrap();
+
+        let mut moved = new_meeting(&server_id, 14, vec![]);
+        moved.rescheduled_from = Some(original.id);
+        let moved = repo.create_meeting(&moved).await.unwrap();
+        assert_eq!(moved.rescheduled_from, Some(original.id));
+
+        let original = repo.get_meeting_by_id(original.id).await.unwrap().unwrap();
+        assert_eq!(original.status, MeetingStatus::Rescheduled.as_str());
+
+        // Only existing meetings can be rescheduled
+        let mut orphan = new_meeting(&server_id, 16, vec![]);
+        orphan.rescheduled_from = Some(Uuid::new_v4());
+        assert!(repo.create_meeting(&orphan).await.is_err());
+    }
+}
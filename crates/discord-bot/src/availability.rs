@@ -0,0 +1,219 @@
+//! Weekly availability editing for `/schedule edit`.
+//!
+//! The web UI stores availability as weekly recurring slots anchored in the
+//! week they were created, one hour at a time. The Discord editor works on
+//! the same model: a set of (weekday, hour) cells in the schedule's timezone
+//! that is read from and written back to `time_slots`.
+//!
+//! `/schedule show` renders any schedule's upcoming week with the same model,
+//! repeating recurring slots into the days shown.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Timelike, Utc, Weekday};
+use chrono_tz::Tz;
+use std::collections::BTreeSet;
+use timesync_core::models::schedule::{CreateTimeSlotRequest, SlotPreference, TimeSlotResponse};
+use timesync_core::parser::local_to_utc;
+
+pub use timesync_core::availability::expand_slots;
+
+/// Days in display order, Monday first like the web UI
+pub const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+/// Hours of the week a user is available, in their schedule's timezone
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WeeklyAvailability {
+    /// (days from Monday, hour of day)
+    hours: BTreeSet<(u32, u32)>,
+}
+
+impl WeeklyAvailability {
+    /// Build from stored slots, keeping only the recurring, fully available ones
+    pub fn from_slots(slots: &[TimeSlotResponse], tz: Tz) -> Self {
+        let mut availability = Self::default();
+
+        for slot in slots.iter().filter(|slot| {
+            slot.is_recurring && !slot.is_unavailable && slot.preference == SlotPreference::Available
+        }) {
+            availability.add_time_range(slot.start, slot.end, tz);
+        }
+
+        availability
+    }
+
+    /// Mark the hours covered by recurring available slots, such as parsed quick entry text
+    ///
+    /// Slots that start or end within an hour mark the whole hour.
+    pub fn add_slots(&mut self, slots: &[CreateTimeSlotRequest], tz: Tz) {
+        for slot in slots.iter().filter(|slot| slot.is_recurring && !slot.is_unavailable) {
+            self.add_time_range(slot.start, slot.end, tz);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hours.is_empty()
+    }
+
+    /// Mark the hours from `start_hour` up to `end_hour` as available on each day
+    ///
+    /// Ranges that end at or before they start continue into the next day.
+    pub fn add(&mut self, days: &[Weekday], start_hour: u32, end_hour: u32) {
+        for cell in range_cells(days, start_hour, end_hour) {
+            self.hours.insert(cell);
+        }
+    }
+
+    /// Mark the hours from `start_hour` up to `end_hour` as unavailable on each day
+    pub fn remove(&mut self, days: &[Weekday], start_hour: u32, end_hour: u32) {
+        for cell in range_cells(days, start_hour, end_hour) {
+            self.hours.remove(&cell);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.hours.clear();
+    }
+
+    fn add_time_range(&mut self, start: DateTime<Utc>, end: DateTime<Utc>, tz: Tz) {
+        let mut current = start.with_timezone(&tz)
+            .with_minute(0)
+            .map_or(start, |hour| hour.with_timezone(&Utc));
+        while current < end {
+            let local = current.with_timezone(&tz);
+            self.hours.insert((local.weekday().num_days_from_monday(), local.hour()));
+            current += Duration::hours(1);
+        }
+    }
+
+    /// Convert to recurring slots in the week starting at `week_start`
+    ///
+    /// Consecutive hours on the same day are merged into a single slot.
+    pub fn to_slots(&self, week_start: NaiveDate, tz: Tz) -> Vec<CreateTimeSlotRequest> {
+        self.day_ranges()
+            .into_iter()
+            .map(|(day, start_hour, end_hour)| {
+                let date = week_start + Duration::days(day as i64);
+                CreateTimeSlotRequest {
+                    start: local_to_utc(date, start_hour * 60, tz),
+                    end: local_to_utc(date, end_hour * 60, tz),
+                    is_recurring: true,
+                    is_unavailable: false,
+                    preference: SlotPreference::Available,
+                }
+            })
+            .collect()
+    }
+
+    /// One line per day listing its available hours, e.g. "**Mon** 18:00-22:00"
+    pub fn summary(&self) -> String {
+        let ranges = self.day_ranges();
+
+        if ranges.is_empty() {
+            return "No weekly availability yet".to_string();
+        }
+
+        WEEKDAYS.iter()
+            .enumerate()
+            .filter_map(|(day, weekday)| {
+                let day_ranges: Vec<String> = ranges.iter()
+                    .filter(|(d, _, _)| *d == day as u32)
+                    .map(|(_, start, end)| format!("{:02}:00-{:02}:00", start, end))
+                    .collect();
+
+                (!day_ranges.is_empty()).then(|| format!("**{}** {}", weekday, day_ranges.join(", ")))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Contiguous (day, start hour, end hour) ranges
+    fn day_ranges(&self) -> Vec<(u32, u32, u32)> {
+        let mut ranges: Vec<(u32, u32, u32)> = Vec::new();
+
+        for &(day, hour) in &self.hours {
+            match ranges.last_mut() {
+                Some((last_day, _, end)) if *last_day == day && *end == hour => *end = hour + 1,
+                _ => ranges.push((day, hour, hour + 1)),
+            }
+        }
+
+        ranges
+    }
+}
+
+/// The Monday starting the week that contains `date`
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// A days × hours grid of the seven days from `first_day`, in `tz`
+///
+/// Each cell is an hour: `x` if a busy slot covers any of it, otherwise `█`
+/// if a one-off slot does, `▒` if only recurring slots do, `░` if only "if
+/// need be" slots do and `·` if none do.
+/// Recurring slots must already be expanded into the days shown, see
+/// [`expand_slots`].
+pub fn render_week_grid(slots: &[TimeSlotResponse], first_day: NaiveDate, tz: Tz) -> String {
+    let header = format!("       {:<6}{:<6}{:<6}{}", 0, 6, 12, 18);
+    let mut lines = vec![header];
+
+    for day in 0..7 {
+        let date = first_day + Duration::days(day);
+        let cells: String = (0..24)
+            .map(|hour| {
+                let start = local_to_utc(date, hour * 60, tz);
+                let end = local_to_utc(date, (hour + 1) * 60, tz);
+                let covering: Vec<&TimeSlotResponse> = slots.iter()
+                    .filter(|slot| start < end && slot.start < end && slot.end > start)
+                    .collect();
+
+                if covering.iter().any(|slot| slot.is_unavailable) {
+                    return 'x';
+                }
+
+                let preferred: Vec<&TimeSlotResponse> = covering.iter()
+                    .copied()
+                    .filter(|slot| slot.preference == SlotPreference::Available)
+                    .collect();
+                if preferred.is_empty() && !covering.is_empty() {
+                    return '░';
+                }
+
+                match preferred.iter().map(|slot| slot.is_recurring).min() {
+                    Some(false) => '█',
+                    Some(true) => '▒',
+                    None => '·',
+                }
+            })
+            .collect();
+
+        lines.push(format!("{} {}", date.format("%a %d"), cells));
+    }
+
+    lines.push("█ available  ▒ every week  ░ if need be  x busy".to_string());
+    lines.join("\n")
+}
+
+fn range_cells(days: &[Weekday], start_hour: u32, end_hour: u32) -> Vec<(u32, u32)> {
+    let mut cells = Vec::new();
+
+    for day in days {
+        let day = day.num_days_from_monday();
+        if start_hour < end_hour {
+            cells.extend((start_hour..end_hour.min(24)).map(|hour| (day, hour)));
+        } else {
+            // Past midnight: the rest of this day plus the start of the next
+            cells.extend((start_hour..24).map(|hour| (day, hour)));
+            cells.extend((0..end_hour).map(|hour| ((day + 1) % 7, hour)));
+        }
+    }
+
+    cells
+}
@@ -54,5 +54,11 @@ pub fn register_commands(commands: &mut CreateApplicationCommands) -> &mut Creat
         command
     });
     
+    // Create the meeting command
+    commands.create_application_command(|command| {
+        *command = schedule::meeting_command();
+        command
+    });
+    
     commands
 }
\ No newline at end of file
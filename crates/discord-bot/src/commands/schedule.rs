@@ -15,6 +15,25 @@ pub fn create_schedule_command() -> CreateApplicationCommand {
                 .description("Create a new availability schedule")
                 .kind(CommandOptionType::SubCommand)
         })
+        .create_option(|option| {
+            option
+                .name("edit")
+                .description("Edit your weekly availability without leaving Discord")
+                .kind(CommandOptionType::SubCommand)
+        })
+        .create_option(|option| {
+            option
+                .name("show")
+                .description("Show someone's availability for the coming week")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|sub_option| {
+                    sub_option
+                        .name("user")
+                        .description("Whose availability to show (defaults to you)")
+                        .kind(CommandOptionType::User)
+                        .required(false)
+                })
+        })
         .dm_permission(false);
     
     command
@@ -43,10 +62,17 @@ pub fn group_command() -> CreateApplicationCommand {
                 .create_sub_option(|sub_option| {
                     sub_option
                         .name("members")
-                        .description("Comma-separated list of mention tags (@user1, @user2) or 'all' for everyone in the current thread")
+                        .description("Mentions (@user1, @user2), role:@Role to import a role, or 'all' for everyone in this thread")
                         .kind(CommandOptionType::String)
                         .required(true)
                 })
+                .create_sub_option(|sub_option| {
+                    sub_option
+                        .name("create_role")
+                        .description("Create a mentionable role for the group's members (default: true)")
+                        .kind(CommandOptionType::Boolean)
+                        .required(false)
+                })
         })
         // List subcommand
         .create_option(|option| {
@@ -67,11 +93,12 @@ pub fn group_command() -> CreateApplicationCommand {
                         .description("Name of the group")
                         .kind(CommandOptionType::String)
                         .required(true)
+                        .set_autocomplete(true)
                 })
                 .create_sub_option(|sub_option| {
                     sub_option
                         .name("members")
-                        .description("Comma-separated list of mention tags (@user1, @user2)")
+                        .description("Mentions (@user1, @user2), role:@Role to import a role, or 'all' for everyone in this thread")
                         .kind(CommandOptionType::String)
                         .required(true)
                 })
@@ -88,11 +115,12 @@ pub fn group_command() -> CreateApplicationCommand {
                         .description("Name of the group")
                         .kind(CommandOptionType::String)
                         .required(true)
+                        .set_autocomplete(true)
                 })
                 .create_sub_option(|sub_option| {
                     sub_option
                         .name("members")
-                        .description("Comma-separated list of mention tags (@user1, @user2)")
+                        .description("Mentions (@user1, @user2), role:@Role to import a role, or 'all' for everyone in this thread")
                         .kind(CommandOptionType::String)
                         .required(true)
                 })
@@ -109,6 +137,140 @@ pub fn group_command() -> CreateApplicationCommand {
                         .description("Name of the group")
                         .kind(CommandOptionType::String)
                         .required(true)
+                        .set_autocomplete(true)
+                })
+        })
+        // Rename subcommand
+        .create_option(|option| {
+            option
+                .name("rename")
+                .description("Rename a group and its role")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|sub_option| {
+                    sub_option
+                        .name("name")
+                        .description("Current name of the group")
+                        .kind(CommandOptionType::String)
+                        .required(true)
+                        .set_autocomplete(true)
+                })
+                .create_sub_option(|sub_option| {
+                    sub_option
+                        .name("new_name")
+                        .description("New name for the group")
+                        .kind(CommandOptionType::String)
+                        .required(true)
+                })
+        })
+        // Delete subcommand
+        .create_option(|option| {
+            option
+                .name("delete")
+                .description("Delete a group and the role created for it")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|sub_option| {
+                    sub_option
+                        .name("name")
+                        .description("Name of the group")
+                        .kind(CommandOptionType::String)
+                        .required(true)
+                        .set_autocomplete(true)
+                })
+        })
+        // Bind subcommand
+        .create_option(|option| {
+            option
+                .name("bind")
+                .description("Keep a group's members in sync with everyone holding a role")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|sub_option| {
+                    sub_option
+                        .name("name")
+                        .description("Name of the group")
+                        .kind(CommandOptionType::String)
+                        .required(true)
+                        .set_autocomplete(true)
+                })
+                .create_sub_option(|sub_option| {
+                    sub_option
+                        .name("role")
+                        .description("Role whose holders are the group's members")
+                        .kind(CommandOptionType::Role)
+                        .required(true)
+                })
+        })
+        // Unbind subcommand
+        .create_option(|option| {
+            option
+                .name("unbind")
+                .description("Stop syncing a group's members from its role")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|sub_option| {
+                    sub_option
+                        .name("name")
+                        .description("Name of the group")
+                        .kind(CommandOptionType::String)
+                        .required(true)
+                        .set_autocomplete(true)
+                })
+        })
+        // Require subcommand
+        .create_option(|option| {
+            option
+                .name("require")
+                .description("Choose whether matches need this group or just prefer it")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|sub_option| {
+                    sub_option
+                        .name("name")
+                        .description("Name of the group")
+                        .kind(CommandOptionType::String)
+                        .required(true)
+                        .set_autocomplete(true)
+                })
+                .create_sub_option(|sub_option| {
+                    sub_option
+                        .name("required")
+                        .description("Whether matches need enough of this group's members (groups are required by default)")
+                        .kind(CommandOptionType::Boolean)
+                        .required(true)
+                })
+        })
+        // Attendee subcommand
+        .create_option(|option| {
+            option
+                .name("attendee")
+                .description("Mark a member as required or nice-to-have, and how much they count")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|sub_option| {
+                    sub_option
+                        .name("name")
+                        .description("Name of the group")
+                        .kind(CommandOptionType::String)
+                        .required(true)
+                        .set_autocomplete(true)
+                })
+                .create_sub_option(|sub_option| {
+                    sub_option
+                        .name("user")
+                        .description("Member of the group")
+                        .kind(CommandOptionType::User)
+                        .required(true)
+                })
+                .create_sub_option(|sub_option| {
+                    sub_option
+                        .name("required")
+                        .description("Whether matches need this member to be available")
+                        .kind(CommandOptionType::Boolean)
+                        .required(false)
+                })
+                .create_sub_option(|sub_option| {
+                    sub_option
+                        .name("weight")
+                        .description("How much this member counts when ranking times (default: 1)")
+                        .kind(CommandOptionType::Number)
+                        .min_number_value(0.0)
+                        .required(false)
                 })
         });
     
@@ -128,6 +290,7 @@ pub fn match_command() -> CreateApplicationCommand {
                 .description("Comma-separated list of group names")
                 .kind(CommandOptionType::String)
                 .required(true)
+                .set_autocomplete(true)
         })
         .create_option(|option| {
             option
@@ -156,6 +319,13 @@ pub fn match_command() -> CreateApplicationCommand {
                 .description("Human-friendly time span (e.g., 'next 3 days', 'this weekend')")
                 .kind(CommandOptionType::String)
                 .required(false)
+        })
+        .create_option(|option| {
+            option
+                .name("required")
+                .description("Members who must all be able to attend (@user1 @user2)")
+                .kind(CommandOptionType::String)
+                .required(false)
         });
     
     command
@@ -180,6 +350,7 @@ pub fn timezone_command() -> CreateApplicationCommand {
                         .description("Timezone name (e.g., 'America/New_York', 'Europe/London', 'Asia/Tokyo')")
                         .kind(CommandOptionType::String)
                         .required(true)
+                        .set_autocomplete(true)
                 })
         })
         // Show subcommand
@@ -195,7 +366,100 @@ pub fn timezone_command() -> CreateApplicationCommand {
                 .name("list")
                 .description("List available timezone options")
                 .kind(CommandOptionType::SubCommand)
+        })
+        // Personal timezone subcommands
+        .create_option(|option| {
+            option
+                .name("me")
+                .description("Manage your personal timezone")
+                .kind(CommandOptionType::SubCommandGroup)
+                .create_sub_option(|sub_option| {
+                    sub_option
+                        .name("set")
+                        .description("Set the timezone your voting views are shown in")
+                        .kind(CommandOptionType::SubCommand)
+                        .create_sub_option(|opt| {
+                            opt.name("timezone")
+                                .description("Timezone name (e.g., 'America/New_York', 'Europe/London', 'Asia/Tokyo')")
+                                .kind(CommandOptionType::String)
+                                .required(true)
+                                .set_autocomplete(true)
+                        })
+                })
+                .create_sub_option(|sub_option| {
+                    sub_option
+                        .name("show")
+                        .description("Show your personal timezone")
+                        .kind(CommandOptionType::SubCommand)
+                })
+        });
+    
+    command
+}
+
+/// Create command for managing finalized meetings
+pub fn meeting_command() -> CreateApplicationCommand {
+    let mut command = CreateApplicationCommand::default();
+    command
+        .name("meeting")
+        .description("Manage meetings scheduled with /match")
+        .dm_permission(false)
+        // List subcommand
+        .create_option(|option| {
+            option
+                .name("list")
+                .description("List upcoming meetings in this server")
+                .kind(CommandOptionType::SubCommand)
+        })
+        // Info subcommand
+        .create_option(|option| {
+            option
+                .name("info")
+                .description("Show details about a meeting")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|sub_option| {
+                    sub_option
+                        .name("meeting")
+                        .description("Meeting ID as shown by /meeting list")
+                        .kind(CommandOptionType::String)
+                        .required(true)
+                })
+        })
+        // Cancel subcommand
+        .create_option(|option| {
+            option
+                .name("cancel")
+                .description("Cancel a meeting and notify its attendees")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|sub_option| {
+                    sub_option
+                        .name("meeting")
+                        .description("Meeting ID as shown by /meeting list")
+                        .kind(CommandOptionType::String)
+                        .required(true)
+                })
+                .create_sub_option(|sub_option| {
+                    sub_option
+                        .name("reason")
+                        .description("Reason shown to attendees")
+                        .kind(CommandOptionType::String)
+                        .required(false)
+                })
+        })
+        // Reschedule subcommand
+        .create_option(|option| {
+            option
+                .name("reschedule")
+                .description("Open a new poll for the same groups to pick another time")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|sub_option| {
+                    sub_option
+                        .name("meeting")
+                        .description("Meeting ID as shown by /meeting list")
+                        .kind(CommandOptionType::String)
+                        .required(true)
+                })
         });
     
     command
-}
\ No newline at end of file
+}
@@ -1,12 +1,14 @@
 use eyre::{eyre, Result};
 use serde::Deserialize;
+use serenity::prelude::GatewayIntents;
 use std::env;
+use std::time::Duration;
 
 /// Configuration for the Discord bot.
 /// 
 /// This struct contains all the necessary configuration parameters
 /// needed for the bot to function properly, including connection details
-/// for Discord and the database.
+/// for Discord and the TimeSync API.
 #[derive(Debug, Clone, Deserialize)]
 pub struct BotConfig {
     /// Discord bot token (required)
@@ -15,12 +17,23 @@ pub struct BotConfig {
     pub application_id: u64,
     /// Base URL for the web application (required for schedule links)
     pub web_base_url: String,
-    /// Database connection URL (required)
-    pub database_url: String,
+    /// Base URL of the TimeSync API (defaults to `web_base_url` + `/api`)
+    pub api_base_url: Option<String>,
     /// Prefix for commands (defaults to "!")
     pub command_prefix: Option<String>,
     /// Test guild ID for faster command registration during development
     pub test_guild_id: Option<u64>,
+    /// Whether to keep role-bound groups in sync with their roles (defaults to false)
+    ///
+    /// Needs the privileged Server Members intent enabled for the bot in the
+    /// Discord developer portal.
+    pub role_sync_enabled: Option<bool>,
+    /// Seconds between full role sync reconciliations (defaults to 3600, 0 disables)
+    pub role_sync_interval_secs: Option<u64>,
+    /// Seconds a settled `/match` poll waits before finishing (defaults to 60, 0 finishes at once)
+    pub poll_grace_period_secs: Option<u64>,
+    /// Role whose members can finish or reopen any `/match` poll, besides its organizer
+    pub poll_manager_role_id: Option<u64>,
 }
 
 impl BotConfig {
@@ -37,8 +50,10 @@ impl BotConfig {
         let web_base_url = env::var("WEB_BASE_URL")
             .map_err(|_| eyre!("WEB_BASE_URL environment variable not set"))?;
             
-        let database_url = env::var("DATABASE_URL")
-            .map_err(|_| eyre!("DATABASE_URL environment variable not set"))?;
+        // An empty value falls back to the default, like an unset one
+        let api_base_url = env::var("API_BASE_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty());
             
         let command_prefix = env::var("DISCORD_COMMAND_PREFIX").ok();
         
@@ -47,13 +62,33 @@ impl BotConfig {
             .ok()
             .and_then(|id| id.parse::<u64>().ok());
         
+        let role_sync_enabled = env::var("ROLE_SYNC_ENABLED")
+            .ok()
+            .and_then(|enabled| enabled.parse::<bool>().ok());
+        
+        let role_sync_interval_secs = env::var("ROLE_SYNC_INTERVAL_SECS")
+            .ok()
+            .and_then(|secs| secs.parse::<u64>().ok());
+        
+        let poll_grace_period_secs = env::var("POLL_GRACE_PERIOD_SECS")
+            .ok()
+            .and_then(|secs| secs.parse::<u64>().ok());
+        
+        let poll_manager_role_id = env::var("POLL_MANAGER_ROLE_ID")
+            .ok()
+            .and_then(|id| id.parse::<u64>().ok());
+        
         Ok(Self {
             token,
             application_id,
             web_base_url,
-            database_url,
+            api_base_url,
             command_prefix,
             test_guild_id,
+            role_sync_enabled,
+            role_sync_interval_secs,
+            poll_grace_period_secs,
+            poll_manager_role_id,
         })
     }
     
@@ -61,4 +96,44 @@ impl BotConfig {
     pub fn command_prefix(&self) -> &str {
         self.command_prefix.as_deref().unwrap_or("!")
     }
+    
+    /// Get the base URL of the TimeSync API
+    pub fn api_base_url(&self) -> String {
+        self.api_base_url.clone()
+            .unwrap_or_else(|| format!("{}/api", self.web_base_url.trim_end_matches('/')))
+    }
+    
+    /// Whether role sync is on (defaults to false)
+    pub fn role_sync_enabled(&self) -> bool {
+        self.role_sync_enabled.unwrap_or(false)
+    }
+    
+    /// Get the gateway intents to connect with
+    ///
+    /// The privileged members intent is only requested for role sync, so the
+    /// bot can connect without it being enabled in the developer portal.
+    pub fn gateway_intents(&self) -> GatewayIntents {
+        if self.role_sync_enabled() {
+            GatewayIntents::non_privileged() | GatewayIntents::GUILD_MEMBERS
+        } else {
+            GatewayIntents::non_privileged()
+        }
+    }
+    
+    /// Get the interval between role sync reconciliations (`None` if disabled)
+    pub fn role_sync_interval(&self) -> Option<Duration> {
+        if !self.role_sync_enabled() {
+            return None;
+        }
+        
+        match self.role_sync_interval_secs.unwrap_or(3600) {
+            0 => None,
+            secs => Some(Duration::from_secs(secs)),
+        }
+    }
+    
+    /// Get how long a settled poll waits for voters to unlock before it finishes
+    pub fn poll_grace_period(&self) -> Duration {
+        Duration::from_secs(self.poll_grace_period_secs.unwrap_or(60))
+    }
 }
\ No newline at end of file
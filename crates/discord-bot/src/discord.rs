@@ -0,0 +1,238 @@
+//! The Discord operations the interaction handlers depend on.
+//!
+//! Handlers reach Discord only through [`DiscordApi`], never through
+//! serenity's `Context`, so they can run against the recording fake in
+//! `crate::testing`. [`SerenityDiscord`] is the real implementation, built
+//! on serenity's HTTP client.
+//!
+//! Methods take serenity's builders so handlers keep building requests the
+//! usual way; see the helpers on [`HandlerContext`](crate::handlers::HandlerContext).
+
+use eyre::Result;
+use serenity::{
+    async_trait,
+    builder::{
+        CreateInteractionResponse, CreateInteractionResponseFollowup, CreateMessage, EditInteractionResponse,
+        EditMessage, EditRole,
+    },
+    http::Http,
+    json::{self, Value},
+    model::{
+        application::interaction::{
+            application_command::ApplicationCommandInteraction,
+            autocomplete::AutocompleteInteraction,
+            message_component::MessageComponentInteraction,
+            modal::ModalSubmitInteraction,
+        },
+        channel::Message,
+        guild::{Member, Role},
+        id::{ChannelId, GuildId, InteractionId, MessageId, RoleId, UserId},
+    },
+};
+use std::sync::Arc;
+
+use crate::members::{fetch_all_guild_members, resolve_member_specs, MemberSpec};
+
+/// The parts of an interaction needed to respond to it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InteractionRef {
+    pub id: InteractionId,
+    pub token: String,
+}
+
+macro_rules! impl_interaction_ref {
+    ($($interaction:ty),*) => {
+        $(
+            impl From<&$interaction> for InteractionRef {
+                fn from(interaction: &$interaction) -> Self {
+                    Self {
+                        id: interaction.id,
+                        token: interaction.token.clone(),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_interaction_ref!(
+    ApplicationCommandInteraction,
+    MessageComponentInteraction,
+    ModalSubmitInteraction,
+    AutocompleteInteraction
+);
+
+/// Discord requests made by the interaction handlers
+#[async_trait]
+pub trait DiscordApi: Send + Sync {
+    /// Send the initial response to an interaction
+    async fn create_interaction_response(
+        &self,
+        interaction: &InteractionRef,
+        response: CreateInteractionResponse<'static>,
+    ) -> serenity::Result<()>;
+
+    /// Edit the initial response to an interaction
+    async fn edit_original_interaction_response(
+        &self,
+        interaction: &InteractionRef,
+        response: EditInteractionResponse,
+    ) -> serenity::Result<Message>;
+
+    /// Send a followup message for an interaction that has been responded to
+    async fn create_followup_message(
+        &self,
+        interaction: &InteractionRef,
+        followup: CreateInteractionResponseFollowup<'static>,
+    ) -> serenity::Result<Message>;
+
+    /// Post a message to a channel
+    async fn send_message(&self, channel_id: ChannelId, message: CreateMessage<'static>) -> serenity::Result<Message>;
+
+    /// Edit a message the bot posted
+    async fn edit_message(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        message: EditMessage<'static>,
+    ) -> serenity::Result<Message>;
+
+    /// Fetch every member of a guild
+    async fn guild_members(&self, guild_id: GuildId) -> Result<Vec<Member>>;
+
+    /// Resolve a `members` option into Discord IDs, see [`resolve_member_specs`]
+    async fn resolve_members(&self, guild_id: GuildId, channel_id: ChannelId, specs: &[MemberSpec]) -> Result<Vec<String>>;
+
+    /// Create a role in a guild
+    async fn create_role(&self, guild_id: GuildId, role: EditRole) -> serenity::Result<Role>;
+
+    /// Change a role's settings
+    async fn edit_role(&self, guild_id: GuildId, role_id: RoleId, role: EditRole) -> serenity::Result<Role>;
+
+    /// Delete a role, which also removes it from every member
+    async fn delete_role(&self, guild_id: GuildId, role_id: RoleId) -> serenity::Result<()>;
+
+    /// Give a member a role
+    async fn add_member_role(&self, guild_id: GuildId, user_id: UserId, role_id: RoleId) -> serenity::Result<()>;
+
+    /// Take a role away from a member
+    async fn remove_member_role(&self, guild_id: GuildId, user_id: UserId, role_id: RoleId) -> serenity::Result<()>;
+}
+
+/// [`DiscordApi`] backed by serenity's HTTP client
+///
+/// Requests are sent as serenity's own builder methods would send them,
+/// except that message lengths are left for Discord to check.
+#[derive(Clone)]
+pub struct SerenityDiscord {
+    http: Arc<Http>,
+}
+
+impl SerenityDiscord {
+    /// Create a client that sends requests with `http`
+    pub fn new(http: Arc<Http>) -> Self {
+        Self { http }
+    }
+}
+
+#[async_trait]
+impl DiscordApi for SerenityDiscord {
+    async fn create_interaction_response(
+        &self,
+        interaction: &InteractionRef,
+        response: CreateInteractionResponse<'static>,
+    ) -> serenity::Result<()> {
+        let CreateInteractionResponse(map, files) = response;
+        let map = Value::from(json::hashmap_to_json_map(map));
+
+        if files.is_empty() {
+            self.http.create_interaction_response(interaction.id.0, &interaction.token, &map).await
+        } else {
+            self.http
+                .create_interaction_response_with_files(interaction.id.0, &interaction.token, &map, files)
+                .await
+        }
+    }
+
+    async fn edit_original_interaction_response(
+        &self,
+        interaction: &InteractionRef,
+        response: EditInteractionResponse,
+    ) -> serenity::Result<Message> {
+        let map = Value::from(json::hashmap_to_json_map(response.0));
+
+        self.http.edit_original_interaction_response(&interaction.token, &map).await
+    }
+
+    async fn create_followup_message(
+        &self,
+        interaction: &InteractionRef,
+        followup: CreateInteractionResponseFollowup<'static>,
+    ) -> serenity::Result<Message> {
+        let CreateInteractionResponseFollowup(map, files) = followup;
+        let map = Value::from(json::hashmap_to_json_map(map));
+
+        if files.is_empty() {
+            self.http.create_followup_message(&interaction.token, &map).await
+        } else {
+            self.http.create_followup_message_with_files(&interaction.token, &map, files).await
+        }
+    }
+
+    async fn send_message(&self, channel_id: ChannelId, message: CreateMessage<'static>) -> serenity::Result<Message> {
+        let CreateMessage(map, reactions, files) = message;
+        let map = json::hashmap_to_json_map(map);
+
+        let message = if files.is_empty() {
+            self.http.send_message(channel_id.0, &Value::from(map)).await?
+        } else {
+            self.http.send_files(channel_id.0, files, &map).await?
+        };
+
+        for reaction in reactions.unwrap_or_default() {
+            channel_id.create_reaction(&self.http, message.id, reaction).await?;
+        }
+
+        Ok(message)
+    }
+
+    async fn edit_message(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        message: EditMessage<'static>,
+    ) -> serenity::Result<Message> {
+        let EditMessage(map, files) = message;
+        let map = Value::from(json::hashmap_to_json_map(map));
+
+        self.http.edit_message_and_attachments(channel_id.0, message_id.0, &map, files).await
+    }
+
+    async fn guild_members(&self, guild_id: GuildId) -> Result<Vec<Member>> {
+        fetch_all_guild_members(&self.http, guild_id).await
+    }
+
+    async fn resolve_members(&self, guild_id: GuildId, channel_id: ChannelId, specs: &[MemberSpec]) -> Result<Vec<String>> {
+        resolve_member_specs(&self.http, guild_id, channel_id, specs).await
+    }
+
+    async fn create_role(&self, guild_id: GuildId, role: EditRole) -> serenity::Result<Role> {
+        self.http.create_role(guild_id.0, &json::hashmap_to_json_map(role.0), None).await
+    }
+
+    async fn edit_role(&self, guild_id: GuildId, role_id: RoleId, role: EditRole) -> serenity::Result<Role> {
+        self.http.edit_role(guild_id.0, role_id.0, &json::hashmap_to_json_map(role.0), None).await
+    }
+
+    async fn delete_role(&self, guild_id: GuildId, role_id: RoleId) -> serenity::Result<()> {
+        self.http.delete_role(guild_id.0, role_id.0).await
+    }
+
+    async fn add_member_role(&self, guild_id: GuildId, user_id: UserId, role_id: RoleId) -> serenity::Result<()> {
+        self.http.add_member_role(guild_id.0, user_id.0, role_id.0, None).await
+    }
+
+    async fn remove_member_role(&self, guild_id: GuildId, user_id: UserId, role_id: RoleId) -> serenity::Result<()> {
+        self.http.remove_member_role(guild_id.0, user_id.0, role_id.0, None).await
+    }
+}
@@ -1,54 +1,67 @@
 use serenity::{
     async_trait,
+    builder::{
+        CreateAutocompleteResponse, CreateEmbed, CreateInteractionResponse, CreateInteractionResponseFollowup,
+        CreateMessage, EditInteractionResponse, EditMessage, EditRole,
+    },
+    json::{self, Value},
     model::{
         application::interaction::{
             Interaction, 
             InteractionResponseType,
         },
+        channel::Message,
         gateway::Ready,
-        id::MessageId,
+        guild::{Member, Role},
+        id::{ChannelId, GuildId, MessageId, RoleId, UserId},
+        user::User,
     },
     prelude::*,
 };
-use sqlx::PgPool;
 use tracing::{error, info};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::collections::HashMap;
 use tokio::sync::RwLock;
-use timesync_core::models::discord::MatchResult;
-
-/// Information about a time slot for voting
-#[derive(Debug, Clone)]
-pub struct SlotInfo {
-    pub id: String,           // Unique identifier for the slot
-    pub start: chrono::DateTime<chrono::Utc>,
-    pub end: chrono::DateTime<chrono::Utc>,
-    pub formatted_time: String, // Formatted time string (e.g., "6pm-7pm")
-    pub available_users: Vec<String>, // Discord IDs of users available at this time
-}
+use timesync_client::TimeSyncClient;
 
+pub mod attendees;
+pub mod autocomplete;
+pub mod match_poll;
+pub mod meeting;
+pub mod role_sync;
 pub mod schedule;
+pub mod schedule_edit;
+pub mod schedule_show;
 
 use crate::config::BotConfig;
+use crate::discord::{DiscordApi, InteractionRef, SerenityDiscord};
+use crate::poll::Poll;
 
 /// Main Discord handler that processes all events.
 ///
 /// This handler is responsible for responding to Discord events like
 /// ready events and commands/interactions. It maintains access to the 
-/// bot configuration and database connection.
+/// bot configuration and the TimeSync API.
 pub struct Handler {
     config: BotConfig,
-    db_pool: PgPool,
-    active_polls: Arc<RwLock<HashMap<MessageId, ActivePoll>>>,
+    api: TimeSyncClient,
+    active_polls: Arc<RwLock<HashMap<MessageId, Poll>>>,
+    availability_drafts: Arc<RwLock<HashMap<UserId, schedule_edit::AvailabilityDraft>>>,
+    role_sync_started: AtomicBool,
 }
 
 impl Handler {
     /// Create a new handler
-    pub fn new(config: BotConfig, db_pool: PgPool) -> Self {
+    pub fn new(config: BotConfig) -> Self {
+        let api = TimeSyncClient::new(config.api_base_url());
+        
         Self { 
             config, 
-            db_pool,
+            api,
             active_polls: Arc::new(RwLock::new(HashMap::new())),
+            availability_drafts: Arc::new(RwLock::new(HashMap::new())),
+            role_sync_started: AtomicBool::new(false),
         }
     }
 }
@@ -59,6 +72,15 @@ impl EventHandler for Handler {
     async fn ready(&self, ctx: Context, ready: Ready) {
         info!("{} is connected!", ready.user.name);
 
+        // Start the periodic role sync once, even if the gateway reconnects
+        if let Some(interval) = self.config.role_sync_interval()
+            && !self.role_sync_started.swap(true, Ordering::SeqCst)
+        {
+            info!("Starting role sync reconciliation every {:?}", interval);
+            let discord = Arc::new(SerenityDiscord::new(ctx.http.clone()));
+            role_sync::spawn_periodic_reconciliation(discord, self.api.clone(), interval);
+        }
+
         // For dev testing, register for specific guilds to avoid global command cache delay
         // If running in dev environment, register commands for development servers
         if let Some(test_guild_id) = self.config.test_guild_id {
@@ -110,25 +132,51 @@ impl EventHandler for Handler {
         }
     }
 
+    /// Keep role-bound groups in sync when a member's roles change
+    async fn guild_member_update(&self, _ctx: Context, _old: Option<Member>, new: Member) {
+        if let Err(e) = role_sync::handle_member_update(&self.api, &new).await {
+            error!("Error syncing roles for member {}: {:?}", new.user.id, e);
+        }
+    }
+
+    /// Drop members who left the server from role-bound groups
+    async fn guild_member_removal(&self, _ctx: Context, guild_id: GuildId, user: User, _member: Option<Member>) {
+        if let Err(e) = role_sync::handle_member_removal(&self.api, guild_id, user.id).await {
+            error!("Error syncing roles for departed member {}: {:?}", user.id, e);
+        }
+    }
+
     /// Handle interactions (slash commands, buttons, etc.)
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        let discord = Arc::new(SerenityDiscord::new(ctx.http.clone()));
+
+        self.handle_interaction(discord, interaction).await;
+    }
+}
+
+impl Handler {
+    /// Dispatch an interaction to its handler, replying through `discord`
+    ///
+    /// Errors are logged and, where Discord allows it, shown to the user.
+    pub async fn handle_interaction(&self, discord: Arc<dyn DiscordApi>, interaction: Interaction) {
+        let handler_ctx = HandlerContext {
+            discord,
+            config: self.config.clone(),
+            api: self.api.clone(),
+            active_polls: self.active_polls.clone(),
+            availability_drafts: self.availability_drafts.clone(),
+        };
+
         match interaction {
             Interaction::ApplicationCommand(command) => {
                 info!("Received command: {}", command.data.name);
-                
-                // Create a context with shared data
-                let handler_ctx = HandlerContext {
-                    ctx: ctx.clone(),
-                    config: self.config.clone(),
-                    db_pool: self.db_pool.clone(),
-                    active_polls: self.active_polls.clone(),
-                };
 
                 let result = match command.data.name.as_str() {
-                    "schedule" => schedule::handle_schedule_command(handler_ctx, &command).await,
-                    "group" => schedule::handle_group_command(handler_ctx, &command).await,
-                    "match" => schedule::handle_match_command(handler_ctx, &command).await,
-                    "timezone" => schedule::handle_timezone_command(handler_ctx, &command).await,
+                    "schedule" => schedule::handle_schedule_command(handler_ctx.clone(), &command).await,
+                    "group" => schedule::handle_group_command(handler_ctx.clone(), &command).await,
+                    "match" => match_poll::handle_match_command(handler_ctx.clone(), &command).await,
+                    "timezone" => schedule::handle_timezone_command(handler_ctx.clone(), &command).await,
+                    "meeting" => meeting::handle_meeting_command(handler_ctx.clone(), &command).await,
                     _ => {
                         error!("Unknown command: {}", command.data.name);
                         Err(eyre::eyre!("Unknown command"))
@@ -138,9 +186,9 @@ impl EventHandler for Handler {
                 if let Err(e) = result {
                     error!("Error handling command: {:?}", e);
                     
-                    // Try to respond with error
-                    if let Err(why) = command
-                        .create_interaction_response(&ctx.http, |r| {
+                    // Try to respond with error, editing the response if it was already deferred
+                    if handler_ctx
+                        .create_interaction_response(&command, |r| {
                             r.kind(InteractionResponseType::ChannelMessageWithSource)
                                 .interaction_response_data(|m| {
                                     m.content(format!("Error: {}", e))
@@ -148,24 +196,30 @@ impl EventHandler for Handler {
                                 })
                         })
                         .await
+                        .is_err()
+                        && let Err(why) = handler_ctx
+                            .edit_original_interaction_response(&command, |m| {
+                                m.content(format!("Error: {}", e))
+                            })
+                            .await
                     {
                         error!("Failed to send error response: {:?}", why);
                     }
                 }
             },
             Interaction::MessageComponent(mut component) => {
-                let handler_ctx = HandlerContext {
-                    ctx: ctx.clone(),
-                    config: self.config.clone(),
-                    db_pool: self.db_pool.clone(),
-                    active_polls: self.active_polls.clone(),
+                // The availability editor's components are handled separately from polls
+                let result = if component.data.custom_id.starts_with(schedule_edit::CUSTOM_ID_PREFIX) {
+                    schedule_edit::handle_edit_component(handler_ctx.clone(), &component).await
+                } else {
+                    match_poll::handle_component_interaction(handler_ctx.clone(), &mut component).await
                 };
                 
-                if let Err(e) = schedule::handle_component_interaction(handler_ctx, &mut component).await {
+                if let Err(e) = result {
                     error!("Error handling component interaction: {:?}", e);
                     
-                    if let Err(why) = component
-                        .create_interaction_response(&ctx.http, |r| {
+                    if let Err(why) = handler_ctx
+                        .create_interaction_response(&component, |r| {
                             r.kind(InteractionResponseType::ChannelMessageWithSource)
                                 .interaction_response_data(|m| {
                                     m.content(format!("Error: {}", e))
@@ -178,40 +232,166 @@ impl EventHandler for Handler {
                     }
                 }
             },
+            Interaction::ModalSubmit(modal) => {
+                if let Err(e) = schedule_edit::handle_edit_modal(handler_ctx.clone(), &modal).await {
+                    error!("Error handling modal submit: {:?}", e);
+                    
+                    if let Err(why) = handler_ctx
+                        .create_interaction_response(&modal, |r| {
+                            r.kind(InteractionResponseType::ChannelMessageWithSource)
+                                .interaction_response_data(|m| {
+                                    m.content(format!("Error: {}", e))
+                                        .ephemeral(true)
+                                })
+                        })
+                        .await
+                    {
+                        error!("Failed to send error response: {:?}", why);
+                    }
+                }
+            },
+            Interaction::Autocomplete(autocomplete) => {
+                // Suggestions are best-effort, so there is nothing to show the user on failure
+                if let Err(e) = autocomplete::handle_autocomplete(handler_ctx, &autocomplete).await {
+                    error!("Error handling autocomplete for {}: {:?}", autocomplete.data.name, e);
+                }
+            },
             _ => {}
         }
     }
 }
 
-/// Represents an active poll for a scheduling match
-#[derive(Debug, Clone)]
-pub struct ActivePoll {
-    pub matches: Vec<MatchResult>,
-    pub current_index: usize,
-    pub group_names: Vec<String>,
-    pub min_per_group: i64,
-    pub required_yes_count: usize,
-    pub responses: HashMap<String, bool>, // user_id -> yes/no (legacy)
-    pub slot_responses: HashMap<String, Vec<String>>, // user_id -> list of slot IDs they are available for
-    pub locked_votes: HashMap<String, bool>, // user_id -> whether they've locked in their votes
-    pub db_pool: sqlx::PgPool,
-    pub timezone: String,
-    pub eligible_voters: String, // Comma-separated list of eligible voter IDs
-    pub group_members: HashMap<uuid::Uuid, Vec<String>>, // group_id -> list of member IDs
-    pub slot_duration: i64, // Duration of each slot in minutes
-    pub display_days: i64, // Number of days to display (1-7)
-    pub current_day: usize, // Current day index being displayed
-    pub day_slots: HashMap<usize, Vec<SlotInfo>>, // Slots organized by day index
-}
-
 /// Shared context for command handlers.
 ///
-/// This struct provides a convenient way to pass the Discord context,
-/// bot configuration, and database connection to command handlers.
+/// This struct provides a convenient way to pass the Discord client,
+/// bot configuration, and TimeSync API client to command handlers.
 #[derive(Clone)]
 pub struct HandlerContext {
-    pub ctx: Context,
+    pub discord: Arc<dyn DiscordApi>,
     pub config: BotConfig,
-    pub db_pool: PgPool,
-    pub active_polls: Arc<RwLock<HashMap<MessageId, ActivePoll>>>,
-}
\ No newline at end of file
+    pub api: TimeSyncClient,
+    pub active_polls: Arc<RwLock<HashMap<MessageId, Poll>>>,
+    pub availability_drafts: Arc<RwLock<HashMap<UserId, schedule_edit::AvailabilityDraft>>>,
+}
+
+impl HandlerContext {
+    /// Send the initial response to an interaction
+    pub async fn create_interaction_response<F>(
+        &self,
+        interaction: impl Into<InteractionRef>,
+        f: F,
+    ) -> serenity::Result<()>
+    where
+        F: for<'b> FnOnce(&'b mut CreateInteractionResponse<'static>) -> &'b mut CreateInteractionResponse<'static>,
+    {
+        let mut response = CreateInteractionResponse::default();
+        f(&mut response);
+
+        self.discord.create_interaction_response(&interaction.into(), response).await
+    }
+
+    /// Edit the initial response to an interaction
+    pub async fn edit_original_interaction_response<F>(
+        &self,
+        interaction: impl Into<InteractionRef>,
+        f: F,
+    ) -> serenity::Result<Message>
+    where
+        F: FnOnce(&mut EditInteractionResponse) -> &mut EditInteractionResponse,
+    {
+        let mut response = EditInteractionResponse::default();
+        f(&mut response);
+
+        self.discord.edit_original_interaction_response(&interaction.into(), response).await
+    }
+
+    /// Send a followup message for an interaction that has been responded to
+    pub async fn create_followup_message<F>(
+        &self,
+        interaction: impl Into<InteractionRef>,
+        f: F,
+    ) -> serenity::Result<Message>
+    where
+        F: for<'b> FnOnce(&'b mut CreateInteractionResponseFollowup<'static>) -> &'b mut CreateInteractionResponseFollowup<'static>,
+    {
+        let mut followup = CreateInteractionResponseFollowup::default();
+        f(&mut followup);
+
+        self.discord.create_followup_message(&interaction.into(), followup).await
+    }
+
+    /// Answer an autocomplete interaction with the suggested choices
+    pub async fn create_autocomplete_response<F>(
+        &self,
+        interaction: impl Into<InteractionRef>,
+        f: F,
+    ) -> serenity::Result<()>
+    where
+        F: FnOnce(&mut CreateAutocompleteResponse) -> &mut CreateAutocompleteResponse,
+    {
+        let mut choices = CreateAutocompleteResponse::default();
+        f(&mut choices);
+
+        let mut response = CreateInteractionResponse::default();
+        response.kind(InteractionResponseType::Autocomplete);
+        response.0.insert("data", Value::from(json::hashmap_to_json_map(choices.0)));
+
+        self.discord.create_interaction_response(&interaction.into(), response).await
+    }
+
+    /// Create a role in a guild
+    pub async fn create_role<F>(&self, guild_id: GuildId, f: F) -> serenity::Result<Role>
+    where
+        F: FnOnce(&mut EditRole) -> &mut EditRole,
+    {
+        let mut role = EditRole::default();
+        f(&mut role);
+
+        self.discord.create_role(guild_id, role).await
+    }
+
+    /// Change a role's settings
+    pub async fn edit_role<F>(&self, guild_id: GuildId, role_id: RoleId, f: F) -> serenity::Result<Role>
+    where
+        F: FnOnce(&mut EditRole) -> &mut EditRole,
+    {
+        let mut role = EditRole::default();
+        f(&mut role);
+
+        self.discord.edit_role(guild_id, role_id, role).await
+    }
+
+    /// Post a message to a channel
+    pub async fn send_message<F>(&self, channel_id: ChannelId, f: F) -> serenity::Result<Message>
+    where
+        F: for<'b> FnOnce(&'b mut CreateMessage<'static>) -> &'b mut CreateMessage<'static>,
+    {
+        let mut message = CreateMessage::default();
+        f(&mut message);
+
+        self.discord.send_message(channel_id, message).await
+    }
+
+    /// Edit a message the bot posted, replacing it with the edited version
+    ///
+    /// Like serenity's `Message::edit`, the content and embeds are kept
+    /// unless `f` replaces them.
+    pub async fn edit_message<F>(&self, message: &mut Message, f: F) -> serenity::Result<()>
+    where
+        F: for<'b> FnOnce(&'b mut EditMessage<'static>) -> &'b mut EditMessage<'static>,
+    {
+        let mut edit = EditMessage::default();
+        if !message.content.is_empty() {
+            edit.content(&message.content);
+        }
+        edit.set_embeds(message.embeds.iter().cloned().map(CreateEmbed::from).collect());
+        for attachment in &message.attachments {
+            edit.add_existing_attachment(attachment.id);
+        }
+        f(&mut edit);
+
+        *message = self.discord.edit_message(message.channel_id, message.id, edit).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,148 @@
+//! `/group require` and `/group attendee`: required and optional attendees.
+//!
+//! Matching only accepts times where every required member is available and
+//! every required group has enough members there. Everyone else is optional
+//! and only decides between those times, counting with their weight.
+
+use eyre::Result;
+use serenity::{
+    model::application::interaction::{
+        application_command::{ApplicationCommandInteraction, CommandDataOption},
+        InteractionResponseType,
+    },
+    utils::Color,
+};
+use timesync_core::models::discord::{
+    GetDiscordGroupResponse, UpdateDiscordGroupRequest, UpdateGroupMemberRequest,
+};
+
+use crate::handlers::HandlerContext;
+use crate::handlers::schedule::{get_option_bool, get_option_string};
+
+/// Handle the /group require subcommand
+pub async fn handle_group_require(
+    ctx: HandlerContext,
+    command: &ApplicationCommandInteraction,
+    subcommand: &CommandDataOption,
+) -> Result<()> {
+    let Some(group) = find_group(&ctx, command, subcommand).await? else {
+        return Ok(());
+    };
+    let required = get_option_bool(subcommand, "required")
+        .ok_or_else(|| eyre::eyre!("Missing required parameter"))?;
+
+    let update_request = UpdateDiscordGroupRequest {
+        name: None,
+        add_member_ids: None,
+        remove_member_ids: None,
+        required: Some(required),
+    };
+
+    ctx.api.update_discord_group(group.id, &update_request).await?;
+
+    let description = if required {
+        format!("Matches now need enough members of **{}** to be available.", group.name)
+    } else {
+        format!("**{}** is now optional: its members make a time better but never rule one out.", group.name)
+    };
+
+    ctx.create_interaction_response(command, |r| {
+        r.kind(InteractionResponseType::ChannelMessageWithSource)
+            .interaction_response_data(|m| {
+                m.embed(|e| {
+                    e.title(if required { "Group Required" } else { "Group Optional" })
+                        .description(description)
+                        .color(Color::DARK_GREEN)
+                })
+            })
+    }).await?;
+
+    Ok(())
+}
+
+/// Handle the /group attendee subcommand
+pub async fn handle_group_attendee(
+    ctx: HandlerContext,
+    command: &ApplicationCommandInteraction,
+    subcommand: &CommandDataOption,
+) -> Result<()> {
+    let Some(group) = find_group(&ctx, command, subcommand).await? else {
+        return Ok(());
+    };
+    let user_id = get_option_string(subcommand, "user")?;
+
+    let update_request = UpdateGroupMemberRequest {
+        required: get_option_bool(subcommand, "required"),
+        weight: get_option_number(subcommand, "weight"),
+    };
+
+    if update_request.required.is_none() && update_request.weight.is_none() {
+        return respond_error(&ctx, command, "Set `required`, `weight` or both".to_string()).await;
+    }
+
+    let Some(member) = ctx.api.update_group_member(group.id, &user_id, &update_request).await? else {
+        let message = format!("<@{}> isn't a member of **{}**", user_id, group.name);
+        return respond_error(&ctx, command, message).await;
+    };
+
+    let description = format!(
+        "<@{}> is {} for **{}** and counts with weight **{}** when ranking times.",
+        member.discord_id,
+        if member.required { "required" } else { "optional" },
+        group.name,
+        member.weight
+    );
+
+    ctx.create_interaction_response(command, |r| {
+        r.kind(InteractionResponseType::ChannelMessageWithSource)
+            .interaction_response_data(|m| {
+                m.embed(|e| {
+                    e.title("Attendee Updated")
+                        .description(description)
+                        .color(Color::DARK_GREEN)
+                })
+            })
+    }).await?;
+
+    Ok(())
+}
+
+/// Look up the group named by the `name` option, replying if it doesn't exist
+async fn find_group(
+    ctx: &HandlerContext,
+    command: &ApplicationCommandInteraction,
+    subcommand: &CommandDataOption,
+) -> Result<Option<GetDiscordGroupResponse>> {
+    let server_id = command.guild_id
+        .ok_or_else(|| eyre::eyre!("Command must be used in a server"))?
+        .to_string();
+    let name = get_option_string(subcommand, "name")?;
+
+    let group = ctx.api.get_discord_group_by_name(&server_id, &name).await?;
+
+    if group.is_none() {
+        respond_error(ctx, command, format!("Group not found: {}", name)).await?;
+    }
+
+    Ok(group)
+}
+
+async fn respond_error(
+    ctx: &HandlerContext,
+    command: &ApplicationCommandInteraction,
+    message: String,
+) -> Result<()> {
+    ctx.create_interaction_response(command, |r| {
+        r.kind(InteractionResponseType::ChannelMessageWithSource)
+            .interaction_response_data(|m| m.content(message).ephemeral(true))
+    }).await?;
+
+    Ok(())
+}
+
+fn get_option_number(options: &CommandDataOption, name: &str) -> Option<f64> {
+    options.options.iter()
+        .find(|opt| opt.name == name)
+        .and_then(|opt| opt.value.as_ref())
+        .and_then(|val| val.as_f64())
+}
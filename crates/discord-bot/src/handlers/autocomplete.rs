@@ -0,0 +1,119 @@
+//! Autocomplete suggestions for slash command options.
+//!
+//! Discord asks for suggestions while an option marked with
+//! `set_autocomplete(true)` is being typed. Group names are scoped to the
+//! guild the command is used in; timezones are fuzzy searched from the IANA
+//! database bundled with `chrono_tz`.
+
+use eyre::Result;
+use serenity::model::application::interaction::{
+    application_command::CommandDataOption,
+    autocomplete::AutocompleteInteraction,
+};
+
+use crate::handlers::HandlerContext;
+use crate::handlers::schedule::fetch_server_groups;
+use crate::timezones::search_timezones;
+
+/// Discord accepts at most 25 suggestions
+const MAX_CHOICES: usize = 25;
+
+/// Discord rejects suggestion names and values longer than this
+const MAX_CHOICE_LENGTH: usize = 100;
+
+/// Respond to an autocomplete request with suggestions for the focused option
+pub async fn handle_autocomplete(
+    ctx: HandlerContext,
+    autocomplete: &AutocompleteInteraction,
+) -> Result<()> {
+    let Some(focused) = find_focused_option(&autocomplete.data.options) else {
+        return Ok(());
+    };
+    let input = focused.value.as_ref().and_then(|v| v.as_str()).unwrap_or_default();
+
+    let choices: Vec<String> = match (autocomplete.data.name.as_str(), focused.name.as_str()) {
+        ("match", "groups") | ("group", "name") => {
+            let Some(guild_id) = autocomplete.guild_id else {
+                return Ok(());
+            };
+
+            let group_names: Vec<String> = fetch_server_groups(&ctx, &guild_id.to_string())
+                .await?
+                .into_iter()
+                .map(|group| group.name)
+                .collect();
+
+            if focused.name == "groups" {
+                complete_group_list(input, &group_names, MAX_CHOICES)
+            } else {
+                match_group_names(input, &group_names)
+                    .into_iter()
+                    .take(MAX_CHOICES)
+                    .cloned()
+                    .collect()
+            }
+        }
+        (_, "timezone") => search_timezones(input, MAX_CHOICES)
+            .into_iter()
+            .map(|name| name.to_string())
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    ctx.create_autocomplete_response(autocomplete, |r| {
+        for choice in &choices {
+            r.add_string_choice(choice, choice);
+        }
+        r
+    }).await?;
+
+    Ok(())
+}
+
+/// Group names containing the query, names starting with it first
+pub fn match_group_names<'a>(query: &str, group_names: &'a [String]) -> Vec<&'a String> {
+    let query = query.trim().to_lowercase();
+
+    let mut matches: Vec<(bool, &String)> = group_names.iter()
+        .filter_map(|name| {
+            let lower = name.to_lowercase();
+            lower.contains(&query).then(|| (!lower.starts_with(&query), name))
+        })
+        .collect();
+    matches.sort();
+
+    matches.into_iter().map(|(_, name)| name).collect()
+}
+
+/// Complete the last entry of a comma-separated list of group names
+///
+/// Each suggestion is the whole list with the entry being typed replaced by
+/// a matching group, skipping groups that are already listed.
+pub fn complete_group_list(input: &str, group_names: &[String], limit: usize) -> Vec<String> {
+    let mut entries: Vec<&str> = input.split(',').map(str::trim).collect();
+    let current = entries.pop().unwrap_or_default();
+    let entries: Vec<&str> = entries.into_iter().filter(|e| !e.is_empty()).collect();
+
+    match_group_names(current, group_names)
+        .into_iter()
+        .filter(|name| !entries.iter().any(|e| e.eq_ignore_ascii_case(name)))
+        .map(|name| {
+            let mut completed = entries.clone();
+            completed.push(name);
+            completed.join(", ")
+        })
+        .filter(|value| value.len() <= MAX_CHOICE_LENGTH)
+        .take(limit)
+        .collect()
+}
+
+/// Find the option being typed, looking inside subcommands and groups
+fn find_focused_option(options: &[CommandDataOption]) -> Option<&CommandDataOption> {
+    options.iter().find_map(|option| {
+        if option.focused {
+            Some(option)
+        } else {
+            find_focused_option(&option.options)
+        }
+    })
+}
@@ -0,0 +1,1251 @@
+//! The `/match` command and its voting poll.
+//!
+//! The poll's voting rules live in [`crate::poll`]; the handlers here turn
+//! button clicks into poll events and render the results as Discord messages.
+
+use chrono::{Datelike, Utc};
+use eyre::Result;
+use serenity::{
+    builder::{CreateComponents, CreateEmbed},
+    model::{
+        application::component::ButtonStyle,
+        application::interaction::{
+            application_command::ApplicationCommandInteraction,
+            message_component::MessageComponentInteraction,
+            InteractionResponseType,
+        },
+        channel::{AttachmentType, Message},
+        id::RoleId,
+    },
+    utils::Color,
+};
+use timesync_core::heatmap::{render_heatmap, HeatmapOptions, HeatmapSlot};
+use timesync_core::matching::{require_members, GroupConstraint, MemberConstraint};
+use timesync_core::models::discord::{MatchQuery, MatchResult};
+use timesync_core::models::meeting::{CreateMeetingRequest, MeetingStatus};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use crate::handlers::schedule::{get_server_timezone, voter_timezone};
+use crate::handlers::HandlerContext;
+use crate::members::parse_user_mentions;
+use crate::poll::{Poll, PollEvent, PollSetup, PollOutcome, PollResult, SlotInfo, VotingView, MIN_REQUIRED_PER_GROUP};
+use crate::timezones::{format_personal_slot_time, format_slot_time, parse_timezone};
+
+/// File name of the heatmap attached to match polls
+const HEATMAP_FILENAME: &str = "availability.png";
+
+/// Reply to members of none of the polled groups
+const NOT_ELIGIBLE_MESSAGE: &str = "You are not a member of any of the groups in this poll, so you cannot vote.";
+
+/// Handle the /match command
+pub async fn handle_match_command(
+    ctx: HandlerContext,
+    command: &ApplicationCommandInteraction,
+) -> Result<()> {
+    // Get the groups
+    let groups_str = command.data.options.first()
+        .ok_or_else(|| eyre::eyre!("Missing groups parameter"))?
+        .value.as_ref()
+        .and_then(|val| val.as_str())
+        .ok_or_else(|| eyre::eyre!("Invalid groups parameter"))?;
+    
+    // Get the guild (server) ID
+    let server_id = command.guild_id
+        .ok_or_else(|| eyre::eyre!("Command must be used in a server"))?
+        .to_string();
+    
+    // Get optional parameters
+    let min_per_group = command.data.options.get(1)
+        .and_then(|opt| opt.value.as_ref())
+        .and_then(|val| val.as_i64())
+        .unwrap_or(1);
+        
+    // Get slot duration (default to 120 minutes / 2 hours)
+    let slot_duration = command.data.options.get(2)
+        .and_then(|opt| opt.value.as_ref())
+        .and_then(|val| val.as_i64())
+        .unwrap_or(120);
+        
+    // Get max days to display (default to 7, clamp between 1-7)
+    let display_days = command.data.options.get(3)
+        .and_then(|opt| opt.value.as_ref())
+        .and_then(|val| val.as_i64())
+        .unwrap_or(7)
+        .clamp(1, 7);
+        
+    // Members who must all be able to attend
+    let required_ids = command.data.options.iter()
+        .find(|opt| opt.name == "required")
+        .and_then(|opt| opt.value.as_ref())
+        .and_then(|val| val.as_str())
+        .map(parse_user_mentions)
+        .unwrap_or_default();
+    
+    // Parse the group names
+    let group_names: Vec<String> = groups_str
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    
+    if group_names.is_empty() {
+        ctx.create_interaction_response(command, |r| {
+            r.kind(InteractionResponseType::ChannelMessageWithSource)
+                .interaction_response_data(|m| {
+                    m.content("No valid groups specified").ephemeral(true)
+                })
+        }).await?;
+        
+        return Ok(());
+    }
+    
+    // Find the group IDs for these names
+    let mut group_ids = Vec::new();
+    
+    for name in &group_names {
+        match ctx.api.get_discord_group_by_name(&server_id, name).await? {
+            Some(group) => group_ids.push(group.id),
+            None => {
+                ctx.create_interaction_response(command, |r| {
+                    r.kind(InteractionResponseType::ChannelMessageWithSource)
+                        .interaction_response_data(|m| {
+                            m.content(format!("Group not found: {}", name)).ephemeral(true)
+                        })
+                }).await?;
+                
+                return Ok(());
+            }
+        }
+    }
+    
+    start_match_poll(
+        &ctx,
+        command,
+        MatchPollOptions {
+            group_ids,
+            group_names,
+            server_id,
+            min_per_group,
+            slot_duration,
+            display_days,
+            required_ids,
+            rescheduled_from: None,
+        },
+    )
+    .await
+}
+
+/// Parameters for opening a meeting time poll
+pub(crate) struct MatchPollOptions {
+    pub group_ids: Vec<uuid::Uuid>,
+    pub group_names: Vec<String>,
+    pub server_id: String,
+    pub min_per_group: i64,
+    pub slot_duration: i64,
+    pub display_days: i64,
+    /// Members who must all be able to attend
+    pub required_ids: Vec<String>,
+    /// The meeting this poll replaces, when opened by `/meeting reschedule`
+    pub rescheduled_from: Option<uuid::Uuid>,
+}
+
+/// Find matching times for the given groups and post a voting poll for them
+///
+/// Shared by /match and `/meeting reschedule`. The interaction must not have
+/// been acknowledged yet; this defers it and then edits the original response.
+pub(crate) async fn start_match_poll(
+    ctx: &HandlerContext,
+    command: &ApplicationCommandInteraction,
+    options: MatchPollOptions,
+) -> Result<()> {
+    let MatchPollOptions {
+        group_ids,
+        group_names,
+        server_id,
+        min_per_group,
+        slot_duration,
+        display_days,
+        required_ids,
+        rescheduled_from,
+    } = options;
+    
+    // Request a high number of matches to avoid running out
+    let count = 50; // Set to a high value instead of using a parameter
+    
+    // Acknowledge the command first to buy time for processing
+    ctx.create_interaction_response(command, |r| {
+        r.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+    }).await?;
+    
+    // Make API request to get matches
+    let match_query = MatchQuery {
+        group_ids: group_ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(","),
+        min_per_group: Some(min_per_group as usize),
+        count: Some(count),
+        required_ids: (!required_ids.is_empty()).then(|| required_ids.join(",")),
+        ..MatchQuery::default()
+    };
+    
+    let match_response = ctx.api.match_availability(&match_query).await?;
+    
+    if match_response.matches.is_empty() {
+        let mut description = "There are no time slots where the specified groups have common availability.".to_string();
+        
+        if !match_response.near_misses.is_empty() {
+            let tz = parse_timezone(&get_server_timezone(ctx, &server_id).await);
+            description.push_str("\n\n**Closest times** (ask these members to update their schedules):\n");
+            description.push_str(&format_near_misses(&match_response.near_misses, tz));
+        }
+        
+        ctx.edit_original_interaction_response(command, |m| {
+            m.embed(|e| {
+                e.title("No Matching Times Found")
+                    .description(description)
+                    .color(Color::RED)
+            })
+        }).await?;
+        
+        return Ok(());
+    }
+    
+    // Collect all eligible voters (members of the chosen groups)
+    let mut eligible_voter_ids = std::collections::HashSet::new();
+    let mut group_members = HashMap::new();
+    
+    // Get all members of all groups, along with who is required and has a schedule
+    let mut constraints = Vec::new();
+    let mut role_mentions = Vec::new();
+    let mut linked_member_ids = HashSet::new();
+    for group_id in &group_ids {
+        let group = ctx.api.get_discord_group(*group_id).await?;
+        let required = group.as_ref().is_none_or(|group| group.required);
+        
+        if let Some(role_id) = group.as_ref().and_then(|group| group.role_id.as_ref()) {
+            role_mentions.push(format!("<@&{}>", role_id));
+        }
+        
+        // Create a vector of member IDs for this group
+        let mut member_ids = Vec::new();
+        let mut member_constraints = Vec::new();
+        
+        for member in group.map(|group| group.members).unwrap_or_default() {
+            if member.schedule_id.is_some() {
+                linked_member_ids.insert(member.discord_id.clone());
+            }
+            
+            eligible_voter_ids.insert(member.discord_id.clone());
+            member_ids.push(member.discord_id.clone());
+            member_constraints.push(MemberConstraint {
+                discord_id: member.discord_id,
+                required: member.required,
+                weight: member.weight,
+            });
+        }
+        
+        constraints.push(GroupConstraint {
+            id: *group_id,
+            required,
+            min_available: MIN_REQUIRED_PER_GROUP.min(member_ids.len()),
+            members: member_constraints,
+        });
+        
+        // Store the member list for this group
+        group_members.insert(*group_id, member_ids);
+    }
+    require_members(&mut constraints, &required_ids);
+    
+    // Get the server's timezone or use UTC as default
+    let timezone = get_server_timezone(ctx, &server_id).await;
+    
+    // Organize time slots by day
+    let day_slots = organize_slots_by_day(&match_response.matches, &timezone, slot_duration);
+    
+    // Create initial poll with the new time slot voting system
+    let mut poll = Poll::new(PollSetup {
+        group_names: group_names.clone(),
+        min_per_group,
+        timezone: timezone.clone(),
+        eligible_voters: eligible_voter_ids,
+        group_members,
+        constraints,
+        slot_duration,
+        display_days,
+        day_slots,
+        group_ids: group_ids.clone(),
+        server_id: server_id.clone(),
+        channel_id: command.channel_id,
+        organizer_id: command.user.id.to_string(),
+        rescheduled_from,
+    });
+    
+    let role_ping = if !role_mentions.is_empty() {
+        // More direct role ping format to ensure mentions trigger notifications
+        format!("{} 🗣️ A meeting time has been proposed! Please vote on your availability!", role_mentions.join(" "))
+    } else {
+        String::new()
+    };
+    
+    // Everyone starts with no slots selected, then members with a saved
+    // schedule have every slot pre-selected
+    for member_id in linked_member_ids {
+        poll.apply(PollEvent::SelectAll { voter: member_id });
+    }
+    
+    // First store the poll before creating the message
+    let mut polls = ctx.active_polls.write().await;
+    // Create a temporary message ID for this poll
+    let temp_message_id = serenity::model::id::MessageId(command.id.0);
+    polls.insert(temp_message_id, poll.clone());
+    drop(polls); // Drop the lock before doing more operations
+    
+    // Send the role pings in a separate message to ensure they trigger notifications
+    if !role_ping.is_empty() {
+        ctx.create_followup_message(command, |m| {
+            m.content(&role_ping)
+        }).await?;
+    }
+    
+    // Then edit the original response with the main message UI
+    let main_message = ctx.edit_original_interaction_response(command, |m| {
+        m.embed(|e| proposal_embed(e, &poll))
+            .components(proposal_components)
+    }).await?;
+    
+    // Attach a heatmap of everyone's availability, since the text summary only
+    // lists the best slots
+    if let Some(image) = render_poll_heatmap(&poll) {
+        let heatmap = ctx.create_followup_message(command, |m| {
+            m.add_file(AttachmentType::Bytes {
+                data: image.into(),
+                filename: HEATMAP_FILENAME.to_string(),
+            })
+            .embed(|e| {
+                e.title("Availability by Hour")
+                    .description(format!("Brighter cells mean more members are free. Times are in {}.", timezone))
+                    .attachment(HEATMAP_FILENAME)
+                    .color(Color::GOLD)
+            })
+        }).await;
+        
+        if let Err(e) = heatmap {
+            tracing::warn!("Failed to send availability heatmap: {:?}", e);
+        }
+    }
+    
+    // Update the poll store with the message ID
+    let mut polls = ctx.active_polls.write().await;
+    // Remove the temporary entry with command.id
+    let temp_message_id = serenity::model::id::MessageId(command.id.0);
+    let poll_data = polls.remove(&temp_message_id).unwrap_or(poll);
+    // Insert with the correct message ID
+    polls.insert(main_message.id, poll_data);
+    drop(polls);
+    
+    Ok(())
+}
+
+/// Render the poll's slots as a days × hours heatmap PNG
+///
+/// Cells are shaded relative to the number of eligible voters. Returns `None`
+/// if there is nothing to draw or the image can't be rendered.
+fn render_poll_heatmap(poll: &Poll) -> Option<Vec<u8>> {
+    let tz = parse_timezone(&poll.setup().timezone);
+    let slots: Vec<HeatmapSlot> = poll.setup().day_slots.values()
+        .flatten()
+        .map(|slot| HeatmapSlot {
+            start: slot.start,
+            end: slot.end,
+            available: slot.available_users.len(),
+        })
+        .collect();
+    
+    let first_day = slots.iter().map(|slot| slot.start).min()?.with_timezone(&tz).date_naive();
+    let last_day = slots.iter().map(|slot| slot.start).max()?.with_timezone(&tz).date_naive();
+    let voters = poll.setup().eligible_voters.len();
+    
+    let options = HeatmapOptions {
+        first_day,
+        days: ((last_day - first_day).num_days() + 1).clamp(1, 7) as u32,
+        tz,
+        total: (voters > 0).then_some(voters),
+    };
+    
+    match render_heatmap(&slots, &options) {
+        Ok(image) => Some(image),
+        Err(e) => {
+            tracing::warn!("Failed to render availability heatmap: {:?}", e);
+            None
+        }
+    }
+}
+
+/// Generate a summary message for the main match message
+fn generate_summary_message(poll: &Poll) -> String {
+    let mut message = String::new();
+    
+    // Get the date range
+    let date_range = if !poll.setup().day_slots.is_empty() {
+        let tz = parse_timezone(&poll.setup().timezone);
+        
+        // Get the first and last day
+        let first_day_idx = poll.setup().day_slots.keys().min().unwrap_or(&0);
+        let last_day_idx = poll.setup().day_slots.keys().max().unwrap_or(&0);
+        
+        // Get the dates from the first slots of each day
+        if let (Some(first_day_slots), Some(last_day_slots)) = (poll.setup().day_slots.get(first_day_idx), poll.setup().day_slots.get(last_day_idx)) {
+            if !first_day_slots.is_empty() && !last_day_slots.is_empty() {
+                let first_date = first_day_slots[0].start.with_timezone(&tz).format("%B %d, %Y").to_string();
+                let last_date = last_day_slots[0].start.with_timezone(&tz).format("%B %d, %Y").to_string();
+                
+                if first_date == last_date {
+                    format!("**{}**", first_date)
+                } else {
+                    format!("**{} to {}**", first_date, last_date)
+                }
+            } else {
+                "Date range unavailable".to_string()
+            }
+        } else {
+            "Date range unavailable".to_string()
+        }
+    } else {
+        "Date range unavailable".to_string()
+    };
+    
+    message.push_str(&format!("{}\n\n", date_range));
+
+    // Show how far each group is with locking in
+    let view = poll.proposal_view();
+    message.push_str("**Voting Progress:**\n");
+    for group in &view.groups {
+        message.push_str(&format!(
+            "• **{}**: {}/{} members locked in votes (min required: {})\n",
+            group.name,
+            group.locked(),
+            group.members,
+            MIN_REQUIRED_PER_GROUP
+        ));
+    }
+    
+    message.push_str(&format!("\n**Total: {} of {} members have locked in their votes**\n\n", 
+        view.locked, 
+        view.eligible
+    ));
+    
+    // Show the best slot so far (if any)
+    if let Some(best) = &view.best {
+        let tz = parse_timezone(&poll.setup().timezone);
+        let slot = &best.choice.slot;
+        let day_date = slot.start.with_timezone(&tz).format("%A, %B %d, %Y").to_string();
+        
+        message.push_str("**Most Popular Time Slot:**\n");
+        message.push_str(&format!("• **{}** at **{}**\n", day_date, slot.formatted_time));
+        message.push_str(&format!("• **{} members available**\n", best.choice.attendees.len()));
+        
+        for (group_name, available, needed) in &best.groups {
+            message.push_str(&format!(
+                "• **{}**: {}/{} members available {}\n",
+                group_name,
+                available,
+                needed,
+                if available >= needed { "✅" } else { "❌" }
+            ));
+        }
+        
+        if best.meets_all_requirements() {
+            message.push_str("\n✅ **This time slot meets all group requirements!**\n");
+        } else {
+            message.push_str("\n❌ **This time slot does not meet all group requirements yet.**\n");
+        }
+    } else {
+        message.push_str("**No suitable time slot found yet**\n");
+        message.push_str("More members need to vote to find a time that works for everyone.\n");
+    }
+    
+    // Warn that the poll is about to finish, while votes can still be unlocked
+    match &view.pending {
+        Some(PollResult::Confirmed(_)) => message.push_str(
+            "\n⏳ **Enough members have locked in, so this time will be confirmed shortly.** Unlock your votes now if you need to change them.\n"
+        ),
+        Some(_) => message.push_str(
+            "\n⏳ **Not enough members are available, so this poll will close shortly.** Unlock your votes now if you can still make it.\n"
+        ),
+        None => {}
+    }
+    
+    // Add instructions
+    message.push_str("\n**How to Vote:**\n");
+    message.push_str("• Click **Edit Your Availability** to select the times you're available\n");
+    message.push_str("• Your votes are automatically saved as you select them\n");
+    message.push_str("• When you're done, click **Lock In My Votes** to finalize your selections\n");
+    message.push_str("• Click **Unlock My Votes** to change them again before the poll finishes\n");
+    message.push_str("• If you don't select any times, you'll be marked as unavailable\n");
+    message.push_str("• Members with saved schedules have their slots pre-selected\n");
+    message.push_str("• The organizer can **Finalize Now** with the votes locked in so far\n");
+    
+    message
+}
+
+/// Fill in the embed of the main poll message
+fn proposal_embed<'a>(e: &'a mut CreateEmbed, poll: &Poll) -> &'a mut CreateEmbed {
+    e.title("Meeting Time Proposal")
+        .description(generate_summary_message(poll))
+        .color(Color::GOLD)
+        .footer(|f| f.text(format!(
+            "Min members per group: {} • Slot duration: {} min • Timezone: {}",
+            MIN_REQUIRED_PER_GROUP,
+            poll.setup().slot_duration,
+            poll.setup().timezone
+        )))
+}
+
+/// Add the buttons of the main poll message
+fn proposal_components(c: &mut CreateComponents) -> &mut CreateComponents {
+    c.create_action_row(|row| {
+        row.create_button(|b| {
+            b.custom_id("open_voting")
+                .label("Edit Your Availability")
+                .style(ButtonStyle::Primary)
+                .emoji('✏')
+        })
+        .create_button(|b| {
+            b.custom_id("lock_votes")
+                .label("Lock In My Votes")
+                .style(ButtonStyle::Success)
+                .emoji('🔒')
+        })
+        .create_button(|b| {
+            b.custom_id("unlock_votes")
+                .label("Unlock My Votes")
+                .style(ButtonStyle::Secondary)
+                .emoji('🔓')
+        })
+    })
+    .create_action_row(|row| {
+        row.create_button(|b| {
+            b.custom_id("finalize_poll")
+                .label("Finalize Now")
+                .style(ButtonStyle::Danger)
+        })
+        .create_button(|b| {
+            b.custom_id("cancel_poll")
+                .label("Cancel Poll")
+                .style(ButtonStyle::Secondary)
+        })
+    })
+}
+
+/// Add the button of a finished poll message
+fn finished_components(c: &mut CreateComponents) -> &mut CreateComponents {
+    c.create_action_row(|row| {
+        row.create_button(|b| {
+            b.custom_id("reopen_poll")
+                .label("Reopen Poll")
+                .style(ButtonStyle::Secondary)
+        })
+    })
+}
+
+/// Organize time slots by day
+fn organize_slots_by_day(
+    matches: &[MatchResult], 
+    timezone_str: &str, 
+    slot_duration: i64
+) -> HashMap<usize, Vec<SlotInfo>> {
+    let mut day_slots = HashMap::new();
+    let tz = chrono_tz::Tz::from_str(timezone_str).unwrap_or(chrono_tz::UTC);
+    
+    // Convert all match times to slots
+    for (match_idx, match_result) in matches.iter().enumerate() {
+        // Convert to local timezone for display
+        let start_local = match_result.start.with_timezone(&tz);
+        
+        // Calculate day index (days since epoch for the start date)
+        let day_idx = start_local.date_naive().num_days_from_ce() as usize;
+        
+        // Create time chunks for this match based on slot_duration
+        let mut current_time = match_result.start;
+        let match_end = match_result.end;
+        
+        let duration_chunk = chrono::Duration::minutes(slot_duration);
+        
+        while current_time < match_end {
+            let chunk_end = std::cmp::min(current_time + duration_chunk, match_end);
+            
+            // Format the time for display
+            let formatted_time = format_slot_time(current_time, chunk_end, tz);
+            
+            // Create a unique ID for this slot
+            let slot_id = format!("{}_{}", match_idx, current_time.timestamp());
+            
+            // Create the slot info
+            let slot = SlotInfo {
+                id: slot_id,
+                start: current_time,
+                end: chunk_end,
+                formatted_time,
+                available_users: match_result.groups.iter()
+                    .flat_map(|g| g.available_users.clone())
+                    .collect(),
+                tentative_users: match_result.groups.iter()
+                    .flat_map(|g| g.tentative_users.clone())
+                    .collect(),
+            };
+            
+            // Add to the day's slots
+            day_slots.entry(day_idx).or_insert_with(Vec::new).push(slot);
+            
+            // Move to next chunk
+            current_time = chunk_end;
+        }
+    }
+    
+    // Sort slots within each day by start time
+    for slots in day_slots.values_mut() {
+        slots.sort_by_key(|slot| slot.start);
+    }
+    
+    // Remap the days to be 0-indexed in chronological order
+    let day_indices: Vec<usize> = day_slots.keys().cloned().collect();
+    if !day_indices.is_empty() {
+        let mut remapped_slots = HashMap::new();
+        let mut sorted_days: Vec<usize> = day_indices.clone();
+        sorted_days.sort();
+        
+        for (i, day) in sorted_days.iter().enumerate() {
+            if let Some(slots) = day_slots.remove(day) {
+                remapped_slots.insert(i, slots);
+            }
+        }
+        
+        return remapped_slots;
+    }
+    
+    day_slots
+}
+
+/// Handle button interactions for scheduling matches
+pub async fn handle_component_interaction(
+    ctx: HandlerContext,
+    component: &mut MessageComponentInteraction
+) -> Result<()> {
+    let custom_id = component.data.custom_id.clone();
+    
+    // Buttons on the personal voting interface edit that ephemeral message,
+    // so acknowledge them first to keep the interaction alive
+    let is_voting_button = ["prev_day", "next_day", "select_all", "clear_all"]
+        .contains(&custom_id.as_str()) || custom_id.starts_with("slot_");
+    
+    if is_voting_button
+        && let Err(e) = ctx.create_interaction_response(&*component, |r| {
+            r.kind(InteractionResponseType::DeferredUpdateMessage)
+        }).await
+    {
+        tracing::warn!("Failed to acknowledge voting interface interaction, but continuing: {}", e);
+    }
+    
+    // Helper to safely handle interaction with error recovery
+    let result = match custom_id.as_str() {
+        // Main message buttons
+        "open_voting" => handle_open_voting_interface(ctx.clone(), component).await,
+        "lock_votes" => {
+            let voter = component.user.id.to_string();
+            handle_poll_event(ctx.clone(), component, PollEvent::Lock { voter }).await
+        }
+        "unlock_votes" => {
+            let voter = component.user.id.to_string();
+            handle_poll_event(ctx.clone(), component, PollEvent::Unlock { voter }).await
+        }
+        
+        // Organizer buttons
+        "finalize_poll" => handle_poll_event(ctx.clone(), component, PollEvent::Deadline).await,
+        "cancel_poll" => handle_poll_event(ctx.clone(), component, PollEvent::Cancel).await,
+        "reopen_poll" => handle_poll_event(ctx.clone(), component, PollEvent::Reopen).await,
+        
+        // Personal voting interface buttons
+        _ if is_voting_button => handle_voting_button(ctx.clone(), component, &custom_id).await,
+        _ => Ok(()),
+    };
+    
+    // Properly handle errors with a user-friendly follow-up message
+    if let Err(e) = result {
+        tracing::error!("Error handling component interaction: \n{}", e);
+        
+        // When an error occurs, try to send a follow-up message
+        // This is a fallback in case the interaction can't be acknowledged
+        if !is_voting_button {
+            // Only attempt to send follow-up for main message interactions
+            // as we may have already used up our response for ephemeral ones
+            let _ = ctx.create_followup_message(&*component, |m| {
+                m.content("There was an error processing your request. Please try again.".to_string())
+                    .ephemeral(true)
+            }).await;
+        }
+        
+        return Err(e);
+    }
+    
+    Ok(())
+}
+
+/// Handle opening the personal voting interface
+async fn handle_open_voting_interface(
+    ctx: HandlerContext,
+    component: &mut MessageComponentInteraction,
+) -> Result<()> {
+    // Get the voter ID
+    let voter_id = component.user.id.to_string();
+    
+    // Get the poll associated with the main message
+    let polls = ctx.active_polls.read().await;
+    let poll = polls.get(&component.message.id)
+        .ok_or_else(|| eyre::eyre!("No active poll found for this message"))?
+        .clone(); // Clone to avoid holding the read lock
+    drop(polls);
+    
+    if !poll.is_eligible(&voter_id) {
+        ctx.create_interaction_response(&*component, |r| {
+            r.kind(InteractionResponseType::ChannelMessageWithSource)
+                .interaction_response_data(|m| m.content(NOT_ELIGIBLE_MESSAGE).ephemeral(true))
+        }).await?;
+        
+        return Ok(());
+    }
+    
+    // Render the personal interface in the voter's own timezone
+    let voter_tz = parse_timezone(&voter_timezone(&ctx, &voter_id).await.unwrap_or_else(|| poll.setup().timezone.clone()));
+    let view = poll.voting_view(&voter_id);
+    
+    // Send an ephemeral message, visible only to the user who clicked the button
+    ctx.create_interaction_response(&*component, |r| {
+        r.kind(InteractionResponseType::ChannelMessageWithSource)
+            .interaction_response_data(|m| {
+                m.ephemeral(true)
+                    .embed(|e| voting_embed(e, &view, voter_tz))
+                    .components(|c| voting_components(c, &view, voter_tz))
+            })
+    }).await?;
+    
+    Ok(())
+}
+
+/// Handle the day navigation, slot and select/clear buttons of the personal voting interface
+///
+/// The interaction has already been acknowledged, so the interface is
+/// redrawn by editing the original response.
+async fn handle_voting_button(
+    ctx: HandlerContext,
+    component: &mut MessageComponentInteraction,
+    custom_id: &str,
+) -> Result<()> {
+    let voter_id = component.user.id.to_string();
+    
+    // The voting interface is a separate ephemeral message, so find the open
+    // poll this voter is taking part in
+    let mut polls = ctx.active_polls.write().await;
+    let poll_message_id = if polls.contains_key(&component.message.id) {
+        component.message.id
+    } else {
+        polls.iter()
+            .find(|(_, poll)| poll.result().is_none() && poll.is_eligible(&voter_id))
+            .map(|(message_id, _)| *message_id)
+            .ok_or_else(|| eyre::eyre!("No active poll found for this user"))?
+    };
+    let poll = polls.get_mut(&poll_message_id)
+        .ok_or_else(|| eyre::eyre!("Poll not found for message ID"))?;
+    
+    let outcome = match custom_id {
+        "prev_day" => poll.apply(PollEvent::PreviousDay { voter: voter_id.clone() }),
+        "next_day" => poll.apply(PollEvent::NextDay { voter: voter_id.clone() }),
+        "select_all" => poll.apply(PollEvent::SelectAll { voter: voter_id.clone() }),
+        "clear_all" => poll.apply(PollEvent::ClearAll { voter: voter_id.clone() }),
+        _ => poll.apply(PollEvent::Toggle {
+            voter: voter_id.clone(),
+            slot_id: custom_id.strip_prefix("slot_").unwrap_or_default().to_string(),
+        }),
+    };
+    let poll = poll.clone();
+    drop(polls);
+    
+    let refusal = match outcome {
+        PollOutcome::NotEligible => Some(NOT_ELIGIBLE_MESSAGE),
+        PollOutcome::VotesLocked => Some("Your votes are locked in. Click **Unlock My Votes** on the poll to change them."),
+        PollOutcome::AlreadyFinished => Some("This poll has already finished."),
+        _ => None,
+    };
+    if let Some(refusal) = refusal {
+        let _ = ctx.create_followup_message(&*component, |m| {
+            m.content(refusal).ephemeral(true)
+        }).await;
+        
+        return Ok(());
+    }
+    
+    // Render the personal interface in the voter's own timezone
+    let voter_tz = parse_timezone(&voter_timezone(&ctx, &voter_id).await.unwrap_or_else(|| poll.setup().timezone.clone()));
+    let view = poll.voting_view(&voter_id);
+    
+    ctx.edit_original_interaction_response(&*component, |m| {
+        m.embed(|e| voting_embed(e, &view, voter_tz))
+            .components(|c| voting_components(c, &view, voter_tz))
+    }).await?;
+    
+    Ok(())
+}
+
+/// Fill in the embed of a voter's personal voting interface
+fn voting_embed<'a>(e: &'a mut CreateEmbed, view: &VotingView, tz: chrono_tz::Tz) -> &'a mut CreateEmbed {
+    e.title(format!("Your Availability - Day {} of {}", view.day + 1, view.day_count))
+        .description(format_personal_time_slots(view, tz))
+        .color(Color::GOLD)
+        .footer(|f| f.text("Select all times when you are available"))
+}
+
+/// Add the slot, day navigation and select/clear buttons of a voter's personal voting interface
+fn voting_components<'a>(c: &'a mut CreateComponents, view: &VotingView, tz: chrono_tz::Tz) -> &'a mut CreateComponents {
+    let shown_date = shown_date(view, tz);
+    
+    // Selected slots are green, with everyone's vote count in brackets
+    for chunk in view.slots.chunks(5) {
+        c.create_action_row(|row| {
+            for slot in chunk {
+                row.create_button(|b| {
+                    b.custom_id(format!("slot_{}", slot.slot_id))
+                        .label(format!(
+                            "{} [{}]",
+                            format_personal_slot_time(slot.start, slot.end, shown_date, tz),
+                            slot.votes
+                        ))
+                        .style(if slot.selected { ButtonStyle::Success } else { ButtonStyle::Secondary })
+                        .disabled(view.locked)
+                });
+            }
+            row
+        });
+    }
+    
+    c.create_action_row(|row| {
+        row.create_button(|b| {
+            b.custom_id("prev_day")
+                .label("◀️ Previous Day")
+                .style(ButtonStyle::Primary)
+                .disabled(!view.has_previous_day)
+        })
+        .create_button(|b| {
+            b.custom_id("next_day")
+                .label("Next Day ▶️")
+                .style(ButtonStyle::Primary)
+                .disabled(!view.has_next_day)
+        })
+    })
+    .create_action_row(|row| {
+        row.create_button(|b| {
+            b.custom_id("select_all")
+                .label("Select All Days")
+                .style(ButtonStyle::Success)
+                .disabled(view.locked)
+        })
+        .create_button(|b| {
+            b.custom_id("clear_all")
+                .label("Clear All Days")
+                .style(ButtonStyle::Danger)
+                .disabled(view.locked)
+        })
+    })
+}
+
+/// Format time slots for display in the personal interface, in the voter's timezone
+fn format_personal_time_slots(view: &VotingView, tz: chrono_tz::Tz) -> String {
+    let Some(first_slot) = view.slots.first() else {
+        return "No time slots available for this day.".to_string();
+    };
+    
+    // Get the date from the first slot's start time
+    let day_date = first_slot.start.with_timezone(&tz).format("%A, %B %d, %Y").to_string();
+    
+    let mut message = format!(
+        "**{}**\nTimes are shown in **{}**. Use `/timezone me set` to change this.\n\nSelect all time slots when you are available:\n\n",
+        day_date,
+        tz.name()
+    );
+    
+    // Show which slots the user has selected
+    message.push_str("**Selected Time Slots:**\n");
+    
+    if !view.any_selected {
+        message.push_str("You haven't selected any time slots yet.\n");
+    } else {
+        message.push_str(&format!("You've selected {} time slots for this day.\n", view.selected_on_day()));
+    }
+    
+    // Display lock status
+    let lock_status = if view.locked {
+        "✅ Your votes are locked in. Click 'Unlock My Votes' on the main message to change them."
+    } else {
+        "❌ You have not locked in your votes yet."
+    };
+    
+    message.push_str(&format!("\n**Vote Status:** {}\n\n", lock_status));
+    
+    // Add instructions
+    message.push_str("Click on a time to toggle your availability. Green buttons indicate times you're available for.\n");
+    message.push_str("Use the navigation buttons to switch between days.\n");
+    message.push_str("Use 'Select All Days' to mark yourself as available for all time slots across all days.\n");
+    message.push_str("To finalize your votes, click 'Lock In My Votes' on the main message.\n");
+    
+    message
+}
+
+/// Handle the buttons on the main poll message that change the poll
+///
+/// Voters lock and unlock their own votes. Finalizing, cancelling and reopening
+/// is left to whoever ran `/match` and members with the poll manager role.
+async fn handle_poll_event(
+    ctx: HandlerContext,
+    component: &mut MessageComponentInteraction,
+    event: PollEvent,
+) -> Result<()> {
+    let unlocking = matches!(event, PollEvent::Unlock { .. });
+    let repeated = match event {
+        PollEvent::Lock { .. } => "Your votes are already locked in.",
+        PollEvent::Unlock { .. } => "Your votes aren't locked in.",
+        _ => "This poll is still open.",
+    };
+    
+    // Get the poll associated with this message
+    let mut polls = ctx.active_polls.write().await;
+    let poll = polls.get_mut(&component.message.id)
+        .ok_or_else(|| eyre::eyre!("No active poll found for this message"))?;
+    
+    let outcome = if matches!(event, PollEvent::Deadline | PollEvent::Cancel | PollEvent::Reopen) && !can_manage_poll(&ctx, component, poll) {
+        None
+    } else {
+        Some(poll.apply(event))
+    };
+    
+    // A reopened poll no longer stands behind the meeting it confirmed
+    let replaced_meeting = match outcome {
+        Some(PollOutcome::Reopened { meeting_id }) => meeting_id,
+        _ => None,
+    };
+    let poll = poll.clone();
+    drop(polls);
+    
+    let refusal = match &outcome {
+        None => Some("Only the organizer of this poll or poll managers can do that."),
+        Some(PollOutcome::NotEligible) => Some(NOT_ELIGIBLE_MESSAGE),
+        Some(PollOutcome::AlreadyFinished) => Some("This poll has already finished."),
+        Some(PollOutcome::Unchanged) => Some(repeated),
+        _ => None,
+    };
+    let (Some(outcome), None) = (outcome, refusal) else {
+        ctx.create_interaction_response(&*component, |r| {
+            r.kind(InteractionResponseType::ChannelMessageWithSource)
+                .interaction_response_data(|m| m.content(refusal.unwrap_or_default()).ephemeral(true))
+        }).await?;
+        
+        return Ok(());
+    };
+    
+    // Acknowledge the interaction before updating the message
+    let _ = ctx.create_interaction_response(&*component, |r| {
+        r.kind(InteractionResponseType::DeferredUpdateMessage)
+    }).await;
+    
+    if let PollOutcome::Finished(result) = &outcome {
+        return finish_poll(&ctx, &mut component.message, &poll, result).await;
+    }
+    
+    // Otherwise, update the message with the latest voting status
+    ctx.edit_message(&mut component.message, |m| {
+        m.content("")
+            .embed(|e| proposal_embed(e, &poll))
+            .components(proposal_components)
+    }).await?;
+    
+    if let Some(meeting_id) = replaced_meeting {
+        if let Err(e) = ctx.api.update_meeting_status(meeting_id, MeetingStatus::Cancelled).await {
+            tracing::error!("Failed to cancel meeting {} of reopened poll: {:?}", meeting_id, e);
+        }
+        
+        // Recording the meeting marked the one it replaced as rescheduled,
+        // which stands again until the poll confirms another time
+        if let Some(original_id) = poll.setup().rescheduled_from
+            && let Err(e) = ctx.api.update_meeting_status(original_id, MeetingStatus::Scheduled).await
+        {
+            tracing::error!("Failed to restore meeting {} of reopened poll: {:?}", original_id, e);
+        }
+    }
+    
+    let grace_period = ctx.config.poll_grace_period();
+    let confirmation = match outcome {
+        PollOutcome::Settled { round, .. } if grace_period.is_zero() => {
+            return finish_after_grace_period(ctx, component.message.clone(), round).await;
+        }
+        PollOutcome::Settled { round, .. } => {
+            let message = component.message.clone();
+            let ctx = ctx.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(grace_period).await;
+                if let Err(e) = finish_after_grace_period(ctx, message, round).await {
+                    tracing::error!("Failed to finish poll after its grace period: {:?}", e);
+                }
+            });
+            
+            format!(
+                "{} This poll finishes in {} seconds unless someone unlocks their votes.",
+                if unlocking { "Your votes are unlocked." } else { "Your votes have been locked in!" },
+                grace_period.as_secs()
+            )
+        }
+        PollOutcome::Locked => "Your votes have been locked in!".to_string(),
+        PollOutcome::Unlocked => "Your votes are unlocked. Lock them in again once you've updated your availability.".to_string(),
+        PollOutcome::Reopened { .. } => "The poll is open again. Everyone needs to lock in their votes again.".to_string(),
+        _ => return Ok(()),
+    };
+    
+    ctx.create_followup_message(&*component, |m| {
+        m.ephemeral(true)
+            .content(confirmation)
+    }).await?;
+    
+    Ok(())
+}
+
+/// Finish a settled poll, unless its lock-ins changed during the grace period
+async fn finish_after_grace_period(ctx: HandlerContext, mut message: Message, round: u32) -> Result<()> {
+    let mut polls = ctx.active_polls.write().await;
+    let Some(poll) = polls.get_mut(&message.id) else {
+        return Ok(());
+    };
+    
+    let outcome = poll.apply(PollEvent::GracePeriodOver { round });
+    let poll = poll.clone();
+    drop(polls);
+    
+    match outcome {
+        PollOutcome::Finished(result) => finish_poll(&ctx, &mut message, &poll, &result).await,
+        _ => Ok(()),
+    }
+}
+
+/// Check whether the user who clicked can finalize, cancel or reopen the poll
+///
+/// That's whoever ran `/match`, and members with the configured poll manager role.
+fn can_manage_poll(ctx: &HandlerContext, component: &MessageComponentInteraction, poll: &Poll) -> bool {
+    if component.user.id.to_string() == poll.setup().organizer_id {
+        return true;
+    }
+    
+    ctx.config.poll_manager_role_id.is_some_and(|role_id| {
+        component.member.as_ref().is_some_and(|member| member.roles.contains(&RoleId(role_id)))
+    })
+}
+
+/// Replace the poll message with how the poll ended
+///
+/// A confirmed time is recorded as a meeting, so it can be listed, cancelled
+/// or rescheduled later.
+async fn finish_poll(ctx: &HandlerContext, message: &mut Message, poll: &Poll, result: &PollResult) -> Result<()> {
+    let role_mentions = group_role_mentions(ctx, &poll.setup().group_ids).await;
+    
+    match result {
+        PollResult::NotEnoughMembers(tallies) => {
+            let mut description = "**Matching Failed**\n\n".to_string();
+            description.push_str("The meeting cannot be scheduled because not enough members are available.\n\n");
+            
+            // Add details about each group
+            description.push_str("**Group Status:**\n");
+            for tally in tallies {
+                description.push_str(&format!(
+                    "• **{}**: {}/{} members available, {} unavailable, {} haven't voted yet (min required: {})\n",
+                    tally.name, tally.available, tally.min_required, tally.unavailable, tally.remaining, tally.min_required
+                ));
+            }
+            
+            let notification = if !role_mentions.is_empty() {
+                format!("❌ {} Meeting scheduling failed due to insufficient availability.", 
+                       role_mentions.join(" "))
+            } else {
+                "❌ Meeting scheduling failed due to insufficient availability.".to_string()
+            };
+            
+            ctx.edit_message(message, |m| {
+                m.content(&notification)
+                    .embed(|e| {
+                        e.title("Not Enough Members Available")
+                            .description(description)
+                            .color(Color::RED)
+                            .footer(|f| f.text("Members who didn't select any time slots are considered unavailable"))
+                    })
+                    .components(finished_components)
+            }).await?;
+        }
+        PollResult::Confirmed(choice) => {
+            let tz = parse_timezone(&poll.setup().timezone);
+            let slot = &choice.slot;
+            let day_date = slot.start.with_timezone(&tz).format("%A, %B %d, %Y").to_string();
+            
+            let mut description = format!("**{}**\n**{}**\n\n", day_date, slot.formatted_time);
+            
+            // Add details about each group
+            description.push_str("**Group Attendance:**\n");
+            for tally in poll.tallies() {
+                description.push_str(&format!(
+                    "• **{}**: {}/{} members available (minimum required: {}) ✅\n",
+                    tally.name, 
+                    tally.available, 
+                    tally.min_required,
+                    tally.min_required
+                ));
+            }
+            
+            description.push_str("\n**Attendees:**\n");
+            for user_id in &choice.attendees {
+                description.push_str(&format!("• <@{}>\n", user_id));
+            }
+            
+            // The meeting this poll replaces is marked as rescheduled along the way
+            let new_meeting = CreateMeetingRequest {
+                server_id: poll.setup().server_id.clone(),
+                channel_id: poll.setup().channel_id.to_string(),
+                organizer_id: poll.setup().organizer_id.clone(),
+                start_time: slot.start,
+                end_time: slot.end,
+                timezone: poll.setup().timezone.clone(),
+                min_per_group: poll.setup().min_per_group as i32,
+                slot_duration: poll.setup().slot_duration as i32,
+                group_ids: poll.setup().group_ids.clone(),
+                attendee_ids: choice.attendees.clone(),
+                rescheduled_from: poll.setup().rescheduled_from,
+            };
+            let meeting_id = match ctx.api.create_meeting(&new_meeting).await {
+                Ok(meeting) => Some(meeting.id),
+                Err(e) => {
+                    tracing::error!("Failed to record meeting: {:?}", e);
+                    None
+                }
+            };
+            
+            // Remember the meeting, so reopening the poll can cancel it
+            if let Some(meeting_id) = meeting_id
+                && let Some(stored) = ctx.active_polls.write().await.get_mut(&message.id)
+            {
+                stored.apply(PollEvent::MeetingRecorded { meeting_id });
+            }
+            
+            let mut ping_message = "🔔 Meeting confirmed! ".to_string();
+            if !role_mentions.is_empty() {
+                ping_message.push_str(&format!("{} ", role_mentions.join(" ")));
+            }
+            ping_message.push_str("Please mark your calendars!");
+            
+            ctx.edit_message(message, |m| {
+                m.content(&ping_message)
+                    .embed(|e| {
+                        e.title("Meeting Time Confirmed!")
+                            .description(description)
+                            .color(Color::DARK_GREEN)
+                            .footer(|f| f.text(format!(
+                                "Slot duration: {} min • Timezone: {} • Successfully matched {} attendees{}",
+                                poll.setup().slot_duration,
+                                poll.setup().timezone,
+                                choice.attendees.len(),
+                                meeting_id
+                                    .map(|id| format!(" • Meeting ID: {}", &id.to_string()[..8]))
+                                    .unwrap_or_default()
+                            )))
+                    })
+                    .components(finished_components)
+            }).await?;
+        }
+        PollResult::NoSuitableTime => {
+            ctx.edit_message(message, |m| {
+                m.content("❌ No suitable meeting time could be found despite having enough votes.")
+                    .embed(|e| {
+                        e.title("No Suitable Meeting Time Found")
+                            .description("We could not find a time slot where enough members from each group are available. You may want to try again with different parameters or ask members to update their availability.")
+                            .color(Color::RED)
+                    })
+                    .components(finished_components)
+            }).await?;
+        }
+        PollResult::Cancelled => {
+            ctx.edit_message(message, |m| {
+                m.content("❌ This meeting poll was cancelled.")
+                    .embed(|e| {
+                        e.title("Meeting Poll Cancelled")
+                            .description("Voting has been closed without choosing a time.")
+                            .color(Color::RED)
+                    })
+                    .components(finished_components)
+            }).await?;
+        }
+    }
+    
+    Ok(())
+}
+
+/// List the near misses a match returned, with who each group is missing
+///
+/// Groups that fall short list their unavailable members, so organisers can
+/// ask them to update their schedules.
+fn format_near_misses(near_misses: &[MatchResult], tz: chrono_tz::Tz) -> String {
+    let mut lines = String::new();
+    
+    for near_miss in near_misses.iter().take(5) {
+        lines.push_str(&format!(
+            "• **{} {}**\n",
+            near_miss.start.with_timezone(&tz).format("%a %b %-d"),
+            format_slot_time(near_miss.start, near_miss.end, tz)
+        ));
+        
+        for group in near_miss.groups.iter().filter(|group| group.shortfall > 0) {
+            let mentions = |ids: &[String]| ids.iter()
+                .map(|id| format!("<@{}>", id))
+                .collect::<Vec<_>>()
+                .join(", ");
+            
+            lines.push_str(&format!("  {} needs {} more", group.name, group.shortfall));
+            if !group.missing_required.is_empty() {
+                lines.push_str(&format!(", including {}", mentions(&group.missing_required)));
+            }
+            if !group.unavailable_users.is_empty() {
+                // Keep long member lists from overflowing the embed
+                let shown = group.unavailable_users.len().min(10);
+                lines.push_str(&format!(" — not available: {}", mentions(&group.unavailable_users[..shown])));
+                if group.unavailable_users.len() > shown {
+                    lines.push_str(&format!(" and {} more", group.unavailable_users.len() - shown));
+                }
+            }
+            lines.push('\n');
+        }
+    }
+    
+    lines
+}
+
+/// Mentions of the roles of the given groups, skipping groups without one
+///
+/// Lookups that fail are skipped, since the mentions only add notifications.
+async fn group_role_mentions(ctx: &HandlerContext, group_ids: &[uuid::Uuid]) -> Vec<String> {
+    let mut mentions = Vec::new();
+    for group_id in group_ids {
+        if let Ok(Some(group)) = ctx.api.get_discord_group(*group_id).await
+            && let Some(role_id) = group.role_id
+        {
+            mentions.push(format!("<@&{}>", role_id));
+        }
+    }
+    
+    mentions
+}
+
+
+/// The date of the day being voted on as seen in the given timezone
+fn shown_date(view: &VotingView, tz: chrono_tz::Tz) -> chrono::NaiveDate {
+    view.slots.first()
+        .map(|slot| slot.start.with_timezone(&tz).date_naive())
+        .unwrap_or_else(|| Utc::now().with_timezone(&tz).date_naive())
+}
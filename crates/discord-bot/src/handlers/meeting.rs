@@ -0,0 +1,448 @@
+use eyre::Result;
+use serenity::{
+    model::{
+        application::interaction::{
+            application_command::{ApplicationCommandInteraction, CommandDataOption},
+            InteractionResponseType,
+        },
+        id::ChannelId,
+        Permissions,
+    },
+    utils::Color,
+};
+use std::collections::HashMap;
+use timesync_core::models::{
+    discord::GetDiscordGroupResponse,
+    meeting::{Meeting, MeetingStatus},
+};
+use uuid::Uuid;
+
+use crate::handlers::HandlerContext;
+use crate::handlers::match_poll::{start_match_poll, MatchPollOptions};
+use crate::handlers::schedule::{fetch_server_groups, format_member_list, get_option_string};
+
+/// Number of characters of a meeting ID shown in lists and accepted as a short ID
+const SHORT_ID_LEN: usize = 8;
+
+/// Handle the /meeting command
+pub async fn handle_meeting_command(
+    ctx: HandlerContext,
+    command: &ApplicationCommandInteraction,
+) -> Result<()> {
+    // Get the subcommand
+    let subcommand = command.data.options.first()
+        .ok_or_else(|| eyre::eyre!("Missing subcommand"))?;
+
+    match subcommand.name.as_str() {
+        "list" => handle_meeting_list(ctx, command).await,
+        "info" => handle_meeting_info(ctx, command, subcommand).await,
+        "cancel" => handle_meeting_cancel(ctx, command, subcommand).await,
+        "reschedule" => handle_meeting_reschedule(ctx, command, subcommand).await,
+        _ => {
+            ctx.create_interaction_response(command, |r| {
+                r.kind(InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|m| {
+                        m.content("Unknown subcommand").ephemeral(true)
+                    })
+            }).await?;
+
+            Ok(())
+        }
+    }
+}
+
+/// Handle the /meeting list subcommand
+async fn handle_meeting_list(
+    ctx: HandlerContext,
+    command: &ApplicationCommandInteraction,
+) -> Result<()> {
+    // Get the guild (server) ID
+    let server_id = command.guild_id
+        .ok_or_else(|| eyre::eyre!("Command must be used in a server"))?
+        .to_string();
+
+    let meetings = ctx.api.list_server_meetings(&server_id, MeetingStatus::Scheduled).await?;
+
+    if meetings.is_empty() {
+        ctx.create_interaction_response(command, |r| {
+            r.kind(InteractionResponseType::ChannelMessageWithSource)
+                .interaction_response_data(|m| {
+                    m.content("There are no upcoming meetings in this server").ephemeral(true)
+                })
+        }).await?;
+
+        return Ok(());
+    }
+
+    // Fetch the server's groups once rather than each meeting's groups in turn
+    let group_names_by_id: HashMap<Uuid, String> = fetch_server_groups(&ctx, &server_id)
+        .await?
+        .into_iter()
+        .map(|g| (g.id, g.name))
+        .collect();
+
+    let mut description = String::from("Here are the upcoming meetings in this server:\n\n");
+
+    for meeting in &meetings {
+        let group_names = meeting.group_ids.iter()
+            .filter_map(|id| group_names_by_id.get(id).map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        description.push_str(&format!(
+            "`{}` **{}** (<t:{}:R>) - {} - {} {}\n",
+            short_id(meeting),
+            format_discord_time(meeting),
+            meeting.start_time.timestamp(),
+            if group_names.is_empty() { "no groups" } else { &group_names },
+            meeting.attendee_ids.len(),
+            if meeting.attendee_ids.len() == 1 { "attendee" } else { "attendees" }
+        ));
+    }
+
+    // Respond to the interaction
+    ctx.create_interaction_response(command, |r| {
+        r.kind(InteractionResponseType::ChannelMessageWithSource)
+            .interaction_response_data(|m| {
+                m.embed(|e| {
+                    e.title("Upcoming Meetings")
+                        .description(description)
+                        .color(Color::BLUE)
+                        .footer(|f| f.text("Use /meeting info <id> to see details about a specific meeting"))
+                })
+            })
+    }).await?;
+
+    Ok(())
+}
+
+/// Handle the /meeting info subcommand
+async fn handle_meeting_info(
+    ctx: HandlerContext,
+    command: &ApplicationCommandInteraction,
+    subcommand: &CommandDataOption,
+) -> Result<()> {
+    let Some(meeting) = find_meeting(&ctx, command, subcommand).await? else {
+        return Ok(());
+    };
+
+    let groups = meeting_groups(&ctx, &meeting).await?;
+
+    let group_list = if groups.is_empty() {
+        "None".to_string()
+    } else {
+        groups.iter()
+            .map(|g| match &g.role_id {
+                Some(role_id) => format!("{} (<@&{}>)", g.name, role_id),
+                None => g.name.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let color = match meeting.status {
+        MeetingStatus::Scheduled => Color::DARK_GREEN,
+        MeetingStatus::Cancelled => Color::RED,
+        MeetingStatus::Rescheduled => Color::GOLD,
+    };
+
+    let mut description = format!(
+        "**{}** (<t:{}:R>)\nOrganized by <@{}> • Status: **{}**",
+        format_discord_time(&meeting),
+        meeting.start_time.timestamp(),
+        meeting.organizer_id,
+        meeting.status.as_str()
+    );
+
+    if let Some(previous_id) = meeting.rescheduled_from {
+        description.push_str(&format!("\nRescheduled from meeting `{}`", &previous_id.to_string()[..SHORT_ID_LEN]));
+    }
+
+    ctx.create_interaction_response(command, |r| {
+        r.kind(InteractionResponseType::ChannelMessageWithSource)
+            .interaction_response_data(|m| {
+                m.embed(|e| {
+                    e.title("Meeting Details")
+                        .description(description)
+                        .field("Groups", group_list, false)
+                        .field(
+                            format!("Attendees ({})", meeting.attendee_ids.len()),
+                            format_member_list(&meeting.attendee_ids),
+                            false
+                        )
+                        .footer(|f| f.text(format!(
+                            "Meeting ID: {} • Slot duration: {} min • Timezone: {}",
+                            meeting.id,
+                            meeting.slot_duration,
+                            meeting.timezone
+                        )))
+                        .color(color)
+                })
+            })
+    }).await?;
+
+    Ok(())
+}
+
+/// Handle the /meeting cancel subcommand
+async fn handle_meeting_cancel(
+    ctx: HandlerContext,
+    command: &ApplicationCommandInteraction,
+    subcommand: &CommandDataOption,
+) -> Result<()> {
+    let Some(meeting) = find_scheduled_meeting_to_manage(&ctx, command, subcommand).await? else {
+        return Ok(());
+    };
+
+    // Get the optional reason
+    let reason = get_option_string(subcommand, "reason").ok();
+
+    let meeting = ctx.api.update_meeting_status(meeting.id, MeetingStatus::Cancelled).await?;
+
+    let mentions = meeting.attendee_ids.iter()
+        .map(|id| format!("<@{}>", id))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut description = format!(
+        "The meeting on **{}** has been cancelled by <@{}>.",
+        format_discord_time(&meeting),
+        command.user.id
+    );
+    if let Some(reason) = &reason {
+        description.push_str(&format!("\n\n**Reason:** {}", reason));
+    }
+
+    // Notify the attendees in the channel the meeting was scheduled in
+    let meeting_channel = meeting.channel_id.parse::<u64>().ok().map(ChannelId);
+    let notified_in_place = meeting_channel.is_none_or(|channel| channel == command.channel_id);
+
+    if let Some(channel) = meeting_channel.filter(|_| !notified_in_place) {
+        ctx.send_message(channel, |m| {
+            m.content(format!("❌ {} Meeting cancelled.", mentions))
+                .embed(|e| {
+                    e.title("Meeting Cancelled")
+                        .description(&description)
+                        .color(Color::RED)
+                })
+        }).await?;
+    }
+
+    ctx.create_interaction_response(command, |r| {
+        r.kind(InteractionResponseType::ChannelMessageWithSource)
+            .interaction_response_data(|m| {
+                if notified_in_place {
+                    m.content(format!("❌ {} Meeting cancelled.", mentions))
+                        .embed(|e| {
+                            e.title("Meeting Cancelled")
+                                .description(&description)
+                                .color(Color::RED)
+                        })
+                } else {
+                    m.content(format!("Meeting `{}` cancelled and its attendees notified.", short_id(&meeting)))
+                        .ephemeral(true)
+                }
+            })
+    }).await?;
+
+    Ok(())
+}
+
+/// Handle the /meeting reschedule subcommand
+async fn handle_meeting_reschedule(
+    ctx: HandlerContext,
+    command: &ApplicationCommandInteraction,
+    subcommand: &CommandDataOption,
+) -> Result<()> {
+    let Some(meeting) = find_scheduled_meeting_to_manage(&ctx, command, subcommand).await? else {
+        return Ok(());
+    };
+
+    let groups = meeting_groups(&ctx, &meeting).await?;
+    if groups.is_empty() {
+        ctx.create_interaction_response(command, |r| {
+            r.kind(InteractionResponseType::ChannelMessageWithSource)
+                .interaction_response_data(|m| {
+                    m.content("The groups of this meeting no longer exist, use /match to start a new poll")
+                        .ephemeral(true)
+                })
+        }).await?;
+
+        return Ok(());
+    }
+
+    // Reopen a poll for the original groups; the old meeting is marked as
+    // rescheduled once the new poll is finalized
+    start_match_poll(
+        &ctx,
+        command,
+        MatchPollOptions {
+            group_ids: groups.iter().map(|g| g.id).collect(),
+            group_names: groups.iter().map(|g| g.name.clone()).collect(),
+            server_id: meeting.server_id.clone(),
+            min_per_group: i64::from(meeting.min_per_group),
+            slot_duration: i64::from(meeting.slot_duration),
+            display_days: 7,
+            required_ids: Vec::new(),
+            rescheduled_from: Some(meeting.id),
+        },
+    )
+    .await?;
+
+    // Let the attendees know that the time they agreed on is being changed
+    if !meeting.attendee_ids.is_empty() {
+        let mentions = meeting.attendee_ids.iter()
+            .map(|id| format!("<@{}>", id))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        ctx.create_followup_message(command, |m| {
+            m.content(format!(
+                "📅 {} The meeting on **{}** is being rescheduled. Please vote on the new poll above!",
+                mentions,
+                format_discord_time(&meeting)
+            ))
+        }).await?;
+    }
+
+    Ok(())
+}
+
+/// Look up the meeting named by the `meeting` option, replying with an error if there is none
+async fn find_meeting(
+    ctx: &HandlerContext,
+    command: &ApplicationCommandInteraction,
+    subcommand: &CommandDataOption,
+) -> Result<Option<Meeting>> {
+    // Get the guild (server) ID
+    let server_id = command.guild_id
+        .ok_or_else(|| eyre::eyre!("Command must be used in a server"))?
+        .to_string();
+
+    let meeting_id = get_option_string(subcommand, "meeting")?;
+    let meeting_id = meeting_id.trim().trim_matches('`');
+
+    // Accept both full meeting IDs and the short IDs shown by /meeting list
+    let found = match uuid::Uuid::parse_str(meeting_id) {
+        Ok(id) => ctx.api.get_meeting(id).await?,
+        Err(_) if meeting_id.len() >= 4 => {
+            let meetings = ctx.api.list_server_meetings(&server_id, MeetingStatus::Scheduled).await?;
+            let mut matches = meetings_with_short_id(meeting_id, &meetings).into_iter();
+
+            match (matches.next(), matches.next()) {
+                (Some(_), Some(_)) => {
+                    ctx.create_interaction_response(command, |r| {
+                        r.kind(InteractionResponseType::ChannelMessageWithSource)
+                            .interaction_response_data(|m| {
+                                m.content(format!(
+                                    "Ambiguous meeting ID: {} matches more than one meeting, use more of the ID",
+                                    meeting_id
+                                )).ephemeral(true)
+                            })
+                    }).await?;
+
+                    return Ok(None);
+                }
+                (found, _) => found.cloned(),
+            }
+        }
+        Err(_) => None,
+    };
+
+    match found.filter(|m| m.server_id == server_id) {
+        Some(meeting) => Ok(Some(meeting)),
+        None => {
+            ctx.create_interaction_response(command, |r| {
+                r.kind(InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|m| {
+                        m.content(format!("Meeting not found: {}", meeting_id)).ephemeral(true)
+                    })
+            }).await?;
+
+            Ok(None)
+        }
+    }
+}
+
+/// Look up a scheduled meeting the invoking user is allowed to cancel or reschedule
+///
+/// Only the organizer of the meeting and members with the Manage Server
+/// permission may change a meeting.
+async fn find_scheduled_meeting_to_manage(
+    ctx: &HandlerContext,
+    command: &ApplicationCommandInteraction,
+    subcommand: &CommandDataOption,
+) -> Result<Option<Meeting>> {
+    let Some(meeting) = find_meeting(ctx, command, subcommand).await? else {
+        return Ok(None);
+    };
+
+    let error = if meeting.status != MeetingStatus::Scheduled {
+        Some(format!("This meeting has already been {}", meeting.status.as_str()))
+    } else if meeting.end_time <= chrono::Utc::now() {
+        Some("This meeting has already taken place".to_string())
+    } else if !can_manage_meeting(command, &meeting) {
+        Some("Only the meeting organizer or server managers can change this meeting".to_string())
+    } else {
+        None
+    };
+
+    if let Some(error) = error {
+        ctx.create_interaction_response(command, |r| {
+            r.kind(InteractionResponseType::ChannelMessageWithSource)
+                .interaction_response_data(|m| {
+                    m.content(error).ephemeral(true)
+                })
+        }).await?;
+
+        return Ok(None);
+    }
+
+    Ok(Some(meeting))
+}
+
+/// Meetings whose ID starts with the given short ID, ignoring case
+pub fn meetings_with_short_id<'a>(short_id: &str, meetings: &'a [Meeting]) -> Vec<&'a Meeting> {
+    let short_id = short_id.to_lowercase();
+
+    meetings.iter()
+        .filter(|m| m.id.to_string().starts_with(&short_id))
+        .collect()
+}
+
+/// The meeting's groups, skipping any that have since been deleted
+async fn meeting_groups(ctx: &HandlerContext, meeting: &Meeting) -> Result<Vec<GetDiscordGroupResponse>> {
+    let mut groups = Vec::with_capacity(meeting.group_ids.len());
+    for group_id in &meeting.group_ids {
+        if let Some(group) = ctx.api.get_discord_group(*group_id).await? {
+            groups.push(group);
+        }
+    }
+
+    Ok(groups)
+}
+
+/// Check whether the invoking user organized the meeting or can manage the server
+fn can_manage_meeting(command: &ApplicationCommandInteraction, meeting: &Meeting) -> bool {
+    if command.user.id.to_string() == meeting.organizer_id {
+        return true;
+    }
+
+    command.member.as_ref()
+        .and_then(|member| member.permissions)
+        .is_some_and(|permissions| permissions.contains(Permissions::MANAGE_GUILD))
+}
+
+/// Format the meeting time as a Discord timestamp, shown in each reader's local time
+fn format_discord_time(meeting: &Meeting) -> String {
+    format!(
+        "<t:{}:F> - <t:{}:t>",
+        meeting.start_time.timestamp(),
+        meeting.end_time.timestamp()
+    )
+}
+
+/// The shortened meeting ID shown in lists
+fn short_id(meeting: &Meeting) -> String {
+    meeting.id.to_string()[..SHORT_ID_LEN].to_string()
+}
@@ -0,0 +1,290 @@
+//! Keeps groups that are bound to a Discord role in sync with the role's holders.
+//!
+//! A group bound with `/group bind` follows its role: `guild_member_update`
+//! events add or remove the member as the role is granted or revoked, and a
+//! periodic full reconciliation repairs any drift from missed events or time
+//! the bot spent offline. Both require the Server Members privileged intent.
+
+use eyre::Result;
+use serenity::{
+    model::{
+        application::interaction::{
+            application_command::{ApplicationCommandInteraction, CommandDataOption},
+            InteractionResponseType,
+        },
+        guild::Member,
+        id::{GuildId, RoleId, UserId},
+        Permissions,
+    },
+    utils::Color,
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use timesync_client::TimeSyncClient;
+use timesync_core::models::discord::GetDiscordGroupResponse;
+use tracing::{error, info};
+
+use crate::discord::DiscordApi;
+use crate::handlers::HandlerContext;
+use crate::handlers::schedule::get_option_string;
+
+/// Membership changes needed to turn one member list into another
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MembershipChanges {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/// Compute which members to add and remove so `current` matches `desired`
+pub fn membership_changes(current: &[String], desired: &[String]) -> MembershipChanges {
+    let current_set: HashSet<&String> = current.iter().collect();
+    let desired_set: HashSet<&String> = desired.iter().collect();
+
+    let mut added: Vec<String> = desired_set.difference(&current_set).map(|id| (*id).clone()).collect();
+    let mut removed: Vec<String> = current_set.difference(&desired_set).map(|id| (*id).clone()).collect();
+    added.sort();
+    removed.sort();
+
+    MembershipChanges { added, removed }
+}
+
+/// Update role-bound group membership after a member's roles changed
+pub async fn handle_member_update(api: &TimeSyncClient, member: &Member) -> Result<()> {
+    if member.user.bot {
+        return Ok(());
+    }
+
+    let groups = api.list_role_synced_groups(Some(&member.guild_id.to_string())).await?;
+    let discord_id = member.user.id.to_string();
+
+    for group in groups {
+        let Some(role_id) = group_role_id(&group) else {
+            continue;
+        };
+
+        if member.roles.contains(&role_id) {
+            if api.add_group_member(group.id, &discord_id).await?.added {
+                info!("Role sync: added {} to group {}", discord_id, group.name);
+            }
+        } else if group.members.iter().any(|m| m.discord_id == discord_id) {
+            api.remove_group_member(group.id, &discord_id).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Remove a member who left the server from all of its role-bound groups
+pub async fn handle_member_removal(api: &TimeSyncClient, guild_id: GuildId, user_id: UserId) -> Result<()> {
+    let groups = api.list_role_synced_groups(Some(&guild_id.to_string())).await?;
+    let discord_id = user_id.to_string();
+
+    for group in groups {
+        if group.members.iter().any(|m| m.discord_id == discord_id) {
+            api.remove_group_member(group.id, &discord_id).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Bring the given groups of one server in line with their roles
+///
+/// Returns the changes applied to each group.
+pub async fn reconcile_groups(
+    discord: &dyn DiscordApi,
+    api: &TimeSyncClient,
+    guild_id: GuildId,
+    groups: &[GetDiscordGroupResponse],
+) -> Result<Vec<(GetDiscordGroupResponse, MembershipChanges)>> {
+    let members = discord.guild_members(guild_id).await?;
+    let mut results = Vec::new();
+
+    for group in groups {
+        let Some(role_id) = group_role_id(group) else {
+            continue;
+        };
+
+        let desired: Vec<String> = members.iter()
+            .filter(|m| !m.user.bot && m.roles.contains(&role_id))
+            .map(|m| m.user.id.to_string())
+            .collect();
+        let current: Vec<String> = group.members.iter()
+            .map(|m| m.discord_id.clone())
+            .collect();
+
+        let changes = membership_changes(&current, &desired);
+        for discord_id in &changes.added {
+            api.add_group_member(group.id, discord_id).await?;
+        }
+        for discord_id in &changes.removed {
+            api.remove_group_member(group.id, discord_id).await?;
+        }
+
+        if !changes.added.is_empty() || !changes.removed.is_empty() {
+            info!(
+                "Role sync: reconciled group {} (+{} / -{})",
+                group.name,
+                changes.added.len(),
+                changes.removed.len()
+            );
+        }
+
+        results.push((group.clone(), changes));
+    }
+
+    Ok(results)
+}
+
+/// Reconcile every role-bound group in every server
+pub async fn reconcile_all(discord: &dyn DiscordApi, api: &TimeSyncClient) -> Result<()> {
+    let groups = api.list_role_synced_groups(None).await?;
+
+    let mut groups_by_server: HashMap<String, Vec<GetDiscordGroupResponse>> = HashMap::new();
+    for group in groups {
+        groups_by_server.entry(group.server_id.clone()).or_default().push(group);
+    }
+
+    for (server_id, groups) in groups_by_server {
+        let Ok(guild_id) = server_id.parse::<u64>().map(GuildId) else {
+            continue;
+        };
+
+        // Keep going with other servers if one fails (e.g. the bot was removed)
+        if let Err(e) = reconcile_groups(discord, api, guild_id, &groups).await {
+            error!("Role sync: failed to reconcile server {}: {:?}", server_id, e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Spawn a background task that reconciles all role-bound groups on an interval
+///
+/// The first reconciliation runs immediately, which repairs changes missed
+/// while the bot was offline.
+pub fn spawn_periodic_reconciliation(discord: Arc<dyn DiscordApi>, api: TimeSyncClient, interval: Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+
+        loop {
+            ticker.tick().await;
+
+            if let Err(e) = reconcile_all(discord.as_ref(), &api).await {
+                error!("Role sync: reconciliation failed: {:?}", e);
+            }
+        }
+    });
+}
+
+/// Handle the /group bind subcommand
+pub async fn handle_group_bind(
+    ctx: HandlerContext,
+    command: &ApplicationCommandInteraction,
+    subcommand: &CommandDataOption,
+) -> Result<()> {
+    let Some(group) = find_group_to_bind(&ctx, command, subcommand).await? else {
+        return Ok(());
+    };
+
+    let role_id = get_option_string(subcommand, "role")?;
+    let guild_id = command.guild_id
+        .ok_or_else(|| eyre::eyre!("Command must be used in a server"))?;
+
+    let group = ctx.api.bind_group_role(group.id, &role_id).await?;
+
+    // Syncing may page through the whole member list, so acknowledge first
+    ctx.create_interaction_response(command, |r| {
+        r.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+    }).await?;
+
+    let results = reconcile_groups(ctx.discord.as_ref(), &ctx.api, guild_id, std::slice::from_ref(&group)).await?;
+    let changes = results.into_iter().next().map(|(_, changes)| changes).unwrap_or_default();
+
+    ctx.edit_original_interaction_response(command, |m| {
+        m.embed(|e| {
+            e.title("Group Bound to Role")
+                .description(format!(
+                    "Membership of **{}** now follows <@&{}>. Members are added and removed as the role is granted or revoked.",
+                    group.name,
+                    role_id
+                ))
+                .field("Added", changes.added.len().to_string(), true)
+                .field("Removed", changes.removed.len().to_string(), true)
+                .color(Color::DARK_GREEN)
+        })
+    }).await?;
+
+    Ok(())
+}
+
+/// Handle the /group unbind subcommand
+pub async fn handle_group_unbind(
+    ctx: HandlerContext,
+    command: &ApplicationCommandInteraction,
+    subcommand: &CommandDataOption,
+) -> Result<()> {
+    let Some(group) = find_group_to_bind(&ctx, command, subcommand).await? else {
+        return Ok(());
+    };
+
+    let message = if group.role_sync {
+        ctx.api.unbind_group_role(group.id).await?;
+        format!("Group **{}** is no longer synced with its role. Its current members are kept.", group.name)
+    } else {
+        format!("Group **{}** is not bound to a role", group.name)
+    };
+
+    ctx.create_interaction_response(command, |r| {
+        r.kind(InteractionResponseType::ChannelMessageWithSource)
+            .interaction_response_data(|m| {
+                m.content(message).ephemeral(!group.role_sync)
+            })
+    }).await?;
+
+    Ok(())
+}
+
+/// Look up the group named by the `name` option, checking the user may manage roles
+async fn find_group_to_bind(
+    ctx: &HandlerContext,
+    command: &ApplicationCommandInteraction,
+    subcommand: &CommandDataOption,
+) -> Result<Option<GetDiscordGroupResponse>> {
+    let server_id = command.guild_id
+        .ok_or_else(|| eyre::eyre!("Command must be used in a server"))?
+        .to_string();
+    let name = get_option_string(subcommand, "name")?;
+
+    let can_manage_roles = command.member.as_ref()
+        .and_then(|member| member.permissions)
+        .is_some_and(|permissions| permissions.contains(Permissions::MANAGE_ROLES));
+
+    let group = ctx.api.get_discord_group_by_name(&server_id, &name).await?;
+
+    let error = match &group {
+        _ if !can_manage_roles => Some("You need the Manage Roles permission to change role bindings".to_string()),
+        None => Some(format!("Group not found: {}", name)),
+        Some(_) => None,
+    };
+
+    if let Some(error) = error {
+        ctx.create_interaction_response(command, |r| {
+            r.kind(InteractionResponseType::ChannelMessageWithSource)
+                .interaction_response_data(|m| {
+                    m.content(error).ephemeral(true)
+                })
+        }).await?;
+
+        return Ok(None);
+    }
+
+    Ok(group)
+}
+
+fn group_role_id(group: &GetDiscordGroupResponse) -> Option<RoleId> {
+    group.role_id.as_deref()
+        .and_then(|id| id.parse::<u64>().ok())
+        .map(RoleId)
+}
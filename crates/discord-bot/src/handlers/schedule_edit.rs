@@ -0,0 +1,447 @@
+//! `/schedule edit`: set weekly availability without leaving Discord.
+//!
+//! The editor is an ephemeral message with select menus for days and an hour
+//! range, plus a modal for quick text entry. Changes are saved to the invoking
+//! user's linked schedule, creating and linking one on first use.
+//! Only recurring slots are edited; one-off slots from the web UI are kept.
+
+use chrono::{Utc, Weekday};
+use chrono_tz::Tz;
+use eyre::Result;
+use serenity::{
+    builder::{CreateComponents, CreateEmbed},
+    model::{
+        application::{
+            component::{ActionRowComponent, ButtonStyle, InputTextStyle},
+            interaction::{
+                application_command::ApplicationCommandInteraction,
+                message_component::MessageComponentInteraction,
+                modal::ModalSubmitInteraction,
+                InteractionResponseType,
+            },
+        },
+        id::{GuildId, UserId},
+        user::User,
+    },
+    utils::Color,
+};
+use timesync_core::{
+    errors::TimeError,
+    models::schedule::{CreateScheduleRequest, CreateTimeSlotRequest, SlotPreference, UpdateScheduleRequest},
+    parser::parse_weekly_availability,
+};
+use uuid::Uuid;
+
+use crate::availability::{week_start, WeeklyAvailability, WEEKDAYS};
+use crate::handlers::HandlerContext;
+use crate::handlers::schedule::get_server_timezone;
+use crate::timezones::parse_timezone;
+
+/// Custom IDs of all editor components start with this prefix
+pub const CUSTOM_ID_PREFIX: &str = "avail_";
+
+const QUICK_ENTRY_MODAL_ID: &str = "avail_modal";
+const QUICK_ENTRY_INPUT_ID: &str = "avail_text";
+
+/// Selections made in a user's editor that haven't been applied yet
+#[derive(Debug, Clone)]
+pub struct AvailabilityDraft {
+    pub days: Vec<Weekday>,
+    pub start_hour: u32,
+    pub end_hour: u32,
+}
+
+impl Default for AvailabilityDraft {
+    fn default() -> Self {
+        Self {
+            days: WEEKDAYS[..5].to_vec(),
+            start_hour: 18,
+            end_hour: 22,
+        }
+    }
+}
+
+/// A user's linked schedule as seen by the editor
+struct EditorState {
+    schedule_id: Option<Uuid>,
+    tz: Tz,
+    availability: WeeklyAvailability,
+    /// One-off, busy and "if need be" slots, written back unchanged
+    other_slots: Vec<CreateTimeSlotRequest>,
+}
+
+/// Handle the /schedule edit subcommand
+pub async fn handle_schedule_edit(
+    ctx: HandlerContext,
+    command: &ApplicationCommandInteraction,
+) -> Result<()> {
+    let state = load_state(&ctx, command.user.id, command.guild_id).await?;
+    let draft = get_draft(&ctx, command.user.id).await;
+    let (embed, components) = render_editor(&ctx, &state, &draft, None);
+
+    ctx.create_interaction_response(command, |r| {
+        r.kind(InteractionResponseType::ChannelMessageWithSource)
+            .interaction_response_data(|m| {
+                m.ephemeral(true)
+                    .set_embed(embed)
+                    .set_components(components)
+            })
+    }).await?;
+
+    Ok(())
+}
+
+/// Handle select menus and buttons in the editor
+pub async fn handle_edit_component(
+    ctx: HandlerContext,
+    component: &MessageComponentInteraction,
+) -> Result<()> {
+    let user_id = component.user.id;
+    let mut draft = get_draft(&ctx, user_id).await;
+    let mut state = load_state(&ctx, user_id, component.guild_id).await?;
+    let values = &component.data.values;
+
+    let status = match component.data.custom_id.as_str() {
+        "avail_days" => {
+            draft.days = values.iter()
+                .filter_map(|v| v.parse::<usize>().ok())
+                .filter_map(|i| WEEKDAYS.get(i).copied())
+                .collect();
+            None
+        }
+        "avail_start" => {
+            draft.start_hour = values.first().and_then(|v| v.parse().ok()).unwrap_or(draft.start_hour);
+            None
+        }
+        "avail_end" => {
+            draft.end_hour = values.first().and_then(|v| v.parse().ok()).unwrap_or(draft.end_hour);
+            None
+        }
+        "avail_add" | "avail_remove" => {
+            let adding = component.data.custom_id == "avail_add";
+
+            if adding {
+                state.availability.add(&draft.days, draft.start_hour, draft.end_hour);
+            } else {
+                state.availability.remove(&draft.days, draft.start_hour, draft.end_hour);
+            }
+            save_state(&ctx, &component.user, &mut state).await?;
+
+            Some(format!(
+                "{} {} {:02}:00-{:02}:00",
+                if adding { "Added" } else { "Removed" },
+                format_days(&draft.days),
+                draft.start_hour,
+                draft.end_hour
+            ))
+        }
+        "avail_clear" => {
+            state.availability.clear();
+            save_state(&ctx, &component.user, &mut state).await?;
+
+            Some("Cleared your weekly availability".to_string())
+        }
+        "avail_quick" => {
+            ctx.create_interaction_response(component, |r| {
+                r.kind(InteractionResponseType::Modal)
+                    .interaction_response_data(|d| {
+                        d.custom_id(QUICK_ENTRY_MODAL_ID)
+                            .title("Quick Availability Entry")
+                            .components(|c| {
+                                c.create_action_row(|row| {
+                                    row.create_input_text(|t| {
+                                        t.custom_id(QUICK_ENTRY_INPUT_ID)
+                                            .style(InputTextStyle::Paragraph)
+                                            .label("When are you free?")
+                                            .placeholder("weekdays after 6pm, not Thursday\nSat 10-14\ntomorrow 9am-noon")
+                                            .required(true)
+                                    })
+                                })
+                            })
+                    })
+            }).await?;
+
+            return Ok(());
+        }
+        _ => None,
+    };
+
+    set_draft(&ctx, user_id, draft.clone()).await;
+
+    let (embed, components) = render_editor(&ctx, &state, &draft, status);
+
+    ctx.create_interaction_response(component, |r| {
+        r.kind(InteractionResponseType::UpdateMessage)
+            .interaction_response_data(|m| m.set_embed(embed).set_components(components))
+    }).await?;
+
+    Ok(())
+}
+
+/// Handle the quick entry modal
+pub async fn handle_edit_modal(
+    ctx: HandlerContext,
+    modal: &ModalSubmitInteraction,
+) -> Result<()> {
+    let text = modal.data.components.iter()
+        .flat_map(|row| row.components.iter())
+        .find_map(|component| match component {
+            ActionRowComponent::InputText(input) if input.custom_id == QUICK_ENTRY_INPUT_ID => {
+                Some(input.value.clone())
+            }
+            _ => None,
+        })
+        .unwrap_or_default();
+
+    let mut state = load_state(&ctx, modal.user.id, modal.guild_id).await?;
+
+    let today = Utc::now().with_timezone(&state.tz).date_naive();
+
+    let status = match parse_weekly_availability(&text, today, state.tz) {
+        Ok(slots) if slots.is_empty() => "⚠️ That doesn't leave any available time".to_string(),
+        Ok(slots) => {
+            // Explicit dates like "tomorrow" are kept as one-off slots
+            let (weekly, one_off): (Vec<_>, Vec<_>) = slots.into_iter().partition(|slot| slot.is_recurring);
+            state.availability.add_slots(&weekly, state.tz);
+            state.other_slots.extend(one_off.iter().cloned());
+            save_state(&ctx, &modal.user, &mut state).await?;
+
+            match one_off.len() {
+                0 => format!("Added {} weekly {}", weekly.len(), if weekly.len() == 1 { "slot" } else { "slots" }),
+                count => format!(
+                    "Added {} weekly and {} one-off {}",
+                    weekly.len(),
+                    count,
+                    if count == 1 { "slot" } else { "slots" }
+                ),
+            }
+        }
+        Err(TimeError::Validation(message)) => format!("⚠️ {}", message),
+        Err(e) => format!("⚠️ {}", e),
+    };
+
+    let draft = get_draft(&ctx, modal.user.id).await;
+    let (embed, components) = render_editor(&ctx, &state, &draft, Some(status));
+
+    ctx.create_interaction_response(modal, |r| {
+        r.kind(InteractionResponseType::UpdateMessage)
+            .interaction_response_data(|m| m.set_embed(embed).set_components(components))
+    }).await?;
+
+    Ok(())
+}
+
+/// Load the user's linked schedule, or an empty state if they have none yet
+async fn load_state(ctx: &HandlerContext, user_id: UserId, guild_id: Option<GuildId>) -> Result<EditorState> {
+    let discord_id = user_id.to_string();
+    let user = ctx.api.get_discord_user(&discord_id).await?;
+
+    let linked = match user.as_ref().and_then(|user| user.schedule_id) {
+        Some(schedule_id) => ctx.api.get_schedule(schedule_id).await?,
+        None => None,
+    };
+
+    let Some(linked) = linked else {
+        // New schedules use the user's timezone, then the server's
+        let timezone = match (user.and_then(|user| user.timezone), guild_id) {
+            (Some(timezone), _) => timezone,
+            (None, Some(guild_id)) => get_server_timezone(ctx, &guild_id.to_string()).await,
+            (None, None) => "UTC".to_string(),
+        };
+
+        return Ok(EditorState {
+            schedule_id: None,
+            tz: parse_timezone(&timezone),
+            availability: WeeklyAvailability::default(),
+            other_slots: Vec::new(),
+        });
+    };
+
+    let tz = parse_timezone(&linked.timezone);
+
+    Ok(EditorState {
+        schedule_id: Some(linked.id),
+        tz,
+        availability: WeeklyAvailability::from_slots(&linked.slots, tz),
+        other_slots: linked.slots.iter()
+            .filter(|slot| !slot.is_recurring || slot.is_unavailable || slot.preference != SlotPreference::Available)
+            .map(|slot| CreateTimeSlotRequest {
+                start: slot.start,
+                end: slot.end,
+                is_recurring: slot.is_recurring,
+                is_unavailable: slot.is_unavailable,
+                preference: slot.preference,
+            })
+            .collect(),
+    })
+}
+
+/// Write the edited availability back, creating and linking a schedule if needed
+async fn save_state(ctx: &HandlerContext, user: &User, state: &mut EditorState) -> Result<()> {
+    let this_week = week_start(Utc::now().with_timezone(&state.tz).date_naive());
+    let mut slots = state.other_slots.clone();
+    slots.extend(state.availability.to_slots(this_week, state.tz));
+
+    match state.schedule_id {
+        Some(schedule_id) => {
+            let request = UpdateScheduleRequest {
+                name: None,
+                slots,
+                password: None,
+                timezone: None,
+            };
+            ctx.api.update_schedule(schedule_id, &request).await?;
+        }
+        None => {
+            // Creating the schedule with a Discord ID also links it
+            let request = CreateScheduleRequest {
+                name: user.name.clone(),
+                password: None,
+                slots,
+                discord_id: Some(user.id.to_string()),
+                timezone: state.tz.name().to_string(),
+            };
+            let created = ctx.api.create_schedule(&request).await?;
+            state.schedule_id = Some(created.id);
+        }
+    }
+
+    Ok(())
+}
+
+async fn get_draft(ctx: &HandlerContext, user_id: UserId) -> AvailabilityDraft {
+    ctx.availability_drafts.read().await
+        .get(&user_id)
+        .cloned()
+        .unwrap_or_default()
+}
+
+async fn set_draft(ctx: &HandlerContext, user_id: UserId, draft: AvailabilityDraft) {
+    ctx.availability_drafts.write().await.insert(user_id, draft);
+}
+
+fn render_editor(
+    ctx: &HandlerContext,
+    state: &EditorState,
+    draft: &AvailabilityDraft,
+    status: Option<String>,
+) -> (CreateEmbed, CreateComponents) {
+    let mut description = format!(
+        "{}\n\nTimes are in **{}** and repeat every week.",
+        state.availability.summary(),
+        state.tz.name()
+    );
+    if let Some(schedule_id) = state.schedule_id {
+        description.push_str(&format!(
+            " [View on the web]({}/{})",
+            ctx.config.web_base_url,
+            schedule_id
+        ));
+    }
+    if let Some(status) = status {
+        description.push_str(&format!("\n\n{}", status));
+    }
+
+    let mut embed = CreateEmbed::default();
+    embed.title("Your Weekly Availability")
+        .description(description)
+        .field(
+            "Selected",
+            format!("{} {:02}:00-{:02}:00", format_days(&draft.days), draft.start_hour, draft.end_hour),
+            false,
+        )
+        .color(Color::GOLD)
+        .footer(|f| f.text("Pick days and hours, then Add or Remove. Quick Entry accepts text like \"Mon-Fri 18:00-22:00\"."));
+
+    let mut components = CreateComponents::default();
+    components
+        .create_action_row(|row| {
+            row.create_select_menu(|menu| {
+                menu.custom_id("avail_days")
+                    .placeholder("Days")
+                    .min_values(1)
+                    .max_values(WEEKDAYS.len() as u64)
+                    .options(|o| {
+                        for (i, day) in WEEKDAYS.iter().enumerate() {
+                            o.create_option(|opt| {
+                                opt.label(day.to_string())
+                                    .value(i)
+                                    .default_selection(draft.days.contains(day))
+                            });
+                        }
+                        o
+                    })
+            })
+        })
+        .create_action_row(|row| {
+            row.create_select_menu(|menu| {
+                menu.custom_id("avail_start")
+                    .placeholder("From")
+                    .options(|o| {
+                        for hour in 0..24 {
+                            o.create_option(|opt| {
+                                opt.label(format!("From {:02}:00", hour))
+                                    .value(hour)
+                                    .default_selection(hour == draft.start_hour)
+                            });
+                        }
+                        o
+                    })
+            })
+        })
+        .create_action_row(|row| {
+            row.create_select_menu(|menu| {
+                menu.custom_id("avail_end")
+                    .placeholder("Until")
+                    .options(|o| {
+                        for hour in 1..=24 {
+                            o.create_option(|opt| {
+                                opt.label(format!("Until {:02}:00", hour))
+                                    .value(hour)
+                                    .default_selection(hour == draft.end_hour)
+                            });
+                        }
+                        o
+                    })
+            })
+        })
+        .create_action_row(|row| {
+            row.create_button(|b| {
+                b.custom_id("avail_add")
+                    .label("Add")
+                    .style(ButtonStyle::Success)
+                    .disabled(draft.days.is_empty())
+            })
+            .create_button(|b| {
+                b.custom_id("avail_remove")
+                    .label("Remove")
+                    .style(ButtonStyle::Secondary)
+                    .disabled(draft.days.is_empty())
+            })
+            .create_button(|b| {
+                b.custom_id("avail_quick")
+                    .label("Quick Entry")
+                    .style(ButtonStyle::Primary)
+            })
+            .create_button(|b| {
+                b.custom_id("avail_clear")
+                    .label("Clear All")
+                    .style(ButtonStyle::Danger)
+                    .disabled(state.availability.is_empty())
+            })
+        });
+
+    (embed, components)
+}
+
+fn format_days(days: &[Weekday]) -> String {
+    if days.is_empty() {
+        return "No days".to_string();
+    }
+
+    WEEKDAYS.iter()
+        .filter(|day| days.contains(day))
+        .map(|day| day.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
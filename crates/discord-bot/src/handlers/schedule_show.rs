@@ -0,0 +1,90 @@
+//! `/schedule show`: view a member's availability for the coming week.
+//!
+//! The linked schedule is fetched from the TimeSync API and rendered as a
+//! compact text grid in the invoker's timezone, so everyone reading it sees
+//! times they don't have to convert.
+
+use chrono::{Duration, TimeZone, Utc};
+use eyre::Result;
+use serenity::{
+    model::application::interaction::{
+        application_command::ApplicationCommandInteraction,
+        InteractionResponseType,
+    },
+    utils::Color,
+};
+
+use crate::availability::{expand_slots, render_week_grid};
+use crate::handlers::HandlerContext;
+use crate::handlers::schedule::{get_option_string, get_server_timezone, voter_timezone};
+use crate::timezones::parse_timezone;
+
+/// Handle the /schedule show subcommand
+pub async fn handle_schedule_show(
+    ctx: HandlerContext,
+    command: &ApplicationCommandInteraction,
+) -> Result<()> {
+    let subcommand = command.data.options.first()
+        .ok_or_else(|| eyre::eyre!("Missing subcommand"))?;
+    let invoker_id = command.user.id.to_string();
+    let target_id = get_option_string(subcommand, "user").unwrap_or_else(|_| invoker_id.clone());
+
+    let linked = match ctx.api.get_discord_user(&target_id).await?.and_then(|user| user.schedule_id) {
+        Some(schedule_id) => ctx.api.get_schedule(schedule_id).await?,
+        None => None,
+    };
+
+    let Some(linked) = linked else {
+        let message = if target_id == invoker_id {
+            "You haven't linked a schedule yet. Use `/schedule edit` or `/schedule create` to set one up.".to_string()
+        } else {
+            format!("<@{}> hasn't linked a schedule yet.", target_id)
+        };
+
+        ctx.create_interaction_response(command, |r| {
+            r.kind(InteractionResponseType::ChannelMessageWithSource)
+                .interaction_response_data(|m| m.content(message).ephemeral(true))
+        }).await?;
+
+        return Ok(());
+    };
+
+    // Show times in the invoker's timezone, falling back to the server's
+    let timezone = match (voter_timezone(&ctx, &invoker_id).await, command.guild_id) {
+        (Some(timezone), _) => timezone,
+        (None, Some(guild_id)) => get_server_timezone(&ctx, &guild_id.to_string()).await,
+        (None, None) => "UTC".to_string(),
+    };
+    let tz = parse_timezone(&timezone);
+
+    let today = Utc::now().with_timezone(&tz).date_naive();
+    let from = tz.from_local_datetime(&today.and_hms_opt(0, 0, 0).unwrap_or_default())
+        .earliest()
+        .map_or_else(Utc::now, |start| start.with_timezone(&Utc));
+    let week = expand_slots(&linked.slots, from, from + Duration::days(7));
+
+    let mut description = format!("<@{}>'s availability for the next 7 days, in **{}**.\n", target_id, tz.name());
+    if week.is_empty() {
+        description.push_str("\nNothing entered for this week.\n");
+    }
+    description.push_str(&format!(
+        "```\n{}\n```[View on the web]({}/{})",
+        render_week_grid(&week, today, tz),
+        ctx.config.web_base_url,
+        linked.id
+    ));
+
+    ctx.create_interaction_response(command, |r| {
+        r.kind(InteractionResponseType::ChannelMessageWithSource)
+            .interaction_response_data(|m| {
+                m.ephemeral(true)
+                    .embed(|e| {
+                        e.title(&linked.name)
+                            .description(description)
+                            .color(Color::BLURPLE)
+                    })
+            })
+    }).await?;
+
+    Ok(())
+}
@@ -1,43 +1,48 @@
 use eyre::Result;
 use serenity::{
     Client, 
-    prelude::GatewayIntents,
     framework::StandardFramework,
 };
-use sqlx::PgPool;
 use tracing::info;
 
+pub mod availability;
 pub mod commands;
+pub mod discord;
 pub mod handlers;
 pub mod config;
+pub mod members;
+pub mod poll;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod timezones;
 
-/// Start the Discord bot with the provided configuration and database connection.
+/// Start the Discord bot with the provided configuration.
 ///
-/// This function initializes and runs the Discord bot with the given configuration
-/// and database pool. It will continue running until the bot disconnects or an error occurs.
+/// This function initializes and runs the Discord bot with the given configuration.
+/// All data is read and written through the TimeSync API at `config.api_base_url()`.
+/// It will continue running until the bot disconnects or an error occurs.
 ///
 /// # Arguments
 ///
 /// * `config` - The bot configuration containing token, application ID, etc.
-/// * `db_pool` - A PostgreSQL connection pool for database operations
 ///
 /// # Returns
 ///
 /// * `Ok(())` if the bot shut down gracefully
 /// * `Err` if an error occurred during initialization or operation
-pub async fn start_bot(config: config::BotConfig, db_pool: PgPool) -> Result<()> {
+pub async fn start_bot(config: config::BotConfig) -> Result<()> {
     info!("Starting Discord bot");
     
     // Create a new Discord client
-    let handler = handlers::Handler::new(config.clone(), db_pool);
+    let handler = handlers::Handler::new(config.clone());
     
     // Create a framework
     let framework = StandardFramework::new()
         .configure(|c| c.prefix("!"))  // Set the prefix to !
         .group(&commands::GENERAL_GROUP);
     
-    // Configure the client
-    let mut client = Client::builder(&config.token, GatewayIntents::non_privileged())
+    // Configure the client; the members intent is only requested for role sync
+    let mut client = Client::builder(&config.token, config.gateway_intents())
         .event_handler(handler)
         .framework(framework)  // Add the framework
         .await?;
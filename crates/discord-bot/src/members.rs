@@ -0,0 +1,239 @@
+//! Resolution of member lists given to `/group` commands.
+//!
+//! The `members` option accepts a comma-separated list mixing:
+//!
+//! * user mentions or raw user IDs (`<@123>`, `123`)
+//! * `all`, meaning everyone in the current thread, or everyone who can view
+//!   the current channel when used outside of a thread
+//! * role imports (`role:@Raiders`, `role:Raiders`, or a bare `<@&456>` role
+//!   mention), meaning everyone holding that role
+
+use eyre::Result;
+use serenity::{
+    http::Http,
+    model::{
+        channel::{Channel, ChannelType},
+        guild::Member,
+        id::{ChannelId, GuildId, RoleId, UserId},
+        Permissions,
+    },
+};
+use std::collections::HashSet;
+
+/// Maximum page size Discord allows when listing guild members
+const MEMBER_PAGE_SIZE: u64 = 1000;
+
+/// One entry of a `members` option
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemberSpec {
+    /// A single user, by Discord ID
+    User(String),
+    /// Everyone holding the role with this ID
+    Role(String),
+    /// Everyone holding the role with this name
+    RoleName(String),
+    /// Everyone in the current thread or channel
+    All,
+}
+
+/// Parse a comma-separated `members` option
+///
+/// Entries that can't be understood are skipped.
+pub fn parse_member_specs(input: &str) -> Vec<MemberSpec> {
+    input.split(',')
+        .filter_map(|part| parse_member_spec(part.trim()))
+        .collect()
+}
+
+/// Parse user mentions or IDs separated by commas or spaces
+///
+/// Roles and anything else that isn't a single user are skipped, as are
+/// repeats.
+pub fn parse_user_mentions(input: &str) -> Vec<String> {
+    let mut users = Vec::new();
+
+    for part in input.split(|c: char| c == ',' || c.is_whitespace()) {
+        if let Some(MemberSpec::User(id)) = parse_member_spec(part.trim())
+            && !users.contains(&id)
+        {
+            users.push(id);
+        }
+    }
+
+    users
+}
+
+fn parse_member_spec(part: &str) -> Option<MemberSpec> {
+    if part.is_empty() {
+        return None;
+    }
+
+    if part.eq_ignore_ascii_case("all") || part == "@everyone" {
+        return Some(MemberSpec::All);
+    }
+
+    // Role imports, with or without the role: prefix for role mentions
+    if let Some(role) = part.get(.."role:".len())
+        .filter(|prefix| prefix.eq_ignore_ascii_case("role:"))
+        .map(|prefix| &part[prefix.len()..])
+    {
+        let role = role.trim();
+        return parse_role_mention(role)
+            .or_else(|| role.parse::<u64>().ok().map(|id| MemberSpec::Role(id.to_string())))
+            .or_else(|| {
+                let name = role.trim_start_matches('@').trim();
+                (!name.is_empty()).then(|| MemberSpec::RoleName(name.to_string()))
+            });
+    }
+
+    if let Some(spec) = parse_role_mention(part) {
+        return Some(spec);
+    }
+
+    // User mentions look like <@123> or <@!123>
+    if part.starts_with("<@") && part.ends_with('>') {
+        let id_part = part.trim_start_matches("<@").trim_start_matches('!').trim_end_matches('>');
+        return id_part.parse::<u64>().ok().map(|id| MemberSpec::User(id.to_string()));
+    }
+
+    // Try to parse as a raw user ID
+    part.parse::<u64>().ok().map(|id| MemberSpec::User(id.to_string()))
+}
+
+fn parse_role_mention(part: &str) -> Option<MemberSpec> {
+    part.strip_prefix("<@&")
+        .and_then(|rest| rest.strip_suffix('>'))
+        .and_then(|id| id.parse::<u64>().ok())
+        .map(|id| MemberSpec::Role(id.to_string()))
+}
+
+/// Fetch every member of a guild, following Discord's pagination
+///
+/// The member list endpoint returns at most 1000 members per request, so
+/// large servers need several requests, each starting after the highest user
+/// ID of the previous page.
+pub async fn fetch_all_guild_members(http: &Http, guild_id: GuildId) -> Result<Vec<Member>> {
+    let mut members = Vec::new();
+    let mut after: Option<UserId> = None;
+
+    loop {
+        let page = guild_id.members(http, Some(MEMBER_PAGE_SIZE), after).await?;
+        let page_len = page.len() as u64;
+
+        after = page.iter().map(|m| m.user.id).max();
+        members.extend(page);
+
+        if page_len < MEMBER_PAGE_SIZE || after.is_none() {
+            break;
+        }
+    }
+
+    Ok(members)
+}
+
+/// Resolve parsed member specs into a de-duplicated list of Discord IDs
+///
+/// Member lists are only fetched when a role import or `all` needs them, and
+/// at most once per call. Bots are never included by `all` or role imports.
+pub async fn resolve_member_specs(
+    http: &Http,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    specs: &[MemberSpec],
+) -> Result<Vec<String>> {
+    let mut guild_members: Option<Vec<Member>> = None;
+    let mut resolved = Vec::new();
+    let mut seen = HashSet::new();
+
+    for spec in specs {
+        let ids = match spec {
+            MemberSpec::User(id) => vec![id.clone()],
+            MemberSpec::Role(role_id) => {
+                let role_id = RoleId(role_id.parse()?);
+                let members = cached_members(http, guild_id, &mut guild_members).await?;
+                role_member_ids(members, role_id)
+            }
+            MemberSpec::RoleName(name) => {
+                let roles = guild_id.roles(http).await?;
+                let role_id = roles.values()
+                    .find(|role| role.name.eq_ignore_ascii_case(name))
+                    .map(|role| role.id)
+                    .ok_or_else(|| eyre::eyre!("Role not found: {}", name))?;
+                let members = cached_members(http, guild_id, &mut guild_members).await?;
+                role_member_ids(members, role_id)
+            }
+            MemberSpec::All => {
+                resolve_channel_members(http, guild_id, channel_id, &mut guild_members).await?
+            }
+        };
+
+        for id in ids {
+            if seen.insert(id.clone()) {
+                resolved.push(id);
+            }
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// Members of the thread, or members who can view the channel
+async fn resolve_channel_members(
+    http: &Http,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    guild_members: &mut Option<Vec<Member>>,
+) -> Result<Vec<String>> {
+    let channel = match channel_id.to_channel(http).await? {
+        Channel::Guild(channel) => channel,
+        _ => return Err(eyre::eyre!("'all' can only be used in a server channel")),
+    };
+
+    if matches!(
+        channel.kind,
+        ChannelType::PublicThread | ChannelType::PrivateThread | ChannelType::NewsThread
+    ) {
+        let thread_members: HashSet<UserId> = channel_id.get_thread_members(http).await?
+            .into_iter()
+            .filter_map(|m| m.user_id)
+            .collect();
+        let members = cached_members(http, guild_id, guild_members).await?;
+
+        // Thread members don't say who's a bot, so check against the guild's members
+        return Ok(members.iter()
+            .filter(|m| !m.user.bot && thread_members.contains(&m.user.id))
+            .map(|m| m.user.id.to_string())
+            .collect());
+    }
+
+    let guild = guild_id.to_partial_guild(http).await?;
+    let members = cached_members(http, guild_id, guild_members).await?;
+
+    Ok(members.iter()
+        .filter(|m| !m.user.bot)
+        .filter(|m| {
+            guild.user_permissions_in(&channel, m)
+                .is_ok_and(|p| p.contains(Permissions::VIEW_CHANNEL))
+        })
+        .map(|m| m.user.id.to_string())
+        .collect())
+}
+
+async fn cached_members<'a>(
+    http: &Http,
+    guild_id: GuildId,
+    guild_members: &'a mut Option<Vec<Member>>,
+) -> Result<&'a [Member]> {
+    if guild_members.is_none() {
+        *guild_members = Some(fetch_all_guild_members(http, guild_id).await?);
+    }
+
+    Ok(guild_members.as_deref().unwrap_or_default())
+}
+
+fn role_member_ids(members: &[Member], role_id: RoleId) -> Vec<String> {
+    members.iter()
+        .filter(|m| !m.user.bot && m.roles.contains(&role_id))
+        .map(|m| m.user.id.to_string())
+        .collect()
+}
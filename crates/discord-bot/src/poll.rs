@@ -0,0 +1,591 @@
+//! The voting state of a `/match` poll.
+//!
+//! [`Poll`] holds everyone's slot selections and lock-ins and decides when the
+//! poll is over. Handlers turn button clicks into [`PollEvent`]s, apply them,
+//! and render the [`PollOutcome`] and view models it returns; nothing here
+//! talks to Discord or the API.
+//!
+//! A poll is settled once every group has enough locked-in members who
+//! picked at least one slot, or as soon as a group can no longer reach that
+//! number. It then waits out a grace period, so voters can still unlock and
+//! change their votes, before it finishes. Organisers can finish a poll early
+//! with [`PollEvent::Deadline`], call it off with [`PollEvent::Cancel`], and
+//! reopen a finished one.
+
+use chrono::{DateTime, Utc};
+use serenity::model::id::ChannelId;
+use std::collections::{HashMap, HashSet};
+use timesync_core::matching::{evaluate_slot, GroupConstraint};
+
+/// Locked-in available members each group needs, or all of them in smaller groups
+pub const MIN_REQUIRED_PER_GROUP: usize = 6;
+
+/// Information about a time slot for voting
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotInfo {
+    pub id: String,           // Unique identifier for the slot
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub formatted_time: String, // Formatted time string (e.g., "6pm-7pm")
+    pub available_users: Vec<String>, // Discord IDs of users available at this time
+    pub tentative_users: Vec<String>, // Available users whose schedules only say "if need be"
+}
+
+/// What a poll is about, fixed when it opens
+#[derive(Debug, Clone)]
+pub struct PollSetup {
+    pub group_names: Vec<String>,
+    pub min_per_group: i64,
+    pub timezone: String,
+    pub eligible_voters: HashSet<String>, // Members of the polled groups
+    pub group_members: HashMap<uuid::Uuid, Vec<String>>, // group_id -> list of member IDs
+    pub constraints: Vec<GroupConstraint>, // Required groups/members and member weights
+    pub slot_duration: i64, // Duration of each slot in minutes
+    pub display_days: i64, // Number of days to display (1-7)
+    pub day_slots: HashMap<usize, Vec<SlotInfo>>, // Slots organized by day index
+    pub group_ids: Vec<uuid::Uuid>, // Matched groups, in the order they were requested
+    pub server_id: String,
+    pub channel_id: ChannelId, // Channel the poll was posted in
+    pub organizer_id: String, // Discord ID of whoever opened the poll
+    pub rescheduled_from: Option<uuid::Uuid>, // Meeting this poll replaces, if any
+}
+
+/// An open poll for a scheduling match
+///
+/// The voting state only changes through [`Poll::apply`].
+#[derive(Debug, Clone)]
+pub struct Poll {
+    setup: PollSetup,
+    slot_responses: HashMap<String, Vec<String>>, // user_id -> list of slot IDs they are available for
+    locked_votes: HashSet<String>, // Voters who have locked in their votes
+    voter_days: HashMap<String, usize>, // user_id -> day index their voting interface shows
+    pending: Option<PollResult>, // How the poll ends if nothing changes during the grace period
+    pending_round: u32, // Bumped every time the poll settles, so stale grace periods are ignored
+    result: Option<PollResult>, // Set once the poll has finished
+    meeting_id: Option<uuid::Uuid>, // Meeting recorded for a confirmed time
+}
+
+/// Something that happened to a poll
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PollEvent {
+    /// A voter clicked one of the slot buttons
+    Toggle { voter: String, slot_id: String },
+    /// A voter marked themselves available for every slot
+    SelectAll { voter: String },
+    /// A voter marked themselves unavailable for every slot
+    ClearAll { voter: String },
+    /// A voter locked in their selections
+    Lock { voter: String },
+    /// A voter took back their lock-in to change their selections
+    Unlock { voter: String },
+    /// The grace period that started when the poll settled in `round` is over
+    GracePeriodOver { round: u32 },
+    /// A voter switched their voting interface to the previous day
+    PreviousDay { voter: String },
+    /// A voter switched their voting interface to the next day
+    NextDay { voter: String },
+    /// Voting time is up, so the poll finishes with the votes locked in so far
+    Deadline,
+    /// The poll was called off
+    Cancel,
+    /// A finished poll was opened for voting again, with every lock-in cleared
+    Reopen,
+    /// The confirmed time was recorded as this meeting
+    MeetingRecorded { meeting_id: uuid::Uuid },
+}
+
+/// What applying a [`PollEvent`] did
+#[derive(Debug, Clone, PartialEq)]
+pub enum PollOutcome {
+    /// The voter's selections changed
+    Updated,
+    /// The voter locked in, and the poll is still open
+    Locked,
+    /// The voter unlocked, and the poll is still open
+    Unlocked,
+    /// The poll settled and finishes with `result` once the grace period for
+    /// `round` is over, unless someone unlocks first
+    Settled { result: PollResult, round: u32 },
+    /// The voter isn't a member of any of the polled groups, so nothing changed
+    NotEligible,
+    /// The voter has locked in and must unlock before changing their selections
+    VotesLocked,
+    /// The poll finished with this result
+    Finished(PollResult),
+    /// The poll was opened for voting again, and no longer stands behind
+    /// the meeting it had recorded, if any
+    Reopened { meeting_id: Option<uuid::Uuid> },
+    /// The event no longer applies or was a repeat, such as locking in
+    /// twice, so nothing changed
+    Unchanged,
+    /// The poll had already finished, so nothing changed
+    AlreadyFinished,
+}
+
+/// How a poll ended
+#[derive(Debug, Clone, PartialEq)]
+pub enum PollResult {
+    /// Every group has enough members at the chosen slot
+    Confirmed(SlotChoice),
+    /// A group can't reach its minimum with the members left
+    NotEnoughMembers(Vec<GroupTally>),
+    /// Groups had enough votes, but never at the same slot
+    NoSuitableTime,
+    Cancelled,
+}
+
+/// The slot a poll settled on, with the voters who picked it
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotChoice {
+    pub day: usize,
+    pub slot: SlotInfo,
+    pub attendees: Vec<String>,
+}
+
+/// How far along one group is
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupTally {
+    pub name: String,
+    pub members: usize,
+    /// Locked in with at least one slot picked
+    pub available: usize,
+    /// Locked in with no slots picked
+    pub unavailable: usize,
+    /// Not locked in yet
+    pub remaining: usize,
+    pub min_required: usize,
+}
+
+impl GroupTally {
+    pub fn locked(&self) -> usize {
+        self.available + self.unavailable
+    }
+
+    pub fn has_enough(&self) -> bool {
+        self.available >= self.min_required
+    }
+
+    /// Whether the group can still reach its minimum if everyone left picks a slot
+    pub fn can_reach_minimum(&self) -> bool {
+        self.available + self.remaining >= self.min_required
+    }
+}
+
+/// The main poll message: lock-in progress and the current best slot
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProposalView {
+    pub groups: Vec<GroupTally>,
+    pub locked: usize,
+    pub eligible: usize,
+    pub best: Option<BestSlotView>,
+    /// How the poll ends once the grace period is over
+    pub pending: Option<PollResult>,
+}
+
+/// The best slot so far, with how many of each group can make it
+#[derive(Debug, Clone, PartialEq)]
+pub struct BestSlotView {
+    pub choice: SlotChoice,
+    /// (group name, members who picked the slot, members needed)
+    pub groups: Vec<(String, usize, usize)>,
+}
+
+impl BestSlotView {
+    pub fn meets_all_requirements(&self) -> bool {
+        self.groups.iter().all(|(_, available, needed)| available >= needed)
+    }
+}
+
+/// A voter's personal voting interface for the day being shown
+#[derive(Debug, Clone, PartialEq)]
+pub struct VotingView {
+    pub day: usize,
+    pub day_count: usize,
+    pub slots: Vec<SlotButton>,
+    pub has_previous_day: bool,
+    pub has_next_day: bool,
+    /// Whether the voter has picked anything on any day
+    pub any_selected: bool,
+    pub locked: bool,
+}
+
+impl VotingView {
+    /// Slots the voter picked on the day being shown
+    pub fn selected_on_day(&self) -> usize {
+        self.slots.iter().filter(|slot| slot.selected).count()
+    }
+}
+
+/// One slot button, with everyone's vote count
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotButton {
+    pub slot_id: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub votes: usize,
+    pub selected: bool,
+}
+
+impl Poll {
+    /// Open a poll where nobody has picked a slot or locked in yet
+    pub fn new(setup: PollSetup) -> Self {
+        let slot_responses = setup.eligible_voters.iter()
+            .map(|voter| (voter.clone(), Vec::new()))
+            .collect();
+
+        Self {
+            setup,
+            slot_responses,
+            locked_votes: HashSet::new(),
+            voter_days: HashMap::new(),
+            pending: None,
+            pending_round: 0,
+            result: None,
+            meeting_id: None,
+        }
+    }
+
+    /// Apply an event, returning what changed
+    pub fn apply(&mut self, event: PollEvent) -> PollOutcome {
+        if self.result.is_some() {
+            return match event {
+                PollEvent::Reopen => {
+                    self.result = None;
+                    self.pending = None;
+                    self.locked_votes.clear();
+                    PollOutcome::Reopened { meeting_id: self.meeting_id.take() }
+                }
+                PollEvent::MeetingRecorded { meeting_id } => {
+                    self.meeting_id = Some(meeting_id);
+                    PollOutcome::Unchanged
+                }
+                _ => PollOutcome::AlreadyFinished,
+            };
+        }
+
+        match event {
+            PollEvent::Toggle { voter, slot_id } => self.vote(voter, |slots| {
+                if let Some(index) = slots.iter().position(|s| *s == slot_id) {
+                    slots.remove(index);
+                } else {
+                    slots.push(slot_id);
+                }
+            }),
+            PollEvent::SelectAll { voter } => {
+                let all_slot_ids = self.all_slot_ids();
+                self.vote(voter, |slots| *slots = all_slot_ids)
+            }
+            PollEvent::ClearAll { voter } => self.vote(voter, Vec::clear),
+            PollEvent::Lock { voter } => {
+                if !self.is_eligible(&voter) {
+                    return PollOutcome::NotEligible;
+                }
+
+                if !self.locked_votes.insert(voter) {
+                    return PollOutcome::Unchanged;
+                }
+                self.settle(PollOutcome::Locked)
+            }
+            PollEvent::Unlock { voter } => {
+                if !self.is_eligible(&voter) {
+                    return PollOutcome::NotEligible;
+                }
+
+                if !self.locked_votes.remove(&voter) {
+                    return PollOutcome::Unchanged;
+                }
+                self.settle(PollOutcome::Unlocked)
+            }
+            PollEvent::GracePeriodOver { round } => {
+                if self.pending.is_none() || round != self.pending_round {
+                    return PollOutcome::Unchanged;
+                }
+
+                match self.conclusion() {
+                    Some(result) => self.finish(result),
+                    None => {
+                        self.pending = None;
+                        PollOutcome::Unchanged
+                    }
+                }
+            }
+            PollEvent::PreviousDay { voter } => {
+                let day = self.shown_day(&voter).checked_sub(1);
+                self.show_day(voter, day)
+            }
+            PollEvent::NextDay { voter } => {
+                let day = Some(self.shown_day(&voter) + 1).filter(|day| *day < self.setup.day_slots.len());
+                self.show_day(voter, day)
+            }
+            PollEvent::Deadline => {
+                let tallies = self.tallies();
+
+                if tallies.iter().all(GroupTally::has_enough) {
+                    let result = self.best_slot_result();
+                    self.finish(result)
+                } else {
+                    self.finish(PollResult::NotEnoughMembers(tallies))
+                }
+            }
+            PollEvent::Cancel => self.finish(PollResult::Cancelled),
+            PollEvent::Reopen | PollEvent::MeetingRecorded { .. } => PollOutcome::Unchanged,
+        }
+    }
+
+    pub fn setup(&self) -> &PollSetup {
+        &self.setup
+    }
+
+    /// How the poll ended, if it has finished
+    pub fn result(&self) -> Option<&PollResult> {
+        self.result.as_ref()
+    }
+
+    /// The meeting recorded for the confirmed time, if any
+    pub fn meeting_id(&self) -> Option<uuid::Uuid> {
+        self.meeting_id
+    }
+
+    /// How many times the poll has settled so far
+    pub fn pending_round(&self) -> u32 {
+        self.pending_round
+    }
+
+    pub fn is_eligible(&self, voter: &str) -> bool {
+        self.setup.eligible_voters.contains(voter)
+    }
+
+    pub fn is_locked(&self, voter: &str) -> bool {
+        self.locked_votes.contains(voter)
+    }
+
+    /// The IDs of the slots a voter picked, in the order they picked them
+    pub fn selections(&self, voter: &str) -> &[String] {
+        self.slot_responses.get(voter).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Each group's progress, in the order the groups were requested
+    pub fn tallies(&self) -> Vec<GroupTally> {
+        self.setup.group_ids.iter()
+            .enumerate()
+            .map(|(idx, group_id)| {
+                let members = self.setup.group_members.get(group_id).map(Vec::as_slice).unwrap_or_default();
+                let (locked, remaining): (Vec<&String>, Vec<&String>) = members.iter()
+                    .partition(|member| self.locked_votes.contains(*member));
+                let available = locked.iter()
+                    .filter(|member| self.slot_responses.get(**member).is_some_and(|slots| !slots.is_empty()))
+                    .count();
+
+                GroupTally {
+                    name: self.group_name(idx),
+                    members: members.len(),
+                    available,
+                    unavailable: locked.len() - available,
+                    remaining: remaining.len(),
+                    min_required: MIN_REQUIRED_PER_GROUP.min(members.len()),
+                }
+            })
+            .collect()
+    }
+
+    /// Find the optimal meeting slot based on votes
+    ///
+    /// Takes into account only locked votes. Slots missing a required member or
+    /// without enough votes from a required group are skipped, and the rest are
+    /// ranked by the weights of everyone who voted for them. Voters whose saved
+    /// schedule only has them free "if need be" count for less, so slots where
+    /// everyone is fully available win.
+    pub fn best_slot(&self) -> Option<SlotChoice> {
+        // A map to track votes for each slot
+        let mut slot_votes: HashMap<&str, Vec<String>> = HashMap::new();
+
+        // Users who locked in with no selections are unavailable everywhere
+        for (user_id, selected_slots) in &self.slot_responses {
+            if !self.locked_votes.contains(user_id) {
+                continue;
+            }
+
+            for slot_id in selected_slots {
+                slot_votes.entry(slot_id).or_default().push(user_id.clone());
+            }
+        }
+
+        let mut best: Option<(f64, SlotChoice)> = None;
+
+        // Check each day, in order so ties go to the earliest slot
+        let mut days: Vec<_> = self.setup.day_slots.iter().collect();
+        days.sort_by_key(|(day, _)| **day);
+
+        for (day, slots) in days {
+            for slot in slots {
+                let Some(voters) = slot_votes.get(slot.id.as_str()) else {
+                    continue;
+                };
+
+                let available: HashSet<&str> = voters.iter().map(String::as_str).collect();
+                let tentative: HashSet<&str> = slot.tentative_users.iter()
+                    .map(String::as_str)
+                    .filter(|user_id| available.contains(user_id))
+                    .collect();
+
+                if let Some(score) = evaluate_slot(&self.setup.constraints, &available, &tentative)
+                    && best.as_ref().is_none_or(|(best_score, _)| score > *best_score)
+                {
+                    best = Some((score, SlotChoice {
+                        day: *day,
+                        slot: slot.clone(),
+                        attendees: voters.clone(),
+                    }));
+                }
+            }
+        }
+
+        best.map(|(_, choice)| choice)
+    }
+
+    /// The main poll message
+    pub fn proposal_view(&self) -> ProposalView {
+        let groups = self.tallies();
+        let best = self.best_slot().map(|choice| {
+            let groups = self.setup.group_ids.iter()
+                .enumerate()
+                .map(|(idx, group_id)| {
+                    let members = self.setup.group_members.get(group_id).map(Vec::as_slice).unwrap_or_default();
+                    let available = members.iter().filter(|m| choice.attendees.contains(m)).count();
+                    (self.group_name(idx), available, MIN_REQUIRED_PER_GROUP.min(members.len()))
+                })
+                .collect();
+
+            BestSlotView { choice, groups }
+        });
+
+        ProposalView {
+            groups,
+            locked: self.locked_votes.len(),
+            eligible: self.setup.eligible_voters.len(),
+            best,
+            pending: self.pending.clone(),
+        }
+    }
+
+    /// A voter's personal voting interface for the day they're looking at
+    pub fn voting_view(&self, voter: &str) -> VotingView {
+        let selected = self.slot_responses.get(voter).map(Vec::as_slice).unwrap_or_default();
+        let day = self.shown_day(voter);
+
+        let slots = self.setup.day_slots.get(&day)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .map(|slot| SlotButton {
+                slot_id: slot.id.clone(),
+                start: slot.start,
+                end: slot.end,
+                votes: self.slot_responses.values().filter(|slots| slots.contains(&slot.id)).count(),
+                selected: selected.contains(&slot.id),
+            })
+            .collect();
+
+        VotingView {
+            day,
+            day_count: self.setup.day_slots.len(),
+            slots,
+            has_previous_day: day > 0,
+            has_next_day: day + 1 < self.setup.day_slots.len(),
+            any_selected: !selected.is_empty(),
+            locked: self.locked_votes.contains(voter),
+        }
+    }
+
+    /// The group's name, or a placeholder if the poll has fewer names than groups
+    fn group_name(&self, idx: usize) -> String {
+        self.setup.group_names.get(idx).cloned().unwrap_or_else(|| format!("Group {}", idx + 1))
+    }
+
+    /// The day a voter's voting interface shows, the first one until they navigate
+    fn shown_day(&self, voter: &str) -> usize {
+        self.voter_days.get(voter).copied().unwrap_or_default()
+    }
+
+    /// Switch an eligible voter's voting interface to another day, if there is one
+    ///
+    /// Locked-in voters can still look around, so this doesn't check locks.
+    fn show_day(&mut self, voter: String, day: Option<usize>) -> PollOutcome {
+        if !self.is_eligible(&voter) {
+            return PollOutcome::NotEligible;
+        }
+        let Some(day) = day else {
+            return PollOutcome::Unchanged;
+        };
+
+        self.voter_days.insert(voter, day);
+        PollOutcome::Updated
+    }
+
+    fn all_slot_ids(&self) -> Vec<String> {
+        let mut days: Vec<_> = self.setup.day_slots.iter().collect();
+        days.sort_by_key(|(day, _)| **day);
+
+        days.into_iter()
+            .flat_map(|(_, slots)| slots.iter().map(|slot| slot.id.clone()))
+            .collect()
+    }
+
+    /// Change the selections of an eligible voter who hasn't locked in
+    fn vote(&mut self, voter: String, change: impl FnOnce(&mut Vec<String>)) -> PollOutcome {
+        if !self.is_eligible(&voter) {
+            return PollOutcome::NotEligible;
+        }
+        if self.locked_votes.contains(&voter) {
+            return PollOutcome::VotesLocked;
+        }
+
+        change(self.slot_responses.entry(voter).or_default());
+        PollOutcome::Updated
+    }
+
+    /// How the poll ends with the current lock-ins, if that's already decided
+    fn conclusion(&self) -> Option<PollResult> {
+        let tallies = self.tallies();
+
+        if tallies.iter().any(|tally| !tally.can_reach_minimum()) {
+            Some(PollResult::NotEnoughMembers(tallies))
+        } else if tallies.iter().all(GroupTally::has_enough) {
+            Some(self.best_slot_result())
+        } else {
+            None
+        }
+    }
+
+    /// Start a grace period if the lock-ins decide the poll, otherwise return `open`
+    ///
+    /// A running grace period carries on if the poll would still end the same way.
+    fn settle(&mut self, open: PollOutcome) -> PollOutcome {
+        let pending = self.conclusion();
+        if pending.is_some() && pending == self.pending {
+            return open;
+        }
+        self.pending = pending;
+
+        match &self.pending {
+            Some(result) => {
+                self.pending_round += 1;
+                PollOutcome::Settled { result: result.clone(), round: self.pending_round }
+            }
+            None => open,
+        }
+    }
+
+    fn best_slot_result(&self) -> PollResult {
+        match self.best_slot() {
+            Some(choice) => PollResult::Confirmed(choice),
+            None => PollResult::NoSuitableTime,
+        }
+    }
+
+    fn finish(&mut self, result: PollResult) -> PollOutcome {
+        self.pending = None;
+        self.result = Some(result.clone());
+        PollOutcome::Finished(result)
+    }
+}
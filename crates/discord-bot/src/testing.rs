@@ -0,0 +1,497 @@
+//! # Fake Discord
+//!
+//! `RecordingDiscord` stands in for Discord when driving the interaction
+//! handlers from tests. It records every request the bot makes, keeps the
+//! messages those requests would have created so later button clicks can
+//! refer to them, and builds the slash command and button interactions a
+//! user would send. Everything happens in one test guild and channel.
+
+use serenity::{
+    async_trait,
+    builder::{
+        CreateInteractionResponse, CreateInteractionResponseFollowup, CreateMessage, EditInteractionResponse,
+        EditMessage, EditRole,
+    },
+    json::{self, Value},
+    model::{
+        application::interaction::{
+            application_command::ApplicationCommandInteraction,
+            message_component::MessageComponentInteraction,
+            InteractionResponseType,
+        },
+        channel::Message,
+        guild::{Member, Role},
+        Permissions,
+        id::{ChannelId, GuildId, InteractionId, MessageId, RoleId, UserId},
+    },
+};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::discord::{DiscordApi, InteractionRef};
+use crate::members::MemberSpec;
+
+/// The guild every fake interaction comes from
+pub const GUILD_ID: GuildId = GuildId(100);
+
+/// The channel every fake interaction and message is in
+pub const CHANNEL_ID: ChannelId = ChannelId(200);
+
+/// The bot's own user, the author of every message it sends
+pub const BOT_USER_ID: UserId = UserId(1);
+
+/// A request the bot made to Discord
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiscordCall {
+    InteractionResponse { interaction: InteractionId, body: Value },
+    EditInteractionResponse { interaction: InteractionId, body: Value },
+    Followup { interaction: InteractionId, body: Value },
+    SendMessage { channel: ChannelId, body: Value },
+    EditMessage { channel: ChannelId, message: MessageId, body: Value },
+    CreateRole { body: Value },
+    EditRole { role: RoleId, body: Value },
+    DeleteRole { role: RoleId },
+    AddMemberRole { user: UserId, role: RoleId },
+    RemoveMemberRole { user: UserId, role: RoleId },
+}
+
+impl DiscordCall {
+    /// The request body, for calls that have one
+    pub fn body(&self) -> Option<&Value> {
+        match self {
+            Self::InteractionResponse { body, .. }
+            | Self::EditInteractionResponse { body, .. }
+            | Self::Followup { body, .. }
+            | Self::SendMessage { body, .. }
+            | Self::EditMessage { body, .. }
+            | Self::CreateRole { body }
+            | Self::EditRole { body, .. } => Some(body),
+            Self::DeleteRole { .. } | Self::AddMemberRole { .. } | Self::RemoveMemberRole { .. } => None,
+        }
+    }
+}
+
+/// A `DiscordApi` that records requests instead of sending them
+#[derive(Default)]
+pub struct RecordingDiscord {
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    calls: Vec<DiscordCall>,
+    /// Messages as Discord would return them, by ID
+    messages: HashMap<MessageId, Value>,
+    /// The message holding each interaction's original response
+    responses: HashMap<InteractionId, MessageId>,
+    members: Vec<Member>,
+    /// Permissions granted to users in the test guild, sent with their interactions
+    permissions: HashMap<UserId, Permissions>,
+    roles: Vec<Role>,
+    last_id: u64,
+}
+
+impl State {
+    /// A new snowflake, unique within this fake
+    fn next_id(&mut self) -> u64 {
+        self.last_id += 1;
+        // Stay clear of the fixed guild, channel and user IDs
+        1_000 + self.last_id
+    }
+
+    /// Store a new message in the test channel built from a request body
+    fn create_message(&mut self, body: &Value) -> MessageId {
+        let id = MessageId(self.next_id());
+        let mut message = json!({
+            "id": id.to_string(),
+            "channel_id": CHANNEL_ID.to_string(),
+            "guild_id": GUILD_ID.to_string(),
+            "author": user_json(BOT_USER_ID, true),
+            "content": "",
+            "timestamp": "2024-01-01T00:00:00Z",
+            "edited_timestamp": null,
+            "tts": false,
+            "mention_everyone": false,
+            "mentions": [],
+            "mention_roles": [],
+            "attachments": [],
+            "embeds": [],
+            "components": [],
+            "pinned": false,
+            "type": 0,
+        });
+        apply_edit(&mut message, body);
+
+        self.messages.insert(id, message);
+        id
+    }
+
+    fn message(&self, id: MessageId) -> Option<Message> {
+        self.messages.get(&id).map(|message| {
+            serde_json::from_value(message.clone()).expect("fake message should deserialize")
+        })
+    }
+
+    /// The interaction's original response, created empty if there isn't one yet
+    fn response_message(&mut self, interaction: InteractionId) -> MessageId {
+        match self.responses.get(&interaction) {
+            Some(id) => *id,
+            None => {
+                let id = self.create_message(&json!({}));
+                self.responses.insert(interaction, id);
+                id
+            }
+        }
+    }
+
+    /// Apply an edit to a stored message, `None` if there is no such message
+    fn edit_message(&mut self, id: MessageId, body: &Value) -> Option<Message> {
+        apply_edit(self.messages.get_mut(&id)?, body);
+        self.message(id)
+    }
+}
+
+/// Copy the parts of a message request the fake keeps onto a stored message
+fn apply_edit(message: &mut Value, body: &Value) {
+    for key in ["content", "embeds", "components"] {
+        if let Some(value) = body.get(key) {
+            message[key] = value.clone();
+        }
+    }
+
+    // Embeds may point at attached images, which the fake doesn't store
+    if let Some(embeds) = message["embeds"].as_array_mut() {
+        for embed in embeds.iter_mut().filter_map(Value::as_object_mut) {
+            embed.remove("image");
+        }
+    }
+}
+
+fn user_json(id: UserId, bot: bool) -> Value {
+    json!({
+        "id": id.to_string(),
+        "username": format!("user{}", id),
+        "discriminator": "0001",
+        "avatar": null,
+        "bot": bot,
+    })
+}
+
+fn member_json(user_id: UserId, role_ids: &[RoleId], permissions: Permissions) -> Value {
+    json!({
+        "user": user_json(user_id, false),
+        "guild_id": GUILD_ID.to_string(),
+        "roles": role_ids.iter().map(|id| id.to_string()).collect::<Vec<_>>(),
+        "joined_at": "2024-01-01T00:00:00Z",
+        "deaf": false,
+        "mute": false,
+        "permissions": permissions.bits().to_string(),
+    })
+}
+
+fn to_value(map: HashMap<&'static str, Value>) -> Value {
+    Value::from(json::hashmap_to_json_map(map))
+}
+
+impl RecordingDiscord {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a member to the test guild holding the given roles
+    pub fn add_member(&self, user_id: UserId, role_ids: &[RoleId]) {
+        let member = serde_json::from_value(member_json(user_id, role_ids, Permissions::empty())).expect("fake member should deserialize");
+        self.state.lock().unwrap().members.push(member);
+    }
+
+    /// Give `user_id` permissions in the test guild, such as Manage Roles
+    pub fn grant_permissions(&self, user_id: UserId, permissions: Permissions) {
+        *self.state.lock().unwrap().permissions.entry(user_id).or_default() |= permissions;
+    }
+
+    /// The roles `user_id` holds in the test guild, if they were added as a member
+    fn member_roles(&self, user_id: UserId) -> Vec<RoleId> {
+        self.state.lock().unwrap().members.iter()
+            .find(|member| member.user.id == user_id)
+            .map(|member| member.roles.clone())
+            .unwrap_or_default()
+    }
+
+    /// `user_id` as the member sending an interaction, with their roles and permissions
+    fn interaction_member(&self, user_id: UserId) -> Value {
+        let permissions = self.state.lock().unwrap().permissions.get(&user_id).copied().unwrap_or_default();
+        member_json(user_id, &self.member_roles(user_id), permissions)
+    }
+
+    /// Every request made so far, oldest first
+    pub fn calls(&self) -> Vec<DiscordCall> {
+        self.state.lock().unwrap().calls.clone()
+    }
+
+    /// Requests made since the last call to `take_calls`
+    pub fn take_calls(&self) -> Vec<DiscordCall> {
+        std::mem::take(&mut self.state.lock().unwrap().calls)
+    }
+
+    /// A message the bot sent, as it looks after any edits
+    pub fn message(&self, id: MessageId) -> Option<Message> {
+        self.state.lock().unwrap().message(id)
+    }
+
+    /// The message holding an interaction's original response
+    pub fn original_response(&self, interaction: impl Into<InteractionRef>) -> Option<Message> {
+        let interaction = interaction.into();
+        let state = self.state.lock().unwrap();
+        state.responses.get(&interaction.id).and_then(|id| state.message(*id))
+    }
+
+    /// A slash command run by `user_id` in the test channel
+    ///
+    /// `options` is the command's `options` array as Discord sends it.
+    pub fn command(&self, user_id: UserId, name: &str, options: Value) -> ApplicationCommandInteraction {
+        let id = self.state.lock().unwrap().next_id();
+
+        serde_json::from_value(json!({
+            "id": id.to_string(),
+            "application_id": BOT_USER_ID.to_string(),
+            "type": 2,
+            "data": {
+                "id": "1",
+                "name": name,
+                "type": 1,
+                "options": options,
+            },
+            "guild_id": GUILD_ID.to_string(),
+            "channel_id": CHANNEL_ID.to_string(),
+            "member": self.interaction_member(user_id),
+            "token": format!("token{}", id),
+            "version": 1,
+            "locale": "en-US",
+        }))
+        .expect("fake command should deserialize")
+    }
+
+    /// `user_id` clicking the button `custom_id` on `message`
+    ///
+    /// As on Discord, the clicked message is the interaction's original
+    /// response until the bot responds with a new message.
+    pub fn click(&self, user_id: UserId, message: &Message, custom_id: &str) -> MessageComponentInteraction {
+        let mut state = self.state.lock().unwrap();
+        let id = state.next_id();
+        state.responses.insert(InteractionId(id), message.id);
+        drop(state);
+
+        serde_json::from_value(json!({
+            "id": id.to_string(),
+            "application_id": BOT_USER_ID.to_string(),
+            "type": 3,
+            "data": {
+                "custom_id": custom_id,
+                "component_type": 2,
+            },
+            "guild_id": GUILD_ID.to_string(),
+            "channel_id": CHANNEL_ID.to_string(),
+            "member": self.interaction_member(user_id),
+            "message": message,
+            "token": format!("token{}", id),
+            "version": 1,
+            "locale": "en-US",
+        }))
+        .expect("fake button click should deserialize")
+    }
+}
+
+#[async_trait]
+impl DiscordApi for RecordingDiscord {
+    async fn create_interaction_response(
+        &self,
+        interaction: &InteractionRef,
+        response: CreateInteractionResponse<'static>,
+    ) -> serenity::Result<()> {
+        let body = to_value(response.0);
+        let mut state = self.state.lock().unwrap();
+        state.calls.push(DiscordCall::InteractionResponse { interaction: interaction.id, body: body.clone() });
+
+        // Responses that post a message become the interaction's original
+        // response, while updates change the message a button was on
+        let kind = body["type"].as_u64();
+        let is = |kind_of: InteractionResponseType| kind == Some(kind_of as u64);
+
+        if is(InteractionResponseType::ChannelMessageWithSource)
+            || is(InteractionResponseType::DeferredChannelMessageWithSource)
+        {
+            let id = state.create_message(&body["data"]);
+            state.responses.insert(interaction.id, id);
+        } else if is(InteractionResponseType::UpdateMessage) {
+            let id = state.response_message(interaction.id);
+            state.edit_message(id, &body["data"]);
+        }
+
+        Ok(())
+    }
+
+    async fn edit_original_interaction_response(
+        &self,
+        interaction: &InteractionRef,
+        response: EditInteractionResponse,
+    ) -> serenity::Result<Message> {
+        let body = to_value(response.0);
+        let mut state = self.state.lock().unwrap();
+        state.calls.push(DiscordCall::EditInteractionResponse { interaction: interaction.id, body: body.clone() });
+
+        let id = state.response_message(interaction.id);
+        state.edit_message(id, &body).ok_or(serenity::Error::Other("Unknown message"))
+    }
+
+    async fn create_followup_message(
+        &self,
+        interaction: &InteractionRef,
+        followup: CreateInteractionResponseFollowup<'static>,
+    ) -> serenity::Result<Message> {
+        let body = to_value(followup.0);
+        let mut state = self.state.lock().unwrap();
+        state.calls.push(DiscordCall::Followup { interaction: interaction.id, body: body.clone() });
+
+        let id = state.create_message(&body);
+        Ok(state.message(id).expect("message was just created"))
+    }
+
+    async fn send_message(&self, channel_id: ChannelId, message: CreateMessage<'static>) -> serenity::Result<Message> {
+        let body = to_value(message.0);
+        let mut state = self.state.lock().unwrap();
+        state.calls.push(DiscordCall::SendMessage { channel: channel_id, body: body.clone() });
+
+        let id = state.create_message(&body);
+        Ok(state.message(id).expect("message was just created"))
+    }
+
+    async fn edit_message(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        message: EditMessage<'static>,
+    ) -> serenity::Result<Message> {
+        let body = to_value(message.0);
+        let mut state = self.state.lock().unwrap();
+        state.calls.push(DiscordCall::EditMessage { channel: channel_id, message: message_id, body: body.clone() });
+
+        state.edit_message(message_id, &body).ok_or(serenity::Error::Other("Unknown message"))
+    }
+
+    async fn guild_members(&self, _guild_id: GuildId) -> eyre::Result<Vec<Member>> {
+        Ok(self.state.lock().unwrap().members.clone())
+    }
+
+    /// Users resolve to themselves, roles to the test guild's members holding
+    /// them and `all` to every member
+    async fn resolve_members(
+        &self,
+        _guild_id: GuildId,
+        _channel_id: ChannelId,
+        specs: &[MemberSpec],
+    ) -> eyre::Result<Vec<String>> {
+        let state = self.state.lock().unwrap();
+        let holders = |role_id: RoleId| {
+            state.members.iter()
+                .filter(|m| m.roles.contains(&role_id))
+                .map(|m| m.user.id.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let mut resolved = Vec::new();
+        for spec in specs {
+            let ids = match spec {
+                MemberSpec::User(id) => vec![id.clone()],
+                MemberSpec::Role(id) => holders(RoleId(id.parse()?)),
+                MemberSpec::RoleName(name) => {
+                    let role = state.roles.iter()
+                        .find(|role| role.name.eq_ignore_ascii_case(name))
+                        .ok_or_else(|| eyre::eyre!("Role not found: {}", name))?;
+                    holders(role.id)
+                }
+                MemberSpec::All => state.members.iter().map(|m| m.user.id.to_string()).collect(),
+            };
+
+            for id in ids {
+                if !resolved.contains(&id) {
+                    resolved.push(id);
+                }
+            }
+        }
+
+        Ok(resolved)
+    }
+
+    async fn create_role(&self, guild_id: GuildId, role: EditRole) -> serenity::Result<Role> {
+        let body = to_value(role.0);
+        let mut state = self.state.lock().unwrap();
+        state.calls.push(DiscordCall::CreateRole { body: body.clone() });
+
+        let role: Role = serde_json::from_value(json!({
+            "id": state.next_id().to_string(),
+            "guild_id": guild_id.to_string(),
+            "name": body["name"],
+            "color": body.get("color").cloned().unwrap_or(json!(0)),
+            "hoist": body.get("hoist").cloned().unwrap_or(json!(false)),
+            "mentionable": body.get("mentionable").cloned().unwrap_or(json!(false)),
+            "managed": false,
+            "permissions": "0",
+            "position": state.roles.len() + 1,
+        }))?;
+        state.roles.push(role.clone());
+
+        Ok(role)
+    }
+
+    async fn edit_role(&self, _guild_id: GuildId, role_id: RoleId, role: EditRole) -> serenity::Result<Role> {
+        let body = to_value(role.0);
+        let mut state = self.state.lock().unwrap();
+        state.calls.push(DiscordCall::EditRole { role: role_id, body: body.clone() });
+
+        let role = state.roles.iter_mut()
+            .find(|role| role.id == role_id)
+            .ok_or(serenity::Error::Other("Unknown role"))?;
+        if let Some(name) = body["name"].as_str() {
+            role.name = name.to_string();
+        }
+
+        Ok(role.clone())
+    }
+
+    async fn delete_role(&self, _guild_id: GuildId, role_id: RoleId) -> serenity::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.calls.push(DiscordCall::DeleteRole { role: role_id });
+
+        state.roles.retain(|role| role.id != role_id);
+        for member in &mut state.members {
+            member.roles.retain(|id| *id != role_id);
+        }
+
+        Ok(())
+    }
+
+    async fn add_member_role(&self, _guild_id: GuildId, user_id: UserId, role_id: RoleId) -> serenity::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.calls.push(DiscordCall::AddMemberRole { user: user_id, role: role_id });
+
+        if let Some(member) = state.members.iter_mut().find(|m| m.user.id == user_id)
+            && !member.roles.contains(&role_id)
+        {
+            member.roles.push(role_id);
+        }
+
+        Ok(())
+    }
+
+    async fn remove_member_role(&self, _guild_id: GuildId, user_id: UserId, role_id: RoleId) -> serenity::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.calls.push(DiscordCall::RemoveMemberRole { user: user_id, role: role_id });
+
+        if let Some(member) = state.members.iter_mut().find(|m| m.user.id == user_id) {
+            member.roles.retain(|id| *id != role_id);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+//! Timezone helpers for rendering poll slots.
+//!
+//! Polls group their slots into days using the server timezone, while each
+//! voter's personal view may use their own `/timezone me` preference. Slot
+//! labels are therefore rendered per viewer rather than stored once.
+
+use chrono::{DateTime, NaiveDate, Timelike, Utc};
+use chrono_tz::Tz;
+use std::str::FromStr;
+
+/// Parse an IANA timezone name, falling back to UTC
+pub fn parse_timezone(timezone: &str) -> Tz {
+    Tz::from_str(timezone).unwrap_or(chrono_tz::UTC)
+}
+
+/// Format a slot's time range in the given timezone, e.g. "6pm-8pm" or "6:30pm-7pm"
+pub fn format_slot_time(start: DateTime<Utc>, end: DateTime<Utc>, tz: Tz) -> String {
+    let start_local = start.with_timezone(&tz);
+    let end_local = end.with_timezone(&tz);
+
+    // Format using shorthand - no minutes if on the hour
+    if start_local.minute() == 0 && end_local.minute() == 0 {
+        format!("{}-{}", start_local.format("%-I%p"), end_local.format("%-I%p"))
+    } else {
+        format!("{}-{}", start_local.format("%-I:%M%p"), end_local.format("%-I:%M%p"))
+    }
+    .to_lowercase()
+    .replace(' ', "")
+}
+
+/// Format a slot for a voter's personal view
+///
+/// Days are grouped in the server timezone, so a slot can fall on a different
+/// local date for the voter than the day being shown. Those slots get a
+/// weekday prefix so they aren't mistaken for times on the shown date.
+pub fn format_personal_slot_time(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    shown_date: NaiveDate,
+    tz: Tz,
+) -> String {
+    let time = format_slot_time(start, end, tz);
+    let local_date = start.with_timezone(&tz).date_naive();
+
+    if local_date == shown_date {
+        time
+    } else {
+        format!("{} {}", local_date.format("%a"), time)
+    }
+}
+
+/// Zones suggested before the user has typed anything
+const COMMON_TIMEZONES: &[&str] = &[
+    "America/Los_Angeles",
+    "America/Denver",
+    "America/Chicago",
+    "America/New_York",
+    "Europe/London",
+    "Europe/Paris",
+    "Europe/Helsinki",
+    "Asia/Tokyo",
+    "Asia/Shanghai",
+    "Asia/Kolkata",
+    "Australia/Sydney",
+    "UTC",
+];
+
+/// Fuzzy search IANA timezone names, best matches first
+///
+/// Matching ignores case and treats spaces as underscores, so "new york"
+/// finds `America/New_York`. Exact and prefix matches rank above matches on
+/// the city part, which rank above substring and then subsequence matches.
+pub fn search_timezones(query: &str, limit: usize) -> Vec<&'static str> {
+    let query = query.trim().to_lowercase().replace(' ', "_");
+
+    if query.is_empty() {
+        return COMMON_TIMEZONES.iter().copied().take(limit).collect();
+    }
+
+    let mut scored: Vec<(u8, &'static str)> = chrono_tz::TZ_VARIANTS.iter()
+        .filter_map(|tz| {
+            let name = tz.name();
+            timezone_match_rank(&name.to_lowercase(), &query).map(|rank| (rank, name))
+        })
+        .collect();
+
+    scored.sort_by(|(rank_a, name_a), (rank_b, name_b)| {
+        rank_a.cmp(rank_b)
+            .then(name_a.len().cmp(&name_b.len()))
+            .then(name_a.cmp(name_b))
+    });
+
+    scored.into_iter().map(|(_, name)| name).take(limit).collect()
+}
+
+/// How well a lowercase zone name matches the query, lower is better
+fn timezone_match_rank(name: &str, query: &str) -> Option<u8> {
+    let city = name.rsplit('/').next().unwrap_or(name);
+
+    if name == query {
+        Some(0)
+    } else if name.starts_with(query) {
+        Some(1)
+    } else if city.starts_with(query) {
+        Some(2)
+    } else if name.contains(query) {
+        Some(3)
+    } else if is_subsequence(query, name) {
+        Some(4)
+    } else {
+        None
+    }
+}
+
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut haystack = haystack.chars();
+    needle.chars().all(|c| haystack.any(|h| h == c))
+}
@@ -0,0 +1,32 @@
+use timesync_discord_bot::handlers::autocomplete::{complete_group_list, match_group_names};
+
+fn names(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+}
+
+#[test]
+fn test_match_group_names_prefers_prefix_matches() {
+    let groups = names(&["Raid Team", "Tanks", "Healers", "Team B"]);
+    
+    let matches = match_group_names("team", &groups);
+    assert_eq!(matches, vec!["Team B", "Raid Team"]);
+    
+    // An empty query matches every group
+    assert_eq!(match_group_names("", &groups).len(), 4);
+}
+
+#[test]
+fn test_complete_group_list_replaces_last_entry() {
+    let groups = names(&["Tanks", "Healers", "Damage"]);
+    
+    assert_eq!(complete_group_list("ta", &groups, 25), vec!["Tanks"]);
+    assert_eq!(complete_group_list("Tanks, he", &groups, 25), vec!["Tanks, Healers"]);
+}
+
+#[test]
+fn test_complete_group_list_skips_listed_groups() {
+    let groups = names(&["Tanks", "Healers", "Damage"]);
+    
+    let suggestions = complete_group_list("tanks, ", &groups, 25);
+    assert_eq!(suggestions, vec!["tanks, Damage", "tanks, Healers"]);
+}
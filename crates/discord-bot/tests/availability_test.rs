@@ -0,0 +1,192 @@
+use chrono::{NaiveDate, TimeZone, Utc, Weekday};
+use rstest::rstest;
+use timesync_core::{
+    models::schedule::{SlotPreference, TimeSlotResponse},
+    parser::parse_weekly_availability,
+};
+use timesync_discord_bot::availability::{expand_slots, render_week_grid, week_start, WeeklyAvailability};
+
+#[rstest]
+#[case("Mon-Fri 18:00-22:00", "**Mon** 18:00-22:00\n**Tue** 18:00-22:00\n**Wed** 18:00-22:00\n**Thu** 18:00-22:00\n**Fri** 18:00-22:00")]
+#[case("weekends 9am - 12pm", "**Sat** 09:00-12:00\n**Sun** 09:00-12:00")]
+#[case("Sat-Mon 20-00", "**Mon** 20:00-24:00\n**Sat** 20:00-24:00\n**Sun** 20:00-24:00")]
+#[case("Fri 22-2", "**Fri** 22:00-24:00\n**Sat** 00:00-02:00")]
+#[case("Tue 18:30-19:15", "**Tue** 18:00-20:00")]
+#[case("weekdays after 8pm, not Wednesday", "**Mon** 20:00-24:00\n**Tue** 20:00-24:00\n**Thu** 20:00-24:00\n**Fri** 20:00-24:00")]
+fn test_weekly_availability_adds_parsed_quick_entry(#[case] input: &str, #[case] summary: &str) {
+    let tz = chrono_tz::America::New_York;
+    let today = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+    let slots = parse_weekly_availability(input, today, tz).unwrap();
+
+    let mut availability = WeeklyAvailability::default();
+    availability.add_slots(&slots, tz);
+
+    assert_eq!(availability.summary(), summary);
+}
+
+#[test]
+fn test_weekly_availability_ignores_one_off_quick_entry() {
+    let today = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+    let slots = parse_weekly_availability("tomorrow 10-12", today, chrono_tz::UTC).unwrap();
+
+    let mut availability = WeeklyAvailability::default();
+    availability.add_slots(&slots, chrono_tz::UTC);
+
+    assert!(availability.is_empty());
+}
+
+#[test]
+fn test_weekly_availability_merges_hours_into_slots() {
+    let tz = chrono_tz::Europe::Berlin;
+    let monday = NaiveDate::from_ymd_opt(2025, 1, 13).unwrap();
+    
+    let mut availability = WeeklyAvailability::default();
+    availability.add(&[Weekday::Mon], 18, 20);
+    availability.add(&[Weekday::Mon], 20, 22);
+    availability.remove(&[Weekday::Mon], 19, 20);
+    
+    let slots = availability.to_slots(monday, tz);
+    assert_eq!(slots.len(), 2);
+    assert_eq!(slots[0].start, Utc.with_ymd_and_hms(2025, 1, 13, 17, 0, 0).unwrap());
+    assert_eq!(slots[0].end, Utc.with_ymd_and_hms(2025, 1, 13, 18, 0, 0).unwrap());
+    assert_eq!(slots[1].start, Utc.with_ymd_and_hms(2025, 1, 13, 19, 0, 0).unwrap());
+    assert_eq!(slots[1].end, Utc.with_ymd_and_hms(2025, 1, 13, 21, 0, 0).unwrap());
+    assert!(slots.iter().all(|slot| slot.is_recurring));
+    
+    assert_eq!(availability.summary(), "**Mon** 18:00-19:00, 20:00-22:00");
+}
+
+#[test]
+fn test_weekly_availability_wraps_past_midnight() {
+    let mut availability = WeeklyAvailability::default();
+    availability.add(&[Weekday::Sun], 22, 2);
+    
+    assert_eq!(availability.summary(), "**Mon** 00:00-02:00\n**Sun** 22:00-24:00");
+}
+
+#[test]
+fn test_weekly_availability_round_trips_stored_slots() {
+    let tz = chrono_tz::America::Chicago;
+    let monday = week_start(NaiveDate::from_ymd_opt(2025, 1, 16).unwrap());
+    assert_eq!(monday, NaiveDate::from_ymd_opt(2025, 1, 13).unwrap());
+    
+    let mut availability = WeeklyAvailability::default();
+    availability.add(&[Weekday::Tue, Weekday::Sat], 9, 12);
+    
+    let stored: Vec<TimeSlotResponse> = availability.to_slots(monday, tz)
+        .into_iter()
+        .map(|slot| TimeSlotResponse { start: slot.start, end: slot.end, is_recurring: true, is_unavailable: false, preference: SlotPreference::Available })
+        .collect();
+    
+    assert_eq!(WeeklyAvailability::from_slots(&stored, tz), availability);
+}
+
+#[test]
+fn test_weekly_availability_ignores_one_off_slots() {
+    let one_off = TimeSlotResponse {
+        start: Utc.with_ymd_and_hms(2025, 1, 13, 17, 0, 0).unwrap(),
+        end: Utc.with_ymd_and_hms(2025, 1, 13, 19, 0, 0).unwrap(),
+        is_recurring: false,
+        is_unavailable: false,
+        preference: SlotPreference::Available,
+    };
+    
+    assert!(WeeklyAvailability::from_slots(&[one_off], chrono_tz::UTC).is_empty());
+}
+
+#[test]
+fn test_weekly_availability_ignores_busy_slots() {
+    let busy = TimeSlotResponse {
+        start: Utc.with_ymd_and_hms(2025, 1, 13, 17, 0, 0).unwrap(),
+        end: Utc.with_ymd_and_hms(2025, 1, 13, 19, 0, 0).unwrap(),
+        is_recurring: true,
+        is_unavailable: true,
+        preference: SlotPreference::Available,
+    };
+    
+    assert!(WeeklyAvailability::from_slots(&[busy], chrono_tz::UTC).is_empty());
+}
+
+#[test]
+fn test_expand_slots_repeats_recurring_slots() {
+    let weekly = TimeSlotResponse {
+        start: Utc.with_ymd_and_hms(2025, 1, 6, 18, 0, 0).unwrap(),
+        end: Utc.with_ymd_and_hms(2025, 1, 6, 20, 0, 0).unwrap(),
+        is_recurring: true,
+        is_unavailable: false,
+        preference: SlotPreference::Available,
+    };
+    let one_off = TimeSlotResponse {
+        start: Utc.with_ymd_and_hms(2025, 1, 23, 9, 0, 0).unwrap(),
+        end: Utc.with_ymd_and_hms(2025, 1, 23, 10, 30, 0).unwrap(),
+        is_recurring: false,
+        is_unavailable: false,
+        preference: SlotPreference::Available,
+    };
+    let past = TimeSlotResponse {
+        start: Utc.with_ymd_and_hms(2025, 1, 2, 9, 0, 0).unwrap(),
+        end: Utc.with_ymd_and_hms(2025, 1, 2, 10, 0, 0).unwrap(),
+        is_recurring: false,
+        is_unavailable: false,
+        preference: SlotPreference::Available,
+    };
+    
+    let from = Utc.with_ymd_and_hms(2025, 1, 22, 0, 0, 0).unwrap();
+    let week = expand_slots(&[weekly, one_off, past], from, from + chrono::Duration::days(14));
+    
+    let starts: Vec<_> = week.iter().map(|slot| (slot.start, slot.is_recurring)).collect();
+    assert_eq!(starts, vec![
+        (Utc.with_ymd_and_hms(2025, 1, 23, 9, 0, 0).unwrap(), false),
+        (Utc.with_ymd_and_hms(2025, 1, 27, 18, 0, 0).unwrap(), true),
+        (Utc.with_ymd_and_hms(2025, 2, 3, 18, 0, 0).unwrap(), true),
+    ]);
+}
+
+#[test]
+fn test_render_week_grid() {
+    let slots = vec![
+        TimeSlotResponse {
+            start: Utc.with_ymd_and_hms(2025, 1, 23, 9, 0, 0).unwrap(),
+            end: Utc.with_ymd_and_hms(2025, 1, 23, 10, 30, 0).unwrap(),
+            is_recurring: false,
+            is_unavailable: false,
+            preference: SlotPreference::Available,
+        },
+        TimeSlotResponse {
+            start: Utc.with_ymd_and_hms(2025, 1, 27, 18, 0, 0).unwrap(),
+            end: Utc.with_ymd_and_hms(2025, 1, 27, 20, 0, 0).unwrap(),
+            is_recurring: true,
+            is_unavailable: false,
+            preference: SlotPreference::Available,
+        },
+        TimeSlotResponse {
+            start: Utc.with_ymd_and_hms(2025, 1, 27, 19, 0, 0).unwrap(),
+            end: Utc.with_ymd_and_hms(2025, 1, 27, 19, 30, 0).unwrap(),
+            is_recurring: false,
+            is_unavailable: true,
+            preference: SlotPreference::Available,
+        },
+        TimeSlotResponse {
+            start: Utc.with_ymd_and_hms(2025, 1, 25, 10, 0, 0).unwrap(),
+            end: Utc.with_ymd_and_hms(2025, 1, 25, 12, 0, 0).unwrap(),
+            is_recurring: false,
+            is_unavailable: false,
+            preference: SlotPreference::IfNeedBe,
+        },
+    ];
+    
+    // Shown an hour ahead of UTC
+    let grid = render_week_grid(&slots, NaiveDate::from_ymd_opt(2025, 1, 22).unwrap(), chrono_tz::Europe::Paris);
+    
+    assert_eq!(grid, [
+        "       0     6     12    18",
+        "Wed 22 ························",
+        "Thu 23 ··········██············",
+        "Fri 24 ························",
+        "Sat 25 ···········░░···········",
+        "Sun 26 ························",
+        "Mon 27 ···················▒x···",
+        "Tue 28 ························",
+        "█ available  ▒ every week  ░ if need be  x busy",
+    ].join("\n"));
+}
@@ -9,4 +9,61 @@ fn test_register_commands() {
     
     // Just test that it doesn't panic
     // Actual command testing is complex due to opaque builder pattern
-}
\ No newline at end of file
+}
+#[test]
+fn test_schedule_command_subcommands() {
+    let command = commands::schedule::create_schedule_command();
+    
+    let options = command.0.get("options")
+        .and_then(|v| v.as_array())
+        .expect("schedule command has options");
+    let subcommands: Vec<&str> = options.iter()
+        .filter_map(|option| option.get("name").and_then(|v| v.as_str()))
+        .collect();
+    assert_eq!(subcommands, vec!["create", "edit", "show"]);
+    
+    // Show defaults to the invoking user
+    let user = options[2].get("options")
+        .and_then(|v| v.as_array())
+        .and_then(|opts| opts.first())
+        .expect("show has a user option");
+    assert_eq!(user.get("required").and_then(|v| v.as_bool()), Some(false));
+}
+
+#[test]
+fn test_meeting_command_subcommands() {
+    let command = commands::schedule::meeting_command();
+    
+    assert_eq!(command.0.get("name").and_then(|v| v.as_str()), Some("meeting"));
+    
+    let subcommands: Vec<&str> = command.0.get("options")
+        .and_then(|v| v.as_array())
+        .expect("meeting command has options")
+        .iter()
+        .filter_map(|option| option.get("name").and_then(|v| v.as_str()))
+        .collect();
+    assert_eq!(subcommands, vec!["list", "info", "cancel", "reschedule"]);
+}
+
+#[test]
+fn test_group_command_subcommands() {
+    let command = commands::schedule::group_command();
+    
+    let options = command.0.get("options")
+        .and_then(|v| v.as_array())
+        .expect("group command has options");
+    let subcommands: Vec<&str> = options.iter()
+        .filter_map(|option| option.get("name").and_then(|v| v.as_str()))
+        .collect();
+    assert_eq!(
+        subcommands,
+        vec!["create", "list", "add", "remove", "info", "rename", "delete", "bind", "unbind", "require", "attendee"]
+    );
+    
+    // Role creation is optional on create
+    let create_role = options[0].get("options")
+        .and_then(|v| v.as_array())
+        .and_then(|opts| opts.iter().find(|opt| opt.get("name").and_then(|v| v.as_str()) == Some("create_role")))
+        .expect("create has a create_role option");
+    assert_eq!(create_role.get("required").and_then(|v| v.as_bool()), Some(false));
+}
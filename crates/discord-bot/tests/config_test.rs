@@ -1,16 +1,26 @@
+use serenity::prelude::GatewayIntents;
 use timesync_discord_bot::config::BotConfig;
 
-#[test]
-fn test_command_prefix_default() {
-    // Test the default command prefix
-    let config = BotConfig {
+/// A config with only the required fields set
+fn test_config() -> BotConfig {
+    BotConfig {
         token: "test_token".to_string(),
         application_id: 12345,
         web_base_url: "http://localhost".to_string(),
-        database_url: "postgres://localhost".to_string(),
+        api_base_url: None,
         command_prefix: None,
         test_guild_id: None,
-    };
+        role_sync_enabled: None,
+        role_sync_interval_secs: None,
+        poll_grace_period_secs: None,
+        poll_manager_role_id: None,
+    }
+}
+
+#[test]
+fn test_command_prefix_default() {
+    // Test the default command prefix
+    let config = test_config();
     
     assert_eq!(config.command_prefix(), "!");
 }
@@ -19,13 +29,82 @@ fn test_command_prefix_default() {
 fn test_command_prefix_custom() {
     // Test a custom command prefix
     let config = BotConfig {
-        token: "test_token".to_string(),
-        application_id: 12345,
-        web_base_url: "http://localhost".to_string(),
-        database_url: "postgres://localhost".to_string(),
         command_prefix: Some("/".to_string()),
-        test_guild_id: None,
+        ..test_config()
     };
     
     assert_eq!(config.command_prefix(), "/");
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_role_sync_interval() {
+    let mut config = test_config();
+    
+    // Off unless role sync is enabled
+    assert_eq!(config.role_sync_interval(), None);
+    
+    // Defaults to hourly
+    config.role_sync_enabled = Some(true);
+    assert_eq!(config.role_sync_interval(), Some(std::time::Duration::from_secs(3600)));
+    
+    config.role_sync_interval_secs = Some(300);
+    assert_eq!(config.role_sync_interval(), Some(std::time::Duration::from_secs(300)));
+    
+    // Zero disables the periodic reconciliation
+    config.role_sync_interval_secs = Some(0);
+    assert_eq!(config.role_sync_interval(), None);
+}
+
+#[test]
+fn test_gateway_intents_need_role_sync_for_members() {
+    let mut config = test_config();
+    
+    // The members intent is privileged, so it's left out by default
+    assert!(!config.gateway_intents().contains(GatewayIntents::GUILD_MEMBERS));
+    assert!(config.gateway_intents().contains(GatewayIntents::GUILDS));
+    
+    config.role_sync_enabled = Some(true);
+    assert!(config.gateway_intents().contains(GatewayIntents::GUILD_MEMBERS));
+}
+
+#[test]
+fn test_api_base_url() {
+    let mut config = BotConfig {
+        web_base_url: "http://localhost:3000/".to_string(),
+        ..test_config()
+    };
+    
+    // Defaults to the API served alongside the web app
+    assert_eq!(config.api_base_url(), "http://localhost:3000/api");
+    
+    config.api_base_url = Some("http://api:3000/api".to_string());
+    assert_eq!(config.api_base_url(), "http://api:3000/api");
+}
+
+#[test]
+fn test_from_env_ignores_empty_api_base_url() {
+    // As left by copying `.env.example` without filling it in. No other test
+    // in this binary reads the environment.
+    unsafe {
+        std::env::set_var("DISCORD_TOKEN", "test_token");
+        std::env::set_var("DISCORD_APPLICATION_ID", "12345");
+        std::env::set_var("WEB_BASE_URL", "http://localhost:3000");
+        std::env::set_var("API_BASE_URL", "");
+    }
+    
+    let config = BotConfig::from_env().unwrap();
+    assert_eq!(config.api_base_url, None);
+    assert_eq!(config.api_base_url(), "http://localhost:3000/api");
+}
+
+#[test]
+fn test_poll_grace_period() {
+    let mut config = test_config();
+    
+    // Defaults to a minute
+    assert_eq!(config.poll_grace_period(), std::time::Duration::from_secs(60));
+    
+    // Zero finishes settled polls straight away
+    config.poll_grace_period_secs = Some(0);
+    assert!(config.poll_grace_period().is_zero());
+}
@@ -0,0 +1,251 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::Request,
+    http::{Method, StatusCode},
+    middleware::{self, Next},
+    response::IntoResponse,
+};
+use serde_json::{json, Value};
+use serenity::model::{
+    application::interaction::{application_command::ApplicationCommandInteraction, Interaction},
+    id::{RoleId, UserId},
+    Permissions,
+};
+use timesync_api::ApiState;
+use timesync_client::TimeSyncClient;
+use timesync_core::models::discord::GetDiscordGroupResponse;
+use timesync_discord_bot::{
+    config::BotConfig,
+    handlers::Handler,
+    testing::{DiscordCall, RecordingDiscord, GUILD_ID},
+};
+use tokio::net::TcpListener;
+
+const ALICE: UserId = UserId(10);
+const BOB: UserId = UserId(11);
+
+/// A bot talking to an in-memory API and a fake Discord
+struct TestBot {
+    handler: Handler,
+    discord: Arc<RecordingDiscord>,
+    api: TimeSyncClient,
+}
+
+impl TestBot {
+    async fn spawn() -> Self {
+        Self::spawn_with_router(timesync_api::api_routes()).await
+    }
+
+    /// A bot whose API fails every attempt to store a group's role
+    async fn spawn_failing_role_updates() -> Self {
+        let routes = timesync_api::api_routes().layer(middleware::from_fn(|request: Request, next: Next| async move {
+            if request.method() == Method::PUT && request.uri().path().ends_with("/role") {
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+
+            next.run(request).await
+        }));
+
+        Self::spawn_with_router(routes).await
+    }
+
+    async fn spawn_with_router(routes: axum::Router<Arc<ApiState>>) -> Self {
+        let app = axum::Router::new()
+            .nest("/api", routes)
+            .with_state(Arc::new(ApiState::in_memory()));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("Failed to bind");
+        let addr = listener.local_addr().expect("Failed to get address");
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.expect("Server failed");
+        });
+
+        let api_base_url = format!("http://{}/api", addr);
+        let config = BotConfig {
+            token: "token".to_string(),
+            application_id: 1,
+            web_base_url: "http://localhost:3000".to_string(),
+            api_base_url: Some(api_base_url.clone()),
+            command_prefix: None,
+            test_guild_id: None,
+            role_sync_enabled: None,
+            role_sync_interval_secs: Some(0),
+            poll_grace_period_secs: Some(0),
+            poll_manager_role_id: None,
+        };
+
+        let discord = Arc::new(RecordingDiscord::new());
+        discord.add_member(ALICE, &[]);
+        discord.add_member(BOB, &[]);
+        discord.grant_permissions(ALICE, Permissions::MANAGE_ROLES);
+
+        Self {
+            handler: Handler::new(config),
+            discord,
+            api: TimeSyncClient::new(api_base_url),
+        }
+    }
+
+    /// Run a `/group` subcommand as `user` and return the interaction
+    async fn group(&self, user: UserId, subcommand: &str, options: Value) -> ApplicationCommandInteraction {
+        let options = json!([{ "name": subcommand, "type": 1, "options": options }]);
+        let command = self.discord.command(user, "group", options);
+        self.handler
+            .handle_interaction(self.discord.clone(), Interaction::ApplicationCommand(command.clone()))
+            .await;
+
+        command
+    }
+
+    /// Create a group of Alice and Bob, with a role unless `create_role` is false
+    async fn create_group(&self, name: &str, create_role: bool) -> ApplicationCommandInteraction {
+        self.group(ALICE, "create", json!([
+            { "name": "name", "type": 3, "value": name },
+            { "name": "members", "type": 3, "value": format!("<@{}>, <@{}>", ALICE, BOB) },
+            { "name": "create_role", "type": 5, "value": create_role },
+        ]))
+        .await
+    }
+
+    async fn find_group(&self, name: &str) -> Option<GetDiscordGroupResponse> {
+        self.api.get_discord_group_by_name(&GUILD_ID.to_string(), name).await.unwrap()
+    }
+}
+
+/// The names of the roles the bot created
+fn created_roles(calls: &[DiscordCall]) -> Vec<&str> {
+    calls.iter()
+        .filter_map(|call| match call {
+            DiscordCall::CreateRole { body } => body["name"].as_str(),
+            _ => None,
+        })
+        .collect()
+}
+
+fn deleted_roles(calls: &[DiscordCall]) -> Vec<RoleId> {
+    calls.iter()
+        .filter_map(|call| match call {
+            DiscordCall::DeleteRole { role } => Some(*role),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The content of ephemeral responses, in order
+fn ephemeral_messages(calls: &[DiscordCall]) -> Vec<String> {
+    calls.iter()
+        .filter_map(|call| match call {
+            DiscordCall::InteractionResponse { body, .. } => Some(&body["data"]),
+            _ => None,
+        })
+        .filter(|data| data["flags"].as_u64().is_some_and(|flags| flags & 64 != 0))
+        .filter_map(|data| data["content"].as_str().map(str::to_string))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_group_create_makes_managed_role() {
+    let bot = TestBot::spawn().await;
+
+    bot.create_group("Raiders", true).await;
+
+    let group = bot.find_group("Raiders").await.expect("group was created");
+    assert_eq!(group.members.len(), 2);
+    assert!(!group.role_sync);
+
+    let calls = bot.discord.take_calls();
+    assert_eq!(created_roles(&calls), vec!["Raiders"]);
+    let role_id = RoleId(group.role_id.expect("role was saved").parse().unwrap());
+    let mut assigned: Vec<UserId> = calls.iter()
+        .filter_map(|call| match call {
+            DiscordCall::AddMemberRole { user, role } if *role == role_id => Some(*user),
+            _ => None,
+        })
+        .collect();
+    assigned.sort();
+    assert_eq!(assigned, vec![ALICE, BOB]);
+}
+
+#[tokio::test]
+async fn test_group_create_without_role() {
+    let bot = TestBot::spawn().await;
+
+    bot.create_group("Raiders", false).await;
+
+    let group = bot.find_group("Raiders").await.expect("group was created");
+    assert_eq!(group.role_id, None);
+    assert!(created_roles(&bot.discord.take_calls()).is_empty());
+}
+
+#[tokio::test]
+async fn test_group_create_deletes_role_that_could_not_be_saved() {
+    let bot = TestBot::spawn_failing_role_updates().await;
+
+    bot.create_group("Raiders", true).await;
+
+    let calls = bot.discord.take_calls();
+    assert_eq!(created_roles(&calls), vec!["Raiders"]);
+    assert_eq!(deleted_roles(&calls).len(), 1);
+    assert!(!calls.iter().any(|call| matches!(call, DiscordCall::AddMemberRole { .. })));
+
+    let group = bot.find_group("Raiders").await.expect("group was still created");
+    assert_eq!(group.role_id, None);
+}
+
+#[tokio::test]
+async fn test_group_rename_renames_managed_role() {
+    let bot = TestBot::spawn().await;
+    bot.create_group("Raiders", true).await;
+    let role_id = bot.find_group("Raiders").await.unwrap().role_id.unwrap();
+    bot.discord.take_calls();
+
+    bot.group(ALICE, "rename", json!([
+        { "name": "name", "type": 3, "value": "Raiders" },
+        { "name": "new_name", "type": 3, "value": "Builders" },
+    ]))
+    .await;
+
+    assert!(bot.find_group("Raiders").await.is_none());
+    assert_eq!(bot.find_group("Builders").await.unwrap().role_id.as_deref(), Some(role_id.as_str()));
+    let renamed: Vec<(String, Value)> = bot.discord.take_calls().into_iter()
+        .filter_map(|call| match call {
+            DiscordCall::EditRole { role, body } => Some((role.to_string(), body["name"].clone())),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(renamed, vec![(role_id, json!("Builders"))]);
+}
+
+#[tokio::test]
+async fn test_group_rename_with_role_needs_manage_roles() {
+    let bot = TestBot::spawn().await;
+    bot.create_group("Raiders", true).await;
+    bot.discord.take_calls();
+
+    bot.group(BOB, "rename", json!([
+        { "name": "name", "type": 3, "value": "Raiders" },
+        { "name": "new_name", "type": 3, "value": "Builders" },
+    ]))
+    .await;
+
+    assert_eq!(
+        ephemeral_messages(&bot.discord.take_calls()),
+        vec!["You need the Manage Roles permission to rename a group with a role"]
+    );
+    assert!(bot.find_group("Raiders").await.is_some());
+}
+
+#[tokio::test]
+async fn test_group_delete_deletes_managed_role() {
+    let bot = TestBot::spawn().await;
+    bot.create_group("Raiders", true).await;
+    let role_id = bot.find_group("Raiders").await.unwrap().role_id.unwrap();
+    bot.discord.take_calls();
+
+    bot.group(ALICE, "delete", json!([{ "name": "name", "type": 3, "value": "Raiders" }])).await;
+
+    assert!(bot.find_group("Raiders").await.is_none());
+    assert_eq!(deleted_roles(&bot.discord.take_calls()), vec![RoleId(role_id.parse().unwrap())]);
+}
+
@@ -0,0 +1,536 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use serde_json::{json, Value};
+use serenity::model::{
+    application::interaction::{
+        application_command::ApplicationCommandInteraction, message_component::MessageComponentInteraction,
+        Interaction,
+    },
+    channel::Message,
+    id::{RoleId, UserId},
+};
+use timesync_api::ApiState;
+use timesync_client::TimeSyncClient;
+use timesync_core::models::{
+    discord::CreateDiscordGroupRequest,
+    meeting::MeetingStatus,
+    schedule::{CreateScheduleRequest, CreateTimeSlotRequest},
+};
+use timesync_discord_bot::{
+    config::BotConfig,
+    handlers::Handler,
+    testing::{DiscordCall, RecordingDiscord, GUILD_ID},
+};
+use tokio::net::TcpListener;
+
+const ALICE: UserId = UserId(10);
+const BOB: UserId = UserId(11);
+const OUTSIDER: UserId = UserId(12);
+const MANAGER_ROLE: RoleId = RoleId(300);
+
+/// A bot talking to an in-memory API and a fake Discord
+struct TestBot {
+    handler: Handler,
+    discord: Arc<RecordingDiscord>,
+    api: TimeSyncClient,
+}
+
+impl TestBot {
+    /// A bot whose polls finish as soon as they settle
+    async fn spawn() -> Self {
+        Self::spawn_with_grace_period(0).await
+    }
+
+    async fn spawn_with_grace_period(grace_period_secs: u64) -> Self {
+        let app = axum::Router::new()
+            .nest("/api", timesync_api::api_routes())
+            .with_state(Arc::new(ApiState::in_memory()));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("Failed to bind");
+        let addr = listener.local_addr().expect("Failed to get address");
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.expect("Server failed");
+        });
+
+        let api_base_url = format!("http://{}/api", addr);
+        let config = BotConfig {
+            token: "token".to_string(),
+            application_id: 1,
+            web_base_url: "http://localhost:3000".to_string(),
+            api_base_url: Some(api_base_url.clone()),
+            command_prefix: None,
+            test_guild_id: None,
+            role_sync_enabled: None,
+            role_sync_interval_secs: Some(0),
+            poll_grace_period_secs: Some(grace_period_secs),
+            poll_manager_role_id: Some(MANAGER_ROLE.0),
+        };
+
+        Self {
+            handler: Handler::new(config),
+            discord: Arc::new(RecordingDiscord::new()),
+            api: TimeSyncClient::new(api_base_url),
+        }
+    }
+
+    /// Give each member a schedule that is free tomorrow evening, and put them in a group
+    async fn create_group(&self, name: &str, members: &[UserId]) {
+        let start = tomorrow_at(18);
+
+        for member in members {
+            self.api
+                .create_schedule(&CreateScheduleRequest {
+                    name: format!("Schedule for {}", member),
+                    password: None,
+                    slots: vec![CreateTimeSlotRequest {
+                        start,
+                        end: start + Duration::hours(2),
+                        is_recurring: false,
+                        is_unavailable: false,
+                        preference: Default::default(),
+                    }],
+                    discord_id: Some(member.to_string()),
+                    timezone: "UTC".to_string(),
+                })
+                .await
+                .expect("Failed to create schedule");
+        }
+
+        self.api
+            .create_discord_group(&CreateDiscordGroupRequest {
+                name: name.to_string(),
+                server_id: GUILD_ID.to_string(),
+                member_ids: members.iter().map(UserId::to_string).collect(),
+            })
+            .await
+            .expect("Failed to create group");
+    }
+
+    /// Run a slash command and return the interaction
+    async fn command(&self, user: UserId, name: &str, options: Value) -> ApplicationCommandInteraction {
+        let command = self.discord.command(user, name, options);
+        self.handler
+            .handle_interaction(self.discord.clone(), Interaction::ApplicationCommand(command.clone()))
+            .await;
+
+        command
+    }
+
+    /// Click a button and return the interaction
+    async fn click(&self, user: UserId, message: &Message, custom_id: &str) -> MessageComponentInteraction {
+        let component = self.discord.click(user, message, custom_id);
+        self.handler
+            .handle_interaction(self.discord.clone(), Interaction::MessageComponent(component.clone()))
+            .await;
+
+        component
+    }
+
+    /// Open a poll for the group and return its message
+    async fn start_poll(&self, group: &str) -> Message {
+        let command = self.command(ALICE, "match", json!([{ "name": "groups", "type": 3, "value": group }])).await;
+
+        self.discord.original_response(&command).expect("match poll was posted")
+    }
+
+    /// The poll message as it currently looks
+    fn reload(&self, message: &Message) -> Message {
+        self.discord.message(message.id).expect("message exists")
+    }
+}
+
+fn tomorrow_at(hour: i64) -> DateTime<Utc> {
+    Utc::now().duration_trunc(Duration::days(1)).unwrap() + Duration::days(1) + Duration::hours(hour)
+}
+
+fn embed_title(message: &Message) -> Option<&str> {
+    message.embeds.first().and_then(|embed| embed.title.as_deref())
+}
+
+fn custom_ids(message: &Message) -> Vec<String> {
+    let components = serde_json::to_value(&message.components).unwrap();
+
+    components.as_array().unwrap().iter()
+        .flat_map(|row| row["components"].as_array().cloned().unwrap_or_default())
+        .filter_map(|button| button["custom_id"].as_str().map(str::to_string))
+        .collect()
+}
+
+/// The content of ephemeral responses and followups, in order
+fn ephemeral_messages(calls: &[DiscordCall]) -> Vec<String> {
+    calls.iter()
+        .filter_map(|call| match call {
+            DiscordCall::InteractionResponse { body, .. } => Some(&body["data"]),
+            DiscordCall::Followup { body, .. } => Some(body),
+            _ => None,
+        })
+        .filter(|data| data["flags"].as_u64().is_some_and(|flags| flags & 64 != 0))
+        .filter_map(|data| data["content"].as_str().map(str::to_string))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_match_posts_poll() {
+    let bot = TestBot::spawn().await;
+    bot.create_group("Raiders", &[ALICE, BOB]).await;
+
+    let poll = bot.start_poll("Raiders").await;
+
+    assert_eq!(embed_title(&poll), Some("Meeting Time Proposal"));
+    assert_eq!(custom_ids(&poll), vec!["open_voting", "lock_votes", "unlock_votes", "finalize_poll", "cancel_poll"]);
+
+    // The command is deferred while matches are computed
+    let calls = bot.discord.calls();
+    assert!(matches!(
+        &calls[0],
+        DiscordCall::InteractionResponse { body, .. } if body["type"] == 5
+    ));
+}
+
+#[tokio::test]
+async fn test_match_unknown_group() {
+    let bot = TestBot::spawn().await;
+
+    bot.command(ALICE, "match", json!([{ "name": "groups", "type": 3, "value": "Nobody" }])).await;
+
+    assert_eq!(ephemeral_messages(&bot.discord.calls()), vec!["Group not found: Nobody"]);
+}
+
+#[tokio::test]
+async fn test_match_vote_and_lock_confirms_meeting() {
+    let bot = TestBot::spawn().await;
+    bot.create_group("Raiders", &[ALICE, BOB]).await;
+    let poll = bot.start_poll("Raiders").await;
+    bot.discord.take_calls();
+
+    // The first lock only updates the tally
+    bot.click(ALICE, &poll, "lock_votes").await;
+
+    let poll = bot.reload(&poll);
+    assert_eq!(embed_title(&poll), Some("Meeting Time Proposal"));
+    assert_eq!(ephemeral_messages(&bot.discord.take_calls()), vec!["Your votes have been locked in!"]);
+
+    // Once everyone has locked in, the meeting is confirmed and recorded
+    bot.click(BOB, &poll, "lock_votes").await;
+
+    let poll = bot.reload(&poll);
+    assert_eq!(embed_title(&poll), Some("Meeting Time Confirmed!"));
+    assert_eq!(custom_ids(&poll), vec!["reopen_poll"]);
+
+    let meetings = bot.api
+        .list_server_meetings(&GUILD_ID.to_string(), MeetingStatus::Scheduled)
+        .await
+        .unwrap();
+    assert_eq!(meetings.len(), 1);
+    assert_eq!(meetings[0].start_time, tomorrow_at(18));
+    assert_eq!(meetings[0].organizer_id, ALICE.to_string());
+    let mut attendees = meetings[0].attendee_ids.clone();
+    attendees.sort();
+    assert_eq!(attendees, vec![ALICE.to_string(), BOB.to_string()]);
+}
+
+#[tokio::test]
+async fn test_match_clearing_votes_fails_poll() {
+    let bot = TestBot::spawn().await;
+    bot.create_group("Raiders", &[ALICE, BOB]).await;
+    let poll = bot.start_poll("Raiders").await;
+
+    // Bob opens his voting interface and clears every slot
+    let open = bot.click(BOB, &poll, "open_voting").await;
+    let voting = bot.discord.original_response(&open).expect("voting interface was shown");
+    assert!(custom_ids(&voting).iter().any(|id| id.starts_with("slot_")));
+
+    bot.click(BOB, &voting, "clear_all").await;
+
+    // Locking in with nothing selected leaves too few members available
+    bot.click(ALICE, &poll, "lock_votes").await;
+    bot.click(BOB, &poll, "lock_votes").await;
+
+    let poll = bot.reload(&poll);
+    assert_eq!(embed_title(&poll), Some("Not Enough Members Available"));
+    let meetings = bot.api
+        .list_server_meetings(&GUILD_ID.to_string(), MeetingStatus::Scheduled)
+        .await
+        .unwrap();
+    assert!(meetings.is_empty());
+}
+
+#[tokio::test]
+async fn test_match_outsider_cannot_vote() {
+    let bot = TestBot::spawn().await;
+    bot.create_group("Raiders", &[ALICE, BOB]).await;
+    let poll = bot.start_poll("Raiders").await;
+    bot.discord.take_calls();
+
+    bot.click(OUTSIDER, &poll, "lock_votes").await;
+
+    assert_eq!(
+        ephemeral_messages(&bot.discord.calls()),
+        vec!["You are not a member of any of the groups in this poll, so you cannot vote."]
+    );
+    assert_eq!(embed_title(&bot.reload(&poll)), Some("Meeting Time Proposal"));
+}
+
+#[tokio::test]
+async fn test_match_unlock_keeps_poll_open() {
+    let bot = TestBot::spawn().await;
+    bot.create_group("Raiders", &[ALICE, BOB]).await;
+    let poll = bot.start_poll("Raiders").await;
+
+    bot.click(ALICE, &poll, "lock_votes").await;
+    bot.discord.take_calls();
+    bot.click(ALICE, &poll, "unlock_votes").await;
+
+    assert_eq!(
+        ephemeral_messages(&bot.discord.take_calls()),
+        vec!["Your votes are unlocked. Lock them in again once you've updated your availability."]
+    );
+
+    // Bob locking in alone no longer decides the poll
+    bot.click(BOB, &poll, "lock_votes").await;
+    assert_eq!(embed_title(&bot.reload(&poll)), Some("Meeting Time Proposal"));
+
+    bot.click(ALICE, &poll, "lock_votes").await;
+    assert_eq!(embed_title(&bot.reload(&poll)), Some("Meeting Time Confirmed!"));
+}
+
+#[tokio::test]
+async fn test_match_unlock_during_grace_period_keeps_poll_open() {
+    let bot = TestBot::spawn_with_grace_period(1).await;
+    bot.create_group("Raiders", &[ALICE, BOB]).await;
+    let poll = bot.start_poll("Raiders").await;
+    bot.click(ALICE, &poll, "lock_votes").await;
+    bot.discord.take_calls();
+
+    bot.click(BOB, &poll, "lock_votes").await;
+    assert_eq!(
+        ephemeral_messages(&bot.discord.take_calls()),
+        vec!["Your votes have been locked in! This poll finishes in 1 seconds unless someone unlocks their votes."]
+    );
+
+    // Unlocking before the grace period is over keeps the poll open
+    bot.click(BOB, &poll, "unlock_votes").await;
+    tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+    assert_eq!(embed_title(&bot.reload(&poll)), Some("Meeting Time Proposal"));
+
+    // Locking in again starts a new grace period, which runs out
+    bot.click(BOB, &poll, "lock_votes").await;
+    assert_eq!(embed_title(&bot.reload(&poll)), Some("Meeting Time Proposal"));
+    tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+    assert_eq!(embed_title(&bot.reload(&poll)), Some("Meeting Time Confirmed!"));
+}
+
+#[tokio::test]
+async fn test_match_only_organizer_or_manager_can_finalize() {
+    let bot = TestBot::spawn().await;
+    bot.create_group("Raiders", &[ALICE, BOB]).await;
+    let poll = bot.start_poll("Raiders").await;
+    bot.discord.take_calls();
+
+    bot.click(BOB, &poll, "finalize_poll").await;
+
+    assert_eq!(
+        ephemeral_messages(&bot.discord.take_calls()),
+        vec!["Only the organizer of this poll or poll managers can do that."]
+    );
+    assert_eq!(embed_title(&bot.reload(&poll)), Some("Meeting Time Proposal"));
+
+    // Poll managers can finalize polls they didn't start
+    bot.discord.add_member(BOB, &[MANAGER_ROLE]);
+    bot.click(BOB, &poll, "finalize_poll").await;
+
+    assert_eq!(embed_title(&bot.reload(&poll)), Some("Not Enough Members Available"));
+}
+
+#[tokio::test]
+async fn test_match_organizer_finalizes_with_locked_votes() {
+    let bot = TestBot::spawn().await;
+    bot.create_group("Raiders", &[ALICE, BOB]).await;
+    let poll = bot.start_poll("Raiders").await;
+
+    bot.click(BOB, &poll, "lock_votes").await;
+    bot.click(ALICE, &poll, "finalize_poll").await;
+
+    // Alice never locked in, so only Bob counts
+    assert_eq!(embed_title(&bot.reload(&poll)), Some("Not Enough Members Available"));
+}
+
+#[tokio::test]
+async fn test_match_organizer_cancels_poll() {
+    let bot = TestBot::spawn().await;
+    bot.create_group("Raiders", &[ALICE, BOB]).await;
+    let poll = bot.start_poll("Raiders").await;
+    bot.discord.take_calls();
+
+    bot.click(BOB, &poll, "cancel_poll").await;
+
+    assert_eq!(
+        ephemeral_messages(&bot.discord.take_calls()),
+        vec!["Only the organizer of this poll or poll managers can do that."]
+    );
+    assert_eq!(embed_title(&bot.reload(&poll)), Some("Meeting Time Proposal"));
+
+    bot.click(ALICE, &poll, "cancel_poll").await;
+
+    let poll = bot.reload(&poll);
+    assert_eq!(embed_title(&poll), Some("Meeting Poll Cancelled"));
+    assert_eq!(custom_ids(&poll), vec!["reopen_poll"]);
+
+    // Votes after cancelling don't count
+    bot.click(BOB, &poll, "lock_votes").await;
+    assert_eq!(
+        ephemeral_messages(&bot.discord.take_calls()),
+        vec!["This poll has already finished."]
+    );
+}
+
+#[tokio::test]
+async fn test_match_reopen_cancels_meeting() {
+    let bot = TestBot::spawn().await;
+    bot.create_group("Raiders", &[ALICE, BOB]).await;
+    let poll = bot.start_poll("Raiders").await;
+    bot.click(ALICE, &poll, "lock_votes").await;
+    bot.click(BOB, &poll, "lock_votes").await;
+    let poll = bot.reload(&poll);
+    bot.discord.take_calls();
+    let confirmed = bot.api
+        .list_server_meetings(&GUILD_ID.to_string(), MeetingStatus::Scheduled)
+        .await
+        .unwrap();
+    assert_eq!(confirmed.len(), 1);
+
+    bot.click(BOB, &poll, "reopen_poll").await;
+
+    assert_eq!(
+        ephemeral_messages(&bot.discord.take_calls()),
+        vec!["Only the organizer of this poll or poll managers can do that."]
+    );
+
+    bot.click(ALICE, &poll, "reopen_poll").await;
+
+    let poll = bot.reload(&poll);
+    assert_eq!(embed_title(&poll), Some("Meeting Time Proposal"));
+    assert_eq!(custom_ids(&poll), vec!["open_voting", "lock_votes", "unlock_votes", "finalize_poll", "cancel_poll"]);
+    assert_eq!(
+        ephemeral_messages(&bot.discord.take_calls()),
+        vec!["The poll is open again. Everyone needs to lock in their votes again."]
+    );
+
+    // The meeting the poll had confirmed is cancelled
+    let cancelled = bot.api
+        .list_server_meetings(&GUILD_ID.to_string(), MeetingStatus::Cancelled)
+        .await
+        .unwrap();
+    assert_eq!(cancelled.iter().map(|m| m.id).collect::<Vec<_>>(), vec![confirmed[0].id]);
+    let meetings = bot.api
+        .list_server_meetings(&GUILD_ID.to_string(), MeetingStatus::Scheduled)
+        .await
+        .unwrap();
+    assert!(meetings.is_empty());
+
+    // Everyone votes again before the poll can finish
+    bot.click(ALICE, &poll, "lock_votes").await;
+    assert_eq!(embed_title(&bot.reload(&poll)), Some("Meeting Time Proposal"));
+    bot.click(BOB, &poll, "lock_votes").await;
+    assert_eq!(embed_title(&bot.reload(&poll)), Some("Meeting Time Confirmed!"));
+}
+
+#[tokio::test]
+async fn test_match_reopen_restores_rescheduled_meeting() {
+    let bot = TestBot::spawn().await;
+    bot.create_group("Raiders", &[ALICE, BOB]).await;
+    let poll = bot.start_poll("Raiders").await;
+    bot.click(ALICE, &poll, "lock_votes").await;
+    bot.click(BOB, &poll, "lock_votes").await;
+    let original = bot.api
+        .list_server_meetings(&GUILD_ID.to_string(), MeetingStatus::Scheduled)
+        .await
+        .unwrap()
+        .remove(0);
+
+    let command = bot.command(ALICE, "meeting", json!([{
+        "name": "reschedule",
+        "type": 1,
+        "options": [{ "name": "meeting", "type": 3, "value": original.id.to_string() }],
+    }])).await;
+    let poll = bot.discord.original_response(&command).expect("reschedule poll was posted");
+    bot.click(ALICE, &poll, "lock_votes").await;
+    bot.click(BOB, &poll, "lock_votes").await;
+    let poll = bot.reload(&poll);
+    assert_eq!(embed_title(&poll), Some("Meeting Time Confirmed!"));
+    assert_eq!(bot.api.get_meeting(original.id).await.unwrap().unwrap().status, MeetingStatus::Rescheduled);
+
+    bot.click(ALICE, &poll, "reopen_poll").await;
+
+    // The new meeting is cancelled and the original one stands again
+    assert_eq!(embed_title(&bot.reload(&poll)), Some("Meeting Time Proposal"));
+    assert_eq!(bot.api.get_meeting(original.id).await.unwrap().unwrap().status, MeetingStatus::Scheduled);
+    let cancelled = bot.api
+        .list_server_meetings(&GUILD_ID.to_string(), MeetingStatus::Cancelled)
+        .await
+        .unwrap();
+    assert_eq!(cancelled.len(), 1);
+    assert_eq!(cancelled[0].rescheduled_from, Some(original.id));
+}
+
+#[tokio::test]
+async fn test_meeting_list_shows_group_names() {
+    let bot = TestBot::spawn().await;
+    bot.create_group("Raiders", &[ALICE, BOB]).await;
+    let poll = bot.start_poll("Raiders").await;
+    bot.click(ALICE, &poll, "lock_votes").await;
+    bot.click(BOB, &poll, "lock_votes").await;
+    bot.discord.take_calls();
+
+    bot.command(ALICE, "meeting", json!([{ "name": "list", "type": 1 }])).await;
+
+    let calls = bot.discord.take_calls();
+    let description = calls.iter()
+        .find_map(|call| match call {
+            DiscordCall::InteractionResponse { body, .. } => body["data"]["embeds"][0]["description"].as_str(),
+            _ => None,
+        })
+        .expect("meeting list was sent");
+    assert!(description.contains(" - Raiders - 2 attendees"), "{}", description);
+}
+
+#[tokio::test]
+async fn test_match_manager_can_reopen() {
+    let bot = TestBot::spawn().await;
+    bot.create_group("Raiders", &[ALICE, BOB]).await;
+    bot.discord.add_member(OUTSIDER, &[MANAGER_ROLE]);
+    let poll = bot.start_poll("Raiders").await;
+    bot.click(ALICE, &poll, "lock_votes").await;
+    bot.click(BOB, &poll, "lock_votes").await;
+    let poll = bot.reload(&poll);
+
+    // Managers don't have to be in any of the polled groups
+    bot.click(OUTSIDER, &poll, "reopen_poll").await;
+
+    assert_eq!(embed_title(&bot.reload(&poll)), Some("Meeting Time Proposal"));
+    let cancelled = bot.api
+        .list_server_meetings(&GUILD_ID.to_string(), MeetingStatus::Cancelled)
+        .await
+        .unwrap();
+    assert_eq!(cancelled.len(), 1);
+}
+
+#[tokio::test]
+async fn test_match_repeated_lock_is_a_no_op() {
+    let bot = TestBot::spawn().await;
+    bot.create_group("Raiders", &[ALICE, BOB]).await;
+    let poll = bot.start_poll("Raiders").await;
+    bot.click(ALICE, &poll, "lock_votes").await;
+    bot.discord.take_calls();
+
+    bot.click(ALICE, &poll, "lock_votes").await;
+    bot.click(BOB, &poll, "unlock_votes").await;
+
+    let calls = bot.discord.take_calls();
+    assert_eq!(
+        ephemeral_messages(&calls),
+        vec!["Your votes are already locked in.", "Your votes aren't locked in."]
+    );
+    assert!(!calls.iter().any(|call| matches!(call, DiscordCall::EditMessage { .. })));
+}
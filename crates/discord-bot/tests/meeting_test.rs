@@ -0,0 +1,54 @@
+use chrono::Utc;
+use timesync_core::models::meeting::{Meeting, MeetingStatus};
+use timesync_discord_bot::handlers::meeting::meetings_with_short_id;
+use uuid::Uuid;
+
+fn meeting(id: &str) -> Meeting {
+    let now = Utc::now();
+
+    Meeting {
+        id: Uuid::parse_str(id).unwrap(),
+        server_id: "100".to_string(),
+        channel_id: "200".to_string(),
+        organizer_id: "10".to_string(),
+        start_time: now,
+        end_time: now,
+        timezone: "UTC".to_string(),
+        status: MeetingStatus::Scheduled,
+        min_per_group: 1,
+        slot_duration: 60,
+        group_ids: Vec::new(),
+        attendee_ids: Vec::new(),
+        rescheduled_from: None,
+        created_at: now,
+    }
+}
+
+fn ids(meetings: &[&Meeting]) -> Vec<Uuid> {
+    meetings.iter().map(|m| m.id).collect()
+}
+
+#[test]
+fn test_short_id_matches_one_meeting() {
+    let meetings = [
+        meeting("1a2b3c4d-0000-4000-8000-000000000001"),
+        meeting("1a2bffff-0000-4000-8000-000000000002"),
+    ];
+    
+    assert_eq!(ids(&meetings_with_short_id("1a2b3c4d", &meetings)), vec![meetings[0].id]);
+    
+    // Short IDs are accepted in either case
+    assert_eq!(ids(&meetings_with_short_id("1A2B3C4D", &meetings)), vec![meetings[0].id]);
+}
+
+#[test]
+fn test_short_id_shared_by_several_meetings() {
+    let meetings = [
+        meeting("1a2b3c4d-0000-4000-8000-000000000001"),
+        meeting("1a2bffff-0000-4000-8000-000000000002"),
+        meeting("99999999-0000-4000-8000-000000000003"),
+    ];
+    
+    assert_eq!(ids(&meetings_with_short_id("1a2b", &meetings)), vec![meetings[0].id, meetings[1].id]);
+    assert!(meetings_with_short_id("abcd", &meetings).is_empty());
+}
@@ -0,0 +1,46 @@
+use rstest::rstest;
+use timesync_discord_bot::members::{parse_member_specs, parse_user_mentions, MemberSpec};
+
+#[rstest]
+#[case("<@123>", vec![MemberSpec::User("123".to_string())])]
+#[case("<@!123>", vec![MemberSpec::User("123".to_string())])]
+#[case("123, 456", vec![MemberSpec::User("123".to_string()), MemberSpec::User("456".to_string())])]
+#[case("all", vec![MemberSpec::All])]
+#[case("ALL", vec![MemberSpec::All])]
+#[case("@everyone", vec![MemberSpec::All])]
+#[case("role:<@&789>", vec![MemberSpec::Role("789".to_string())])]
+#[case("<@&789>", vec![MemberSpec::Role("789".to_string())])]
+#[case("role:789", vec![MemberSpec::Role("789".to_string())])]
+#[case("role:@Raiders", vec![MemberSpec::RoleName("Raiders".to_string())])]
+#[case("role: Night Owls", vec![MemberSpec::RoleName("Night Owls".to_string())])]
+#[case("Role:@Raiders", vec![MemberSpec::RoleName("Raiders".to_string())])]
+#[case("ROLE:<@&789>", vec![MemberSpec::Role("789".to_string())])]
+fn test_parse_member_specs(#[case] input: &str, #[case] expected: Vec<MemberSpec>) {
+    assert_eq!(parse_member_specs(input), expected);
+}
+
+#[test]
+fn test_parse_member_specs_mixed() {
+    let specs = parse_member_specs("<@1>, role:@Raiders, all, <@&2>");
+    
+    assert_eq!(specs, vec![
+        MemberSpec::User("1".to_string()),
+        MemberSpec::RoleName("Raiders".to_string()),
+        MemberSpec::All,
+        MemberSpec::Role("2".to_string()),
+    ]);
+}
+
+#[test]
+fn test_parse_member_specs_skips_invalid_entries() {
+    assert_eq!(parse_member_specs("nobody, , role:, <@abc>"), Vec::new());
+}
+
+#[test]
+fn test_parse_user_mentions() {
+    assert_eq!(
+        parse_user_mentions("<@1> <@!2>, 3 <@&4> role:@Raiders all <@1>"),
+        vec!["1".to_string(), "2".to_string(), "3".to_string()]
+    );
+    assert!(parse_user_mentions("").is_empty());
+}
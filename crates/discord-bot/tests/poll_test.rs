@@ -0,0 +1,382 @@
+use chrono::{Duration, TimeZone, Utc};
+use serenity::model::id::ChannelId;
+use std::collections::{HashMap, HashSet};
+use timesync_core::matching::{GroupConstraint, MemberConstraint};
+use timesync_discord_bot::poll::{Poll, PollEvent, PollOutcome, PollResult, PollSetup, SlotInfo};
+use uuid::Uuid;
+
+/// A poll setup for one group, with two days of two slots each
+///
+/// Slot IDs are "d0s0", "d0s1", "d1s0" and "d1s1".
+fn setup(members: &[&str]) -> PollSetup {
+    let group_id = Uuid::new_v4();
+    let members: Vec<String> = members.iter().map(|m| m.to_string()).collect();
+    let first = Utc.with_ymd_and_hms(2025, 3, 10, 18, 0, 0).unwrap();
+
+    let day_slots = (0..2)
+        .map(|day| {
+            let slots = (0..2)
+                .map(|idx| {
+                    let start = first + Duration::days(day as i64) + Duration::hours(idx);
+                    SlotInfo {
+                        id: format!("d{}s{}", day, idx),
+                        start,
+                        end: start + Duration::hours(1),
+                        formatted_time: start.format("%H:%M").to_string(),
+                        available_users: members.clone(),
+                        tentative_users: Vec::new(),
+                    }
+                })
+                .collect();
+            (day, slots)
+        })
+        .collect();
+
+    PollSetup {
+        group_names: vec!["Raiders".to_string()],
+        min_per_group: 1,
+        timezone: "UTC".to_string(),
+        eligible_voters: members.iter().cloned().collect::<HashSet<_>>(),
+        group_members: HashMap::from([(group_id, members.clone())]),
+        constraints: vec![GroupConstraint {
+            id: group_id,
+            required: true,
+            min_available: members.len().min(6),
+            members: members.iter()
+                .map(|m| MemberConstraint { discord_id: m.clone(), required: false, weight: 1.0 })
+                .collect(),
+        }],
+        slot_duration: 60,
+        display_days: 2,
+        day_slots,
+        group_ids: vec![group_id],
+        server_id: "100".to_string(),
+        channel_id: ChannelId(200),
+        organizer_id: "alice".to_string(),
+        rescheduled_from: None,
+    }
+}
+
+/// A freshly opened poll for [`setup`]
+fn poll(members: &[&str]) -> Poll {
+    Poll::new(setup(members))
+}
+
+fn toggle(voter: &str, slot_id: &str) -> PollEvent {
+    PollEvent::Toggle { voter: voter.to_string(), slot_id: slot_id.to_string() }
+}
+
+fn lock(voter: &str) -> PollEvent {
+    PollEvent::Lock { voter: voter.to_string() }
+}
+
+fn unlock(voter: &str) -> PollEvent {
+    PollEvent::Unlock { voter: voter.to_string() }
+}
+
+fn previous_day(voter: &str) -> PollEvent {
+    PollEvent::PreviousDay { voter: voter.to_string() }
+}
+
+fn next_day(voter: &str) -> PollEvent {
+    PollEvent::NextDay { voter: voter.to_string() }
+}
+
+/// Lock in `voter`'s votes and let the grace period run out
+fn lock_and_wait(poll: &mut Poll, voter: &str) -> PollOutcome {
+    match poll.apply(lock(voter)) {
+        PollOutcome::Settled { round, .. } => poll.apply(PollEvent::GracePeriodOver { round }),
+        outcome => outcome,
+    }
+}
+
+fn selected(poll: &Poll, voter: &str) -> Vec<String> {
+    let mut slots = poll.selections(voter).to_vec();
+    slots.sort();
+    slots
+}
+
+#[test]
+fn test_toggle_selects_and_deselects() {
+    let mut poll = poll(&["alice", "bob"]);
+
+    assert_eq!(poll.apply(toggle("alice", "d0s1")), PollOutcome::Updated);
+    assert_eq!(selected(&poll, "alice"), vec!["d0s1"]);
+
+    assert_eq!(poll.apply(toggle("alice", "d0s1")), PollOutcome::Updated);
+    assert!(selected(&poll, "alice").is_empty());
+}
+
+#[test]
+fn test_select_all_and_clear_all() {
+    let mut poll = poll(&["alice", "bob"]);
+
+    poll.apply(PollEvent::SelectAll { voter: "alice".to_string() });
+    assert_eq!(selected(&poll, "alice"), vec!["d0s0", "d0s1", "d1s0", "d1s1"]);
+
+    poll.apply(PollEvent::ClearAll { voter: "alice".to_string() });
+    assert!(selected(&poll, "alice").is_empty());
+}
+
+#[test]
+fn test_outsiders_cannot_vote() {
+    let mut poll = poll(&["alice", "bob"]);
+
+    assert_eq!(poll.apply(toggle("mallory", "d0s0")), PollOutcome::NotEligible);
+    assert_eq!(poll.apply(lock("mallory")), PollOutcome::NotEligible);
+    assert!(poll.selections("mallory").is_empty());
+    assert!(!poll.is_locked("mallory"));
+}
+
+#[test]
+fn test_lock_waits_for_enough_members() {
+    let mut poll = poll(&["alice", "bob"]);
+    poll.apply(toggle("alice", "d0s1"));
+    poll.apply(toggle("bob", "d0s1"));
+
+    assert_eq!(poll.apply(lock("alice")), PollOutcome::Locked);
+
+    let tally = &poll.tallies()[0];
+    assert_eq!((tally.available, tally.unavailable, tally.remaining, tally.min_required), (1, 0, 1, 2));
+}
+
+#[test]
+fn test_lock_confirms_best_slot_once_everyone_is_in() {
+    let mut poll = poll(&["alice", "bob"]);
+    poll.apply(PollEvent::SelectAll { voter: "alice".to_string() });
+    poll.apply(toggle("bob", "d1s0"));
+    poll.apply(lock("alice"));
+
+    let PollOutcome::Finished(PollResult::Confirmed(choice)) = lock_and_wait(&mut poll, "bob") else {
+        panic!("poll should have been confirmed");
+    };
+
+    assert_eq!(choice.day, 1);
+    assert_eq!(choice.slot.id, "d1s0");
+    let mut attendees = choice.attendees.clone();
+    attendees.sort();
+    assert_eq!(attendees, vec!["alice", "bob"]);
+}
+
+#[test]
+fn test_lock_fails_once_a_group_cannot_reach_minimum() {
+    let mut poll = poll(&["alice", "bob"]);
+
+    // Bob locks in with nothing selected, so the group can't have both members
+    let PollOutcome::Finished(PollResult::NotEnoughMembers(tallies)) = lock_and_wait(&mut poll, "bob") else {
+        panic!("poll should have failed");
+    };
+
+    assert_eq!(tallies[0].name, "Raiders");
+    assert_eq!((tallies[0].available, tallies[0].unavailable, tallies[0].remaining), (0, 1, 1));
+}
+
+#[test]
+fn test_lock_without_common_slot_finds_no_time() {
+    let mut poll = poll(&["alice", "bob"]);
+    poll.apply(toggle("alice", "d0s0"));
+    poll.apply(toggle("bob", "d0s1"));
+    poll.apply(lock("alice"));
+
+    assert_eq!(lock_and_wait(&mut poll, "bob"), PollOutcome::Finished(PollResult::NoSuitableTime));
+}
+
+#[test]
+fn test_deadline_finishes_with_locked_votes() {
+    let mut poll = poll(&["alice", "bob"]);
+    poll.apply(toggle("alice", "d0s0"));
+    poll.apply(toggle("bob", "d0s0"));
+    poll.apply(lock("alice"));
+
+    // Bob picked the same slot but never locked in
+    let PollOutcome::Finished(PollResult::NotEnoughMembers(tallies)) = poll.apply(PollEvent::Deadline) else {
+        panic!("poll should have failed");
+    };
+    assert_eq!((tallies[0].available, tallies[0].remaining), (1, 1));
+}
+
+#[test]
+fn test_lock_waits_out_grace_period() {
+    let mut poll = poll(&["alice", "bob"]);
+    poll.apply(PollEvent::SelectAll { voter: "alice".to_string() });
+    poll.apply(PollEvent::SelectAll { voter: "bob".to_string() });
+    poll.apply(lock("alice"));
+
+    let PollOutcome::Settled { result: PollResult::Confirmed(_), round } = poll.apply(lock("bob")) else {
+        panic!("poll should be about to confirm");
+    };
+    assert!(poll.result().is_none());
+    assert!(poll.proposal_view().pending.is_some());
+
+    assert!(matches!(
+        poll.apply(PollEvent::GracePeriodOver { round }),
+        PollOutcome::Finished(PollResult::Confirmed(_))
+    ));
+}
+
+#[test]
+fn test_unlock_during_grace_period_keeps_poll_open() {
+    let mut poll = poll(&["alice", "bob"]);
+    poll.apply(PollEvent::SelectAll { voter: "alice".to_string() });
+    poll.apply(PollEvent::SelectAll { voter: "bob".to_string() });
+    poll.apply(lock("alice"));
+    let PollOutcome::Settled { round, .. } = poll.apply(lock("bob")) else {
+        panic!("poll should be about to confirm");
+    };
+
+    assert_eq!(poll.apply(unlock("bob")), PollOutcome::Unlocked);
+    assert_eq!(poll.apply(PollEvent::GracePeriodOver { round }), PollOutcome::Unchanged);
+    assert!(poll.result().is_none());
+
+    // Bob can change his votes again, and locking in starts a new grace period
+    assert_eq!(poll.apply(toggle("bob", "d0s0")), PollOutcome::Updated);
+    let PollOutcome::Settled { round: next_round, .. } = poll.apply(lock("bob")) else {
+        panic!("poll should be about to confirm again");
+    };
+    assert_eq!(poll.apply(PollEvent::GracePeriodOver { round }), PollOutcome::Unchanged);
+    assert!(matches!(
+        poll.apply(PollEvent::GracePeriodOver { round: next_round }),
+        PollOutcome::Finished(PollResult::Confirmed(_))
+    ));
+}
+
+#[test]
+fn test_repeated_lock_or_unlock_keeps_grace_period() {
+    let mut poll = poll(&["alice", "bob"]);
+    poll.apply(PollEvent::SelectAll { voter: "alice".to_string() });
+    poll.apply(PollEvent::SelectAll { voter: "bob".to_string() });
+    poll.apply(lock("alice"));
+    let PollOutcome::Settled { round, .. } = poll.apply(lock("bob")) else {
+        panic!("poll should be about to confirm");
+    };
+
+    // Clicking again doesn't restart the grace period
+    assert_eq!(poll.apply(lock("bob")), PollOutcome::Unchanged);
+    assert_eq!(poll.apply(lock("alice")), PollOutcome::Unchanged);
+    assert_eq!(poll.pending_round(), round);
+
+    assert!(matches!(
+        poll.apply(PollEvent::GracePeriodOver { round }),
+        PollOutcome::Finished(PollResult::Confirmed(_))
+    ));
+}
+
+#[test]
+fn test_unlock_without_locking_changes_nothing() {
+    let mut poll = poll(&["alice", "bob"]);
+
+    assert_eq!(poll.apply(unlock("alice")), PollOutcome::Unchanged);
+    assert_eq!(poll.pending_round(), 0);
+}
+
+#[test]
+fn test_locked_voters_cannot_change_votes() {
+    let mut poll = poll(&["alice", "bob"]);
+    poll.apply(toggle("alice", "d0s0"));
+    poll.apply(lock("alice"));
+
+    assert_eq!(poll.apply(toggle("alice", "d0s1")), PollOutcome::VotesLocked);
+    assert_eq!(selected(&poll, "alice"), vec!["d0s0"]);
+}
+
+#[test]
+fn test_reopen_clears_result_and_locks() {
+    let mut poll = poll(&["alice", "bob"]);
+    poll.apply(PollEvent::SelectAll { voter: "alice".to_string() });
+    poll.apply(PollEvent::SelectAll { voter: "bob".to_string() });
+    lock_and_wait(&mut poll, "alice");
+    assert!(matches!(lock_and_wait(&mut poll, "bob"), PollOutcome::Finished(PollResult::Confirmed(_))));
+
+    assert_eq!(poll.apply(PollEvent::Reopen), PollOutcome::Reopened { meeting_id: None });
+    assert!(poll.result().is_none());
+    assert!(!poll.is_locked("alice") && !poll.is_locked("bob"));
+    assert_eq!(selected(&poll, "alice"), vec!["d0s0", "d0s1", "d1s0", "d1s1"]);
+    assert_eq!(poll.apply(toggle("alice", "d0s0")), PollOutcome::Updated);
+}
+
+#[test]
+fn test_reopen_hands_back_recorded_meeting() {
+    let mut poll = poll(&["alice"]);
+    poll.apply(PollEvent::SelectAll { voter: "alice".to_string() });
+    assert!(matches!(lock_and_wait(&mut poll, "alice"), PollOutcome::Finished(PollResult::Confirmed(_))));
+
+    let meeting_id = Uuid::new_v4();
+    poll.apply(PollEvent::MeetingRecorded { meeting_id });
+    assert_eq!(poll.meeting_id(), Some(meeting_id));
+
+    assert_eq!(poll.apply(PollEvent::Reopen), PollOutcome::Reopened { meeting_id: Some(meeting_id) });
+    assert_eq!(poll.meeting_id(), None);
+}
+
+#[test]
+fn test_finished_poll_ignores_events() {
+    let mut poll = poll(&["alice", "bob"]);
+
+    assert_eq!(poll.apply(PollEvent::Cancel), PollOutcome::Finished(PollResult::Cancelled));
+    assert_eq!(poll.apply(toggle("alice", "d0s0")), PollOutcome::AlreadyFinished);
+    assert_eq!(poll.apply(lock("alice")), PollOutcome::AlreadyFinished);
+    assert_eq!(poll.result(), Some(&PollResult::Cancelled));
+    assert!(selected(&poll, "alice").is_empty());
+}
+
+#[test]
+fn test_voting_view_shows_selection_and_counts() {
+    let mut poll = poll(&["alice", "bob"]);
+    poll.apply(toggle("alice", "d0s0"));
+    poll.apply(toggle("bob", "d0s0"));
+    poll.apply(toggle("bob", "d0s1"));
+
+    let view = poll.voting_view("alice");
+    assert_eq!((view.day, view.day_count), (0, 2));
+    assert_eq!(view.slots.iter().map(|slot| (slot.votes, slot.selected)).collect::<Vec<_>>(), vec![(2, true), (1, false)]);
+    assert_eq!(view.selected_on_day(), 1);
+    assert!(!view.has_previous_day && view.has_next_day);
+    assert!(!view.locked);
+}
+
+#[test]
+fn test_day_navigation_stays_in_range() {
+    let mut poll = poll(&["alice"]);
+
+    assert_eq!(poll.apply(previous_day("alice")), PollOutcome::Unchanged);
+    assert_eq!(poll.apply(next_day("alice")), PollOutcome::Updated);
+    assert_eq!(poll.apply(next_day("alice")), PollOutcome::Unchanged);
+
+    let view = poll.voting_view("alice");
+    assert_eq!(view.day, 1);
+    assert_eq!(view.slots[0].slot_id, "d1s0");
+    assert!(view.has_previous_day && !view.has_next_day);
+}
+
+#[test]
+fn test_day_navigation_is_per_voter() {
+    let mut poll = poll(&["alice", "bob"]);
+    poll.apply(toggle("alice", "d0s0"));
+    poll.apply(lock("alice"));
+
+    // Locked-in voters can still look at other days
+    assert_eq!(poll.apply(next_day("alice")), PollOutcome::Updated);
+    assert_eq!(poll.voting_view("alice").day, 1);
+    assert_eq!(poll.voting_view("bob").day, 0);
+
+    assert_eq!(poll.apply(next_day("mallory")), PollOutcome::NotEligible);
+}
+
+#[test]
+fn test_proposal_view_reports_best_slot_per_group() {
+    // An optional group doesn't rule slots out, so a best slot shows up early
+    let mut setup = setup(&["alice", "bob"]);
+    setup.constraints[0].required = false;
+    let mut poll = Poll::new(setup);
+    poll.apply(toggle("alice", "d0s1"));
+    poll.apply(toggle("bob", "d0s1"));
+    poll.apply(lock("alice"));
+
+    let view = poll.proposal_view();
+    assert_eq!((view.locked, view.eligible), (1, 2));
+
+    let best = view.best.expect("alice's locked vote counts");
+    assert_eq!(best.choice.slot.id, "d0s1");
+    assert_eq!(best.groups, vec![("Raiders".to_string(), 1, 2)]);
+    assert!(!best.meets_all_requirements());
+}
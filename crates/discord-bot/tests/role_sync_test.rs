@@ -0,0 +1,31 @@
+use timesync_discord_bot::handlers::role_sync::{membership_changes, MembershipChanges};
+
+fn ids(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+}
+
+#[test]
+fn test_membership_changes_adds_and_removes() {
+    let changes = membership_changes(&ids(&["1", "2", "3"]), &ids(&["2", "3", "4", "5"]));
+    
+    assert_eq!(changes, MembershipChanges {
+        added: ids(&["4", "5"]),
+        removed: ids(&["1"]),
+    });
+}
+
+#[test]
+fn test_membership_changes_in_sync() {
+    let changes = membership_changes(&ids(&["1", "2"]), &ids(&["2", "1"]));
+    
+    assert!(changes.added.is_empty());
+    assert!(changes.removed.is_empty());
+}
+
+#[test]
+fn test_membership_changes_role_emptied() {
+    let changes = membership_changes(&ids(&["1", "2"]), &[]);
+    
+    assert!(changes.added.is_empty());
+    assert_eq!(changes.removed, ids(&["1", "2"]));
+}
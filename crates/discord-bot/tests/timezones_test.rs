@@ -0,0 +1,56 @@
+use chrono::{NaiveDate, TimeZone, Utc};
+use timesync_discord_bot::timezones::{format_personal_slot_time, format_slot_time, parse_timezone, search_timezones};
+
+#[test]
+fn test_parse_timezone_falls_back_to_utc() {
+    assert_eq!(parse_timezone("Europe/Berlin"), chrono_tz::Europe::Berlin);
+    assert_eq!(parse_timezone("Not/AZone"), chrono_tz::UTC);
+}
+
+#[test]
+fn test_format_slot_time_in_timezone() {
+    let start = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2025, 1, 15, 2, 0, 0).unwrap();
+    
+    assert_eq!(format_slot_time(start, end, chrono_tz::America::Chicago), "6pm-8pm");
+    assert_eq!(format_slot_time(start, end, chrono_tz::Europe::Berlin), "1am-3am");
+    assert_eq!(format_slot_time(start, end, chrono_tz::Asia::Kolkata), "5:30am-7:30am");
+}
+
+#[test]
+fn test_format_personal_slot_time_marks_other_dates() {
+    // 6pm Chicago on the 14th is 1am Berlin on the 15th
+    let start = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2025, 1, 15, 2, 0, 0).unwrap();
+    let berlin = chrono_tz::Europe::Berlin;
+    
+    let same_day = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+    assert_eq!(format_personal_slot_time(start, end, same_day, berlin), "1am-3am");
+    
+    let previous_day = NaiveDate::from_ymd_opt(2025, 1, 14).unwrap();
+    assert_eq!(format_personal_slot_time(start, end, previous_day, berlin), "Wed 1am-3am");
+}
+
+#[test]
+fn test_search_timezones_ranks_prefix_and_city_matches() {
+    let results = search_timezones("europe/ber", 5);
+    assert_eq!(results.first(), Some(&"Europe/Berlin"));
+    
+    let results = search_timezones("new york", 5);
+    assert_eq!(results.first(), Some(&"America/New_York"));
+    
+    let results = search_timezones("tokyo", 5);
+    assert_eq!(results.first(), Some(&"Asia/Tokyo"));
+}
+
+#[test]
+fn test_search_timezones_fuzzy_and_limits() {
+    // Subsequence matches still find the zone
+    assert!(search_timezones("amrchcgo", 25).contains(&"America/Chicago"));
+    
+    assert_eq!(search_timezones("a", 25).len(), 25);
+    assert!(search_timezones("zzzzzz", 25).is_empty());
+    
+    // Common zones are suggested before anything is typed
+    assert!(search_timezones("", 25).contains(&"UTC"));
+}
@@ -0,0 +1,33 @@
+#!/usr/bin/env bash
+# Run the test suite against a throwaway Postgres server
+#
+# Starts a server in a temporary directory with initdb and pg_ctl, points
+# TEST_DATABASE_URL at it and runs `cargo test` with any arguments given.
+# The server and its data are removed afterwards. Each test creates its own
+# database on the server, see `timesync_db::testing`.
+#
+#   scripts/test-postgres.sh                  # every test
+#   scripts/test-postgres.sh -p timesync-api  # one crate
+#
+# Set PG_BIN to the directory holding initdb and pg_ctl if they aren't on
+# PATH, and PG_PORT to use a port other than 54329. Postgres refuses to run
+# as root, so run this as a regular user.
+
+set -euo pipefail
+
+PG_BIN="${PG_BIN:-$(dirname "$(command -v initdb || echo /usr/lib/postgresql/bin/initdb)")}"
+PG_PORT="${PG_PORT:-54329}"
+DATA_DIR="$(mktemp -d -t timesync-pg.XXXXXX)"
+
+cleanup() {
+    "$PG_BIN/pg_ctl" -D "$DATA_DIR" -m immediate stop >/dev/null 2>&1 || true
+    rm -rf "$DATA_DIR"
+}
+trap cleanup EXIT
+
+"$PG_BIN/initdb" -D "$DATA_DIR" -U postgres -A trust >/dev/null
+"$PG_BIN/pg_ctl" -D "$DATA_DIR" -l "$DATA_DIR/server.log" -w \
+    -o "-p $PG_PORT -k $DATA_DIR -c listen_addresses=localhost -c fsync=off" start >/dev/null
+
+export TEST_DATABASE_URL="postgres://postgres@localhost:$PG_PORT/postgres"
+cargo test "$@"
@@ -1,7 +1,6 @@
 use color_eyre::eyre::Result;
 use dotenv::dotenv;
 use timesync_discord_bot::config::BotConfig;
-use timesync_db::{create_pool, schema::initialize_database};
 use tracing::{error, info, Level};
 use tracing_subscriber::FmtSubscriber;
 
@@ -24,14 +23,8 @@ async fn main() -> Result<()> {
     // Load configuration
     let config = BotConfig::from_env()?;
     
-    // Create database connection pool
-    let db_pool = create_pool(&config.database_url).await?;
-    
-    // Initialize database schema
-    initialize_database(&db_pool).await?;
-    
     // Start the Discord bot
-    match timesync_discord_bot::start_bot(config, db_pool).await {
+    match timesync_discord_bot::start_bot(config).await {
         Ok(_) => info!("Discord bot shut down gracefully"),
         Err(e) => error!("Discord bot error: {}", e),
     }
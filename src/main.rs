@@ -1,7 +1,7 @@
 use color_eyre::eyre::Result;
 use dotenv::dotenv;
 use timesync_api::config::ApiConfig;
-use timesync_db::{create_pool, schema::initialize_database};
+use timesync_db::Database;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -14,14 +14,14 @@ async fn main() -> Result<()> {
     // Load configuration
     let config = ApiConfig::from_env()?;
 
-    // Create database connection pool
-    let db_pool = create_pool(&config.database_url).await?;
+    // Connect to Postgres or SQLite, depending on the URL
+    let database = Database::connect(&config.database_url).await?;
 
     // Initialize database schema
-    initialize_database(&db_pool).await?;
+    database.initialize().await?;
 
     // Start API server
-    timesync_api::start_server(config, db_pool).await?;
+    timesync_api::start_server(config, database).await?;
 
     Ok(())
 }